@@ -665,6 +665,44 @@ impl pallet_staking::Config for Runtime {
 	type MaxUnlockingChunks = ConstU32<32>;
 	type HistoryDepth = HistoryDepth;
 	type EventListeners = NominationPools;
+	type DeductPendingSlashes = ConstBool<false>;
+	type DeferOverweightElection = ConstBool<false>;
+	type LoyaltyBonus = ();
+	type MaxLoyaltyEras = ConstU32<0>;
+	type MaxExitsPerEra = ();
+	type ValidatorWaitlistSessions = ();
+	type VoterSamplingSeed = ();
+	type ValidatorShuffleSeed = ();
+	type WeightAccountant = System;
+	type RemainderPolicy = ();
+	type MaxRememberedOffences = ConstU32<1024>;
+	type MaxSlashesPerBlock = ConstU32<512>;
+	type MinNominatorBondShare = ();
+	type MinValidatorBondShare = ();
+	type EvictLowestNominators = ();
+	type LogTarget = pallet_staking::DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = ();
+	type MaxAutoPayoutsPerBlock = ();
+	type EraLength = ();
+	type ExternalExposures = ();
+	type ExposureOracle = frame_system::EnsureNever<()>;
+	type ValidatorLiveness = ();
+	type MaxVetoedWinners = ConstU32<0>;
+	type MinNominatorSlash = ();
+	type MaxRunnersUp = ConstU32<0>;
+	type DisablingStrategy = pallet_staking::DisableWhenAsked;
+	type ReplaceDisabledValidators = ConstBool<false>;
+	type MinUnbondingDuration = ();
+	type UnbondingQueueSaturation = ();
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = ConstU32<10>;
+	type MaxEvictionScan = ConstU32<16>;
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = ();
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
 	type BenchmarkingConfig = StakingBenchmarkingConfig;
 }
@@ -724,12 +762,16 @@ parameter_types! {
 		.voters_count(10_000.into()).targets_count(1_500.into()).build();
 	pub ElectionBoundsOnChain: ElectionBounds = ElectionBoundsBuilder::default()
 		.voters_count(5_000.into()).targets_count(1_250.into()).build();
+	// Room for the nominators to grow by a quarter before the snapshot bounds are exhausted.
+	pub SnapshotNominatorsMultiple: FixedU128 = FixedU128::from_rational(5, 4);
 
 	pub MaxNominations: u32 = <NposSolution16 as frame_election_provider_support::NposSolution>::LIMIT as u32;
 	pub MaxElectingVotersSolution: u32 = 40_000;
 	// The maximum winners that can be elected by the Election pallet which is equivalent to the
 	// maximum active validators the staking pallet can have.
 	pub MaxActiveValidators: u32 = 1000;
+	// A winner can not be backed by more voters than there are in a solution.
+	pub MaxBackersPerWinner: u32 = MaxElectingVotersSolution::get();
 }
 
 /// The numbers configured here could always be more than the the maximum limits of staking pallet
@@ -781,7 +823,10 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = <Runtime as pallet_election_provider_multi_phase::Config>::DataProvider;
 	type WeightInfo = frame_election_provider_support::weights::SubstrateWeight<Runtime>;
 	type MaxWinners = <Runtime as pallet_election_provider_multi_phase::Config>::MaxWinners;
+	type MaxBackersPerWinner =
+		<Runtime as pallet_election_provider_multi_phase::Config>::MaxBackersPerWinner;
 	type Bounds = ElectionBoundsOnChain;
+	type SolverBudget = ();
 }
 
 impl pallet_election_provider_multi_phase::MinerConfig for Runtime {
@@ -792,6 +837,7 @@ impl pallet_election_provider_multi_phase::MinerConfig for Runtime {
 	type MaxVotesPerVoter =
 	<<Self as pallet_election_provider_multi_phase::Config>::DataProvider as ElectionDataProvider>::MaxVotesPerVoter;
 	type MaxWinners = MaxActiveValidators;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 
 	// The unsigned submissions have to respect the weight of the submit_unsigned call, thus their
 	// weight estimate function is wired to this call's weight.
@@ -827,12 +873,18 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type DataProvider = Staking;
 	type Fallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type GovernanceFallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
-	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Self>, OffchainRandomBalancing>;
+	type Solver = SequentialPhragmen<
+		AccountId,
+		SolutionAccuracyOf<Self>,
+		pallet_election_provider_multi_phase::BalancingOverride<Self, OffchainRandomBalancing>,
+	>;
 	type ForceOrigin = EnsureRootOrHalfCouncil;
 	type MaxWinners = MaxActiveValidators;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type ElectionBounds = ElectionBoundsMultiPhase;
 	type BenchmarkingConfig = ElectionProviderBenchmarkConfig;
 	type WeightInfo = pallet_election_provider_multi_phase::weights::SubstrateWeight<Self>;
+	type WeightAccountant = System;
 }
 
 parameter_types! {
@@ -1341,6 +1393,9 @@ parameter_types! {
 	pub const StakingUnsignedPriority: TransactionPriority = TransactionPriority::max_value() / 2;
 	pub const MaxAuthorities: u32 = 100;
 	pub const MaxKeys: u32 = 10_000;
+	/// Era points granted to each validator that was online for a session, one twentieth of the
+	/// points for authoring a block.
+	pub const SessionParticipationPoints: u32 = 1;
 	pub const MaxPeerInHeartbeats: u32 = 10_000;
 }
 
@@ -1409,6 +1464,8 @@ impl pallet_im_online::Config for Runtime {
 	type WeightInfo = pallet_im_online::weights::SubstrateWeight<Runtime>;
 	type MaxKeys = MaxKeys;
 	type MaxPeerInHeartbeats = MaxPeerInHeartbeats;
+	type OnSessionParticipation =
+		pallet_staking::participation::ParticipationPoints<Runtime, SessionParticipationPoints>;
 }
 
 impl pallet_offences::Config for Runtime {
@@ -2137,6 +2194,18 @@ type Migrations = (
 	pallet_nomination_pools::migration::v2::MigrateToV2<Runtime>,
 	pallet_alliance::migration::Migration<Runtime>,
 	pallet_contracts::Migration<Runtime>,
+	pallet_staking::migrations::v14::MigrateToV14<Runtime>,
+	pallet_staking::migrations::v15::MigrateToV15<Runtime>,
+	pallet_staking::migrations::v16::MigrateToV16<Runtime>,
+	pallet_staking::migrations::v17::MigrateToV17<Runtime>,
+	pallet_staking::migrations::v18::MigrateToV18<Runtime>,
+	pallet_staking::migrations::v19::MigrateToV19<Runtime>,
+	pallet_staking::migrations::v20::InjectValidatorsIntoTargetList<Runtime>,
+	pallet_staking::migrations::CheckSnapshotBounds<
+		Runtime,
+		ElectionBoundsMultiPhase,
+		SnapshotNominatorsMultiple,
+	>,
 );
 
 type EventRecord = frame_system::EventRecord<
@@ -2342,10 +2411,46 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl pallet_staking_runtime_api::StakingApi<Block, Balance> for Runtime {
+	impl pallet_staking_runtime_api::StakingApi<Block, Balance, AccountId> for Runtime {
 		fn nominations_quota(balance: Balance) -> u32 {
 			Staking::api_nominations_quota(balance)
 		}
+
+		fn exposure_own_ratios(era: sp_staking::EraIndex) -> Vec<(AccountId, Perbill)> {
+			Staking::api_exposure_own_ratios(era)
+		}
+
+		fn oldest_claimable_era() -> Option<sp_staking::EraIndex> {
+			Staking::api_oldest_claimable_era()
+		}
+
+		fn staker_status(who: AccountId) -> Option<sp_staking::StakerRole> {
+			Staking::api_staker_status(who)
+		}
+
+		fn election_health_report() -> sp_staking::ElectionHealthReport<BlockNumber> {
+			Staking::api_election_health_report()
+		}
+
+		fn nomination_exit(nominator: AccountId, validator: AccountId) -> Option<sp_staking::EraIndex> {
+			Staking::api_nomination_exit(nominator, validator)
+		}
+
+		fn project_rewards(stash: AccountId, eras_ahead: u32) -> Vec<Balance> {
+			Staking::api_project_rewards(stash, eras_ahead)
+		}
+
+		fn eras_inflation() -> Vec<(sp_staking::EraIndex, sp_staking::EraInflation<Balance>)> {
+			Staking::api_eras_inflation()
+		}
+
+		fn inclusion_cutoff() -> Option<(AccountId, u64)> {
+			Staking::api_inclusion_cutoff()
+		}
+
+		fn was_included(who: AccountId) -> bool {
+			Staking::api_was_included(who)
+		}
 	}
 
 	impl sp_consensus_babe::BabeApi<Block> for Runtime {
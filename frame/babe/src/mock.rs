@@ -144,8 +144,8 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = frame_support::traits::ConstU32<1>;
 }
 
 pallet_staking_reward_curve::build! {
@@ -175,7 +175,9 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = Staking;
 	type WeightInfo = ();
 	type MaxWinners = ConstU32<100>;
+	type MaxBackersPerWinner = ConstU32<{ u32::MAX }>;
 	type Bounds = ElectionsBounds;
+	type SolverBudget = ();
 }
 
 impl pallet_staking::Config for Test {
@@ -204,6 +206,44 @@ impl pallet_staking::Config for Test {
 	type MaxUnlockingChunks = ConstU32<32>;
 	type HistoryDepth = ConstU32<84>;
 	type EventListeners = ();
+	type DeductPendingSlashes = frame_support::traits::ConstBool<false>;
+	type DeferOverweightElection = frame_support::traits::ConstBool<false>;
+	type LoyaltyBonus = ();
+	type MaxLoyaltyEras = frame_support::traits::ConstU32<0>;
+	type MaxExitsPerEra = ();
+	type ValidatorWaitlistSessions = ();
+	type VoterSamplingSeed = ();
+	type ValidatorShuffleSeed = ();
+	type WeightAccountant = System;
+	type RemainderPolicy = ();
+	type MaxRememberedOffences = frame_support::traits::ConstU32<1024>;
+	type MaxSlashesPerBlock = frame_support::traits::ConstU32<512>;
+	type MinNominatorBondShare = ();
+	type MinValidatorBondShare = ();
+	type EvictLowestNominators = ();
+	type LogTarget = pallet_staking::DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = ();
+	type MaxAutoPayoutsPerBlock = ();
+	type EraLength = ();
+	type ExternalExposures = ();
+	type ExposureOracle = frame_system::EnsureNever<()>;
+	type ValidatorLiveness = ();
+	type MaxVetoedWinners = ConstU32<0>;
+	type MinNominatorSlash = ();
+	type MaxRunnersUp = ConstU32<0>;
+	type DisablingStrategy = pallet_staking::DisableWhenAsked;
+	type ReplaceDisabledValidators = frame_support::traits::ConstBool<false>;
+	type MinUnbondingDuration = ();
+	type UnbondingQueueSaturation = ();
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = ();
+	type MaxEvictionScan = ();
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = ();
 	type BenchmarkingConfig = pallet_staking::TestBenchmarkingConfig;
 	type WeightInfo = ();
 }
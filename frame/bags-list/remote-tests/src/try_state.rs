@@ -41,6 +41,7 @@ pub async fn execute<Runtime, Block>(
 			hashed_prefixes: vec![
 				<pallet_staking::Bonded<Runtime>>::prefix_hash(),
 				<pallet_staking::Ledger<Runtime>>::prefix_hash(),
+				<pallet_staking::StakeOf<Runtime>>::prefix_hash(),
 			],
 			..Default::default()
 		}))
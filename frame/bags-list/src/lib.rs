@@ -123,7 +123,7 @@
 pub mod example {}
 
 use codec::FullCodec;
-use frame_election_provider_support::{ScoreProvider, SortedListProvider};
+use frame_election_provider_support::{RegenerationStatus, ScoreProvider, SortedListProvider};
 use frame_system::ensure_signed;
 use sp_runtime::traits::{AtLeast32BitUnsigned, Bounded, StaticLookup};
 use sp_std::prelude::*;
@@ -142,7 +142,7 @@ pub mod mock;
 mod tests;
 pub mod weights;
 
-pub use list::{notional_bag_for, Bag, List, ListError, Node};
+pub use list::{notional_bag_for, Bag, List, ListError, Node, RegenerationPhase};
 pub use pallet::*;
 pub use weights::WeightInfo;
 
@@ -261,6 +261,13 @@ pub mod pallet {
 	pub(crate) type ListBags<T: Config<I>, I: 'static = ()> =
 		StorageMap<_, Twox64Concat, T::Score, list::Bag<T, I>>;
 
+	/// The phase of the ongoing chunked regeneration of the list, if any.
+	///
+	/// See [`SortedListProvider::unsafe_regenerate_chunked`].
+	#[pallet::storage]
+	pub(crate) type Regeneration<T: Config<I>, I: 'static = ()> =
+		StorageValue<_, list::RegenerationPhase<T::AccountId>>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config<I>, I: 'static = ()> {
@@ -412,6 +419,18 @@ impl<T: Config<I>, I: 'static> SortedListProvider<T::AccountId> for Pallet<T, I>
 		Ok(Box::new(iter.map(|n| n.id().clone())))
 	}
 
+	fn iter_rev() -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(List::<T, I>::iter_rev().map(|n| n.id().clone()))
+	}
+
+	fn range(min: T::Score, max: T::Score) -> Box<dyn Iterator<Item = T::AccountId>> {
+		Box::new(List::<T, I>::range(min, max).map(|n| n.id().clone()))
+	}
+
+	fn position_of(id: &T::AccountId) -> Option<u32> {
+		List::<T, I>::position_of(id)
+	}
+
 	fn count() -> u32 {
 		ListNodes::<T, I>::count()
 	}
@@ -446,6 +465,16 @@ impl<T: Config<I>, I: 'static> SortedListProvider<T::AccountId> for Pallet<T, I>
 		List::<T, I>::unsafe_regenerate(all, score_of)
 	}
 
+	fn unsafe_regenerate_chunked(
+		all_from: Box<dyn Fn(Option<&T::AccountId>) -> Box<dyn Iterator<Item = T::AccountId>>>,
+		score_of: Box<dyn Fn(&T::AccountId) -> T::Score>,
+		limit: u32,
+	) -> RegenerationStatus {
+		// NOTE: This call is unsafe for the same reason as
+		// SortedListProvider::unsafe_regenerate_chunked.
+		List::<T, I>::unsafe_regenerate_chunked(all_from, score_of, limit)
+	}
+
 	#[cfg(feature = "try-runtime")]
 	fn try_state() -> Result<(), TryRuntimeError> {
 		Self::do_try_state()
@@ -26,14 +26,17 @@
 
 use crate::Config;
 use codec::{Decode, Encode, MaxEncodedLen};
-use frame_election_provider_support::ScoreProvider;
+use frame_election_provider_support::{RegenerationStatus, ScoreProvider};
 use frame_support::{
 	defensive, ensure,
 	traits::{Defensive, DefensiveOption, Get},
 	DefaultNoBound, PalletError,
 };
 use scale_info::TypeInfo;
-use sp_runtime::traits::{Bounded, Zero};
+use sp_runtime::{
+	traits::{Bounded, Zero},
+	RuntimeDebug,
+};
 use sp_std::{
 	boxed::Box,
 	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
@@ -101,6 +104,7 @@ impl<T: Config<I>, I: 'static> List<T, I> {
 		crate::ListBags::<T, I>::remove_all(None);
 		#[allow(deprecated)]
 		crate::ListNodes::<T, I>::remove_all();
+		crate::Regeneration::<T, I>::kill();
 	}
 
 	/// Regenerate all of the data from the given ids.
@@ -123,6 +127,52 @@ impl<T: Config<I>, I: 'static> List<T, I> {
 		Self::insert_many(all, score_of)
 	}
 
+	/// Regenerate all of the data from the given ids, over multiple calls that each remove or
+	/// insert at most `limit` ids.
+	///
+	/// See [`frame_election_provider_support::SortedListProvider::unsafe_regenerate_chunked`].
+	pub fn unsafe_regenerate_chunked(
+		all_from: Box<dyn Fn(Option<&T::AccountId>) -> Box<dyn Iterator<Item = T::AccountId>>>,
+		score_of: Box<dyn Fn(&T::AccountId) -> T::Score>,
+		limit: u32,
+	) -> RegenerationStatus {
+		let limit = limit.max(1) as usize;
+		let phase = crate::Regeneration::<T, I>::get().unwrap_or(RegenerationPhase::Clearing);
+
+		let mut last = match phase {
+			RegenerationPhase::Clearing => {
+				let removed = crate::ListNodes::<T, I>::iter_keys().take(limit).collect::<Vec<_>>();
+				removed.iter().for_each(crate::ListNodes::<T, I>::remove);
+				if removed.len() == limit {
+					crate::Regeneration::<T, I>::put(RegenerationPhase::Clearing);
+					return RegenerationStatus::InProgress
+				}
+
+				// there are at most as many bags as thresholds.
+				let _ = crate::ListBags::<T, I>::clear(u32::MAX, None);
+				None
+			},
+			RegenerationPhase::Inserting { last } => last,
+		};
+
+		let mut inserted = 0;
+		for id in all_from(last.as_ref()).take(limit) {
+			let score = score_of(&id);
+			// duplicates are skipped, as in `insert_many`.
+			let _ = Self::insert(id.clone(), score);
+			last = Some(id);
+			inserted += 1;
+		}
+
+		if inserted == limit {
+			crate::Regeneration::<T, I>::put(RegenerationPhase::Inserting { last });
+			RegenerationStatus::InProgress
+		} else {
+			crate::Regeneration::<T, I>::kill();
+			RegenerationStatus::Done
+		}
+	}
+
 	/// Migrate the list from one set of thresholds to another.
 	///
 	/// This should only be called as part of an intentional migration; it's fairly expensive.
@@ -241,6 +291,22 @@ impl<T: Config<I>, I: 'static> List<T, I> {
 	/// Full iteration can be expensive; it's recommended to limit the number of items with
 	/// `.take(n)`.
 	pub(crate) fn iter() -> impl Iterator<Item = Node<T, I>> {
+		Self::bag_uppers().filter_map(Bag::get).flat_map(|bag| bag.iter())
+	}
+
+	/// Iterate over all nodes in all bags in reverse order, from the tail of the lowest bag to the
+	/// head of the highest one.
+	pub(crate) fn iter_rev() -> impl Iterator<Item = Node<T, I>> {
+		Self::bag_uppers()
+			.collect::<Vec<_>>()
+			.into_iter()
+			.rev()
+			.filter_map(Bag::get)
+			.flat_map(|bag| bag.iter_rev())
+	}
+
+	/// Iterate over the upper thresholds of all bags, from the highest to the lowest.
+	fn bag_uppers() -> Box<dyn Iterator<Item = T::Score>> {
 		// We need a touch of special handling here: because we permit `T::BagThresholds` to
 		// omit the final bound, we need to ensure that we explicitly include that threshold in the
 		// list.
@@ -249,17 +315,41 @@ impl<T: Config<I>, I: 'static> List<T, I> {
 		// easier; they can just configure `type BagThresholds = ()`.
 		let thresholds = T::BagThresholds::get();
 		let iter = thresholds.iter().copied();
-		let iter: Box<dyn Iterator<Item = T::Score>> = if thresholds.last() ==
-			Some(&T::Score::max_value())
-		{
+		if thresholds.last() == Some(&T::Score::max_value()) {
 			// in the event that they included it, we can just pass the iterator through unchanged.
 			Box::new(iter.rev())
 		} else {
 			// otherwise, insert it here.
 			Box::new(iter.chain(iter::once(T::Score::max_value())).rev())
-		};
+		}
+	}
 
-		iter.filter_map(Bag::get).flat_map(|bag| bag.iter())
+	/// Iterate over the nodes whose score is within `min..=max`, in the order of the list.
+	///
+	/// Only the bags that such nodes belong to are visited, hence nodes that are in the wrong bag
+	/// because their score changed since they were last rebagged can be missed.
+	pub(crate) fn range(min: T::Score, max: T::Score) -> impl Iterator<Item = Node<T, I>> {
+		let (highest, lowest) = (notional_bag_for::<T, I>(max), notional_bag_for::<T, I>(min));
+		Self::bag_uppers()
+			.skip_while(move |upper| *upper > highest)
+			.take_while(move |upper| *upper >= lowest)
+			.filter_map(Bag::get)
+			.flat_map(|bag| bag.iter())
+			.filter(move |node| min <= node.score && node.score <= max)
+	}
+
+	/// The position of `id` in the list, counted from its head.
+	///
+	/// Only the nodes in front of `id` are visited.
+	pub(crate) fn position_of(id: &T::AccountId) -> Option<u32> {
+		let node = Node::<T, I>::get(id)?;
+		let in_higher_bags: usize = Self::bag_uppers()
+			.take_while(|upper| *upper > node.bag_upper)
+			.filter_map(Bag::get)
+			.map(|bag| bag.iter().count())
+			.sum();
+		let in_bag = sp_std::iter::successors(node.prev(), |prev| prev.prev()).count();
+		Some((in_higher_bags + in_bag) as u32)
 	}
 
 	/// Same as `iter`, but we start from a specific node.
@@ -586,6 +676,16 @@ impl<T: Config<I>, I: 'static> List<T, I> {
 	}
 }
 
+/// The phase of a regeneration of the list that spans multiple calls, see
+/// [`List::unsafe_regenerate_chunked`].
+#[derive(Encode, Decode, MaxEncodedLen, TypeInfo, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum RegenerationPhase<AccountId> {
+	/// The nodes of the previous list are being removed.
+	Clearing,
+	/// The ids are being inserted. `last` is the last one inserted so far, if any.
+	Inserting { last: Option<AccountId> },
+}
+
 /// A Bag is a doubly-linked list of ids, where each id is mapped to a [`Node`].
 ///
 /// Note that we maintain both head and tail pointers. While it would be possible to get away with
@@ -660,6 +760,11 @@ impl<T: Config<I>, I: 'static> Bag<T, I> {
 		sp_std::iter::successors(self.head(), |prev| prev.next())
 	}
 
+	/// Iterate over the nodes in this bag, from its tail to its head.
+	pub(crate) fn iter_rev(&self) -> impl Iterator<Item = Node<T, I>> {
+		sp_std::iter::successors(self.tail(), |next| next.prev())
+	}
+
 	/// Insert a new id into this bag.
 	///
 	/// This is private on purpose because it's naive: it doesn't check whether this is the
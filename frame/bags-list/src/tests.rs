@@ -18,7 +18,7 @@
 use frame_support::{assert_noop, assert_ok, assert_storage_noop, traits::IntegrityTest};
 
 use super::*;
-use frame_election_provider_support::{SortedListProvider, VoteWeight};
+use frame_election_provider_support::{RegenerationStatus, SortedListProvider, VoteWeight};
 use list::Bag;
 use mock::{test_utils::*, *};
 
@@ -560,6 +560,91 @@ mod sorted_list_provider {
 		});
 	}
 
+	#[test]
+	fn iter_rev_works() {
+		ExtBuilder::default().add_ids(vec![(5, 5), (6, 15)]).build_and_execute(|| {
+			// given
+			assert_eq!(BagsList::iter().collect::<Vec<_>>(), vec![2, 3, 4, 6, 1, 5]);
+
+			// then
+			assert_eq!(BagsList::iter_rev().collect::<Vec<_>>(), vec![5, 1, 6, 4, 3, 2]);
+		});
+	}
+
+	#[test]
+	fn range_works() {
+		ExtBuilder::default().add_ids(vec![(5, 5), (6, 15)]).build_and_execute(|| {
+			// given
+			assert_eq!(
+				List::<Runtime>::get_bags(),
+				vec![(10, vec![1, 5]), (20, vec![6]), (1000, vec![2, 3, 4])]
+			);
+
+			let range = |min, max| BagsList::range(min, max).collect::<Vec<_>>();
+			assert_eq!(range(0, VoteWeight::MAX), vec![2, 3, 4, 6, 1, 5]);
+			assert_eq!(range(10, 20), vec![6, 1]);
+			assert_eq!(range(6, 10), vec![1]);
+			assert_eq!(range(11, 999), vec![6]);
+			assert_eq!(range(1000, 1000), vec![2, 3, 4]);
+			assert!(range(20, 10).is_empty());
+			assert!(range(1001, VoteWeight::MAX).is_empty());
+		});
+	}
+
+	#[test]
+	fn position_of_works() {
+		ExtBuilder::default().add_ids(vec![(5, 5), (6, 15)]).build_and_execute(|| {
+			// given
+			assert_eq!(BagsList::iter().collect::<Vec<_>>(), vec![2, 3, 4, 6, 1, 5]);
+
+			assert_eq!(BagsList::position_of(&2), Some(0));
+			assert_eq!(BagsList::position_of(&4), Some(2));
+			assert_eq!(BagsList::position_of(&6), Some(3));
+			assert_eq!(BagsList::position_of(&1), Some(4));
+			assert_eq!(BagsList::position_of(&5), Some(5));
+			assert_eq!(BagsList::position_of(&7), None);
+		});
+	}
+
+	#[test]
+	fn unsafe_regenerate_chunked_works() {
+		ExtBuilder::default().build_and_execute(|| {
+			// given
+			assert_eq!(List::<Runtime>::get_bags(), vec![(10, vec![1]), (1000, vec![2, 3, 4])]);
+
+			let all_from = |last: Option<&AccountId>| -> Box<dyn Iterator<Item = AccountId>> {
+				let all = vec![5, 6, 7];
+				let start =
+					last.map_or(0, |last| all.iter().position(|id| id == last).unwrap() + 1);
+				Box::new(all.into_iter().skip(start))
+			};
+			let score_of = |id: &AccountId| match id {
+				5 => 5,
+				6 => 15,
+				_ => 1_000,
+			};
+			let regenerate =
+				|| BagsList::unsafe_regenerate_chunked(Box::new(all_from), Box::new(score_of), 2);
+
+			// the previous list is removed, two nodes at a time.
+			assert_eq!(regenerate(), RegenerationStatus::InProgress);
+			assert_eq!(BagsList::count(), 2);
+			assert_eq!(regenerate(), RegenerationStatus::InProgress);
+			assert_eq!(BagsList::count(), 0);
+
+			// then the new ids are inserted, two at a time.
+			assert_eq!(regenerate(), RegenerationStatus::InProgress);
+			assert_eq!(List::<Runtime>::get_bags(), vec![(10, vec![5]), (20, vec![6])]);
+			assert_eq!(regenerate(), RegenerationStatus::Done);
+			assert_eq!(
+				List::<Runtime>::get_bags(),
+				vec![(10, vec![5]), (20, vec![6]), (1_000, vec![7])]
+			);
+			assert_eq!(BagsList::count(), 3);
+			assert!(Regeneration::<Runtime>::get().is_none());
+		});
+	}
+
 	#[test]
 	fn count_works() {
 		ExtBuilder::default().build_and_execute(|| {
@@ -23,7 +23,7 @@ use frame_election_provider_support::{
 };
 use frame_support::{
 	construct_runtime, parameter_types,
-	traits::{ConstU16, ConstU32, ConstU64, KeyOwnerProofSystem, OnFinalize, OnInitialize},
+	traits::{ConstU16, ConstU32, ConstU64, KeyOwnerProofSystem},
 };
 use pallet_session::historical as pallet_session_historical;
 use sp_core::{crypto::KeyTypeId, ConstU128, H256};
@@ -158,8 +158,8 @@ impl pallet_balances::Config for Test {
 	type ExistentialDeposit = ConstU128<1>;
 	type AccountStore = System;
 	type WeightInfo = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = frame_support::traits::ConstU32<1>;
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
 }
@@ -197,7 +197,9 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = Staking;
 	type WeightInfo = ();
 	type MaxWinners = ConstU32<100>;
+	type MaxBackersPerWinner = ConstU32<{ u32::MAX }>;
 	type Bounds = ElectionsBoundsOnChain;
+	type SolverBudget = ();
 }
 
 impl pallet_staking::Config for Test {
@@ -226,6 +228,44 @@ impl pallet_staking::Config for Test {
 	type MaxUnlockingChunks = ConstU32<32>;
 	type HistoryDepth = ConstU32<84>;
 	type EventListeners = ();
+	type DeductPendingSlashes = frame_support::traits::ConstBool<false>;
+	type DeferOverweightElection = frame_support::traits::ConstBool<false>;
+	type LoyaltyBonus = ();
+	type MaxLoyaltyEras = frame_support::traits::ConstU32<0>;
+	type MaxExitsPerEra = ();
+	type ValidatorWaitlistSessions = ();
+	type VoterSamplingSeed = ();
+	type ValidatorShuffleSeed = ();
+	type WeightAccountant = System;
+	type RemainderPolicy = ();
+	type MaxRememberedOffences = frame_support::traits::ConstU32<1024>;
+	type MaxSlashesPerBlock = frame_support::traits::ConstU32<512>;
+	type MinNominatorBondShare = ();
+	type MinValidatorBondShare = ();
+	type EvictLowestNominators = ();
+	type LogTarget = pallet_staking::DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = ();
+	type MaxAutoPayoutsPerBlock = ();
+	type EraLength = ();
+	type ExternalExposures = ();
+	type ExposureOracle = frame_system::EnsureNever<()>;
+	type ValidatorLiveness = ();
+	type MaxVetoedWinners = ConstU32<0>;
+	type MinNominatorSlash = ();
+	type MaxRunnersUp = ConstU32<0>;
+	type DisablingStrategy = pallet_staking::DisableWhenAsked;
+	type ReplaceDisabledValidators = frame_support::traits::ConstBool<false>;
+	type MinUnbondingDuration = ();
+	type UnbondingQueueSaturation = ();
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = ();
+	type MaxEvictionScan = ();
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = ();
 	type BenchmarkingConfig = pallet_staking::TestBenchmarkingConfig;
 	type WeightInfo = ();
 }
@@ -301,29 +341,9 @@ pub fn new_test_ext_raw_authorities(authorities: Vec<BeefyId>) -> TestExternalit
 }
 
 pub fn start_session(session_index: SessionIndex) {
-	for i in Session::current_index()..session_index {
-		System::on_finalize(System::block_number());
-		Session::on_finalize(System::block_number());
-		Staking::on_finalize(System::block_number());
-		Beefy::on_finalize(System::block_number());
-
-		let parent_hash = if System::block_number() > 1 {
-			let hdr = System::finalize();
-			hdr.hash()
-		} else {
-			System::parent_hash()
-		};
-
-		System::reset_events();
-		System::initialize(&(i as u64 + 1), &parent_hash, &Default::default());
-		System::set_block_number((i + 1).into());
-		Timestamp::set_timestamp(System::block_number() * 6000);
-
-		System::on_initialize(System::block_number());
-		Session::on_initialize(System::block_number());
-		Staking::on_initialize(System::block_number());
-		Beefy::on_initialize(System::block_number());
-	}
+	pallet_staking::time_travel::advance_sessions_with::<Test, (System, Session, Staking, Beefy)>(
+		session_index.saturating_sub(Session::current_index()),
+	);
 
 	assert_eq!(Session::current_index(), session_index);
 }
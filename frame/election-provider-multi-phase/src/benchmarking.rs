@@ -20,7 +20,7 @@
 use super::*;
 use crate::{unsigned::IndexAssignmentOf, Pallet as MultiPhase};
 use frame_benchmarking::account;
-use frame_election_provider_support::bounds::DataProviderBounds;
+use frame_election_provider_support::{bounds::DataProviderBounds, Voter};
 use frame_support::{
 	assert_ok,
 	traits::{Hooks, TryCollect},
@@ -80,7 +80,7 @@ fn solution_with_size<T: Config>(
 				.try_collect()
 				.expect("<SolutionOf<T::MinerConfig>>::LIMIT is the correct bound; qed.");
 			let voter = frame_benchmarking::account::<T::AccountId>("Voter", i, SEED);
-			(voter, stake, winner_votes)
+			Voter::new(voter, stake, winner_votes)
 		})
 		.collect::<Vec<_>>();
 
@@ -98,7 +98,7 @@ fn solution_with_size<T: Config>(
 				.try_collect()
 				.expect("<SolutionOf<T::MinerConfig>>::LIMIT is the correct bound; qed.");
 			let voter = frame_benchmarking::account::<T::AccountId>("Voter", i, SEED);
-			(voter, stake, votes)
+			Voter::new(voter, stake, votes)
 		})
 		.collect::<Vec<_>>();
 
@@ -119,7 +119,7 @@ fn solution_with_size<T: Config>(
 
 	// write the snapshot to staking or whoever is the data provider, in case it is needed further
 	// down the road.
-	T::DataProvider::put_snapshot(all_voters.clone(), targets.clone(), Some(stake));
+	T::DataProvider::put_snapshot(all_voters.clone(), targets.clone(), Some(stake))?;
 
 	let cache = helpers::generate_voter_cache::<T::MinerConfig>(&all_voters);
 	let stake_of = helpers::stake_of_fn::<T::MinerConfig>(&all_voters, &cache);
@@ -130,7 +130,7 @@ fn solution_with_size<T: Config>(
 
 	let assignments = active_voters
 		.iter()
-		.map(|(voter, _stake, votes)| {
+		.map(|Voter { who: voter, targets: votes, .. }| {
 			let percent_per_edge: InnerOf<SolutionAccuracyOf<T>> =
 				(100 / votes.len()).try_into().unwrap_or_else(|_| panic!("failed to convert"));
 			crate::unsigned::Assignment::<T> {
@@ -156,7 +156,7 @@ fn solution_with_size<T: Config>(
 	Ok(RawSolution { solution, score, round })
 }
 
-fn set_up_data_provider<T: Config>(v: u32, t: u32) {
+fn set_up_data_provider<T: Config>(v: u32, t: u32) -> Result<(), &'static str> {
 	T::DataProvider::clear();
 	log!(
 		info,
@@ -183,11 +183,11 @@ fn set_up_data_provider<T: Config>(v: u32, t: u32) {
 	targets.truncate(<T::DataProvider as ElectionDataProvider>::MaxVotesPerVoter::get() as usize);
 
 	// fill voters.
-	(0..v).for_each(|i| {
+	(0..v).try_for_each(|i| {
 		let voter = frame_benchmarking::account::<T::AccountId>("Voter", i, SEED);
 		let weight = T::Currency::minimum_balance().saturated_into::<u64>() * 1000;
-		T::DataProvider::add_voter(voter, weight, targets.clone().try_into().unwrap());
-	});
+		T::DataProvider::add_voter(voter, weight, targets.clone().try_into().unwrap())
+	})
 }
 
 frame_benchmarking::benchmarks! {
@@ -270,7 +270,7 @@ frame_benchmarking::benchmarks! {
 		let t in (T::BenchmarkingConfig::TARGETS[0]) .. T::BenchmarkingConfig::TARGETS[1];
 
 		// we don't directly need the data-provider to be populated, but it is just easy to use it.
-		set_up_data_provider::<T>(v, t);
+		set_up_data_provider::<T>(v, t)?;
 		// default bounds are unbounded.
 		let targets = T::DataProvider::electable_targets(DataProviderBounds::default())?;
 		let voters = T::DataProvider::electing_voters(DataProviderBounds::default())?;
@@ -426,7 +426,7 @@ frame_benchmarking::benchmarks! {
 		// number of targets in snapshot. Fixed to maximum.
 		let t = T::BenchmarkingConfig::MAXIMUM_TARGETS;
 
-		set_up_data_provider::<T>(v, t);
+		set_up_data_provider::<T>(v, t)?;
 		let now = frame_system::Pallet::<T>::block_number();
 		<CurrentPhase<T>>::put(Phase::Unsigned((true, now)));
 		<MultiPhase::<T>>::create_snapshot().unwrap();
@@ -448,7 +448,7 @@ frame_benchmarking::benchmarks! {
 		// number of targets in snapshot. Fixed to maximum.
 		let t = T::BenchmarkingConfig::MAXIMUM_TARGETS;
 
-		set_up_data_provider::<T>(v, t);
+		set_up_data_provider::<T>(v, t)?;
 		assert!(<MultiPhase<T>>::snapshot().is_none());
 	}: {
 		<MultiPhase::<T>>::create_snapshot().map_err(|_| "could not create snapshot")?;
@@ -492,10 +492,7 @@ frame_benchmarking::benchmarks! {
 
 		// sort assignments by decreasing voter stake
 		assignments.sort_by_key(|crate::unsigned::Assignment::<T> { who, .. }| {
-			let stake = cache.get(who).map(|idx| {
-				let (_, stake, _) = voters[*idx];
-				stake
-			}).unwrap_or_default();
+			let stake = cache.get(who).map(|idx| voters[*idx].weight).unwrap_or_default();
 			sp_std::cmp::Reverse(stake)
 		});
 
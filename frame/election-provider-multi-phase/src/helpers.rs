@@ -51,8 +51,8 @@ pub fn generate_voter_cache<T: MinerConfig>(
 	snapshot: &Vec<MinerVoterOf<T>>,
 ) -> BTreeMap<T::AccountId, usize> {
 	let mut cache: BTreeMap<T::AccountId, usize> = BTreeMap::new();
-	snapshot.iter().enumerate().for_each(|(i, (x, _, _))| {
-		let _existed = cache.insert(x.clone(), i);
+	snapshot.iter().enumerate().for_each(|(i, voter)| {
+		let _existed = cache.insert(voter.who.clone(), i);
 		// if a duplicate exists, we only consider the last one. Defensive only, should never
 		// happen.
 		debug_assert!(_existed.is_none());
@@ -116,7 +116,7 @@ pub fn voter_index_fn_linear<T: MinerConfig>(
 	move |who| {
 		snapshot
 			.iter()
-			.position(|(x, _, _)| x == who)
+			.position(|voter| &voter.who == who)
 			.and_then(|i| <usize as TryInto<SolutionVoterIndexOf<T>>>::try_into(i).ok())
 	}
 }
@@ -167,7 +167,7 @@ pub fn voter_at_fn<T: MinerConfig>(
 	move |i| {
 		<SolutionVoterIndexOf<T> as TryInto<usize>>::try_into(i)
 			.ok()
-			.and_then(|i| snapshot.get(i).map(|(x, _, _)| x).cloned())
+			.and_then(|i| snapshot.get(i).map(|voter| voter.who.clone()))
 	}
 }
 
@@ -193,8 +193,8 @@ pub fn stake_of_fn_linear<T: MinerConfig>(
 	move |who| {
 		snapshot
 			.iter()
-			.find(|(x, _, _)| x == who)
-			.map(|(_, x, _)| *x)
+			.find(|voter| &voter.who == who)
+			.map(|voter| voter.weight)
 			.unwrap_or_default()
 	}
 }
@@ -211,7 +211,7 @@ pub fn stake_of_fn<'a, T: MinerConfig>(
 ) -> impl Fn(&T::AccountId) -> VoteWeight + 'a {
 	move |who| {
 		if let Some(index) = cache.get(who) {
-			snapshot.get(*index).map(|(_, x, _)| x).cloned().unwrap_or_default()
+			snapshot.get(*index).map(|voter| voter.weight).unwrap_or_default()
 		} else {
 			0
 		}
@@ -233,7 +233,7 @@ use codec::{Decode, Encode};
 use frame_election_provider_support::{
 	bounds::{CountBound, ElectionBounds, ElectionBoundsBuilder, SizeBound},
 	BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-	ElectionProviderBase, InstantElectionProvider, NposSolution,
+	ElectionProviderBase, InstantElectionProvider, NposSolution, WeightAccountant,
 };
 use frame_support::{
 	dispatch::DispatchClass,
@@ -248,7 +248,10 @@ use sp_arithmetic::{
 	traits::{CheckedAdd, Zero},
 	UpperOf,
 };
-use sp_npos_elections::{BoundedSupports, ElectionScore, IdentifierT, Supports, VoteWeight};
+use sp_npos_elections::{
+	BalancingConfig, BoundedSupports, ElectionScore, ExtendedBalance, IdentifierT, Supports,
+	VoteWeight,
+};
 use sp_runtime::{
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity,
@@ -431,17 +434,18 @@ impl<C: Default> Default for RawSolution<C> {
 	DefaultNoBound,
 	scale_info::TypeInfo,
 )]
-#[scale_info(skip_type_params(AccountId, MaxWinners))]
-pub struct ReadySolution<AccountId, MaxWinners>
+#[scale_info(skip_type_params(AccountId, MaxWinners, MaxBackersPerWinner))]
+pub struct ReadySolution<AccountId, MaxWinners, MaxBackersPerWinner>
 where
 	AccountId: IdentifierT,
 	MaxWinners: Get<u32>,
+	MaxBackersPerWinner: Get<u32>,
 {
 	/// The final supports of the solution.
 	///
 	/// This is target-major vector, storing each winners, total backing, and each individual
 	/// backer.
-	pub supports: BoundedSupports<AccountId, MaxWinners>,
+	pub supports: BoundedSupports<AccountId, MaxWinners, MaxBackersPerWinner>,
 	/// The score of the solution.
 	///
 	/// This is needed to potentially challenge the solution.
@@ -478,6 +482,42 @@ pub struct SolutionOrSnapshotSize {
 	pub targets: u32,
 }
 
+/// The balancing parameters of the solver, as stored in [`pallet::BalancingConfigOverride`].
+///
+/// This is the encodable counterpart of [`BalancingConfig`].
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Debug, TypeInfo)]
+pub struct StoredBalancingConfig {
+	/// The number of balancing iterations.
+	pub iterations: u32,
+	/// The tolerance of the balancing algorithm.
+	pub tolerance: ExtendedBalance,
+}
+
+impl From<StoredBalancingConfig> for BalancingConfig {
+	fn from(config: StoredBalancingConfig) -> Self {
+		BalancingConfig {
+			iterations: config.iterations.saturated_into(),
+			tolerance: config.tolerance,
+		}
+	}
+}
+
+/// A `Get<Option<BalancingConfig>>` which reads the balancing parameters from
+/// [`pallet::BalancingConfigOverride`], falling back to `Default` if none are stored.
+///
+/// Meant to be used as the `Balancing` parameter of the solvers of
+/// `frame_election_provider_support`, such that the balancing iterations can be tuned via
+/// [`Pallet::set_balancing_config`], without a runtime upgrade.
+pub struct BalancingOverride<T, Default>(sp_std::marker::PhantomData<(T, Default)>);
+
+impl<T: Config, Default: Get<Option<BalancingConfig>>> Get<Option<BalancingConfig>>
+	for BalancingOverride<T, Default>
+{
+	fn get() -> Option<BalancingConfig> {
+		<BalancingConfigOverride<T>>::get().map(Into::into).or_else(Default::get)
+	}
+}
+
 /// Internal errors of the pallet.
 ///
 /// Note that this is different from [`pallet::Error`].
@@ -556,6 +596,8 @@ pub enum FeasibilityError {
 	///
 	/// Should never happen under correct configurations.
 	BoundedConversionFailed,
+	/// A winner has more backers than `MaxBackersPerWinner`.
+	TooManyBackings,
 }
 
 impl From<sp_npos_elections::Error> for FeasibilityError {
@@ -564,6 +606,18 @@ impl From<sp_npos_elections::Error> for FeasibilityError {
 	}
 }
 
+impl From<frame_election_provider_support::FeasibilityCheckError> for FeasibilityError {
+	fn from(e: frame_election_provider_support::FeasibilityCheckError) -> Self {
+		use frame_election_provider_support::FeasibilityCheckError;
+		match e {
+			FeasibilityCheckError::WrongWinnerCount => FeasibilityError::WrongWinnerCount,
+			FeasibilityCheckError::InvalidVoter => FeasibilityError::InvalidVoter,
+			FeasibilityCheckError::InvalidVote => FeasibilityError::InvalidVote,
+			FeasibilityCheckError::NposElection(e) => FeasibilityError::NposElection(e),
+		}
+	}
+}
+
 pub use pallet::*;
 #[frame_support::pallet]
 pub mod pallet {
@@ -620,6 +674,7 @@ pub mod pallet {
 			AccountId = Self::AccountId,
 			MaxVotesPerVoter = <Self::DataProvider as ElectionDataProvider>::MaxVotesPerVoter,
 			MaxWinners = Self::MaxWinners,
+			MaxBackersPerWinner = Self::MaxBackersPerWinner,
 		>;
 
 		/// Maximum number of signed submissions that can be queued.
@@ -667,9 +722,18 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxWinners: Get<u32>;
 
+		/// The maximum number of backers of each winner that can be elected by this
+		/// `ElectionProvider` implementation.
+		///
+		/// Solutions in which a winner has more backers than this are not feasible.
+		#[pallet::constant]
+		type MaxBackersPerWinner: Get<u32>;
+
 		/// The maximum number of electing voters and electable targets to put in the snapshot.
 		/// At the moment, snapshots are only over a single block, but once multi-block elections
 		/// are introduced they will take place over multiple blocks.
+		///
+		/// Can be overridden at runtime via [`Pallet::set_snapshot_bounds`].
 		type ElectionBounds: Get<ElectionBounds>;
 
 		/// Handler for the slashed deposits.
@@ -690,6 +754,7 @@ pub mod pallet {
 			BlockNumber = BlockNumberFor<Self>,
 			DataProvider = Self::DataProvider,
 			MaxWinners = Self::MaxWinners,
+			MaxBackersPerWinner = Self::MaxBackersPerWinner,
 		>;
 
 		/// Configuration of the governance-only fallback.
@@ -701,6 +766,7 @@ pub mod pallet {
 			BlockNumber = BlockNumberFor<Self>,
 			DataProvider = Self::DataProvider,
 			MaxWinners = Self::MaxWinners,
+			MaxBackersPerWinner = Self::MaxBackersPerWinner,
 		>;
 
 		/// OCW election solution miner algorithm implementation.
@@ -713,6 +779,10 @@ pub mod pallet {
 		/// The configuration of benchmarking.
 		type BenchmarkingConfig: BenchmarkingConfig;
 
+		/// Something that accounts for the weight registered by the pallet outside of its
+		/// dispatches, e.g. while creating the snapshot. Usually the system pallet.
+		type WeightAccountant: WeightAccountant;
+
 		/// The weight of the pallet.
 		type WeightInfo: WeightInfo;
 	}
@@ -982,8 +1052,10 @@ pub mod pallet {
 			T::ForceOrigin::ensure_origin(origin)?;
 			ensure!(Self::current_phase().is_emergency(), <Error<T>>::CallNotAllowed);
 
-			// bound supports with T::MaxWinners
-			let supports = supports.try_into().map_err(|_| Error::<T>::TooManyWinners)?;
+			// bound supports with T::MaxWinners and T::MaxBackersPerWinner
+			ensure!(supports.len() as u32 <= T::MaxWinners::get(), Error::<T>::TooManyWinners);
+			let supports = sp_npos_elections::try_bound_supports(supports)
+				.map_err(|_| Error::<T>::BoundNotMet)?;
 
 			// Note: we don't `rotate_round` at this point; the next call to
 			// `ElectionProvider::elect` will succeed and take care of that.
@@ -1129,6 +1201,50 @@ pub mod pallet {
 			<QueuedSolution<T>>::put(solution);
 			Ok(())
 		}
+
+		/// Set the bounds of the voters and targets snapshots, overriding those of
+		/// [`Config::ElectionBounds`].
+		///
+		/// Dispatch origin must be aligned with `T::ForceOrigin`.
+		///
+		/// A `None` bound falls back to that of [`Config::ElectionBounds`]. The new bounds apply
+		/// from the next snapshot onwards.
+		#[pallet::call_index(5)]
+		#[pallet::weight(T::DbWeight::get().writes(2))]
+		pub fn set_snapshot_bounds(
+			origin: OriginFor<T>,
+			maybe_voters: Option<DataProviderBounds>,
+			maybe_targets: Option<DataProviderBounds>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			<VoterSnapshotBounds<T>>::set(maybe_voters);
+			<TargetSnapshotBounds<T>>::set(maybe_targets);
+
+			Self::deposit_event(Event::SnapshotBoundsSet {
+				voters: maybe_voters,
+				targets: maybe_targets,
+			});
+			Ok(())
+		}
+
+		/// Set the balancing parameters of the solvers reading them through
+		/// [`BalancingOverride`].
+		///
+		/// Dispatch origin must be aligned with `T::ForceOrigin`.
+		///
+		/// A `None` value falls back to the default balancing parameters of the solver.
+		#[pallet::call_index(6)]
+		#[pallet::weight(T::DbWeight::get().writes(1))]
+		pub fn set_balancing_config(
+			origin: OriginFor<T>,
+			maybe_config: Option<StoredBalancingConfig>,
+		) -> DispatchResult {
+			T::ForceOrigin::ensure_origin(origin)?;
+			<BalancingConfigOverride<T>>::set(maybe_config);
+
+			Self::deposit_event(Event::BalancingConfigSet { config: maybe_config });
+			Ok(())
+		}
 	}
 
 	#[pallet::event]
@@ -1162,6 +1278,15 @@ pub mod pallet {
 			to: Phase<BlockNumberFor<T>>,
 			round: u32,
 		},
+		/// The snapshot bounds were overridden. `None` means that of [`Config::ElectionBounds`]
+		/// applies.
+		SnapshotBoundsSet {
+			voters: Option<DataProviderBounds>,
+			targets: Option<DataProviderBounds>,
+		},
+		/// The balancing parameters were overridden. `None` means the default ones of the solver
+		/// apply.
+		BalancingConfigSet { config: Option<StoredBalancingConfig> },
 	}
 
 	/// Error of the pallet that can be returned in response to dispatches.
@@ -1271,7 +1396,7 @@ pub mod pallet {
 	#[pallet::storage]
 	#[pallet::getter(fn queued_solution)]
 	pub type QueuedSolution<T: Config> =
-		StorageValue<_, ReadySolution<T::AccountId, T::MaxWinners>>;
+		StorageValue<_, ReadySolution<T::AccountId, T::MaxWinners, T::MaxBackersPerWinner>>;
 
 	/// Snapshot data of the round.
 	///
@@ -1341,6 +1466,24 @@ pub mod pallet {
 	#[pallet::getter(fn minimum_untrusted_score)]
 	pub type MinimumUntrustedScore<T: Config> = StorageValue<_, ElectionScore>;
 
+	/// The bounds of the voters snapshot, overriding those of [`Config::ElectionBounds`].
+	///
+	/// Can be set via `set_snapshot_bounds`.
+	#[pallet::storage]
+	pub type VoterSnapshotBounds<T: Config> = StorageValue<_, DataProviderBounds>;
+
+	/// The bounds of the targets snapshot, overriding those of [`Config::ElectionBounds`].
+	///
+	/// Can be set via `set_snapshot_bounds`.
+	#[pallet::storage]
+	pub type TargetSnapshotBounds<T: Config> = StorageValue<_, DataProviderBounds>;
+
+	/// The balancing parameters read by [`BalancingOverride`].
+	///
+	/// Can be set via `set_balancing_config`.
+	#[pallet::storage]
+	pub type BalancingConfigOverride<T: Config> = StorageValue<_, StoredBalancingConfig>;
+
 	/// The current storage version.
 	///
 	/// v1: https://github.com/paritytech/substrate/pull/12237/
@@ -1423,12 +1566,22 @@ impl<T: Config> Pallet<T> {
 		sp_io::storage::set(&<Snapshot<T>>::hashed_key(), &buffer);
 	}
 
+	/// The bounds of the snapshot: [`Config::ElectionBounds`], overridden by
+	/// [`VoterSnapshotBounds`] and [`TargetSnapshotBounds`] where set.
+	pub fn election_bounds() -> ElectionBounds {
+		let bounds = T::ElectionBounds::get();
+		ElectionBoundsBuilder::from(bounds)
+			.voters(Some(<VoterSnapshotBounds<T>>::get().unwrap_or(bounds.voters)))
+			.targets(Some(<TargetSnapshotBounds<T>>::get().unwrap_or(bounds.targets)))
+			.build()
+	}
+
 	/// Parts of [`create_snapshot`] that happen outside of this pallet.
 	///
 	/// Extracted for easier weight calculation.
 	fn create_snapshot_external(
 	) -> Result<(Vec<T::AccountId>, Vec<VoterOf<T>>, u32), ElectionError<T>> {
-		let election_bounds = T::ElectionBounds::get();
+		let election_bounds = Self::election_bounds();
 
 		let targets = T::DataProvider::electable_targets(election_bounds.targets)
 			.and_then(|t| {
@@ -1478,7 +1631,7 @@ impl<T: Config> Pallet<T> {
 	/// Returns `Ok(())` if operation is okay.
 	///
 	/// This is a *self-weighing* function, it will register its own extra weight as
-	/// [`DispatchClass::Mandatory`] with the system pallet.
+	/// [`DispatchClass::Mandatory`] with [`Config::WeightAccountant`].
 	pub fn create_snapshot() -> Result<(), ElectionError<T>> {
 		// this is self-weighing itself..
 		let (targets, voters, desired_targets) = Self::create_snapshot_external()?;
@@ -1491,14 +1644,11 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// Register some amount of weight directly with the system pallet.
+	/// Register some amount of weight with [`Config::WeightAccountant`].
 	///
 	/// This is always mandatory weight.
 	fn register_weight(weight: Weight) {
-		<frame_system::Pallet<T>>::register_extra_weight_unchecked(
-			weight,
-			DispatchClass::Mandatory,
-		);
+		T::WeightAccountant::register_weight(weight);
 	}
 
 	/// Kill everything created by [`Pallet::create_snapshot`].
@@ -1512,7 +1662,8 @@ impl<T: Config> Pallet<T> {
 	pub fn feasibility_check(
 		raw_solution: RawSolution<SolutionOf<T::MinerConfig>>,
 		compute: ElectionCompute,
-	) -> Result<ReadySolution<T::AccountId, T::MaxWinners>, FeasibilityError> {
+	) -> Result<ReadySolution<T::AccountId, T::MaxWinners, T::MaxBackersPerWinner>, FeasibilityError>
+	{
 		let desired_targets =
 			Self::desired_targets().ok_or(FeasibilityError::SnapshotUnavailable)?;
 
@@ -1592,13 +1743,18 @@ impl<T: Config> Pallet<T> {
 	}
 
 	/// record the weight of the given `supports`.
-	fn weigh_supports(supports: &Supports<T::AccountId>) {
+	fn weigh_supports(supports: &BoundedSupportsOf<Self>) {
+		Self::register_weight(Self::supports_weight(supports));
+	}
+
+	/// The weight of electing `supports`.
+	fn supports_weight(supports: &BoundedSupportsOf<Self>) -> Weight {
 		let active_voters = supports
 			.iter()
 			.map(|(_, x)| x)
 			.fold(Zero::zero(), |acc, next| acc + next.voters.len() as u32);
 		let desired_targets = supports.len() as u32;
-		Self::register_weight(T::WeightInfo::elect_queued(active_voters, desired_targets));
+		T::WeightInfo::elect_queued(active_voters, desired_targets)
 	}
 }
 
@@ -1697,7 +1853,17 @@ impl<T: Config> ElectionProviderBase for Pallet<T> {
 	type BlockNumber = BlockNumberFor<T>;
 	type Error = ElectionError<T>;
 	type MaxWinners = T::MaxWinners;
+	type MaxBackersPerWinner = T::MaxBackersPerWinner;
 	type DataProvider = T::DataProvider;
+
+	/// The weight of electing the queued solution if there is one, else the estimate of the
+	/// fallback.
+	fn estimate_elect_weight() -> Weight {
+		match <QueuedSolution<T>>::get() {
+			Some(ReadySolution { supports, .. }) => Self::supports_weight(&supports),
+			None => T::Fallback::estimate_elect_weight(),
+		}
+	}
 }
 
 impl<T: Config> ElectionProvider for Pallet<T> {
@@ -1743,8 +1909,8 @@ mod feasibility_check {
 
 	use super::*;
 	use crate::mock::{
-		raw_solution, roll_to, EpochLength, ExtBuilder, MultiPhase, Runtime, SignedPhase,
-		TargetIndex, UnsignedPhase, VoterIndex,
+		raw_solution, roll_to, EpochLength, ExtBuilder, MaxBackersPerWinner, MultiPhase, Runtime,
+		SignedPhase, TargetIndex, UnsignedPhase, VoterIndex,
 	};
 	use frame_support::{assert_noop, assert_ok};
 
@@ -1935,6 +2101,24 @@ mod feasibility_check {
 			);
 		})
 	}
+
+	#[test]
+	fn winner_with_too_many_backers_is_infeasible() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to(<EpochLength>::get() - <SignedPhase>::get() - <UnsignedPhase>::get());
+			assert!(MultiPhase::current_phase().is_signed());
+
+			// all of the winners of the solution have at least 3 backers.
+			MaxBackersPerWinner::set(2);
+			assert_noop!(
+				MultiPhase::feasibility_check(raw_solution(), COMPUTE),
+				FeasibilityError::TooManyBackings
+			);
+
+			MaxBackersPerWinner::set(u32::MAX);
+			assert_ok!(MultiPhase::feasibility_check(raw_solution(), COMPUTE));
+		})
+	}
 }
 
 #[cfg(test)]
@@ -1943,13 +2127,26 @@ mod tests {
 	use crate::{
 		mock::{
 			multi_phase_events, raw_solution, roll_to, roll_to_signed, roll_to_unsigned, AccountId,
-			ElectionsBounds, ExtBuilder, MockWeightInfo, MockedWeightInfo, MultiPhase, Runtime,
-			RuntimeOrigin, SignedMaxSubmissions, System, TargetIndex, Targets, Voters,
+			AccountedWeight, ElectionsBounds, ExtBuilder, MockWeightInfo, MockedWeightInfo,
+			MultiPhase, Runtime, RuntimeOrigin, SignedMaxSubmissions, System, TargetIndex, Targets,
+			Voters,
 		},
 		Phase,
 	};
 	use frame_support::{assert_noop, assert_ok};
-	use sp_npos_elections::{BalancingConfig, Support};
+	use sp_npos_elections::{BalancingConfig, BoundedSupport};
+	use sp_runtime::bounded_vec;
+
+	#[test]
+	fn snapshot_weight_is_registered_with_the_weight_accountant() {
+		ExtBuilder::default().build_and_execute(|| {
+			assert_eq!(AccountedWeight::get(), Weight::zero());
+
+			roll_to_signed();
+			assert!(MultiPhase::snapshot().is_some());
+			assert!(AccountedWeight::get().any_gt(Weight::zero()));
+		})
+	}
 
 	#[test]
 	fn phase_rotation_works() {
@@ -2421,8 +2618,20 @@ mod tests {
 			assert_eq!(
 				supports,
 				vec![
-					(30, Support { total: 40, voters: vec![(2, 5), (4, 5), (30, 30)] }),
-					(40, Support { total: 60, voters: vec![(2, 5), (3, 10), (4, 5), (40, 40)] })
+					(
+						30,
+						BoundedSupport {
+							total: 40,
+							voters: bounded_vec![(2, 5), (4, 5), (30, 30)]
+						}
+					),
+					(
+						40,
+						BoundedSupport {
+							total: 60,
+							voters: bounded_vec![(2, 5), (3, 10), (4, 5), (40, 40)]
+						}
+					)
 				]
 			);
 
@@ -2582,6 +2791,75 @@ mod tests {
 		});
 	}
 
+	#[test]
+	fn snapshot_bounds_can_be_set_at_runtime() {
+		ExtBuilder::default().build_and_execute(|| {
+			let new_bounds = ElectionBoundsBuilder::default().targets_count(1_000.into()).build();
+
+			// no single account can set the bounds.
+			assert_noop!(
+				MultiPhase::set_snapshot_bounds(
+					RuntimeOrigin::signed(99),
+					None,
+					Some(new_bounds.targets)
+				),
+				DispatchError::BadOrigin
+			);
+
+			// only root can, and the bounds left out fall back to those of the config.
+			assert_ok!(MultiPhase::set_snapshot_bounds(
+				RuntimeOrigin::root(),
+				None,
+				Some(new_bounds.targets)
+			));
+			assert_eq!(MultiPhase::election_bounds().targets, new_bounds.targets);
+			assert_eq!(MultiPhase::election_bounds().voters, ElectionsBounds::get().voters);
+			assert_eq!(
+				multi_phase_events(),
+				vec![Event::SnapshotBoundsSet { voters: None, targets: Some(new_bounds.targets) }]
+			);
+
+			// the snapshot respects them: too many targets, the signed phase fails to open.
+			Targets::set((0..(1_000 as AccountId) + 1).collect::<Vec<_>>());
+			roll_to(15);
+			assert_eq!(MultiPhase::current_phase(), Phase::Off);
+
+			// they can be reset.
+			assert_ok!(MultiPhase::set_snapshot_bounds(RuntimeOrigin::root(), None, None));
+			assert_eq!(MultiPhase::election_bounds().targets, ElectionsBounds::get().targets);
+		})
+	}
+
+	#[test]
+	fn balancing_config_can_be_set_at_runtime() {
+		ExtBuilder::default().build_and_execute(|| {
+			type Balancer = BalancingOverride<Runtime, crate::mock::Balancing>;
+			let config = StoredBalancingConfig { iterations: 5, tolerance: 10 };
+
+			// by default, that of the config applies.
+			crate::mock::Balancing::set(Some(BalancingConfig { iterations: 2, tolerance: 0 }));
+			assert_eq!(Balancer::get().map(|c| (c.iterations, c.tolerance)), Some((2, 0)));
+
+			// no single account can override it.
+			assert_noop!(
+				MultiPhase::set_balancing_config(RuntimeOrigin::signed(99), Some(config)),
+				DispatchError::BadOrigin
+			);
+
+			// only root can.
+			assert_ok!(MultiPhase::set_balancing_config(RuntimeOrigin::root(), Some(config)));
+			assert_eq!(Balancer::get().map(|c| (c.iterations, c.tolerance)), Some((5, 10)));
+			assert_eq!(
+				multi_phase_events(),
+				vec![Event::BalancingConfigSet { config: Some(config) }]
+			);
+
+			// and it can be reset.
+			assert_ok!(MultiPhase::set_balancing_config(RuntimeOrigin::root(), None));
+			assert_eq!(Balancer::get().map(|c| (c.iterations, c.tolerance)), Some((2, 0)));
+		})
+	}
+
 	#[test]
 	fn snapshot_too_big_failure_no_fallback() {
 		// and if the backup mode is nothing, we go into the emergency mode..
@@ -19,7 +19,7 @@ use super::*;
 use crate::{self as multi_phase, unsigned::MinerConfig};
 use frame_election_provider_support::{
 	bounds::{DataProviderBounds, ElectionBounds},
-	data_provider, onchain, ElectionDataProvider, NposSolution, SequentialPhragmen,
+	data_provider, onchain, ElectionDataProvider, NposSolution, SequentialPhragmen, Voter,
 };
 pub use frame_support::{assert_noop, assert_ok, pallet_prelude::GetDefault};
 use frame_support::{
@@ -134,7 +134,7 @@ pub struct TrimHelpers {
 pub fn trim_helpers() -> TrimHelpers {
 	let RoundSnapshot { voters, targets } = MultiPhase::snapshot().unwrap();
 	let stakes: std::collections::HashMap<_, _> =
-		voters.iter().map(|(id, stake, _)| (*id, *stake)).collect();
+		voters.iter().map(|voter| (voter.who, voter.weight)).collect();
 
 	// Compute the size of a solution comprised of the selected arguments.
 	//
@@ -267,15 +267,15 @@ pub enum MockedWeightInfo {
 parameter_types! {
 	pub static Targets: Vec<AccountId> = vec![10, 20, 30, 40];
 	pub static Voters: Vec<VoterOf<Runtime>> = vec![
-		(1, 10, bounded_vec![10, 20]),
-		(2, 10, bounded_vec![30, 40]),
-		(3, 10, bounded_vec![40]),
-		(4, 10, bounded_vec![10, 20, 30, 40]),
+		Voter::new(1, 10, bounded_vec![10, 20]),
+		Voter::new(2, 10, bounded_vec![30, 40]),
+		Voter::new(3, 10, bounded_vec![40]),
+		Voter::new(4, 10, bounded_vec![10, 20, 30, 40]),
 		// self votes.
-		(10, 10, bounded_vec![10]),
-		(20, 20, bounded_vec![20]),
-		(30, 30, bounded_vec![30]),
-		(40, 40, bounded_vec![40]),
+		Voter::new(10, 10, bounded_vec![10]),
+		Voter::new(20, 20, bounded_vec![20]),
+		Voter::new(30, 30, bounded_vec![30]),
+		Voter::new(40, 40, bounded_vec![40]),
 	];
 
 	pub static DesiredTargets: u32 = 2;
@@ -300,6 +300,8 @@ parameter_types! {
 
 	#[derive(Debug)]
 	pub static MaxWinners: u32 = 200;
+	#[derive(Debug)]
+	pub static MaxBackersPerWinner: u32 = u32::MAX;
 	// `ElectionBounds` and `OnChainElectionsBounds` are defined separately to set them independently in the tests.
 	pub static ElectionsBounds: ElectionBounds = ElectionBoundsBuilder::default().build();
 	pub static OnChainElectionsBounds: ElectionBounds = ElectionBoundsBuilder::default().build();
@@ -314,7 +316,22 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = StakingMock;
 	type WeightInfo = ();
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type Bounds = OnChainElectionsBounds;
+	type SolverBudget = ();
+}
+
+parameter_types! {
+	pub static AccountedWeight: Weight = Weight::zero();
+}
+
+/// Registers weight with the system pallet, keeping track of it in [`AccountedWeight`].
+pub struct TracingWeightAccountant;
+impl WeightAccountant for TracingWeightAccountant {
+	fn register_weight(weight: Weight) {
+		AccountedWeight::set(AccountedWeight::get().saturating_add(weight));
+		<System as WeightAccountant>::register_weight(weight);
+	}
 }
 
 pub struct MockFallback;
@@ -324,6 +341,7 @@ impl ElectionProviderBase for MockFallback {
 	type Error = &'static str;
 	type DataProvider = StakingMock;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 }
 
 impl InstantElectionProvider for MockFallback {
@@ -341,6 +359,23 @@ impl InstantElectionProvider for MockFallback {
 			Err("NoFallback.")
 		}
 	}
+
+	fn instant_elect_with_inputs(
+		voters: Vec<frame_election_provider_support::VoterOf<StakingMock>>,
+		targets: Vec<AccountId>,
+		desired_targets: u32,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		if OnChainFallback::get() {
+			onchain::OnChainExecution::<OnChainSeqPhragmen>::instant_elect_with_inputs(
+				voters,
+				targets,
+				desired_targets,
+			)
+			.map_err(|_| "onchain::OnChainExecution failed.")
+		} else {
+			Err("NoFallback.")
+		}
+	}
 }
 
 parameter_types! {
@@ -366,6 +401,7 @@ impl MinerConfig for Runtime {
 	type MaxWeight = MinerMaxWeight;
 	type MaxVotesPerVoter = <StakingMock as ElectionDataProvider>::MaxVotesPerVoter;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type Solution = TestNposSolution;
 
 	fn solution_weight(v: u32, t: u32, a: u32, d: u32) -> Weight {
@@ -409,9 +445,11 @@ impl crate::Config for Runtime {
 		frame_election_provider_support::onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type MinerConfig = Self;
 	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, Balancing>;
 	type ElectionBounds = ElectionsBounds;
+	type WeightAccountant = TracingWeightAccountant;
 }
 
 impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime
@@ -476,9 +514,10 @@ impl ElectionDataProvider for StakingMock {
 		voters: Vec<VoterOf<Runtime>>,
 		targets: Vec<AccountId>,
 		_target_stake: Option<VoteWeight>,
-	) {
+	) -> data_provider::Result<()> {
 		Targets::set(targets);
 		Voters::set(voters);
+		Ok(())
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -492,10 +531,11 @@ impl ElectionDataProvider for StakingMock {
 		voter: AccountId,
 		weight: VoteWeight,
 		targets: frame_support::BoundedVec<AccountId, Self::MaxVotesPerVoter>,
-	) {
+	) -> data_provider::Result<()> {
 		let mut current = Voters::get();
-		current.push((voter, weight, targets));
+		current.push(Voter::new(voter, weight, targets));
 		Voters::set(current);
+		Ok(())
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -546,7 +586,7 @@ impl ExtBuilder {
 		stake: Balance,
 		targets: BoundedVec<AccountId, MaxNominations>,
 	) -> Self {
-		VOTERS.with(|v| v.borrow_mut().push((who, stake, targets)));
+		VOTERS.with(|v| v.borrow_mut().push(Voter::new(who, stake, targets)));
 		self
 	}
 	pub fn signed_max_submission(self, count: u32) -> Self {
@@ -22,12 +22,11 @@ use crate::{
 	ReadySolution, RoundSnapshot, SolutionAccuracyOf, SolutionOf, SolutionOrSnapshotSize, Weight,
 };
 use codec::Encode;
-use frame_election_provider_support::{NposSolution, NposSolver, PerThing128, VoteWeight};
+use frame_election_provider_support::{NposSolution, NposSolver, PerThing128};
 use frame_support::{
 	dispatch::DispatchResult,
 	ensure,
 	traits::{DefensiveResult, Get},
-	BoundedVec,
 };
 use frame_system::{offchain::SubmitTransaction, pallet_prelude::BlockNumberFor};
 use scale_info::TypeInfo;
@@ -39,7 +38,7 @@ use sp_runtime::{
 	offchain::storage::{MutateStorageError, StorageValueRef},
 	DispatchError, SaturatedConversion,
 };
-use sp_std::prelude::*;
+use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 
 /// Storage key used to store the last block number at which offchain worker ran.
 pub(crate) const OFFCHAIN_LAST_BLOCK: &[u8] = b"parity/multi-phase-unsigned-election";
@@ -381,7 +380,7 @@ pub trait MinerConfig {
 		+ NposSolution
 		+ TypeInfo;
 	/// Maximum number of votes per voter in the snapshots.
-	type MaxVotesPerVoter;
+	type MaxVotesPerVoter: Get<u32>;
 	/// Maximum length of the solution that the miner is allowed to generate.
 	///
 	/// Solutions are trimmed to respect this.
@@ -394,6 +393,8 @@ pub trait MinerConfig {
 	type MaxWeight: Get<Weight>;
 	/// The maximum number of winners that can be elected.
 	type MaxWinners: Get<u32>;
+	/// The maximum number of backers of each winner.
+	type MaxBackersPerWinner: Get<u32>;
 	/// Something that can compute the weight of a solution.
 	///
 	/// This weight estimate is then used to trim the solution, based on [`MinerConfig::MaxWeight`].
@@ -405,7 +406,7 @@ pub struct Miner<T: MinerConfig>(sp_std::marker::PhantomData<T>);
 impl<T: MinerConfig> Miner<T> {
 	/// Same as [`Pallet::mine_solution`], but the input snapshot data must be given.
 	pub fn mine_solution_with_snapshot<S>(
-		voters: Vec<(T::AccountId, VoteWeight, BoundedVec<T::AccountId, T::MaxVotesPerVoter>)>,
+		voters: Vec<MinerVoterOf<T>>,
 		targets: Vec<T::AccountId>,
 		desired_targets: u32,
 	) -> Result<(SolutionOf<T>, ElectionScore, SolutionOrSnapshotSize), MinerError>
@@ -433,7 +434,7 @@ impl<T: MinerConfig> Miner<T> {
 	/// Will always reduce the solution as well.
 	pub fn prepare_election_result_with_snapshot<Accuracy: PerThing128>(
 		election_result: ElectionResult<T::AccountId, Accuracy>,
-		voters: Vec<(T::AccountId, VoteWeight, BoundedVec<T::AccountId, T::MaxVotesPerVoter>)>,
+		voters: Vec<MinerVoterOf<T>>,
 		targets: Vec<T::AccountId>,
 		desired_targets: u32,
 	) -> Result<(SolutionOf<T>, ElectionScore, SolutionOrSnapshotSize), MinerError> {
@@ -470,17 +471,15 @@ impl<T: MinerConfig> Miner<T> {
 					// though staked assignments are expressed in terms of absolute stake, we'd
 					// still need to iterate over all votes in order to actually compute the total
 					// stake. it should be faster to look it up from the cache.
-					let stake = cache
-						.get(who)
-						.map(|idx| {
-							let (_, stake, _) = voters[*idx];
-							stake
-						})
-						.unwrap_or_default();
+					let stake = cache.get(who).map(|idx| voters[*idx].weight).unwrap_or_default();
 					sp_std::cmp::Reverse(stake)
 				},
 			);
 
+			// keep the backers with the most stake of each winner, as a solution with more
+			// than `MaxBackersPerWinner` backers for a winner is not feasible.
+			Self::trim_assignments_backers(&mut staked);
+
 			// convert back.
 			assignment_staked_to_ratio_normalized(staked)?
 		};
@@ -516,6 +515,41 @@ impl<T: MinerConfig> Miner<T> {
 		Ok((solution, score, size))
 	}
 
+	/// Remove the edges of `assignments` to the winners that already have
+	/// [`MinerConfig::MaxBackersPerWinner`] backers, dropping the voters left without any.
+	///
+	/// The assignments are expected to be sorted by decreasing voter stake, so that each winner
+	/// keeps the backers with the most stake. The stake of a voter that loses an edge is spread
+	/// over its remaining edges once converted back to ratios.
+	///
+	/// The score must be computed **after** this step.
+	pub fn trim_assignments_backers(
+		assignments: &mut Vec<sp_npos_elections::StakedAssignment<T::AccountId>>,
+	) {
+		let max_backers = T::MaxBackersPerWinner::get();
+		let mut backers = BTreeMap::<T::AccountId, u32>::new();
+		let mut removed = 0usize;
+		assignments.retain_mut(|assignment| {
+			assignment.distribution.retain(|(target, _)| {
+				let count = backers.entry(target.clone()).or_default();
+				if *count < max_backers {
+					*count += 1;
+					true
+				} else {
+					removed += 1;
+					false
+				}
+			});
+			!assignment.distribution.is_empty()
+		});
+		log_no_system!(
+			debug,
+			"removed {} edges to winners with more than {} backers",
+			removed,
+			max_backers,
+		);
+	}
+
 	/// Greedily reduce the size of the solution to fit into the block w.r.t length.
 	///
 	/// The length of the solution is largely a function of the number of voters. The number of
@@ -705,7 +739,8 @@ impl<T: MinerConfig> Miner<T> {
 		snapshot: RoundSnapshot<T::AccountId, MinerVoterOf<T>>,
 		current_round: u32,
 		minimum_untrusted_score: Option<ElectionScore>,
-	) -> Result<ReadySolution<T::AccountId, T::MaxWinners>, FeasibilityError> {
+	) -> Result<ReadySolution<T::AccountId, T::MaxWinners, T::MaxBackersPerWinner>, FeasibilityError>
+	{
 		let RawSolution { solution, score, round } = raw_solution;
 		let RoundSnapshot { voters: snapshot_voters, targets: snapshot_targets } = snapshot;
 
@@ -728,55 +763,30 @@ impl<T: MinerConfig> Miner<T> {
 			FeasibilityError::UntrustedScoreTooLow
 		);
 
-		// ----- Start building. First, we need some closures.
-		let cache = helpers::generate_voter_cache::<T>(&snapshot_voters);
-		let voter_at = helpers::voter_at_fn::<T>(&snapshot_voters);
-		let target_at = helpers::target_at_fn::<T>(&snapshot_targets);
-		let voter_index = helpers::voter_index_fn_usize::<T>(&cache);
-
-		// Then convert solution -> assignment. This will fail if any of the indices are gibberish,
-		// namely any of the voters or targets.
-		let assignments = solution
-			.into_assignment(voter_at, target_at)
-			.map_err::<FeasibilityError, _>(Into::into)?;
-
-		// Ensure that assignments is correct.
-		let _ = assignments.iter().try_for_each(|assignment| {
-			// Check that assignment.who is actually a voter (defensive-only).
-			// NOTE: while using the index map from `voter_index` is better than a blind linear
-			// search, this *still* has room for optimization. Note that we had the index when
-			// we did `solution -> assignment` and we lost it. Ideal is to keep the index
-			// around.
-
-			// Defensive-only: must exist in the snapshot.
-			let snapshot_index =
-				voter_index(&assignment.who).ok_or(FeasibilityError::InvalidVoter)?;
-			// Defensive-only: index comes from the snapshot, must exist.
-			let (_voter, _stake, targets) =
-				snapshot_voters.get(snapshot_index).ok_or(FeasibilityError::InvalidVoter)?;
-
-			// Check that all of the targets are valid based on the snapshot.
-			if assignment.distribution.iter().any(|(d, _)| !targets.contains(d)) {
-				return Err(FeasibilityError::InvalidVote)
-			}
-			Ok(())
-		})?;
-
-		// ----- Start building support. First, we need one more closure.
-		let stake_of = helpers::stake_of_fn::<T>(&snapshot_voters, &cache);
-
-		// This might fail if the normalization fails. Very unlikely. See `integrity_test`.
-		let staked_assignments = assignment_ratio_to_staked_normalized(assignments, stake_of)
-			.map_err::<FeasibilityError, _>(Into::into)?;
-		let supports = sp_npos_elections::to_supports(&staked_assignments);
+		// ----- Rebuild the supports from the snapshot. This will fail if any of the indices are
+		// gibberish, or any of the edges are not votes in the snapshot.
+		let supports = frame_election_provider_support::feasibility_check(
+			solution,
+			&snapshot_voters,
+			&snapshot_targets,
+			desired_targets,
+		)
+		.map_err::<FeasibilityError, _>(Into::into)?;
 
 		// Finally, check that the claimed score was indeed correct.
 		let known_score = supports.evaluate();
 		ensure!(known_score == score, FeasibilityError::InvalidScore);
 
+		// Ensure that no winner has more backers than `MaxBackersPerWinner`.
+		ensure!(
+			supports
+				.iter()
+				.all(|(_, support)| support.voters.len() as u32 <= T::MaxBackersPerWinner::get()),
+			FeasibilityError::TooManyBackings
+		);
+
 		// Size of winners in miner solution is equal to `desired_targets` <= `MaxWinners`.
-		let supports = supports
-			.try_into()
+		let supports = sp_npos_elections::try_bound_supports(supports)
 			.defensive_map_err(|_| FeasibilityError::BoundedConversionFailed)?;
 
 		Ok(ReadySolution { supports, compute, score })
@@ -975,9 +985,9 @@ mod tests {
 	use crate::{
 		mock::{
 			multi_phase_events, roll_to, roll_to_signed, roll_to_unsigned, roll_to_with_ocw,
-			trim_helpers, witness, BlockNumber, ExtBuilder, Extrinsic, MinerMaxWeight, MultiPhase,
-			Runtime, RuntimeCall, RuntimeOrigin, System, TestNposSolution, TrimHelpers,
-			UnsignedPhase,
+			trim_helpers, witness, BlockNumber, ExtBuilder, Extrinsic, MaxBackersPerWinner,
+			MinerMaxWeight, MultiPhase, Runtime, RuntimeCall, RuntimeOrigin, System,
+			TestNposSolution, TrimHelpers, UnsignedPhase,
 		},
 		Event, InvalidTransaction, Phase, QueuedSolution, TransactionSource,
 		TransactionValidityError,
@@ -1295,6 +1305,21 @@ mod tests {
 			})
 	}
 
+	#[test]
+	fn miner_trims_backers_per_winner() {
+		ExtBuilder::default().build_and_execute(|| {
+			roll_to_unsigned();
+			assert!(MultiPhase::current_phase().is_unsigned());
+
+			// the miner keeps the solution feasible by trimming the backers of each winner.
+			MaxBackersPerWinner::set(2);
+			let (raw, _) = MultiPhase::mine_solution().unwrap();
+			let ready = MultiPhase::feasibility_check(raw, ElectionCompute::Unsigned).unwrap();
+			assert!(ready.supports.iter().all(|(_, support)| support.voters.len() <= 2));
+			MaxBackersPerWinner::set(u32::MAX);
+		})
+	}
+
 	#[test]
 	fn miner_will_not_submit_if_not_enough_winners() {
 		let (mut ext, _) = ExtBuilder::default().desired_targets(8).build_offchainify(0);
@@ -1821,7 +1846,7 @@ mod tests {
 			let count = assignments.len();
 			let min_stake_voter = voters
 				.iter()
-				.map(|(id, weight, _)| (weight, id))
+				.map(|voter| (voter.weight, &voter.who))
 				.min()
 				.and_then(|(_, id)| voter_index(id))
 				.unwrap();
@@ -181,6 +181,8 @@ parameter_types! {
 	pub static TransactionPriority: transaction_validity::TransactionPriority = 1;
 	#[derive(Debug)]
 	pub static MaxWinners: u32 = 100;
+	#[derive(Debug)]
+	pub static MaxBackersPerWinner: u32 = u32::MAX;
 	pub static ElectionBounds: frame_election_provider_support::bounds::ElectionBounds = ElectionBoundsBuilder::default()
 		.voters_count(1_000.into()).targets_count(1_000.into()).build();
 }
@@ -206,15 +208,22 @@ impl pallet_election_provider_multi_phase::Config for Runtime {
 	type SlashHandler = ();
 	type RewardHandler = ();
 	type DataProvider = Staking;
-	type Fallback =
-		frame_election_provider_support::NoElection<(AccountId, BlockNumber, Staking, MaxWinners)>;
+	type Fallback = frame_election_provider_support::NoElection<(
+		AccountId,
+		BlockNumber,
+		Staking,
+		MaxWinners,
+		MaxBackersPerWinner,
+	)>;
 	type GovernanceFallback = onchain::OnChainExecution<OnChainSeqPhragmen>;
 	type Solver = SequentialPhragmen<AccountId, SolutionAccuracyOf<Runtime>, ()>;
 	type ForceOrigin = EnsureRoot<AccountId>;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type ElectionBounds = ElectionBounds;
 	type BenchmarkingConfig = NoopElectionProviderBenchmarkConfig;
 	type WeightInfo = ();
+	type WeightAccountant = System;
 }
 
 impl MinerConfig for Runtime {
@@ -225,6 +234,7 @@ impl MinerConfig for Runtime {
 	type MaxLength = MinerMaxLength;
 	type MaxWeight = MinerMaxWeight;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 
 	fn solution_weight(_v: u32, _t: u32, _a: u32, _d: u32) -> Weight {
 		Weight::zero()
@@ -280,6 +290,44 @@ impl pallet_staking::Config for Runtime {
 	type MaxUnlockingChunks = ConstU32<32>;
 	type HistoryDepth = HistoryDepth;
 	type EventListeners = ();
+	type DeductPendingSlashes = frame_support::traits::ConstBool<false>;
+	type DeferOverweightElection = frame_support::traits::ConstBool<false>;
+	type LoyaltyBonus = ();
+	type MaxLoyaltyEras = frame_support::traits::ConstU32<0>;
+	type MaxExitsPerEra = ();
+	type ValidatorWaitlistSessions = ();
+	type VoterSamplingSeed = ();
+	type ValidatorShuffleSeed = ();
+	type WeightAccountant = System;
+	type RemainderPolicy = ();
+	type MaxRememberedOffences = frame_support::traits::ConstU32<1024>;
+	type MaxSlashesPerBlock = frame_support::traits::ConstU32<512>;
+	type MinNominatorBondShare = ();
+	type MinValidatorBondShare = ();
+	type EvictLowestNominators = ();
+	type LogTarget = pallet_staking::DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = ();
+	type MaxAutoPayoutsPerBlock = ();
+	type EraLength = ();
+	type ExternalExposures = ();
+	type ExposureOracle = frame_system::EnsureNever<()>;
+	type ValidatorLiveness = ();
+	type MaxVetoedWinners = ConstU32<0>;
+	type MinNominatorSlash = ();
+	type MaxRunnersUp = ConstU32<0>;
+	type DisablingStrategy = pallet_staking::DisableWhenAsked;
+	type ReplaceDisabledValidators = frame_support::traits::ConstBool<false>;
+	type MinUnbondingDuration = ();
+	type UnbondingQueueSaturation = ();
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = ();
+	type MaxEvictionScan = ();
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = ();
 	type WeightInfo = pallet_staking::weights::SubstrateWeight<Runtime>;
 	type BenchmarkingConfig = pallet_staking::TestBenchmarkingConfig;
 }
@@ -308,7 +356,9 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = Staking;
 	type WeightInfo = ();
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type Bounds = ElectionBounds;
+	type SolverBudget = ();
 }
 
 pub struct NoopElectionProviderBenchmarkConfig;
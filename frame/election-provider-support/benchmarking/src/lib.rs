@@ -23,7 +23,10 @@
 
 use codec::Decode;
 use frame_benchmarking::v1::benchmarks;
-use frame_election_provider_support::{NposSolver, PhragMMS, SequentialPhragmen};
+use frame_election_provider_support::{
+	BoundedVec, NposSolver, PhragMMS, SequentialPhragmen, Voter,
+};
+use sp_runtime::traits::ConstU32;
 use sp_std::vec::Vec;
 
 pub struct Pallet<T: Config>(frame_system::Pallet<T>);
@@ -33,12 +36,14 @@ const VOTERS: [u32; 2] = [1_000, 2_000];
 const TARGETS: [u32; 2] = [500, 1_000];
 const VOTES_PER_VOTER: [u32; 2] = [5, 16];
 
+type MaxVotesPerVoter = ConstU32<{ VOTES_PER_VOTER[1] }>;
+
 const SEED: u32 = 999;
 fn set_up_voters_targets<AccountId: Decode + Clone>(
 	voters_len: u32,
 	targets_len: u32,
 	degree: usize,
-) -> (Vec<(AccountId, u64, impl IntoIterator<Item = AccountId>)>, Vec<AccountId>) {
+) -> (Vec<Voter<AccountId, MaxVotesPerVoter>>, Vec<AccountId>) {
 	// fill targets.
 	let mut targets = (0..targets_len)
 		.map(|i| frame_benchmarking::account::<AccountId>("Target", i, SEED))
@@ -50,7 +55,8 @@ fn set_up_voters_targets<AccountId: Decode + Clone>(
 	let voters = (0..voters_len)
 		.map(|i| {
 			let voter = frame_benchmarking::account::<AccountId>("Voter", i, SEED);
-			(voter, 1_000, targets.clone())
+			// the degree is at most `MaxVotesPerVoter`, so nothing is truncated.
+			Voter::new(voter, 1_000, BoundedVec::truncate_from(targets.clone()))
 		})
 		.collect::<Vec<_>>();
 
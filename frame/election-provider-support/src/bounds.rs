@@ -22,7 +22,8 @@
 //! This module defines and implements types that help creating and handling election bounds.
 //! [`DataProviderBounds`] encapsulates the upper limits for the results provided by `DataProvider`
 //! implementors. Those limits can be defined over two axis: number of elements returned (`count`)
-//! and/or the size of the returned SCALE encoded structure (`size`).
+//! and/or the size of the returned SCALE encoded structure (`size`). Optionally, the weight that
+//! the implementor may consume to produce the result can be bounded as well (`weight`).
 //!
 //! [`ElectionBoundsBuilder`] is a helper to construct data election bounds and it aims at
 //! preventing the caller from mistake the order of size and count limits.
@@ -54,9 +55,15 @@
 //! A default or `None` bound means that no bounds are enforced (i.e. unlimited result size). In
 //! general, be careful when using unbounded election bounds in production.
 
+use crate::Weight;
+use codec::{Decode, Encode, MaxEncodedLen};
 use core::ops::Add;
+use scale_info::TypeInfo;
 use sp_runtime::traits::Zero;
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 /// Count type for data provider bounds.
 ///
 /// Encapsulates the counting of things that can be bounded in an election, such as voters,
@@ -64,7 +71,10 @@ use sp_runtime::traits::Zero;
 ///
 /// This struct is defined mostly to prevent callers from mistankingly using `CountBound` instead of
 /// `SizeBound` and vice-versa.
-#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(
+	Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Encode, Decode, MaxEncodedLen, TypeInfo,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct CountBound(pub u32);
 
 impl From<u32> for CountBound {
@@ -98,7 +108,10 @@ impl Zero for CountBound {
 ///
 /// This struct is defined mostly to prevent callers from mistankingly using `CountBound` instead of
 /// `SizeBound` and vice-versa.
-#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+#[derive(
+	Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd, Encode, Decode, MaxEncodedLen, TypeInfo,
+)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SizeBound(pub u32);
 
 impl From<u32> for SizeBound {
@@ -127,13 +140,21 @@ impl Add for SizeBound {
 ///
 /// Limits the data returned by `DataProvider` implementors, defined over two axis: `count`,
 /// defining the maximum number of elements returned, and `size`, defining the limit in size
-/// (bytes) of the SCALE encoded result.
+/// (bytes) of the SCALE encoded result. Additionally, `weight` limits the weight that the
+/// implementor may consume to produce the result, which is useful when that weight is not uniform
+/// across elements.
+///
+/// `None` represents unlimited bounds in the `count`, `size` and `weight` axis, and the default
+/// bounds are unlimited in all of them.
 ///
-/// `None` represents unlimited bounds in both `count` and `size` axis.
-#[derive(Clone, Copy, Default, Debug, Eq, PartialEq)]
+/// The bounds are encodable, so that they can be kept in storage and set at runtime, and
+/// optionally serializable, e.g. to be set in a chain spec.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Encode, Decode, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct DataProviderBounds {
 	pub count: Option<CountBound>,
 	pub size: Option<SizeBound>,
+	pub weight: Option<Weight>,
 }
 
 impl DataProviderBounds {
@@ -147,6 +168,11 @@ impl DataProviderBounds {
 		self.size.map_or(false, |size| given_size > size)
 	}
 
+	///  Returns true if `given_weight` exhausts `self.weight` in any dimension.
+	pub fn weight_exhausted(self, given_weight: Weight) -> bool {
+		self.weight.map_or(false, |weight| given_weight.any_gt(weight))
+	}
+
 	/// Returns true if `given_size` or `given_count` exhausts `self.size` or `self_count`,
 	/// respectively.
 	pub fn exhausted(self, given_size: Option<SizeBound>, given_count: Option<CountBound>) -> bool {
@@ -154,8 +180,8 @@ impl DataProviderBounds {
 			self.size_exhausted(given_size.unwrap_or(SizeBound::zero()))
 	}
 
-	/// Returns an instance of `Self` that is constructed by capping both the `count` and `size`
-	/// fields. If `self` is None, overwrite it with the provided bounds.
+	/// Returns an instance of `Self` that is constructed by capping the `count`, `size` and
+	/// `weight` fields. If `self` is None, overwrite it with the provided bounds.
 	pub fn max(self, bounds: DataProviderBounds) -> Self {
 		DataProviderBounds {
 			count: self
@@ -170,6 +196,61 @@ impl DataProviderBounds {
 					c.clamp(SizeBound::zero(), bounds.size.unwrap_or(SizeBound(u32::MAX))).into()
 				})
 				.or(bounds.size),
+			weight: self
+				.weight
+				.map(|w| w.min(bounds.weight.unwrap_or(Weight::MAX)))
+				.or(bounds.weight),
+		}
+	}
+
+	/// Returns the tightest of `self` and `other` in each of the `count`, `size` and `weight`
+	/// axis. An axis is unbounded only if it is unbounded in both.
+	pub fn min(self, other: DataProviderBounds) -> Self {
+		fn tightest<B: Ord>(a: Option<B>, b: Option<B>) -> Option<B> {
+			match (a, b) {
+				(Some(a), Some(b)) => Some(a.min(b)),
+				(a, b) => a.or(b),
+			}
+		}
+
+		DataProviderBounds {
+			count: tightest(self.count, other.count),
+			size: tightest(self.size, other.size),
+			weight: match (self.weight, other.weight) {
+				(Some(a), Some(b)) => Some(a.min(b)),
+				(a, b) => a.or(b),
+			},
+		}
+	}
+
+	/// Returns the budget left in `self` once `used` is consumed, in each of the `count`, `size`
+	/// and `weight` axis.
+	///
+	/// Unbounded axis of `self` stay unbounded, and unbounded axis of `used` are taken as nothing
+	/// consumed.
+	pub fn saturating_sub(self, used: DataProviderBounds) -> Self {
+		DataProviderBounds {
+			count: self
+				.count
+				.map(|c| CountBound(c.0.saturating_sub(used.count.map_or(0, |u| u.0)))),
+			size: self.size.map(|s| SizeBound(s.0.saturating_sub(used.size.map_or(0, |u| u.0)))),
+			weight: self.weight.map(|w| w.saturating_sub(used.weight.unwrap_or_else(Weight::zero))),
+		}
+	}
+
+	/// Returns the bounds of each of `n_pages` pages sharing `self` evenly, in each of the
+	/// `count`, `size` and `weight` axis.
+	///
+	/// Divisions are rounded down, so the pages together never exceed `self`. Unbounded axis stay
+	/// unbounded, and zero pages are treated as one.
+	pub fn split(self, n_pages: u32) -> Self {
+		let n_pages = n_pages.max(1);
+		DataProviderBounds {
+			count: self.count.map(|c| CountBound(c.0 / n_pages)),
+			size: self.size.map(|s| SizeBound(s.0 / n_pages)),
+			weight: self.weight.map(|w| {
+				Weight::from_parts(w.ref_time() / n_pages as u64, w.proof_size() / n_pages as u64)
+			}),
 		}
 	}
 }
@@ -179,6 +260,7 @@ impl DataProviderBounds {
 /// The bounds are defined over two axis: `count` of element of the election (voters or targets) and
 /// the `size` of the SCALE encoded result snapshot.
 #[derive(Clone, Debug, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ElectionBounds {
 	pub voters: DataProviderBounds,
 	pub targets: DataProviderBounds,
@@ -229,7 +311,7 @@ impl ElectionBoundsBuilder {
 	/// Sets the voters count bounds.
 	pub fn voters_count(mut self, count: CountBound) -> Self {
 		self.voters = self.voters.map_or(
-			Some(DataProviderBounds { count: Some(count), size: None }),
+			Some(DataProviderBounds { count: Some(count), size: None, weight: None }),
 			|mut bounds| {
 				bounds.count = Some(count);
 				Some(bounds)
@@ -241,7 +323,7 @@ impl ElectionBoundsBuilder {
 	/// Sets the voters size bounds.
 	pub fn voters_size(mut self, size: SizeBound) -> Self {
 		self.voters = self.voters.map_or(
-			Some(DataProviderBounds { count: None, size: Some(size) }),
+			Some(DataProviderBounds { count: None, size: Some(size), weight: None }),
 			|mut bounds| {
 				bounds.size = Some(size);
 				Some(bounds)
@@ -253,7 +335,7 @@ impl ElectionBoundsBuilder {
 	/// Sets the targets count bounds.
 	pub fn targets_count(mut self, count: CountBound) -> Self {
 		self.targets = self.targets.map_or(
-			Some(DataProviderBounds { count: Some(count), size: None }),
+			Some(DataProviderBounds { count: Some(count), size: None, weight: None }),
 			|mut bounds| {
 				bounds.count = Some(count);
 				Some(bounds)
@@ -265,7 +347,7 @@ impl ElectionBoundsBuilder {
 	/// Sets the targets size bounds.
 	pub fn targets_size(mut self, size: SizeBound) -> Self {
 		self.targets = self.targets.map_or(
-			Some(DataProviderBounds { count: None, size: Some(size) }),
+			Some(DataProviderBounds { count: None, size: Some(size), weight: None }),
 			|mut bounds| {
 				bounds.size = Some(size);
 				Some(bounds)
@@ -274,6 +356,30 @@ impl ElectionBoundsBuilder {
 		self
 	}
 
+	/// Sets the voters weight bounds.
+	pub fn voters_weight(mut self, weight: Weight) -> Self {
+		self.voters = self.voters.map_or(
+			Some(DataProviderBounds { count: None, size: None, weight: Some(weight) }),
+			|mut bounds| {
+				bounds.weight = Some(weight);
+				Some(bounds)
+			},
+		);
+		self
+	}
+
+	/// Sets the targets weight bounds.
+	pub fn targets_weight(mut self, weight: Weight) -> Self {
+		self.targets = self.targets.map_or(
+			Some(DataProviderBounds { count: None, size: None, weight: Some(weight) }),
+			|mut bounds| {
+				bounds.weight = Some(weight);
+				Some(bounds)
+			},
+		);
+		self
+	}
+
 	/// Set the voters bounds.
 	pub fn voters(mut self, bounds: Option<DataProviderBounds>) -> Self {
 		self.voters = bounds;
@@ -358,11 +464,124 @@ mod test {
 		assert!(bounds.targets.exhausted(SizeBound(2_001).into(), None));
 	}
 
+	#[test]
+	fn weight_bounds_work() {
+		let bounds = ElectionBoundsBuilder::default()
+			.voters_count(100.into())
+			.voters_weight(Weight::from_parts(1_000, 100))
+			.build();
+
+		assert_eq!(bounds.voters.count, Some(CountBound(100)));
+		assert!(!bounds.voters.weight_exhausted(Weight::from_parts(1_000, 100)));
+		// exhausting any dimension exhausts the bounds.
+		assert!(bounds.voters.weight_exhausted(Weight::from_parts(1_001, 0)));
+		assert!(bounds.voters.weight_exhausted(Weight::from_parts(0, 101)));
+		// the weight is unbounded by default.
+		assert!(!bounds.targets.weight_exhausted(Weight::MAX));
+
+		// capping keeps the lowest weight of each dimension.
+		let capped = bounds.voters.max(DataProviderBounds {
+			count: None,
+			size: None,
+			weight: Some(Weight::from_parts(2_000, 50)),
+		});
+		assert_eq!(capped.count, Some(CountBound(100)));
+		assert_eq!(capped.weight, Some(Weight::from_parts(1_000, 50)));
+		assert_eq!(
+			DataProviderBounds::default().max(bounds.voters).weight,
+			Some(Weight::from_parts(1_000, 100))
+		);
+	}
+
+	#[test]
+	fn bounds_arithmetic_works() {
+		let global = DataProviderBounds {
+			count: Some(CountBound(10)),
+			size: Some(SizeBound(100)),
+			weight: None,
+		};
+		let other = DataProviderBounds {
+			count: Some(CountBound(20)),
+			size: Some(SizeBound(50)),
+			weight: Some(Weight::from_parts(1_000, 10)),
+		};
+
+		// the tightest axis are kept, and a bounded axis wins over an unbounded one.
+		let min = global.min(other);
+		assert_eq!(
+			min,
+			DataProviderBounds {
+				count: Some(CountBound(10)),
+				size: Some(SizeBound(50)),
+				weight: Some(Weight::from_parts(1_000, 10)),
+			}
+		);
+		assert_eq!(other.min(global), min);
+		assert_eq!(global.min(DataProviderBounds::default()), global);
+
+		// consumed budget is subtracted, and never goes below zero.
+		let used = DataProviderBounds {
+			count: Some(CountBound(4)),
+			size: Some(SizeBound(200)),
+			weight: Some(Weight::from_parts(500, 20)),
+		};
+		assert_eq!(
+			min.saturating_sub(used),
+			DataProviderBounds {
+				count: Some(CountBound(6)),
+				size: Some(SizeBound(0)),
+				weight: Some(Weight::from_parts(500, 0)),
+			}
+		);
+		// unbounded axis stay unbounded, and nothing used leaves the bounds untouched.
+		assert_eq!(global.saturating_sub(used).weight, None);
+		assert_eq!(min.saturating_sub(DataProviderBounds::default()), min);
+
+		// pages share the bounds evenly, rounding down.
+		assert_eq!(
+			min.split(3),
+			DataProviderBounds {
+				count: Some(CountBound(3)),
+				size: Some(SizeBound(16)),
+				weight: Some(Weight::from_parts(333, 3)),
+			}
+		);
+		assert_eq!(min.split(0), min);
+		assert_eq!(DataProviderBounds::default().split(3), DataProviderBounds::default());
+	}
+
+	#[test]
+	fn data_provider_bounds_codec_works() {
+		let bounds = ElectionBoundsBuilder::default()
+			.voters_count(100.into())
+			.voters_size(1_000.into())
+			.voters_weight(Weight::from_parts(1_000, 100))
+			.build()
+			.voters;
+
+		let encoded = bounds.encode();
+		assert!(encoded.len() <= DataProviderBounds::max_encoded_len());
+		assert_eq!(DataProviderBounds::decode(&mut &encoded[..]).unwrap(), bounds);
+
+		// the default bounds are unbounded, and encode as such.
+		let unbounded = DataProviderBounds::default();
+		assert_eq!(unbounded, DataProviderBounds { count: None, size: None, weight: None });
+		assert_eq!(DataProviderBounds::decode(&mut &unbounded.encode()[..]).unwrap(), unbounded);
+	}
+
 	#[test]
 	fn election_bounds_ensure_limits_works() {
 		let bounds = ElectionBounds {
-			voters: DataProviderBounds { count: Some(CountBound(10)), size: Some(SizeBound(10)) },
-			targets: DataProviderBounds { count: Some(CountBound(10)), size: Some(SizeBound(10)) },
+			voters: DataProviderBounds {
+				count: Some(CountBound(10)),
+				size: Some(SizeBound(10)),
+				weight: None,
+			},
+			targets: DataProviderBounds {
+				count: Some(CountBound(10)),
+				size: Some(SizeBound(10)),
+				weight: None,
+			},
 		};
 
 		assert_ok!(bounds.ensure_voters_limits(CountBound(1), SizeBound(1)));
@@ -396,24 +615,37 @@ mod test {
 
 		// max of some bounds with unbounded data provider bounds will always return the defined
 		// bounds.
-		let bounds = DataProviderBounds { count: CountBound(5).into(), size: SizeBound(10).into() };
+		let bounds = DataProviderBounds {
+			count: CountBound(5).into(),
+			size: SizeBound(10).into(),
+			weight: None,
+		};
 		assert_eq!(unbounded.max(bounds), bounds);
 
-		let bounds = DataProviderBounds { count: None, size: SizeBound(10).into() };
+		let bounds = DataProviderBounds { count: None, size: SizeBound(10).into(), weight: None };
 		assert_eq!(unbounded.max(bounds), bounds);
 
-		let bounds = DataProviderBounds { count: CountBound(5).into(), size: None };
+		let bounds = DataProviderBounds { count: CountBound(5).into(), size: None, weight: None };
 		assert_eq!(unbounded.max(bounds), bounds);
 	}
 
 	#[test]
 	fn data_provider_max_bounded_works() {
-		let bounds_one =
-			DataProviderBounds { count: CountBound(10).into(), size: SizeBound(100).into() };
-		let bounds_two =
-			DataProviderBounds { count: CountBound(100).into(), size: SizeBound(10).into() };
-		let max_bounds_expected =
-			DataProviderBounds { count: CountBound(10).into(), size: SizeBound(10).into() };
+		let bounds_one = DataProviderBounds {
+			count: CountBound(10).into(),
+			size: SizeBound(100).into(),
+			weight: None,
+		};
+		let bounds_two = DataProviderBounds {
+			count: CountBound(100).into(),
+			size: SizeBound(10).into(),
+			weight: None,
+		};
+		let max_bounds_expected = DataProviderBounds {
+			count: CountBound(10).into(),
+			size: SizeBound(10).into(),
+			weight: None,
+		};
 
 		assert_eq!(bounds_one.max(bounds_two), max_bounds_expected);
 		assert_eq!(bounds_two.max(bounds_one), max_bounds_expected);
@@ -427,11 +659,13 @@ mod test {
 			.voters_or_lower(DataProviderBounds {
 				count: CountBound(5).into(),
 				size: SizeBound(20).into(),
+				weight: None,
 			})
 			.targets_count(20.into())
 			.targets_or_lower(DataProviderBounds {
 				count: CountBound(30).into(),
 				size: SizeBound(30).into(),
+				weight: None,
 			})
 			.build();
 
@@ -445,10 +679,12 @@ mod test {
 			.voters_or_lower(DataProviderBounds {
 				count: CountBound(5).into(),
 				size: SizeBound(20).into(),
+				weight: None,
 			})
 			.targets_or_lower(DataProviderBounds {
 				count: CountBound(10).into(),
 				size: SizeBound(10).into(),
+				weight: None,
 			})
 			.build();
 
@@ -0,0 +1,78 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Moving election inputs and outputs to and from JSON.
+//!
+//! This is meant for offline analysis, e.g. to feed the [`crate::Supports`] or
+//! [`crate::ElectionResult`] of an election, or the [`crate::bounds::ElectionBounds`] it ran with,
+//! to the solver benchmarks or to external tools, and back. Only available with the `json`
+//! feature.
+
+use serde::{de::DeserializeOwned, Serialize};
+
+pub use serde_json::Error;
+
+/// Serialize `value` to (pretty-printed) JSON.
+pub fn to_json<T: Serialize>(value: &T) -> Result<String, Error> {
+	serde_json::to_string_pretty(value)
+}
+
+/// Deserialize a value from `json`, as serialized by [`to_json`].
+pub fn from_json<T: DeserializeOwned>(json: &str) -> Result<T, Error> {
+	serde_json::from_str(json)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::{
+		bounds::{ElectionBounds, ElectionBoundsBuilder},
+		Assignment, ElectionResult, Support, Supports,
+	};
+	use sp_arithmetic::Perbill;
+
+	#[test]
+	fn election_outputs_round_trip() {
+		let supports: Supports<u32> = vec![
+			(10, Support { total: 30, voters: vec![(1, 20), (10, 10)] }),
+			(20, Support { total: 10, voters: vec![(20, 10)] }),
+		];
+		assert_eq!(from_json::<Supports<u32>>(&to_json(&supports).unwrap()).unwrap(), supports);
+
+		let result = ElectionResult::<u32, Perbill> {
+			winners: vec![(10, 30), (20, 10)],
+			assignments: vec![Assignment {
+				who: 1,
+				distribution: vec![
+					(10, Perbill::from_percent(50)),
+					(20, Perbill::from_percent(50)),
+				],
+			}],
+		};
+		let decoded =
+			from_json::<ElectionResult<u32, Perbill>>(&to_json(&result).unwrap()).unwrap();
+		assert_eq!(decoded.winners, result.winners);
+		assert_eq!(decoded.assignments, result.assignments);
+
+		let bounds = ElectionBoundsBuilder::default().voters_count(100.into()).build();
+		let decoded = from_json::<ElectionBounds>(&to_json(&bounds).unwrap()).unwrap();
+		assert_eq!(decoded.voters, bounds.voters);
+		assert_eq!(decoded.targets, bounds.targets);
+
+		assert!(from_json::<Supports<u32>>("not json").is_err());
+	}
+}
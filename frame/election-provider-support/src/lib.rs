@@ -139,7 +139,7 @@
 //!         type Error = &'static str;
 //!         type DataProvider = T::DataProvider;
 //!         type MaxWinners = ConstU32<{ u32::MAX }>;
-//!
+//!         type MaxBackersPerWinner = ConstU32<{ u32::MAX }>;
 //!     }
 //!
 //!     impl<T: Config> ElectionProvider for GenericElectionProvider<T> {
@@ -147,7 +147,7 @@
 //!         fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
 //!             Self::DataProvider::electable_targets(DataProviderBounds::default())
 //!                 .map_err(|_| "failed to elect")
-//!                 .map(|t| bounded_vec![(t[0], Support::default())])
+//!                 .map(|t| bounded_vec![(t[0], BoundedSupport::default())])
 //!         }
 //!     }
 //! }
@@ -174,6 +174,8 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod bounds;
+#[cfg(feature = "json")]
+pub mod json;
 pub mod onchain;
 pub mod traits;
 
@@ -191,14 +193,16 @@ pub use frame_support::{traits::Get, weights::Weight, BoundedVec};
 /// Re-export some type as they are used in the interface.
 pub use sp_arithmetic::PerThing;
 pub use sp_npos_elections::{
-	Assignment, BalancingConfig, BoundedSupports, ElectionResult, Error, ExtendedBalance,
-	IdentifierT, PerThing128, Support, Supports, VoteWeight,
+	Assignment, BalancingConfig, BoundedSupport, BoundedSupports, ElectionResult, ElectionScore,
+	Error, ExtendedBalance, IdentifierT, PerThing128, Support, Supports, VoteWeight,
 };
 pub use traits::NposSolution;
 
 #[cfg(feature = "try-runtime")]
 use sp_runtime::TryRuntimeError;
 
+const LOG_TARGET: &str = "runtime::election-provider";
+
 // re-export for the solution macro, with the dependencies of the macro.
 #[doc(hidden)]
 pub mod private {
@@ -279,6 +283,9 @@ pub mod data_provider {
 	pub type Result<T> = sp_std::result::Result<T, &'static str>;
 }
 
+/// The index of a page of an election snapshot.
+pub type PageIndex = u32;
+
 /// Something that can provide the data to an [`ElectionProvider`].
 pub trait ElectionDataProvider {
 	/// The account identifier type.
@@ -306,6 +313,60 @@ pub trait ElectionDataProvider {
 	/// appropriate weight at the end of execution with the system pallet directly.
 	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>>;
 
+	/// Page `page` of the voters that participate in the election, within `bounds`.
+	///
+	/// Lets a stateful election provider build its snapshot over several blocks. Page `0` starts
+	/// a new snapshot, and each following page continues where the previous one stopped. An empty
+	/// page means that all the voters have been provided.
+	///
+	/// By default, page `0` contains all of [`Self::electing_voters`] and all other pages are
+	/// empty.
+	///
+	/// This should be implemented as a self-weighing function. The implementor should register its
+	/// appropriate weight at the end of execution with the system pallet directly.
+	fn voters_page(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
+		if page == 0 {
+			Self::electing_voters(bounds)
+		} else {
+			Ok(Vec::new())
+		}
+	}
+
+	/// The voters that participate in the election, within `bounds`, as an iterator.
+	///
+	/// Unlike [`Self::electing_voters`], the voters can be produced lazily, so that an election
+	/// provider can stop consuming them as soon as its own limits are hit.
+	///
+	/// By default, this iterates over [`Self::electing_voters`].
+	///
+	/// This should be implemented as a self-weighing function. A lazy implementor should register
+	/// the weight of each voter as it is produced, so that only the consumed voters are paid for.
+	fn voters_iter(
+		bounds: DataProviderBounds,
+	) -> data_provider::Result<Box<dyn Iterator<Item = VoterOf<Self>>>>
+	where
+		VoterOf<Self>: 'static,
+	{
+		Self::electing_voters(bounds).map(|voters| Box::new(voters.into_iter()) as Box<_>)
+	}
+
+	/// Page `page` of the targets of the election, within `bounds`.
+	///
+	/// Same as [`Self::voters_page`], for [`Self::electable_targets`].
+	fn targets_page(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<Self::AccountId>> {
+		if page == 0 {
+			Self::electable_targets(bounds)
+		} else {
+			Ok(Vec::new())
+		}
+	}
+
 	/// The number of targets to elect.
 	///
 	/// This should be implemented as a self-weighing function. The implementor should register its
@@ -328,12 +389,16 @@ pub trait ElectionDataProvider {
 
 	/// Utility function only to be used in benchmarking scenarios, to be implemented optionally,
 	/// else a noop.
+	///
+	/// Returns an error, rather than panicking, if the snapshot cannot be stored, for example
+	/// because a stake does not fit into the implementor's balance type.
 	#[cfg(any(feature = "runtime-benchmarks", test))]
 	fn put_snapshot(
 		_voters: Vec<VoterOf<Self>>,
 		_targets: Vec<Self::AccountId>,
 		_target_stake: Option<VoteWeight>,
-	) {
+	) -> data_provider::Result<()> {
+		Ok(())
 	}
 
 	/// Utility function only to be used in benchmarking scenarios, to be implemented optionally,
@@ -345,7 +410,8 @@ pub trait ElectionDataProvider {
 		_voter: Self::AccountId,
 		_weight: VoteWeight,
 		_targets: BoundedVec<Self::AccountId, Self::MaxVotesPerVoter>,
-	) {
+	) -> data_provider::Result<()> {
+		Ok(())
 	}
 
 	/// Utility function only to be used in benchmarking scenarios, to be implemented optionally,
@@ -360,6 +426,27 @@ pub trait ElectionDataProvider {
 	fn clear() {}
 }
 
+/// Something that decides when elections happen.
+///
+/// This complements [`ElectionDataProvider::next_election_prediction`], letting a chain move
+/// elections around, e.g. away from blocks that are known to be congested.
+pub trait ElectionSchedule {
+	/// The block number type.
+	type BlockNumber;
+
+	/// The block at which the next election is expected to happen.
+	fn next_election() -> Self::BlockNumber;
+
+	/// Trigger an election as soon as possible.
+	fn force_election();
+
+	/// Delay the next election by (at least) `blocks`, with respect to its regular schedule.
+	///
+	/// An implementation might round the delay up, e.g. to whole sessions. Setting a new offset
+	/// replaces the previous one, and an offset of zero cancels it.
+	fn set_election_offset(blocks: Self::BlockNumber);
+}
+
 /// Base trait for types that can provide election
 pub trait ElectionProviderBase {
 	/// The account identifier type.
@@ -381,12 +468,26 @@ pub trait ElectionProviderBase {
 	/// truncate the output to meet this bound.
 	type MaxWinners: Get<u32>;
 
+	/// The upper bound on the backers of each winner that can be returned.
+	///
+	/// An implementation can chose to either return an error and/or sort and truncate the backers
+	/// of each winner to meet this bound.
+	type MaxBackersPerWinner: Get<u32>;
+
 	/// The data provider of the election.
 	type DataProvider: ElectionDataProvider<
 		AccountId = Self::AccountId,
 		BlockNumber = Self::BlockNumber,
 	>;
 
+	/// Best-effort estimate of the weight of running the election now.
+	///
+	/// Callers can use this to check whether an election fits into the current block before
+	/// triggering it. The default implementation provides no estimate and returns zero.
+	fn estimate_elect_weight() -> Weight {
+		Weight::zero()
+	}
+
 	/// checked call to `Self::DataProvider::desired_targets()` ensuring the value never exceeds
 	/// [`Self::MaxWinners`].
 	fn desired_targets_checked() -> data_provider::Result<u32> {
@@ -415,6 +516,20 @@ pub trait ElectionProvider: ElectionProviderBase {
 	/// implementor should register its appropriate weight at the end of execution with the
 	/// system pallet directly.
 	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error>;
+
+	/// Performs the election as [`Self::elect`], along with up to `runners_up` of the targets that
+	/// did not win, best first, with the support they would have had.
+	///
+	/// The default implementation returns no runner-up. Only [`onchain::OnChainExecution`]
+	/// provides them, at the cost of a second run of its solver, so this is meant for the
+	/// chains that elect on-chain. Any other provider returns none, and so does a [`Fallback`]
+	/// unless it falls back to one that provides them.
+	fn elect_with_runners_up(
+		runners_up: u32,
+	) -> Result<(BoundedSupportsOf<Self>, RunnersUpOf<Self>), Self::Error> {
+		let _ = runners_up;
+		Self::elect().map(|supports| (supports, Vec::new()))
+	}
 }
 
 /// A (almost) marker trait that signifies an election provider as working synchronously. i.e. being
@@ -428,29 +543,52 @@ pub trait InstantElectionProvider: ElectionProviderBase {
 		forced_input_voters_bound: DataProviderBounds,
 		forced_input_target_bound: DataProviderBounds,
 	) -> Result<BoundedSupportsOf<Self>, Self::Error>;
+
+	/// Elect `desired_targets` winners out of `targets`, as voted by `voters`.
+	///
+	/// Unlike [`Self::instant_elect`], the data provider is not called into: the election is
+	/// stateless with respect to it, which suits emergency and genesis elections over inputs that
+	/// are already at hand.
+	///
+	/// By default, the inputs are ignored and this falls back to [`Self::instant_elect`] with no
+	/// forced bounds, which does call into the data provider. Implementations that can elect out
+	/// of the given inputs should override it.
+	fn instant_elect_with_inputs(
+		voters: Vec<VoterOf<Self::DataProvider>>,
+		targets: Vec<Self::AccountId>,
+		desired_targets: u32,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		let _ = (voters, targets, desired_targets);
+		Self::instant_elect(DataProviderBounds::default(), DataProviderBounds::default())
+	}
 }
 
 /// An election provider that does nothing whatsoever.
+///
+/// Every election fails, which suits chains that never want elections.
 pub struct NoElection<X>(sp_std::marker::PhantomData<X>);
 
-impl<AccountId, BlockNumber, DataProvider, MaxWinners> ElectionProviderBase
-	for NoElection<(AccountId, BlockNumber, DataProvider, MaxWinners)>
+impl<AccountId, BlockNumber, DataProvider, MaxWinners, MaxBackersPerWinner> ElectionProviderBase
+	for NoElection<(AccountId, BlockNumber, DataProvider, MaxWinners, MaxBackersPerWinner)>
 where
 	DataProvider: ElectionDataProvider<AccountId = AccountId, BlockNumber = BlockNumber>,
 	MaxWinners: Get<u32>,
+	MaxBackersPerWinner: Get<u32>,
 {
 	type AccountId = AccountId;
 	type BlockNumber = BlockNumber;
 	type Error = &'static str;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type DataProvider = DataProvider;
 }
 
-impl<AccountId, BlockNumber, DataProvider, MaxWinners> ElectionProvider
-	for NoElection<(AccountId, BlockNumber, DataProvider, MaxWinners)>
+impl<AccountId, BlockNumber, DataProvider, MaxWinners, MaxBackersPerWinner> ElectionProvider
+	for NoElection<(AccountId, BlockNumber, DataProvider, MaxWinners, MaxBackersPerWinner)>
 where
 	DataProvider: ElectionDataProvider<AccountId = AccountId, BlockNumber = BlockNumber>,
 	MaxWinners: Get<u32>,
+	MaxBackersPerWinner: Get<u32>,
 {
 	fn ongoing() -> bool {
 		false
@@ -461,11 +599,12 @@ where
 	}
 }
 
-impl<AccountId, BlockNumber, DataProvider, MaxWinners> InstantElectionProvider
-	for NoElection<(AccountId, BlockNumber, DataProvider, MaxWinners)>
+impl<AccountId, BlockNumber, DataProvider, MaxWinners, MaxBackersPerWinner> InstantElectionProvider
+	for NoElection<(AccountId, BlockNumber, DataProvider, MaxWinners, MaxBackersPerWinner)>
 where
 	DataProvider: ElectionDataProvider<AccountId = AccountId, BlockNumber = BlockNumber>,
 	MaxWinners: Get<u32>,
+	MaxBackersPerWinner: Get<u32>,
 {
 	fn instant_elect(
 		_: DataProviderBounds,
@@ -473,6 +612,220 @@ where
 	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
 		Err("`NoElection` cannot do anything.")
 	}
+
+	fn instant_elect_with_inputs(
+		_: Vec<VoterOf<Self::DataProvider>>,
+		_: Vec<Self::AccountId>,
+		_: u32,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		Err("`NoElection` cannot do anything.")
+	}
+}
+
+/// An election provider that elects with `Primary`, and falls back to `Secondary` if `Primary`
+/// fails.
+///
+/// Both providers must share the same data provider and bounds. The branch that was taken is
+/// logged, and if both fail, the error of `Secondary` is returned.
+pub struct Fallback<Primary, Secondary>(sp_std::marker::PhantomData<(Primary, Secondary)>);
+
+impl<Primary, Secondary> ElectionProviderBase for Fallback<Primary, Secondary>
+where
+	Primary: ElectionProviderBase,
+	Secondary: ElectionProviderBase<
+		AccountId = Primary::AccountId,
+		BlockNumber = Primary::BlockNumber,
+		MaxWinners = Primary::MaxWinners,
+		MaxBackersPerWinner = Primary::MaxBackersPerWinner,
+		DataProvider = Primary::DataProvider,
+	>,
+{
+	type AccountId = Primary::AccountId;
+	type BlockNumber = Primary::BlockNumber;
+	type Error = Secondary::Error;
+	type MaxWinners = Primary::MaxWinners;
+	type MaxBackersPerWinner = Primary::MaxBackersPerWinner;
+	type DataProvider = Primary::DataProvider;
+
+	/// The worst case, where both providers run.
+	fn estimate_elect_weight() -> Weight {
+		Primary::estimate_elect_weight().saturating_add(Secondary::estimate_elect_weight())
+	}
+}
+
+impl<Primary, Secondary> ElectionProvider for Fallback<Primary, Secondary>
+where
+	Primary: ElectionProvider,
+	Secondary: ElectionProvider<
+		AccountId = Primary::AccountId,
+		BlockNumber = Primary::BlockNumber,
+		MaxWinners = Primary::MaxWinners,
+		MaxBackersPerWinner = Primary::MaxBackersPerWinner,
+		DataProvider = Primary::DataProvider,
+	>,
+{
+	fn ongoing() -> bool {
+		Primary::ongoing()
+	}
+
+	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		Primary::elect()
+			.map(|supports| {
+				log::debug!(target: LOG_TARGET, "elected with the primary election provider");
+				supports
+			})
+			.or_else(|error| {
+				log::warn!(
+					target: LOG_TARGET,
+					"primary election provider failed due to {:?}, falling back to the secondary",
+					error,
+				);
+				Secondary::elect()
+			})
+	}
+
+	fn elect_with_runners_up(
+		runners_up: u32,
+	) -> Result<(BoundedSupportsOf<Self>, RunnersUpOf<Self>), Self::Error> {
+		Primary::elect_with_runners_up(runners_up)
+			.map(|result| {
+				log::debug!(target: LOG_TARGET, "elected with the primary election provider");
+				result
+			})
+			.or_else(|error| {
+				log::warn!(
+					target: LOG_TARGET,
+					"primary election provider failed due to {:?}, falling back to the secondary",
+					error,
+				);
+				Secondary::elect_with_runners_up(runners_up)
+			})
+	}
+}
+
+impl<Primary, Secondary> InstantElectionProvider for Fallback<Primary, Secondary>
+where
+	Primary: InstantElectionProvider,
+	Primary::AccountId: Clone,
+	Secondary: InstantElectionProvider<
+		AccountId = Primary::AccountId,
+		BlockNumber = Primary::BlockNumber,
+		MaxWinners = Primary::MaxWinners,
+		MaxBackersPerWinner = Primary::MaxBackersPerWinner,
+		DataProvider = Primary::DataProvider,
+	>,
+{
+	fn instant_elect(
+		forced_input_voters_bound: DataProviderBounds,
+		forced_input_target_bound: DataProviderBounds,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		Primary::instant_elect(forced_input_voters_bound, forced_input_target_bound)
+			.map(|supports| {
+				log::debug!(target: LOG_TARGET, "elected with the primary election provider");
+				supports
+			})
+			.or_else(|error| {
+				log::warn!(
+					target: LOG_TARGET,
+					"primary election provider failed due to {:?}, falling back to the secondary",
+					error,
+				);
+				Secondary::instant_elect(forced_input_voters_bound, forced_input_target_bound)
+			})
+	}
+
+	fn instant_elect_with_inputs(
+		voters: Vec<VoterOf<Self::DataProvider>>,
+		targets: Vec<Self::AccountId>,
+		desired_targets: u32,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		Primary::instant_elect_with_inputs(voters.clone(), targets.clone(), desired_targets)
+			.map(|supports| {
+				log::debug!(target: LOG_TARGET, "elected with the primary election provider");
+				supports
+			})
+			.or_else(|error| {
+				log::warn!(
+					target: LOG_TARGET,
+					"primary election provider failed due to {:?}, falling back to the secondary",
+					error,
+				);
+				Secondary::instant_elect_with_inputs(voters, targets, desired_targets)
+			})
+	}
+}
+
+/// An election provider that elects with `Inner`, and returns the winners of the last successful
+/// election of `Inner` instead if it fails.
+///
+/// The winners of each successful election are kept in storage, under the prefix given by
+/// `Prefix`, which must not be used by anything else in the runtime. If `Inner` fails before ever
+/// succeeding, its error is returned.
+pub struct ContinueWithPrevious<Inner, Prefix>(sp_std::marker::PhantomData<(Inner, Prefix)>);
+
+impl<Inner: ElectionProviderBase, Prefix: Get<&'static str>> ContinueWithPrevious<Inner, Prefix> {
+	/// The storage key of the winners of the last successful election.
+	fn previous_key() -> [u8; 32] {
+		frame_support::storage::storage_prefix(Prefix::get().as_bytes(), b"PreviousSupports")
+	}
+}
+
+impl<Inner, Prefix> ElectionProviderBase for ContinueWithPrevious<Inner, Prefix>
+where
+	Inner: ElectionProviderBase,
+	Prefix: Get<&'static str>,
+{
+	type AccountId = Inner::AccountId;
+	type BlockNumber = Inner::BlockNumber;
+	type Error = Inner::Error;
+	type MaxWinners = Inner::MaxWinners;
+	type MaxBackersPerWinner = Inner::MaxBackersPerWinner;
+	type DataProvider = Inner::DataProvider;
+
+	fn estimate_elect_weight() -> Weight {
+		Inner::estimate_elect_weight()
+	}
+}
+
+impl<Inner, Prefix> ElectionProvider for ContinueWithPrevious<Inner, Prefix>
+where
+	Inner: ElectionProvider,
+	Inner::AccountId: Encode + Decode,
+	Prefix: Get<&'static str>,
+{
+	fn ongoing() -> bool {
+		Inner::ongoing()
+	}
+
+	fn elect() -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		match Inner::elect() {
+			Ok(supports) => {
+				frame_support::storage::unhashed::put(&Self::previous_key(), &supports);
+				Ok(supports)
+			},
+			Err(error) => {
+				let previous = frame_support::storage::unhashed::get::<BoundedSupportsOf<Self>>(
+					&Self::previous_key(),
+				);
+				log::warn!(
+					target: LOG_TARGET,
+					"election provider failed due to {:?}, continuing with the previous winners: {}",
+					error,
+					previous.is_some(),
+				);
+				previous.ok_or(error)
+			},
+		}
+	}
+}
+
+/// The progress of [`SortedListProvider::unsafe_regenerate_chunked`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegenerationStatus {
+	/// The list is being regenerated, and must not be relied upon yet.
+	InProgress,
+	/// The list has been regenerated.
+	Done,
 }
 
 /// A utility trait for something to implement `ElectionDataProvider` in a sensible way.
@@ -489,7 +842,7 @@ pub trait SortedListProvider<AccountId> {
 	type Error: sp_std::fmt::Debug;
 
 	/// The type used by the list to compare nodes for ordering.
-	type Score: Bounded + Saturating + Zero;
+	type Score: Bounded + Saturating + Zero + PartialOrd;
 
 	/// An iterator over the list, which can have `take` called on it.
 	fn iter() -> Box<dyn Iterator<Item = AccountId>>;
@@ -499,6 +852,40 @@ pub trait SortedListProvider<AccountId> {
 	/// May return an error if `start` is invalid.
 	fn iter_from(start: &AccountId) -> Result<Box<dyn Iterator<Item = AccountId>>, Self::Error>;
 
+	/// An iterator over the list in reverse order, from its tail to its head.
+	///
+	/// The default implementation yields nothing, as walking the list backwards would require
+	/// collecting all of it first. Lists that can be walked backwards should override it.
+	fn iter_rev() -> Box<dyn Iterator<Item = AccountId>>
+	where
+		AccountId: 'static,
+	{
+		Box::new(sp_std::iter::empty())
+	}
+
+	/// Returns an iterator over the ids of the list whose score is within `min..=max`, in the
+	/// order of the list.
+	///
+	/// The default implementation goes through the whole list. Lists that are sorted by score
+	/// should only go through the part of it that is within the range.
+	fn range(min: Self::Score, max: Self::Score) -> Box<dyn Iterator<Item = AccountId>>
+	where
+		Self: 'static,
+		Self::Score: 'static,
+	{
+		Box::new(Self::iter().filter(move |id| {
+			Self::get_score(id).map_or(false, |score| min <= score && score <= max)
+		}))
+	}
+
+	/// Returns the position of `id` in the list, counted from its head, if it is in the list.
+	fn position_of(id: &AccountId) -> Option<u32>
+	where
+		AccountId: PartialEq,
+	{
+		Self::iter().position(|other| &other == id).map(|position| position as u32)
+	}
+
 	/// The current count of ids in the list.
 	fn count() -> u32;
 
@@ -558,6 +945,27 @@ pub trait SortedListProvider<AccountId> {
 		score_of: Box<dyn Fn(&AccountId) -> Self::Score>,
 	) -> u32;
 
+	/// Same as [`Self::unsafe_regenerate`], but spread over multiple calls that each remove or
+	/// insert at most `limit` items, so that big lists can be regenerated over multiple blocks.
+	///
+	/// The first call starts the regeneration, which goes on until [`RegenerationStatus::Done`]
+	/// is returned. `all_from` returns the ids to insert that come after the given id, or all of
+	/// them if `None`, and must iterate over them in the same order on each call.
+	///
+	/// The default implementation regenerates the whole list in one call.
+	///
+	/// ## WARNING
+	///
+	/// The list must not be relied upon while it is being regenerated.
+	fn unsafe_regenerate_chunked(
+		all_from: Box<dyn Fn(Option<&AccountId>) -> Box<dyn Iterator<Item = AccountId>>>,
+		score_of: Box<dyn Fn(&AccountId) -> Self::Score>,
+		_limit: u32,
+	) -> RegenerationStatus {
+		Self::unsafe_regenerate(all_from(None), score_of);
+		RegenerationStatus::Done
+	}
+
 	/// Remove all items from the list.
 	///
 	/// ## WARNING
@@ -590,6 +998,103 @@ pub trait ScoreProvider<AccountId> {
 	fn set_score_of(_: &AccountId, _: Self::Score) {}
 }
 
+/// Something that can provide the [`VoteWeight`] of an account.
+///
+/// This is the former, less general version of [`ScoreProvider`], and is implemented for any
+/// [`ScoreProvider`] whose `Score` is a [`VoteWeight`].
+#[deprecated(note = "use `ScoreProvider<AccountId, Score = VoteWeight>` instead")]
+pub trait VoteWeightProvider<AccountId>: ScoreProvider<AccountId, Score = VoteWeight> {
+	/// Get the current `VoteWeight` of `who`.
+	fn vote_weight(who: &AccountId) -> VoteWeight {
+		Self::score(who)
+	}
+}
+
+#[allow(deprecated)]
+impl<AccountId, T: ScoreProvider<AccountId, Score = VoteWeight>> VoteWeightProvider<AccountId>
+	for T
+{
+}
+
+/// Something that accounts for the weight consumed outside of a dispatch, e.g. while creating an
+/// election snapshot.
+///
+/// The weight is always mandatory. Implemented for [`frame_system::Pallet`], which registers it as
+/// extra block weight. Runtimes that keep their own weight bookkeeping, e.g. a PoV-aware one, or
+/// that want to trace it, can provide their own implementation.
+pub trait WeightAccountant {
+	/// Account for `weight`, consumed as [`frame_support::dispatch::DispatchClass::Mandatory`].
+	fn register_weight(weight: Weight);
+}
+
+impl<T: frame_system::Config> WeightAccountant for frame_system::Pallet<T> {
+	fn register_weight(weight: Weight) {
+		Self::register_extra_weight_unchecked(
+			weight,
+			frame_support::dispatch::DispatchClass::Mandatory,
+		);
+	}
+}
+
+/// A budget within which an [`NposSolver`] should compute a solution.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum SolverBudget {
+	/// No budget.
+	Unlimited,
+	/// At most the given number of iterations, e.g. of balancing.
+	MaxIterations(u32),
+	/// At most the given weight, as measured by [`NposSolver::weight`].
+	MaxWeight(Weight),
+}
+
+impl Default for SolverBudget {
+	fn default() -> Self {
+		SolverBudget::Unlimited
+	}
+}
+
+impl SolverBudget {
+	/// Fit the `configured` balancing of a solver into this budget, with `weight` being the weight
+	/// of the solver without balancing.
+	///
+	/// Returns the balancing to use, and whether it falls short of the configured one.
+	fn fit_balancing(
+		self,
+		configured: Option<BalancingConfig>,
+		weight: impl FnOnce() -> Weight,
+	) -> (Option<BalancingConfig>, bool) {
+		let balancing = match self {
+			SolverBudget::Unlimited => configured,
+			SolverBudget::MaxIterations(max) => configured.map(|config| BalancingConfig {
+				iterations: config.iterations.min(max as usize),
+				..config
+			}),
+			// the weight does not account for balancing, hence it is skipped entirely if the
+			// budget is already exceeded.
+			SolverBudget::MaxWeight(max) =>
+				if weight().any_gt(max) {
+					None
+				} else {
+					configured
+				},
+		};
+		let degraded = configured.map_or(false, |config| {
+			balancing.map_or(true, |balancing| balancing.iterations < config.iterations)
+		});
+		(balancing, degraded)
+	}
+}
+
+/// The result of [`NposSolver::solve_with_budget`].
+#[derive(RuntimeDebug)]
+pub struct BudgetedElectionResult<AccountId, P: PerThing> {
+	/// The best result found within the budget.
+	pub result: ElectionResult<AccountId, P>,
+	/// Whether the budget stopped the solver early, in which case `result` can be of lower
+	/// quality than that of [`NposSolver::solve`].
+	pub degraded: bool,
+}
+
 /// Something that can compute the result to an NPoS solution.
 pub trait NposSolver {
 	/// The account identifier type of this solver.
@@ -601,10 +1106,10 @@ pub trait NposSolver {
 
 	/// Solve an NPoS solution with the given `voters`, `targets`, and select `to_elect` count
 	/// of `targets`.
-	fn solve(
+	fn solve<Bound: Get<u32>>(
 		to_elect: usize,
 		targets: Vec<Self::AccountId>,
-		voters: Vec<(Self::AccountId, VoteWeight, impl IntoIterator<Item = Self::AccountId>)>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
 	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error>;
 
 	/// Measure the weight used in the calculation of the solver.
@@ -612,6 +1117,19 @@ pub trait NposSolver {
 	/// - `targets` is the number of targets.
 	/// - `vote_degree` is the degree ie the maximum numbers of votes per voter.
 	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight;
+
+	/// Same as [`Self::solve`], yet within `budget`, with `T` measuring the weight.
+	///
+	/// By default, the budget is ignored and the result is never degraded.
+	fn solve_with_budget<Bound: Get<u32>, T: WeightInfo>(
+		to_elect: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
+		_budget: SolverBudget,
+	) -> Result<BudgetedElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		Self::solve(to_elect, targets, voters)
+			.map(|result| BudgetedElectionResult { result, degraded: false })
+	}
 }
 
 /// A wrapper for [`sp_npos_elections::seq_phragmen`] that implements [`NposSolver`]. See the
@@ -626,17 +1144,34 @@ impl<AccountId: IdentifierT, Accuracy: PerThing128, Balancing: Get<Option<Balanc
 	type AccountId = AccountId;
 	type Accuracy = Accuracy;
 	type Error = sp_npos_elections::Error;
-	fn solve(
+	fn solve<Bound: Get<u32>>(
 		winners: usize,
 		targets: Vec<Self::AccountId>,
-		voters: Vec<(Self::AccountId, VoteWeight, impl IntoIterator<Item = Self::AccountId>)>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
 	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		let voters = voters.into_iter().map(Voter::into_tuple).collect::<Vec<_>>();
 		sp_npos_elections::seq_phragmen(winners, targets, voters, Balancing::get())
 	}
 
 	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
 		T::phragmen(voters, targets, vote_degree)
 	}
+
+	/// The balancing iterations are truncated to fit `budget`. As [`Self::weight`] does not
+	/// account for balancing, a weight budget that is already exceeded skips balancing entirely.
+	fn solve_with_budget<Bound: Get<u32>, T: WeightInfo>(
+		winners: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
+		budget: SolverBudget,
+	) -> Result<BudgetedElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		let (balancing, degraded) = budget.fit_balancing(Balancing::get(), || {
+			Self::weight::<T>(voters.len() as u32, targets.len() as u32, Bound::get())
+		});
+		let voters = voters.into_iter().map(Voter::into_tuple).collect::<Vec<_>>();
+		sp_npos_elections::seq_phragmen(winners, targets, voters, balancing)
+			.map(|result| BudgetedElectionResult { result, degraded })
+	}
 }
 
 /// A wrapper for [`sp_npos_elections::phragmms()`] that implements [`NposSolver`]. See the
@@ -651,21 +1186,178 @@ impl<AccountId: IdentifierT, Accuracy: PerThing128, Balancing: Get<Option<Balanc
 	type AccountId = AccountId;
 	type Accuracy = Accuracy;
 	type Error = sp_npos_elections::Error;
-	fn solve(
+	fn solve<Bound: Get<u32>>(
 		winners: usize,
 		targets: Vec<Self::AccountId>,
-		voters: Vec<(Self::AccountId, VoteWeight, impl IntoIterator<Item = Self::AccountId>)>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
 	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		let voters = voters.into_iter().map(Voter::into_tuple).collect::<Vec<_>>();
 		sp_npos_elections::phragmms(winners, targets, voters, Balancing::get())
 	}
 
 	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
 		T::phragmms(voters, targets, vote_degree)
 	}
+
+	/// Same as [`SequentialPhragmen::solve_with_budget`].
+	fn solve_with_budget<Bound: Get<u32>, T: WeightInfo>(
+		winners: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
+		budget: SolverBudget,
+	) -> Result<BudgetedElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		let (balancing, degraded) = budget.fit_balancing(Balancing::get(), || {
+			Self::weight::<T>(voters.len() as u32, targets.len() as u32, Bound::get())
+		});
+		let voters = voters.into_iter().map(Voter::into_tuple).collect::<Vec<_>>();
+		sp_npos_elections::phragmms(winners, targets, voters, balancing)
+			.map(|result| BudgetedElectionResult { result, degraded })
+	}
+}
+
+/// A wrapper for [`sp_npos_elections::approval_voting()`] that implements [`NposSolver`]. See
+/// the documentation of [`sp_npos_elections::approval_voting()`] for more info.
+///
+/// Much cheaper than [`SequentialPhragmen`] and [`PhragMMS`], yet its solutions are of lower
+/// quality. Suitable as a fallback or genesis election.
+pub struct ApprovalVoting<AccountId, Accuracy>(sp_std::marker::PhantomData<(AccountId, Accuracy)>);
+
+impl<AccountId: IdentifierT, Accuracy: PerThing128> NposSolver
+	for ApprovalVoting<AccountId, Accuracy>
+{
+	type AccountId = AccountId;
+	type Accuracy = Accuracy;
+	type Error = sp_npos_elections::Error;
+	fn solve<Bound: Get<u32>>(
+		winners: usize,
+		targets: Vec<Self::AccountId>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
+	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		let voters = voters.into_iter().map(Voter::into_tuple).collect::<Vec<_>>();
+		sp_npos_elections::approval_voting(winners, targets, voters)
+	}
+
+	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
+		// not benchmarked on its own: bounded by the more expensive phragmen.
+		T::phragmen(voters, targets, vote_degree)
+	}
+}
+
+/// An [`NposSolver`] that elects `to_elect` targets pseudo-randomly, deterministically derived
+/// from `Seed`, and backs each winner only with its own vote for itself, if any.
+///
+/// The quality of its solutions is arbitrary, yet it is linear in the number of voters and
+/// targets. Meant for test networks, or as an emergency fallback when no other solver can fit in
+/// a block.
+pub struct RandomSelection<AccountId, Accuracy, Seed>(
+	sp_std::marker::PhantomData<(AccountId, Accuracy, Seed)>,
+);
+
+impl<AccountId: IdentifierT, Accuracy: PerThing128, Seed: Get<sp_core::H256>> NposSolver
+	for RandomSelection<AccountId, Accuracy, Seed>
+{
+	type AccountId = AccountId;
+	type Accuracy = Accuracy;
+	type Error = sp_npos_elections::Error;
+	fn solve<Bound: Get<u32>>(
+		to_elect: usize,
+		mut targets: Vec<Self::AccountId>,
+		voters: Vec<Voter<Self::AccountId, Bound>>,
+	) -> Result<ElectionResult<Self::AccountId, Self::Accuracy>, Self::Error> {
+		use sp_runtime::traits::{BlakeTwo256, Hash, TrailingZeroInput};
+
+		// a partial Fisher-Yates shuffle, moving the winners to the front.
+		let seed = Seed::get();
+		let to_elect = to_elect.min(targets.len());
+		for i in 0..to_elect {
+			let hash = (seed, i as u32).using_encoded(BlakeTwo256::hash);
+			let random = u32::decode(&mut TrailingZeroInput::new(hash.as_ref()))
+				.expect("input is padded with zeroes; qed");
+			targets.swap(i, i + random as usize % (targets.len() - i));
+		}
+		targets.truncate(to_elect);
+
+		let index = targets
+			.iter()
+			.enumerate()
+			.map(|(i, w)| (w.clone(), i))
+			.collect::<sp_std::collections::btree_map::BTreeMap<_, _>>();
+		let mut winners =
+			targets.into_iter().map(|w| (w, ExtendedBalance::zero())).collect::<Vec<_>>();
+		let mut assignments = Vec::new();
+		for (who, weight, votes) in voters.into_iter().map(Voter::into_tuple) {
+			match index.get(&who) {
+				Some(&i) if votes.contains(&who) => {
+					winners[i].1 = winners[i].1.saturating_add(weight.into());
+					assignments.push(Assignment {
+						who: who.clone(),
+						distribution: vec![(who, Accuracy::one())],
+					});
+				},
+				_ => (),
+			}
+		}
+
+		Ok(ElectionResult { winners, assignments })
+	}
+
+	fn weight<T: WeightInfo>(voters: u32, targets: u32, vote_degree: u32) -> Weight {
+		// not benchmarked on its own: bounded by the more expensive phragmen.
+		T::phragmen(voters, targets, vote_degree)
+	}
 }
 
 /// A voter, at the level of abstraction of this crate.
-pub type Voter<AccountId, Bound> = (AccountId, VoteWeight, BoundedVec<AccountId, Bound>);
+///
+/// Encodes the same as the tuple `(who, weight, targets)`.
+#[derive(Encode, Decode, codec::MaxEncodedLen, scale_info::TypeInfo)]
+#[scale_info(skip_type_params(Bound))]
+pub struct Voter<AccountId, Bound: Get<u32>> {
+	/// The account of the voter.
+	pub who: AccountId,
+	/// The weight of the votes of the voter.
+	pub weight: VoteWeight,
+	/// The targets that the voter votes for.
+	pub targets: BoundedVec<AccountId, Bound>,
+}
+
+impl<AccountId, Bound: Get<u32>> Voter<AccountId, Bound> {
+	/// Create a new voter.
+	pub fn new(who: AccountId, weight: VoteWeight, targets: BoundedVec<AccountId, Bound>) -> Self {
+		Self { who, weight, targets }
+	}
+
+	/// Convert into a tuple of `(who, weight, targets)`, as expected by [`sp_npos_elections`].
+	pub fn into_tuple(self) -> (AccountId, VoteWeight, BoundedVec<AccountId, Bound>) {
+		(self.who, self.weight, self.targets)
+	}
+}
+
+// `Bound` is only a marker, so these are implemented by hand rather than derived, which would
+// require it to implement them as well.
+impl<AccountId: Clone, Bound: Get<u32>> Clone for Voter<AccountId, Bound> {
+	fn clone(&self) -> Self {
+		Self { who: self.who.clone(), weight: self.weight, targets: self.targets.clone() }
+	}
+}
+
+impl<AccountId: PartialEq, Bound: Get<u32>> PartialEq for Voter<AccountId, Bound> {
+	fn eq(&self, other: &Self) -> bool {
+		self.who == other.who && self.weight == other.weight && self.targets == other.targets
+	}
+}
+
+impl<AccountId: Eq, Bound: Get<u32>> Eq for Voter<AccountId, Bound> {}
+
+impl<AccountId: Debug, Bound: Get<u32>> Debug for Voter<AccountId, Bound> {
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+		f.debug_struct("Voter")
+			.field("who", &self.who)
+			.field("weight", &self.weight)
+			.field("targets", &self.targets)
+			.finish()
+	}
+}
 
 /// Same as [`Voter`], but parameterized by an [`ElectionDataProvider`].
 pub type VoterOf<D> =
@@ -675,8 +1367,151 @@ pub type VoterOf<D> =
 pub type BoundedSupportsOf<E> = BoundedSupports<
 	<E as ElectionProviderBase>::AccountId,
 	<E as ElectionProviderBase>::MaxWinners,
+	<E as ElectionProviderBase>::MaxBackersPerWinner,
 >;
 
+/// The runners-up of an election, best first, with the support they would have had, as returned
+/// by [`ElectionProvider::elect_with_runners_up`].
+pub type RunnersUpOf<E> = Vec<(
+	<E as ElectionProviderBase>::AccountId,
+	BoundedSupport<
+		<E as ElectionProviderBase>::AccountId,
+		<E as ElectionProviderBase>::MaxBackersPerWinner,
+	>,
+)>;
+
+/// The [`ElectionScore`] of some `supports`, i.e. the minimal, the sum and the sum of squares of
+/// the stake backing their winners.
+///
+/// Useful to monitor the quality of the outcome of [`ElectionProvider::elect`].
+pub fn score_of<S: sp_npos_elections::EvaluateSupport + ?Sized>(supports: &S) -> ElectionScore {
+	sp_npos_elections::EvaluateSupport::evaluate(supports)
+}
+
+/// An error found by [`validate_supports`].
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum SupportsError<AccountId> {
+	/// More winners than the desired targets were elected.
+	TooManyWinners { winners: u32, desired_targets: u32 },
+	/// A winner was elected more than once.
+	DuplicateWinner(AccountId),
+	/// A winner is not one of the targets of the election.
+	UnknownWinner(AccountId),
+	/// A winner has no backing at all.
+	UnbackedWinner(AccountId),
+}
+
+/// Check that `supports` are a sane outcome of an election of `desired_targets` winners, out of
+/// the targets for which `is_target` holds.
+///
+/// Meant as a defensive check of the outcome of [`ElectionProvider::elect`], before acting upon
+/// it.
+pub fn validate_supports<AccountId: Ord + Clone, MaxBackers: Get<u32>>(
+	supports: &[(AccountId, BoundedSupport<AccountId, MaxBackers>)],
+	desired_targets: u32,
+	is_target: impl Fn(&AccountId) -> bool,
+) -> Result<(), SupportsError<AccountId>> {
+	let winners = supports.len() as u32;
+	if winners > desired_targets {
+		return Err(SupportsError::TooManyWinners { winners, desired_targets })
+	}
+
+	let mut seen = sp_std::collections::btree_set::BTreeSet::new();
+	for (winner, support) in supports {
+		if !seen.insert(winner) {
+			return Err(SupportsError::DuplicateWinner(winner.clone()))
+		}
+		if !is_target(winner) {
+			return Err(SupportsError::UnknownWinner(winner.clone()))
+		}
+		if support.total.is_zero() {
+			return Err(SupportsError::UnbackedWinner(winner.clone()))
+		}
+	}
+
+	Ok(())
+}
+
+/// An error found by [`feasibility_check`].
+#[derive(Clone, Eq, PartialEq, RuntimeDebug)]
+pub enum FeasibilityCheckError {
+	/// The solution does not have `desired_targets` winners.
+	WrongWinnerCount,
+	/// A voter of the solution is not in the snapshot.
+	InvalidVoter,
+	/// A vote of the solution is not one of the votes of its voter in the snapshot.
+	InvalidVote,
+	/// Internal error from the election crate, e.g. an index out of the snapshot.
+	NposElection(sp_npos_elections::Error),
+}
+
+impl From<sp_npos_elections::Error> for FeasibilityCheckError {
+	fn from(e: sp_npos_elections::Error) -> Self {
+		FeasibilityCheckError::NposElection(e)
+	}
+}
+
+/// Reconstruct the supports of the compact `solution` against the `voters` and `targets` of the
+/// snapshot it was computed from, ensuring that it elects exactly `desired_targets` and only uses
+/// edges that are votes in the snapshot.
+///
+/// The score of the returned supports, and any bound on them, are left to the caller to check.
+pub fn feasibility_check<S: NposSolution, AccountId: IdentifierT, Bound: Get<u32>>(
+	solution: S,
+	voters: &[Voter<AccountId, Bound>],
+	targets: &[AccountId],
+	desired_targets: u32,
+) -> Result<Supports<AccountId>, FeasibilityCheckError> {
+	// Winners are not directly encoded in the solution.
+	if solution.unique_targets().len() as u32 != desired_targets {
+		return Err(FeasibilityCheckError::WrongWinnerCount)
+	}
+
+	let cache = voters
+		.iter()
+		.enumerate()
+		.map(|(i, voter)| (voter.who.clone(), i))
+		.collect::<sp_std::collections::btree_map::BTreeMap<_, _>>();
+	let voter_at = |i: S::VoterIndex| {
+		i.try_into()
+			.ok()
+			.and_then(|i: usize| voters.get(i))
+			.map(|voter| voter.who.clone())
+	};
+	let target_at =
+		|i: S::TargetIndex| i.try_into().ok().and_then(|i: usize| targets.get(i)).cloned();
+
+	// This fails if any of the indices are gibberish, namely any of the voters or targets.
+	let assignments = solution.into_assignment(voter_at, target_at)?;
+
+	for assignment in &assignments {
+		// Defensive-only: the voter comes from the snapshot, must exist.
+		let voter = cache
+			.get(&assignment.who)
+			.and_then(|i| voters.get(*i))
+			.ok_or(FeasibilityCheckError::InvalidVoter)?;
+		if assignment
+			.distribution
+			.iter()
+			.any(|(target, _)| !voter.targets.contains(target))
+		{
+			return Err(FeasibilityCheckError::InvalidVote)
+		}
+	}
+
+	let stake_of = |who: &AccountId| {
+		cache
+			.get(who)
+			.and_then(|i| voters.get(*i))
+			.map(|voter| voter.weight)
+			.unwrap_or_default()
+	};
+	// This might fail if the normalization fails. Very unlikely.
+	let staked_assignments =
+		sp_npos_elections::assignment_ratio_to_staked_normalized(assignments, stake_of)?;
+	Ok(sp_npos_elections::to_supports(&staked_assignments))
+}
+
 sp_core::generate_feature_enabled_macro!(
 	runtime_benchmarks_enabled,
 	feature = "runtime-benchmarks",
@@ -21,12 +21,14 @@
 
 use crate::{
 	bounds::{DataProviderBounds, ElectionBounds, ElectionBoundsBuilder},
-	BoundedSupportsOf, Debug, ElectionDataProvider, ElectionProvider, ElectionProviderBase,
-	InstantElectionProvider, NposSolver, WeightInfo,
+	BoundedSupportsOf, BudgetedElectionResult, Debug, ElectionDataProvider, ElectionProvider,
+	ElectionProviderBase, InstantElectionProvider, NposSolver, RunnersUpOf, SolverBudget, VoterOf,
+	Weight, WeightInfo,
 };
 use frame_support::{dispatch::DispatchClass, traits::Get};
 use sp_npos_elections::{
-	assignment_ratio_to_staked_normalized, to_supports, BoundedSupports, ElectionResult, VoteWeight,
+	assignment_ratio_to_staked_normalized, to_supports, BoundedSupport, BoundedSupports,
+	ElectionResult, Support, VoteWeight,
 };
 use sp_std::{collections::btree_map::BTreeMap, marker::PhantomData, prelude::*};
 
@@ -85,45 +87,105 @@ pub trait Config {
 	/// always be more than `DataProvider::desired_target`.
 	type MaxWinners: Get<u32>;
 
+	/// Upper bound on the backers of each winner.
+	///
+	/// The backers of a winner in excess of this bound are dropped, lowest stake first.
+	type MaxBackersPerWinner: Get<u32>;
+
 	/// Elections bounds, to use when calling into [`Config::DataProvider`]. It might be overwritten
 	/// in the `InstantElectionProvider` impl.
 	type Bounds: Get<ElectionBounds>;
+
+	/// The budget given to [`Config::Solver`], e.g. to keep it within a fraction of the block
+	/// weight.
+	type SolverBudget: Get<SolverBudget>;
 }
 
 /// Same as `BoundedSupportsOf` but for `onchain::Config`.
 pub type OnChainBoundedSupportsOf<E> = BoundedSupports<
 	<<E as Config>::System as frame_system::Config>::AccountId,
 	<E as Config>::MaxWinners,
+	<E as Config>::MaxBackersPerWinner,
 >;
 
-fn elect_with_input_bounds<T: Config>(
+/// The voters, the targets and the desired targets of an election within `bounds`.
+fn election_inputs<T: Config>(
 	bounds: ElectionBounds,
-) -> Result<OnChainBoundedSupportsOf<T>, Error> {
+) -> Result<
+	(Vec<VoterOf<T::DataProvider>>, Vec<<T::System as frame_system::Config>::AccountId>, u32),
+	Error,
+> {
 	let (voters, targets) = T::DataProvider::electing_voters(bounds.voters)
 		.and_then(|voters| Ok((voters, T::DataProvider::electable_targets(bounds.targets)?)))
 		.map_err(Error::DataProvider)?;
 
 	let desired_targets = T::DataProvider::desired_targets().map_err(Error::DataProvider)?;
 
+	Ok((voters, targets, desired_targets))
+}
+
+fn elect_with_input_bounds<T: Config>(
+	bounds: ElectionBounds,
+) -> Result<OnChainBoundedSupportsOf<T>, Error> {
+	let (voters, targets, desired_targets) = election_inputs::<T>(bounds)?;
+	elect_with_inputs::<T>(voters, targets, desired_targets)
+}
+
+fn elect_with_inputs<T: Config>(
+	voters: Vec<VoterOf<T::DataProvider>>,
+	targets: Vec<<T::System as frame_system::Config>::AccountId>,
+	desired_targets: u32,
+) -> Result<OnChainBoundedSupportsOf<T>, Error> {
 	if desired_targets > T::MaxWinners::get() {
 		// early exit
 		return Err(Error::TooManyWinners)
 	}
 
+	// defensive: Since npos solver returns a result always bounded by `desired_targets`, this is
+	// never expected to happen as long as npos solver does what is expected for it to do.
+	let supports: OnChainBoundedSupportsOf<T> = solve::<T>(voters, targets, desired_targets)?
+		.into_iter()
+		.map(|(who, support)| (who, BoundedSupport::sorted_truncate_from(support).0))
+		.collect::<Vec<_>>()
+		.try_into()
+		.map_err(|_| Error::TooManyWinners)?;
+
+	Ok(supports)
+}
+
+/// Elect `desired_targets` out of `targets` with [`Config::Solver`], registering its weight.
+fn solve<T: Config>(
+	voters: Vec<VoterOf<T::DataProvider>>,
+	targets: Vec<<T::System as frame_system::Config>::AccountId>,
+	desired_targets: u32,
+) -> Result<
+	Vec<(
+		<T::System as frame_system::Config>::AccountId,
+		Support<<T::System as frame_system::Config>::AccountId>,
+	)>,
+	Error,
+> {
 	let voters_len = voters.len() as u32;
 	let targets_len = targets.len() as u32;
 
-	let stake_map: BTreeMap<_, _> = voters
-		.iter()
-		.map(|(validator, vote_weight, _)| (validator.clone(), *vote_weight))
-		.collect();
+	let stake_map: BTreeMap<_, _> =
+		voters.iter().map(|voter| (voter.who.clone(), voter.weight)).collect();
 
 	let stake_of = |w: &<T::System as frame_system::Config>::AccountId| -> VoteWeight {
 		stake_map.get(w).cloned().unwrap_or_default()
 	};
 
-	let ElectionResult { winners: _, assignments } =
-		T::Solver::solve(desired_targets as usize, targets, voters).map_err(Error::from)?;
+	let BudgetedElectionResult { result: ElectionResult { winners: _, assignments }, degraded } =
+		T::Solver::solve_with_budget::<_, T::WeightInfo>(
+			desired_targets as usize,
+			targets,
+			voters,
+			T::SolverBudget::get(),
+		)
+		.map_err(Error::from)?;
+	if degraded {
+		log::warn!(target: crate::LOG_TARGET, "on-chain solution degraded to fit the solver budget");
+	}
 
 	let staked = assignment_ratio_to_staked_normalized(assignments, &stake_of)?;
 
@@ -137,12 +199,7 @@ fn elect_with_input_bounds<T: Config>(
 		DispatchClass::Mandatory,
 	);
 
-	// defensive: Since npos solver returns a result always bounded by `desired_targets`, this is
-	// never expected to happen as long as npos solver does what is expected for it to do.
-	let supports: OnChainBoundedSupportsOf<T> =
-		to_supports(&staked).try_into().map_err(|_| Error::TooManyWinners)?;
-
-	Ok(supports)
+	Ok(to_supports(&staked))
 }
 
 impl<T: Config> ElectionProviderBase for OnChainExecution<T> {
@@ -150,7 +207,20 @@ impl<T: Config> ElectionProviderBase for OnChainExecution<T> {
 	type BlockNumber = frame_system::pallet_prelude::BlockNumberFor<T::System>;
 	type Error = Error;
 	type MaxWinners = T::MaxWinners;
+	type MaxBackersPerWinner = T::MaxBackersPerWinner;
 	type DataProvider = T::DataProvider;
+
+	/// Estimated from the voter and target count bounds in [`Config::Bounds`]. An unbounded count
+	/// is not accounted for, so the estimate is a lower bound in that case.
+	fn estimate_elect_weight() -> Weight {
+		let bounds = T::Bounds::get();
+		let count_of = |bounds: DataProviderBounds| bounds.count.map_or(0, |c| c.0);
+		T::Solver::weight::<T::WeightInfo>(
+			count_of(bounds.voters),
+			count_of(bounds.targets),
+			<T::DataProvider as ElectionDataProvider>::MaxVotesPerVoter::get(),
+		)
+	}
 }
 
 impl<T: Config> InstantElectionProvider for OnChainExecution<T> {
@@ -165,6 +235,14 @@ impl<T: Config> InstantElectionProvider for OnChainExecution<T> {
 
 		elect_with_input_bounds::<T>(elections_bounds)
 	}
+
+	fn instant_elect_with_inputs(
+		voters: Vec<VoterOf<Self::DataProvider>>,
+		targets: Vec<Self::AccountId>,
+		desired_targets: u32,
+	) -> Result<BoundedSupportsOf<Self>, Self::Error> {
+		elect_with_inputs::<T>(voters, targets, desired_targets)
+	}
 }
 
 impl<T: Config> ElectionProvider for OnChainExecution<T> {
@@ -176,15 +254,46 @@ impl<T: Config> ElectionProvider for OnChainExecution<T> {
 		let election_bounds = ElectionBoundsBuilder::from(T::Bounds::get()).build();
 		elect_with_input_bounds::<T>(election_bounds)
 	}
+
+	/// The runners-up are the targets that would also win if `runners_up` more seats were up for
+	/// election, ranked by the support they would have had.
+	///
+	/// This runs the solver twice, and registers the weight of both runs, so `runners_up` should
+	/// be kept at zero unless the runners-up are needed.
+	fn elect_with_runners_up(
+		runners_up: u32,
+	) -> Result<(BoundedSupportsOf<Self>, RunnersUpOf<Self>), Self::Error> {
+		let election_bounds = ElectionBoundsBuilder::from(T::Bounds::get()).build();
+		let (voters, targets, desired_targets) = election_inputs::<T>(election_bounds)?;
+		let winners = elect_with_inputs::<T>(voters.clone(), targets.clone(), desired_targets)?;
+		if runners_up == 0 {
+			return Ok((winners, Vec::new()))
+		}
+
+		let mut others = solve::<T>(voters, targets, desired_targets.saturating_add(runners_up))?
+			.into_iter()
+			.filter(|(who, _)| !winners.iter().any(|(winner, _)| winner == who))
+			.collect::<Vec<_>>();
+		others.sort_by(|(_, a), (_, b)| b.total.cmp(&a.total));
+		let runners_up = others
+			.into_iter()
+			.take(runners_up as usize)
+			.map(|(who, support)| (who, BoundedSupport::sorted_truncate_from(support).0))
+			.collect();
+
+		Ok((winners, runners_up))
+	}
 }
 
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{ElectionProvider, PhragMMS, SequentialPhragmen};
+	use crate::{
+		ContinueWithPrevious, ElectionProvider, Fallback, NoElection, PhragMMS, SequentialPhragmen,
+		Voter,
+	};
 	use frame_support::{assert_noop, parameter_types};
-	use sp_npos_elections::Support;
-	use sp_runtime::Perbill;
+	use sp_runtime::{bounded_vec, Perbill};
 	type AccountId = u64;
 	type Nonce = u64;
 	type BlockNumber = u64;
@@ -231,6 +340,7 @@ mod tests {
 
 	parameter_types! {
 		pub static MaxWinners: u32 = 10;
+		pub static MaxBackersPerWinner: u32 = 10;
 		pub static DesiredTargets: u32 = 2;
 		pub static Bounds: ElectionBounds = ElectionBoundsBuilder::default().voters_count(600.into()).targets_count(400.into()).build();
 	}
@@ -241,7 +351,9 @@ mod tests {
 		type DataProvider = mock_data_provider::DataProvider;
 		type WeightInfo = ();
 		type MaxWinners = MaxWinners;
+		type MaxBackersPerWinner = MaxBackersPerWinner;
 		type Bounds = Bounds;
+		type SolverBudget = ();
 	}
 
 	impl Config for PhragMMSParams {
@@ -250,15 +362,16 @@ mod tests {
 		type DataProvider = mock_data_provider::DataProvider;
 		type WeightInfo = ();
 		type MaxWinners = MaxWinners;
+		type MaxBackersPerWinner = MaxBackersPerWinner;
 		type Bounds = Bounds;
+		type SolverBudget = ();
 	}
 
 	mod mock_data_provider {
 		use frame_support::traits::ConstU32;
-		use sp_runtime::bounded_vec;
 
 		use super::*;
-		use crate::{data_provider, VoterOf};
+		use crate::{data_provider, Voter, VoterOf};
 
 		pub struct DataProvider;
 		impl ElectionDataProvider for DataProvider {
@@ -267,9 +380,9 @@ mod tests {
 			type MaxVotesPerVoter = ConstU32<2>;
 			fn electing_voters(_: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>> {
 				Ok(vec![
-					(1, 10, bounded_vec![10, 20]),
-					(2, 20, bounded_vec![30, 20]),
-					(3, 30, bounded_vec![10, 30]),
+					Voter::new(1, 10, bounded_vec![10, 20]),
+					Voter::new(2, 20, bounded_vec![30, 20]),
+					Voter::new(3, 30, bounded_vec![10, 30]),
 				])
 			}
 
@@ -293,8 +406,8 @@ mod tests {
 			assert_eq!(
 				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect().unwrap(),
 				vec![
-					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
-					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
+					(10, BoundedSupport { total: 25, voters: bounded_vec![(1, 10), (3, 15)] }),
+					(30, BoundedSupport { total: 35, voters: bounded_vec![(2, 20), (3, 15)] })
 				]
 			);
 		})
@@ -314,14 +427,158 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn onchain_elects_with_explicit_inputs() {
+		type Provider = OnChainExecution<PhragmenParams>;
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			// the inputs of the data provider yield the same winners.
+			let voters = vec![
+				Voter::new(1, 10, bounded_vec![10, 20]),
+				Voter::new(2, 20, bounded_vec![30, 20]),
+				Voter::new(3, 30, bounded_vec![10, 30]),
+			];
+			assert_eq!(
+				Provider::instant_elect_with_inputs(voters, vec![10, 20, 30], 2).unwrap(),
+				<Provider as ElectionProvider>::elect().unwrap()
+			);
+
+			// but the data provider is not called into.
+			assert_eq!(
+				Provider::instant_elect_with_inputs(
+					vec![Voter::new(1, 10, bounded_vec![20])],
+					vec![10, 20, 30],
+					1
+				)
+				.unwrap(),
+				vec![(20, BoundedSupport { total: 10, voters: bounded_vec![(1, 10)] })]
+			);
+
+			// the winners are still bounded.
+			MaxWinners::set(1);
+			assert_eq!(
+				Provider::instant_elect_with_inputs(vec![], vec![10, 20, 30], 2),
+				Err(Error::TooManyWinners)
+			);
+		})
+	}
+
+	#[test]
+	fn onchain_elects_with_runners_up() {
+		type Provider = OnChainExecution<PhragmenParams>;
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			// the winners are the same as without runners-up.
+			let (winners, runners_up) = Provider::elect_with_runners_up(0).unwrap();
+			assert_eq!(winners, Provider::elect().unwrap());
+			assert!(runners_up.is_empty());
+
+			// the only target that did not win is the only runner-up, however many are asked for.
+			let (winners, runners_up) = Provider::elect_with_runners_up(2).unwrap();
+			assert_eq!(winners, Provider::elect().unwrap());
+			assert_eq!(runners_up.len(), 1);
+			assert_eq!(runners_up[0].0, 20);
+			assert!(runners_up[0].1.total > 0);
+		})
+	}
+
+	#[test]
+	fn backers_in_excess_of_max_backers_per_winner_are_dropped() {
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			MaxBackersPerWinner::set(1);
+
+			// only the backer with the highest stake is kept, and the total is updated.
+			assert_eq!(
+				<OnChainExecution::<PhragmenParams> as ElectionProvider>::elect().unwrap(),
+				vec![
+					(10, BoundedSupport { total: 15, voters: bounded_vec![(3, 15)] }),
+					(30, BoundedSupport { total: 20, voters: bounded_vec![(2, 20)] })
+				]
+			);
+		})
+	}
+
+	#[test]
+	fn fallback_elects_with_the_secondary_if_the_primary_fails() {
+		type Failing = NoElection<(
+			AccountId,
+			BlockNumber,
+			mock_data_provider::DataProvider,
+			MaxWinners,
+			MaxBackersPerWinner,
+		)>;
+		let expected = vec![
+			(10, BoundedSupport { total: 25, voters: bounded_vec![(1, 10), (3, 15)] }),
+			(30, BoundedSupport { total: 35, voters: bounded_vec![(2, 20), (3, 15)] }),
+		];
+
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			// the primary is used when it succeeds.
+			assert_eq!(
+				<Fallback<OnChainExecution<PhragmenParams>, Failing> as ElectionProvider>::elect()
+					.unwrap(),
+				expected
+			);
+
+			// the secondary is used otherwise.
+			assert_eq!(
+				<Fallback<Failing, OnChainExecution<PhragmenParams>> as ElectionProvider>::elect()
+					.unwrap(),
+				expected
+			);
+			assert_eq!(
+				<Fallback<Failing, OnChainExecution<PhragmenParams>> as InstantElectionProvider>::instant_elect(
+					Default::default(),
+					Default::default(),
+				)
+				.unwrap(),
+				expected
+			);
+
+			// and its error is returned if it fails too.
+			assert_eq!(
+				<Fallback<Failing, Failing> as ElectionProvider>::elect(),
+				Err("`NoElection` cannot do anything.")
+			);
+		})
+	}
+
+	#[test]
+	fn continue_with_previous_returns_the_last_winners_on_failure() {
+		parameter_types! {
+			pub Prefix: &'static str = "PreviousElection";
+		}
+		type Provider = ContinueWithPrevious<OnChainExecution<PhragmenParams>, Prefix>;
+
+		sp_io::TestExternalities::new_empty().execute_with(|| {
+			// the inner provider fails before ever succeeding: its error is returned.
+			DesiredTargets::set(10);
+			MaxWinners::set(9);
+			assert_eq!(Provider::elect(), Err(Error::TooManyWinners));
+
+			// it succeeds: its winners are returned.
+			DesiredTargets::set(2);
+			let winners = Provider::elect().unwrap();
+			assert_eq!(
+				winners,
+				vec![
+					(10, BoundedSupport { total: 25, voters: bounded_vec![(1, 10), (3, 15)] }),
+					(30, BoundedSupport { total: 35, voters: bounded_vec![(2, 20), (3, 15)] })
+				]
+			);
+
+			// it fails again: the previous winners are returned.
+			DesiredTargets::set(10);
+			assert_eq!(Provider::elect(), Ok(winners));
+		})
+	}
+
 	#[test]
 	fn onchain_phragmms_works() {
 		sp_io::TestExternalities::new_empty().execute_with(|| {
 			assert_eq!(
 				<OnChainExecution::<PhragMMSParams> as ElectionProvider>::elect().unwrap(),
 				vec![
-					(10, Support { total: 25, voters: vec![(1, 10), (3, 15)] }),
-					(30, Support { total: 35, voters: vec![(2, 20), (3, 15)] })
+					(10, BoundedSupport { total: 25, voters: bounded_vec![(1, 10), (3, 15)] }),
+					(30, BoundedSupport { total: 35, voters: bounded_vec![(2, 20), (3, 15)] })
 				]
 			);
 		})
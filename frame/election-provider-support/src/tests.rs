@@ -19,7 +19,12 @@
 
 #![cfg(test)]
 
-use crate::{mock::*, IndexAssignment, NposSolution};
+use crate::{
+	feasibility_check, mock::*, score_of, validate_supports, BoundedSupport,
+	BudgetedElectionResult, ElectionResult, ElectionScore, FeasibilityCheckError, IndexAssignment,
+	NposSolution, NposSolver, PerThing, RandomSelection, SequentialPhragmen, SolverBudget, Support,
+	SupportsError, Voter,
+};
 use frame_support::traits::ConstU32;
 use rand::SeedableRng;
 
@@ -452,3 +457,180 @@ fn index_assignments_generate_same_solution_as_plain_assignments() {
 
 	assert_eq!(solution, index_compact);
 }
+
+#[test]
+fn score_of_works() {
+	let supports = vec![
+		(10u32, Support { total: 30, voters: vec![(1, 20), (10, 10)] }),
+		(20u32, Support { total: 10, voters: vec![(20, 10)] }),
+	];
+
+	assert_eq!(
+		score_of(&supports),
+		ElectionScore { minimal_stake: 10, sum_stake: 40, sum_stake_squared: 1_000 }
+	);
+}
+
+#[test]
+fn validate_supports_works() {
+	let support = |total| BoundedSupport::<u32, ConstU32<4>> {
+		total,
+		voters: sp_runtime::bounded_vec![(1, total)],
+	};
+	let is_target = |who: &u32| [10, 20, 30].contains(who);
+
+	// a sane outcome.
+	let supports = vec![(10u32, support(10)), (20, support(20))];
+	assert_eq!(validate_supports(&supports, 2, is_target), Ok(()));
+	assert_eq!(validate_supports(&supports, 3, is_target), Ok(()));
+
+	// more winners than desired.
+	assert_eq!(
+		validate_supports(&supports, 1, is_target),
+		Err(SupportsError::TooManyWinners { winners: 2, desired_targets: 1 })
+	);
+
+	// the same winner twice.
+	let supports = vec![(10u32, support(10)), (10, support(20))];
+	assert_eq!(validate_supports(&supports, 2, is_target), Err(SupportsError::DuplicateWinner(10)));
+
+	// a winner which is not a target.
+	let supports = vec![(10u32, support(10)), (40, support(20))];
+	assert_eq!(validate_supports(&supports, 2, is_target), Err(SupportsError::UnknownWinner(40)));
+
+	// a winner without any backing.
+	let supports = vec![(10u32, support(10)), (30, support(0))];
+	assert_eq!(validate_supports(&supports, 2, is_target), Err(SupportsError::UnbackedWinner(30)));
+}
+
+#[test]
+fn random_selection_works() {
+	struct Seed;
+	impl frame_support::traits::Get<sp_core::H256> for Seed {
+		fn get() -> sp_core::H256 {
+			[7u8; 32].into()
+		}
+	}
+	type Solver = RandomSelection<u32, sp_runtime::Perbill, Seed>;
+
+	let targets = vec![10, 20, 30, 40];
+	let voters = vec![
+		Voter::<u32, ConstU32<4>>::new(10, 10, sp_runtime::bounded_vec![10]),
+		Voter::new(20, 20, sp_runtime::bounded_vec![20]),
+		Voter::new(30, 30, sp_runtime::bounded_vec![30]),
+		Voter::new(40, 40, sp_runtime::bounded_vec![40]),
+		Voter::new(1, 100, sp_runtime::bounded_vec![10, 20, 30, 40]),
+	];
+
+	let ElectionResult { winners, assignments } =
+		Solver::solve(2, targets.clone(), voters.clone()).unwrap();
+	assert_eq!(winners.len(), 2);
+	assert_ne!(winners[0].0, winners[1].0);
+
+	// each winner is backed by its self-vote only.
+	for (winner, backing) in &winners {
+		assert!(targets.contains(winner));
+		assert_eq!(*backing, *winner as u128);
+	}
+	let mut winner_ids = winners.iter().map(|(w, _)| *w).collect::<Vec<_>>();
+	winner_ids.sort();
+	assert_eq!(assignments.iter().map(|a| a.who).collect::<Vec<_>>(), winner_ids);
+
+	// the selection is deterministic.
+	assert_eq!(Solver::solve(2, targets.clone(), voters.clone()).unwrap().winners, winners);
+
+	// all targets win if there are not enough of them.
+	assert_eq!(Solver::solve(10, targets, voters).unwrap().winners.len(), 4);
+}
+
+#[test]
+fn sequential_phragmen_honors_the_budget() {
+	frame_support::parameter_types! {
+		pub Balancing: Option<crate::BalancingConfig> =
+			Some(crate::BalancingConfig { iterations: 4, tolerance: 0 });
+	}
+	type Solver = SequentialPhragmen<AccountId, sp_runtime::Perbill, Balancing>;
+
+	// the distribution of the voter backing both winners, which balancing changes.
+	let distribution_of_30 = |budget| {
+		let voters = vec![
+			Voter::<AccountId, ConstU32<2>>::new(10, 10, sp_runtime::bounded_vec![1, 2]),
+			Voter::new(20, 20, sp_runtime::bounded_vec![1, 3]),
+			Voter::new(30, 30, sp_runtime::bounded_vec![2, 3]),
+		];
+		let BudgetedElectionResult { result: ElectionResult { assignments, .. }, degraded } =
+			Solver::solve_with_budget::<_, ()>(2, vec![1, 2, 3], voters, budget).unwrap();
+		let distribution = assignments
+			.into_iter()
+			.find(|a| a.who == 30)
+			.unwrap()
+			.distribution
+			.into_iter()
+			.map(|(_, p)| p.deconstruct())
+			.collect::<Vec<_>>();
+		(distribution, degraded)
+	};
+	let balanced = (vec![666666666, 333333334], false);
+	let unbalanced = (vec![500000000, 500000000], true);
+
+	assert_eq!(distribution_of_30(SolverBudget::Unlimited), balanced);
+	assert_eq!(distribution_of_30(SolverBudget::MaxIterations(4)), balanced);
+	assert_eq!(distribution_of_30(SolverBudget::MaxIterations(0)), unbalanced);
+	assert_eq!(distribution_of_30(SolverBudget::MaxWeight(crate::Weight::MAX)), balanced);
+	assert_eq!(distribution_of_30(SolverBudget::MaxWeight(crate::Weight::zero())), unbalanced);
+
+	// fewer iterations than configured are flagged, even if balancing converges within them.
+	let voters = vec![Voter::<AccountId, ConstU32<2>>::new(10, 10, sp_runtime::bounded_vec![1])];
+	let BudgetedElectionResult { degraded, .. } =
+		Solver::solve_with_budget::<_, ()>(1, vec![1], voters, SolverBudget::MaxIterations(3))
+			.unwrap();
+	assert!(degraded);
+}
+
+#[test]
+fn feasibility_check_works() {
+	let snapshot = |votes_of_2: Vec<AccountId>| {
+		vec![
+			Voter::<AccountId, ConstU32<2>>::new(1, 10, sp_runtime::bounded_vec![10, 20]),
+			Voter::new(2, 20, votes_of_2.try_into().unwrap()),
+		]
+	};
+	let voters = snapshot(vec![20]);
+	let targets = vec![10, 20];
+	let solution = TestSolution::from_assignment(
+		&[
+			MockAssignment { who: 1, distribution: vec![(10, p(50)), (20, p(50))] },
+			MockAssignment { who: 2, distribution: vec![(20, p(100))] },
+		],
+		|who: &AccountId| voters.iter().position(|v| &v.who == who).map(|i| i as u32),
+		|who: &AccountId| targets.iter().position(|t| t == who).map(|i| i as u16),
+	)
+	.unwrap();
+
+	// the supports are rebuilt from the snapshot.
+	assert_eq!(
+		feasibility_check(solution.clone(), &voters, &targets, 2),
+		Ok(vec![
+			(10, Support { total: 5, voters: vec![(1, 5)] }),
+			(20, Support { total: 25, voters: vec![(1, 5), (2, 20)] }),
+		])
+	);
+
+	// wrong number of winners.
+	assert_eq!(
+		feasibility_check(solution.clone(), &voters, &targets, 1),
+		Err(FeasibilityCheckError::WrongWinnerCount)
+	);
+
+	// an edge that is not a vote in the snapshot.
+	assert_eq!(
+		feasibility_check(solution.clone(), &snapshot(vec![10]), &targets, 2),
+		Err(FeasibilityCheckError::InvalidVote)
+	);
+
+	// an index out of the snapshot.
+	assert_eq!(
+		feasibility_check(solution, &voters, &targets[..1], 2),
+		Err(FeasibilityCheckError::NposElection(crate::Error::SolutionInvalidIndex))
+	);
+}
@@ -93,8 +93,8 @@ impl pallet_balances::Config for Runtime {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = frame_support::traits::ConstU32<1>;
 }
 
 pallet_staking_reward_curve::build! {
@@ -121,6 +121,7 @@ impl frame_election_provider_support::ElectionProviderBase for MockElection {
 	type AccountId = AccountId;
 	type BlockNumber = BlockNumber;
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = ConstU32<{ u32::MAX }>;
 	type DataProvider = Staking;
 	type Error = ();
 }
@@ -134,6 +135,15 @@ impl frame_election_provider_support::ElectionProvider for MockElection {
 	}
 }
 
+impl frame_election_provider_support::InstantElectionProvider for MockElection {
+	fn instant_elect(
+		_: frame_election_provider_support::bounds::DataProviderBounds,
+		_: frame_election_provider_support::bounds::DataProviderBounds,
+	) -> Result<frame_election_provider_support::BoundedSupportsOf<Self>, Self::Error> {
+		Err(())
+	}
+}
+
 impl pallet_staking::Config for Runtime {
 	type Currency = Balances;
 	type CurrencyBalance = Balance;
@@ -160,6 +170,44 @@ impl pallet_staking::Config for Runtime {
 	type NominationsQuota = pallet_staking::FixedNominationsQuota<16>;
 	type MaxUnlockingChunks = ConstU32<32>;
 	type EventListeners = ();
+	type DeductPendingSlashes = frame_support::traits::ConstBool<false>;
+	type DeferOverweightElection = frame_support::traits::ConstBool<false>;
+	type LoyaltyBonus = ();
+	type MaxLoyaltyEras = frame_support::traits::ConstU32<0>;
+	type MaxExitsPerEra = ();
+	type ValidatorWaitlistSessions = ();
+	type VoterSamplingSeed = ();
+	type ValidatorShuffleSeed = ();
+	type WeightAccountant = System;
+	type RemainderPolicy = ();
+	type MaxRememberedOffences = frame_support::traits::ConstU32<1024>;
+	type MaxSlashesPerBlock = frame_support::traits::ConstU32<512>;
+	type MinNominatorBondShare = ();
+	type MinValidatorBondShare = ();
+	type EvictLowestNominators = ();
+	type LogTarget = pallet_staking::DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = ();
+	type MaxAutoPayoutsPerBlock = ();
+	type EraLength = ();
+	type ExternalExposures = ();
+	type ExposureOracle = frame_system::EnsureNever<()>;
+	type ValidatorLiveness = ();
+	type MaxVetoedWinners = ConstU32<0>;
+	type MinNominatorSlash = ();
+	type MaxRunnersUp = ConstU32<0>;
+	type DisablingStrategy = pallet_staking::DisableWhenAsked;
+	type ReplaceDisabledValidators = frame_support::traits::ConstBool<false>;
+	type MinUnbondingDuration = ();
+	type UnbondingQueueSaturation = ();
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = ();
+	type MaxEvictionScan = ();
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = ();
 	type BenchmarkingConfig = pallet_staking::TestBenchmarkingConfig;
 	type WeightInfo = ();
 }
@@ -263,7 +311,7 @@ impl ExtBuilder {
 				(v, Exposure { total: 0, own: 0, others })
 			})
 			.for_each(|(validator, exposure)| {
-				pallet_staking::ErasStakers::<T>::insert(era, validator, exposure);
+				Staking::store_exposure(era, &validator, exposure);
 			});
 	}
 
@@ -362,9 +410,9 @@ pub fn assert_unstaked(stash: &AccountId) {
 
 pub fn create_exposed_nominator(exposed: AccountId, era: u32) {
 	// create an exposed nominator in era 1
-	pallet_staking::ErasStakers::<T>::mutate(era, VALIDATORS_PER_ERA, |expo| {
-		expo.others.push(IndividualExposure { who: exposed, value: 0 as Balance });
-	});
+	let mut expo = Staking::eras_stakers(era, VALIDATORS_PER_ERA);
+	expo.others.push(IndividualExposure { who: exposed, value: 0 as Balance });
+	Staking::store_exposure(era, &VALIDATORS_PER_ERA, expo);
 	Balances::make_free_balance_be(&exposed, 100);
 	assert_ok!(Staking::bond(
 		RuntimeOrigin::signed(exposed),
@@ -788,7 +788,7 @@ mod on_idle {
 			assert_ok!(FastUnstake::register_fast_unstake(RuntimeOrigin::signed(VALIDATOR_PREFIX)));
 
 			// but they indeed are exposed!
-			assert!(pallet_staking::ErasStakers::<T>::contains_key(
+			assert!(pallet_staking::ErasStakersTotals::<T>::contains_key(
 				BondingDuration::get() - 1,
 				VALIDATOR_PREFIX
 			));
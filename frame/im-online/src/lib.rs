@@ -105,7 +105,7 @@ use sp_runtime::{
 };
 use sp_staking::{
 	offence::{DisableStrategy, Kind, Offence, ReportOffence},
-	SessionIndex,
+	OnSessionParticipation, SessionIndex,
 };
 use sp_std::prelude::*;
 pub use weights::WeightInfo;
@@ -258,9 +258,26 @@ pub mod pallet {
 	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
-	#[pallet::config]
+	/// Default implementations of [`DefaultConfig`], which can be used to implement [`Config`].
+	pub mod config_preludes {
+		use super::*;
+		use frame_support::derive_impl;
+
+		pub struct TestDefaultConfig;
+
+		#[derive_impl(frame_system::config_preludes::TestDefaultConfig as frame_system::DefaultConfig, no_aggregated_types)]
+		impl frame_system::DefaultConfig for TestDefaultConfig {}
+
+		#[frame_support::register_default_impl(TestDefaultConfig)]
+		impl DefaultConfig for TestDefaultConfig {
+			type OnSessionParticipation = ();
+		}
+	}
+
+	#[pallet::config(with_default)]
 	pub trait Config: SendTransactionTypes<Call<Self>> + frame_system::Config {
 		/// The identifier type for an authority.
+		#[pallet::no_default]
 		type AuthorityId: Member
 			+ Parameter
 			+ RuntimeAppPublic
@@ -269,15 +286,19 @@ pub mod pallet {
 			+ MaxEncodedLen;
 
 		/// The maximum number of keys that can be added.
+		#[pallet::no_default]
 		type MaxKeys: Get<u32>;
 
 		/// The maximum number of peers to be stored in `ReceivedHeartbeats`
+		#[pallet::no_default]
 		type MaxPeerInHeartbeats: Get<u32>;
 
 		/// The overarching event type.
+		#[pallet::no_default]
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
 		/// A type for retrieving the validators supposed to be online in a session.
+		#[pallet::no_default]
 		type ValidatorSet: ValidatorSetWithIdentification<Self::AccountId>;
 
 		/// A trait that allows us to estimate the current session progress and also the
@@ -287,23 +308,34 @@ pub mod pallet {
 		/// rough time when we should start considering sending heartbeats, since the workers
 		/// avoids sending them at the very beginning of the session, assuming there is a
 		/// chance the authority will produce a block and they won't be necessary.
+		#[pallet::no_default]
 		type NextSessionRotation: EstimateNextSessionRotation<BlockNumberFor<Self>>;
 
 		/// A type that gives us the ability to submit unresponsiveness offence reports.
+		#[pallet::no_default]
 		type ReportUnresponsiveness: ReportOffence<
 			Self::AccountId,
 			IdentificationTuple<Self>,
 			UnresponsivenessOffence<IdentificationTuple<Self>>,
 		>;
 
+		/// Something that is notified, before each session ends, of the validators that were
+		/// online during it.
+		///
+		/// Defaults to `()`, which ignores it.
+		#[pallet::no_default_bounds]
+		type OnSessionParticipation: OnSessionParticipation<ValidatorId<Self>>;
+
 		/// A configuration for base priority of unsigned transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
 		/// multiple pallets send unsigned transactions.
 		#[pallet::constant]
+		#[pallet::no_default]
 		type UnsignedPriority: Get<TransactionPriority>;
 
 		/// Weight information for extrinsics in this pallet.
+		#[pallet::no_default]
 		type WeightInfo: WeightInfo;
 	}
 
@@ -779,10 +811,16 @@ impl<T: Config> OneSessionHandler<T::AccountId> for Pallet<T> {
 		let keys = Keys::<T>::get();
 		let current_validators = T::ValidatorSet::validators();
 
-		let offenders = current_validators
+		let (online, offline): (Vec<_>, Vec<_>) = current_validators
 			.into_iter()
 			.enumerate()
-			.filter(|(index, id)| !Self::is_online_aux(*index as u32, id))
+			.partition(|(index, id)| Self::is_online_aux(*index as u32, id));
+
+		let online = online.into_iter().map(|(_, id)| id).collect::<Vec<_>>();
+		T::OnSessionParticipation::on_session_participation(session_index, &online);
+
+		let offenders = offline
+			.into_iter()
 			.filter_map(|(_, id)| {
 				<T::ValidatorSet as ValidatorSetWithIdentification<T::AccountId>>::IdentificationOf::convert(
 					id.clone()
@@ -33,7 +33,7 @@ use sp_runtime::{
 };
 use sp_staking::{
 	offence::{OffenceError, ReportOffence},
-	SessionIndex,
+	OnSessionParticipation, SessionIndex,
 };
 
 use crate as imonline;
@@ -175,6 +175,18 @@ parameter_types! {
 	pub static MockAverageSessionLength: Option<u64> = None;
 }
 
+parameter_types! {
+	pub static Participation: Vec<(SessionIndex, Vec<u64>)> = vec![];
+}
+
+/// Records the reported session participation in `Participation`.
+pub struct SessionParticipation;
+impl OnSessionParticipation<u64> for SessionParticipation {
+	fn on_session_participation(session: SessionIndex, online: &[u64]) {
+		Participation::mutate(|p| p.push((session, online.to_vec())));
+	}
+}
+
 pub struct TestNextSessionRotation;
 
 impl frame_support::traits::EstimateNextSessionRotation<u64> for TestNextSessionRotation {
@@ -212,6 +224,7 @@ impl Config for Runtime {
 	type WeightInfo = ();
 	type MaxKeys = ConstU32<10_000>;
 	type MaxPeerInHeartbeats = ConstU32<10_000>;
+	type OnSessionParticipation = SessionParticipation;
 }
 
 impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Runtime
@@ -173,6 +173,26 @@ fn should_mark_online_validator_when_heartbeat_is_received() {
 	});
 }
 
+#[test]
+fn should_report_session_participation() {
+	new_test_ext().execute_with(|| {
+		advance_session();
+		// given
+		Validators::mutate(|l| *l = Some(vec![1, 2, 3, 4, 5, 6]));
+		advance_session();
+		assert_eq!(Session::current_index(), 2);
+		assert_eq!(Session::validators(), vec![1, 2, 3]);
+
+		// when
+		let _ = heartbeat(1, 2, 0, 1.into(), Session::validators()).unwrap();
+		let _ = heartbeat(1, 2, 2, 3.into(), Session::validators()).unwrap();
+		advance_session();
+
+		// then
+		assert_eq!(Participation::get().last(), Some(&(2, vec![1, 3])));
+	});
+}
+
 #[test]
 fn late_heartbeat_and_invalid_keys_len_should_fail() {
 	new_test_ext().execute_with(|| {
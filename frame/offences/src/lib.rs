@@ -128,7 +128,10 @@ where
 
 		let slash_perbill: Vec<_> = (0..concurrent_offenders.len()).map(|_| new_fraction).collect();
 
-		T::OnOffenceHandler::on_offence(
+		let time_slot = time_slot.encode();
+		T::OnOffenceHandler::on_offence_of_kind(
+			&O::ID,
+			&time_slot,
 			&concurrent_offenders,
 			&slash_perbill,
 			offence.session_index(),
@@ -136,7 +139,7 @@ where
 		);
 
 		// Deposit the event.
-		Self::deposit_event(Event::Offence { kind: O::ID, timeslot: time_slot.encode() });
+		Self::deposit_event(Event::Offence { kind: O::ID, timeslot: time_slot });
 
 		Ok(())
 	}
@@ -24,7 +24,7 @@ use frame_election_provider_support::{
 };
 use frame_support::{
 	parameter_types,
-	traits::{ConstU32, ConstU64, Hooks, OneSessionHandler},
+	traits::{ConstU32, ConstU64, OneSessionHandler},
 };
 use pallet_staking::StakerStatus;
 use sp_core::H256;
@@ -42,16 +42,13 @@ type AccountId = u64;
 type Balance = u64;
 type BlockNumber = u64;
 
-pub const INIT_TIMESTAMP: u64 = 30_000;
-pub const BLOCK_TIME: u64 = 1000;
-
 frame_support::construct_runtime!(
 	pub enum Test
 	{
 		System: frame_system::{Pallet, Call, Config<T>, Storage, Event<T>},
 		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
-		Staking: pallet_staking::{Pallet, Call, Config<T>, Storage, Event<T>},
+		Staking: pallet_staking::{Pallet, Call, Config<T>, Storage, Event<T>, HoldReason},
 		Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
 		RootOffences: root_offences::{Pallet, Call, Storage, Event<T>},
 		Historical: pallet_session::historical::{Pallet, Storage},
@@ -122,8 +119,8 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = frame_support::traits::ConstU32<1>;
 }
 
 pallet_staking_reward_curve::build! {
@@ -148,7 +145,9 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = Staking;
 	type WeightInfo = ();
 	type MaxWinners = ConstU32<100>;
+	type MaxBackersPerWinner = ConstU32<{ u32::MAX }>;
 	type Bounds = ElectionsBounds;
+	type SolverBudget = ();
 }
 
 parameter_types! {
@@ -188,6 +187,44 @@ impl pallet_staking::Config for Test {
 	type HistoryDepth = ConstU32<84>;
 	type VoterList = pallet_staking::UseNominatorsAndValidatorsMap<Self>;
 	type EventListeners = ();
+	type DeductPendingSlashes = frame_support::traits::ConstBool<false>;
+	type DeferOverweightElection = frame_support::traits::ConstBool<false>;
+	type LoyaltyBonus = ();
+	type MaxLoyaltyEras = frame_support::traits::ConstU32<0>;
+	type MaxExitsPerEra = ();
+	type ValidatorWaitlistSessions = ();
+	type VoterSamplingSeed = ();
+	type ValidatorShuffleSeed = ();
+	type WeightAccountant = System;
+	type RemainderPolicy = ();
+	type MaxRememberedOffences = frame_support::traits::ConstU32<1024>;
+	type MaxSlashesPerBlock = frame_support::traits::ConstU32<512>;
+	type MinNominatorBondShare = ();
+	type MinValidatorBondShare = ();
+	type EvictLowestNominators = ();
+	type LogTarget = pallet_staking::DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = ();
+	type MaxAutoPayoutsPerBlock = ();
+	type EraLength = ();
+	type ExternalExposures = ();
+	type ExposureOracle = frame_system::EnsureNever<()>;
+	type ValidatorLiveness = ();
+	type MaxVetoedWinners = ConstU32<0>;
+	type MinNominatorSlash = ();
+	type MaxRunnersUp = ConstU32<0>;
+	type DisablingStrategy = pallet_staking::DisableWhenAsked;
+	type ReplaceDisabledValidators = frame_support::traits::ConstBool<false>;
+	type MinUnbondingDuration = ();
+	type UnbondingQueueSaturation = ();
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = ();
+	type MaxEvictionScan = ();
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = ();
 	type BenchmarkingConfig = pallet_staking::TestBenchmarkingConfig;
 	type WeightInfo = ();
 }
@@ -308,14 +345,13 @@ impl ExtBuilder {
 	}
 }
 
-/// Progresses from the current block number (whatever that may be) to the `P * session_index + 1`.
+/// Progresses from the current block number (whatever that may be) to the first block of
+/// `session_index`.
 pub(crate) fn start_session(session_index: SessionIndex) {
-	let end: u64 = if Offset::get().is_zero() {
-		(session_index as u64) * Period::get()
-	} else {
-		Offset::get() + (session_index.saturating_sub(1) as u64) * Period::get()
-	};
-	run_to_block(end);
+	let current_index = Session::current_index();
+	pallet_staking::time_travel::advance_sessions::<Test>(
+		session_index.saturating_sub(current_index),
+	);
 	// session must have progressed properly.
 	assert_eq!(
 		Session::current_index(),
@@ -326,24 +362,6 @@ pub(crate) fn start_session(session_index: SessionIndex) {
 	);
 }
 
-/// Progress to the given block, triggering session and era changes as we progress.
-///
-/// This will finalize the previous block, initialize up to the given block, essentially simulating
-/// a block import/propose process where we first initialize the block, then execute some stuff (not
-/// in the function), and then finalize the block.
-pub(crate) fn run_to_block(n: BlockNumber) {
-	Staking::on_finalize(System::block_number());
-	for b in (System::block_number() + 1)..=n {
-		System::set_block_number(b);
-		Session::on_initialize(b);
-		<Staking as Hooks<u64>>::on_initialize(b);
-		Timestamp::set_timestamp(System::block_number() * BLOCK_TIME + INIT_TIMESTAMP);
-		if b != n {
-			Staking::on_finalize(System::block_number());
-		}
-	}
-}
-
 pub(crate) fn active_era() -> EraIndex {
 	Staking::active_era().unwrap().index
 }
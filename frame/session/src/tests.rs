@@ -382,6 +382,25 @@ fn disable_index_returns_false_if_already_disabled() {
 	});
 }
 
+#[test]
+fn enable_index_returns_false_if_not_disabled() {
+	new_test_ext().execute_with(|| {
+		set_next_validators(vec![1, 2, 3, 4, 5, 6, 7]);
+		force_new_session();
+		initialize_block(1);
+		// apply the new validator set
+		force_new_session();
+		initialize_block(2);
+
+		assert_eq!(Session::enable_index(0), false);
+		assert_eq!(Session::disable_index(0), true);
+		assert_eq!(Session::disable_index(1), true);
+		assert_eq!(Session::enable_index(0), true);
+		assert_eq!(Session::enable_index(0), false);
+		assert_eq!(Session::disabled_validators(), vec![1]);
+	});
+}
+
 #[test]
 fn upgrade_keys() {
 	use frame_support::storage;
@@ -20,13 +20,61 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 use codec::Codec;
+use sp_arithmetic::Perbill;
+use sp_runtime::traits::NumberFor;
+use sp_staking::{ElectionHealthReport, EraIndex, EraInflation, StakerRole};
+use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
-	pub trait StakingApi<Balance>
+	#[api_version(9)]
+	pub trait StakingApi<Balance, AccountId>
 		where
 			Balance: Codec,
+			AccountId: Codec,
 	{
 		/// Returns the nominations quota for a nominator with a given balance.
 		fn nominations_quota(balance: Balance) -> u32;
+
+		/// Returns, for each validator exposed in `era`, the fraction of its total backing that
+		/// is its own stake.
+		#[api_version(2)]
+		fn exposure_own_ratios(era: EraIndex) -> Vec<(AccountId, Perbill)>;
+
+		/// Returns the oldest era whose rewards can still be paid out, if any era has been
+		/// planned.
+		#[api_version(3)]
+		fn oldest_claimable_era() -> Option<EraIndex>;
+
+		/// Returns the role currently played by the stash `who`, or `None` if it is not bonded.
+		#[api_version(4)]
+		fn staker_status(who: AccountId) -> Option<StakerRole>;
+
+		/// Returns a summary of the health of the elections, from the last snapshots and
+		/// election results.
+		#[api_version(5)]
+		fn election_health_report() -> ElectionHealthReport<NumberFor<Block>>;
+
+		/// Returns the era after which `nominator` is no longer exposed to the slashes of
+		/// `validator`, if it dropped it from its nominations.
+		#[api_version(6)]
+		fn nomination_exit(nominator: AccountId, validator: AccountId) -> Option<EraIndex>;
+
+		/// Returns the projected rewards of `stash` in each of the next `eras_ahead` eras,
+		/// assuming the conditions of the active era stay constant, up to the history depth.
+		#[api_version(7)]
+		fn project_rewards(stash: AccountId, eras_ahead: u32) -> Vec<Balance>;
+
+		/// Returns the inflation of each era still in the history, oldest first.
+		#[api_version(8)]
+		fn eras_inflation() -> Vec<(EraIndex, EraInflation<Balance>)>;
+
+		/// Returns the last voter included in the last voter snapshot, with its vote weight, if
+		/// the snapshot left some voters out.
+		#[api_version(9)]
+		fn inclusion_cutoff() -> Option<(AccountId, u64)>;
+
+		/// Returns whether `who` made it into the last voter snapshot.
+		#[api_version(9)]
+		fn was_included(who: AccountId) -> bool;
 	}
 }
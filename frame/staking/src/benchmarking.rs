@@ -22,21 +22,22 @@ use crate::{ConfigOp, Pallet as Staking};
 use testing_utils::*;
 
 use codec::Decode;
-use frame_election_provider_support::{bounds::DataProviderBounds, SortedListProvider};
+use frame_election_provider_support::{bounds::DataProviderBounds, SortedListProvider, VoteWeight};
 use frame_support::{
 	dispatch::UnfilteredDispatchable,
 	pallet_prelude::*,
 	traits::{Currency, Get, Imbalance},
 };
 use sp_runtime::{
-	traits::{Bounded, One, StaticLookup, TrailingZeroInput, Zero},
+	traits::{Bounded, One, SaturatedConversion, StaticLookup, TrailingZeroInput, Zero},
 	Perbill, Percent,
 };
-use sp_staking::{currency_to_vote::CurrencyToVote, SessionIndex};
+use sp_staking::{currency_to_vote::CurrencyToVote, offence::DisableStrategy, SessionIndex};
 use sp_std::prelude::*;
 
 pub use frame_benchmarking::v1::{
 	account, benchmarks, impl_benchmark_test_suite, whitelist_account, whitelisted_caller,
+	BenchmarkError,
 };
 use frame_system::RawOrigin;
 
@@ -141,6 +142,200 @@ pub fn create_validator_with_nominators<T: Config>(
 	Ok((v_stash, nominators))
 }
 
+/// How [`Setup`] converts a [`VoteWeight`] into a balance.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StakeConversion {
+	/// Fail if the weight does not fit into `BalanceOf<T>`.
+	#[default]
+	Strict,
+	/// Saturate at the maximum balance if the weight does not fit into `BalanceOf<T>`. Allows
+	/// runtimes with small balance types to run setups written for `u64` weights.
+	Saturating,
+}
+
+/// Builder for benchmark-only staking state.
+///
+/// This is the one place where benchmarks, either of this pallet or of pallets using it through
+/// `ElectionDataProvider`, `ScoreProvider` or `StakingInterface`, write stakers, exposures and
+/// slashing parameters straight into storage. Stakes are given as [`VoteWeight`] and converted
+/// with [`Setup::stake_of`], so all helpers map a weight to the same balance.
+///
+/// Nothing is written until [`Setup::build`] is called.
+pub struct Setup<T: Config> {
+	targets: Vec<(T::AccountId, Option<VoteWeight>)>,
+	voters: Vec<(T::AccountId, VoteWeight, BoundedVec<T::AccountId, MaxNominationsOf<T>>)>,
+	era_stakers: Vec<(EraIndex, T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)>,
+	slash_reward_fraction: Option<Perbill>,
+	conversion: StakeConversion,
+}
+
+impl<T: Config> Default for Setup<T> {
+	fn default() -> Self {
+		Self {
+			targets: Vec::new(),
+			voters: Vec::new(),
+			era_stakers: Vec::new(),
+			slash_reward_fraction: None,
+			conversion: Default::default(),
+		}
+	}
+}
+
+impl<T: Config> Setup<T> {
+	/// Create an empty setup.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Convert a vote weight into the balance bonded to back it.
+	///
+	/// With [`StakeConversion::Strict`], fails if `weight` does not fit into `BalanceOf<T>`, in
+	/// which case the benchmark needs reconfiguring.
+	pub fn stake_of(
+		weight: VoteWeight,
+		conversion: StakeConversion,
+	) -> Result<BalanceOf<T>, &'static str> {
+		match conversion {
+			StakeConversion::Strict => <BalanceOf<T>>::try_from(weight).map_err(|_| {
+				"cannot convert a VoteWeight into BalanceOf, benchmark needs reconfiguring."
+			}),
+			StakeConversion::Saturating => Ok(weight.saturated_into()),
+		}
+	}
+
+	/// The stake bonded by a target registered without an explicit weight.
+	pub fn default_target_stake() -> BalanceOf<T> {
+		MinNominatorBond::<T>::get() * 100u32.into()
+	}
+
+	/// Set how vote weights are converted into stakes. Defaults to [`StakeConversion::Strict`].
+	pub fn conversion(mut self, conversion: StakeConversion) -> Self {
+		self.conversion = conversion;
+		self
+	}
+
+	/// Register `who` as a validator, bonding `weight` or [`Self::default_target_stake`].
+	pub fn target(mut self, who: T::AccountId, weight: Option<VoteWeight>) -> Self {
+		self.targets.push((who, weight));
+		self
+	}
+
+	/// Register `who` as a nominator of `targets`, bonding `weight`.
+	pub fn voter(
+		mut self,
+		who: T::AccountId,
+		weight: VoteWeight,
+		targets: BoundedVec<T::AccountId, MaxNominationsOf<T>>,
+	) -> Self {
+		self.voters.push((who, weight, targets));
+		self
+	}
+
+	/// Record `exposure` as the exposure of `stash` in `era`.
+	pub fn era_stakers(
+		mut self,
+		era: EraIndex,
+		stash: T::AccountId,
+		exposure: Exposure<T::AccountId, BalanceOf<T>>,
+	) -> Self {
+		self.era_stakers.push((era, stash, exposure));
+		self
+	}
+
+	/// Set the fraction of slashes paid out to reporters.
+	pub fn slash_reward_fraction(mut self, fraction: Perbill) -> Self {
+		self.slash_reward_fraction = Some(fraction);
+		self
+	}
+
+	/// Write everything registered so far to storage.
+	///
+	/// Targets are added before voters, so the voters can nominate them. All stakes are converted
+	/// before anything is written, so nothing is stored if any conversion fails.
+	pub fn build(self) -> Result<(), &'static str> {
+		let conversion = self.conversion;
+		let targets = self
+			.targets
+			.into_iter()
+			.map(|(who, weight)| {
+				let stake = match weight {
+					Some(weight) => Self::stake_of(weight, conversion)?,
+					None => Self::default_target_stake(),
+				};
+				Ok((who, stake))
+			})
+			.collect::<Result<Vec<_>, &'static str>>()?;
+		let voters = self
+			.voters
+			.into_iter()
+			.map(|(who, weight, targets)| Ok((who, Self::stake_of(weight, conversion)?, targets)))
+			.collect::<Result<Vec<_>, &'static str>>()?;
+
+		for (who, stake) in targets {
+			Self::bond(&who, stake);
+			Staking::<T>::do_add_validator(
+				&who,
+				ValidatorPrefs {
+					commission: Perbill::zero(),
+					blocked: false,
+					nominator_bonus: Perbill::zero(),
+					..Default::default()
+				},
+			);
+		}
+
+		for (who, stake, targets) in voters {
+			Self::bond(&who, stake);
+			Staking::<T>::do_add_nominator(
+				&who,
+				Nominations { targets, submitted_in: 0, suppressed: false },
+			);
+		}
+
+		for (era, stash, exposure) in self.era_stakers {
+			Staking::<T>::store_exposure(era, &stash, exposure);
+		}
+
+		if let Some(fraction) = self.slash_reward_fraction {
+			SlashRewardFraction::<T>::put(fraction);
+		}
+
+		Ok(())
+	}
+
+	/// Burn the entire total issuance without settling the resulting imbalance.
+	///
+	/// With zero issuance, an issuance-based `CurrencyToVote` is a 1-1 conversion, which is what
+	/// weight-driven setups such as `ScoreProvider::set_score_of` rely on. The imbalance is
+	/// forgotten rather than dropped, as dropping it would add the burnt amount back to the
+	/// issuance. This leaves the issuance out of sync with the sum of all balances, which is only
+	/// tolerable in a benchmark. Don't use this pattern anywhere else.
+	pub fn burn_total_issuance() {
+		let imbalance = T::Currency::burn(T::Currency::total_issuance());
+		sp_std::mem::forget(imbalance);
+		assert!(
+			T::Currency::total_issuance().is_zero(),
+			"total issuance must be zero once burnt, benchmark needs reconfiguring."
+		);
+	}
+
+	/// Bond `who` to itself with `stake`, without touching its free balance.
+	fn bond(who: &T::AccountId, stake: BalanceOf<T>) {
+		<Bonded<T>>::insert(who, who);
+		<Ledger<T>>::insert(
+			who,
+			StakingLedger {
+				stash: who.clone(),
+				active: stake,
+				total: stake,
+				unlocking: Default::default(),
+				legacy_claimed_rewards: Default::default(),
+			},
+		);
+		<StakeOf<T>>::insert(who, (stake, stake));
+	}
+}
+
 struct ListScenario<T: Config> {
 	/// Stash that is expected to be moved.
 	origin_stash1: T::AccountId,
@@ -168,8 +363,7 @@ impl<T: Config> ListScenario<T> {
 		ensure!(!origin_weight.is_zero(), "origin weight must be greater than 0");
 
 		// burn the entire issuance.
-		let i = T::Currency::burn(T::Currency::total_issuance());
-		sp_std::mem::forget(i);
+		Setup::<T>::burn_total_issuance();
 
 		// create accounts with the origin weight
 
@@ -445,6 +639,38 @@ benchmarks! {
 		assert!(T::VoterList::contains(&stash))
 	}
 
+	evict_lowest_nominator {
+		let s in 1 .. T::MaxEvictionScan::get().max(1);
+
+		// clean up any existing state.
+		clear_validators_and_nominators::<T>();
+		MinValidatorBond::<T>::put(T::Currency::minimum_balance());
+
+		let low_weight = MinNominatorBond::<T>::get().max(T::Currency::minimum_balance());
+		let (lowest, lowest_controller) =
+			create_stash_controller_with_balance::<T>(USER_SEED, low_weight, Default::default())?;
+		let validators = create_validators::<T>(1, 100)?;
+		Staking::<T>::nominate(RawOrigin::Signed(lowest_controller).into(), validators)?;
+
+		// the lowest nominator is behind `s - 1` validators at the tail of the list.
+		for i in 1 .. s {
+			let (_, controller) = create_stash_controller_with_balance::<T>(
+				USER_SEED + i,
+				T::Currency::minimum_balance(),
+				Default::default(),
+			)?;
+			Staking::<T>::validate(RawOrigin::Signed(controller).into(), Default::default())?;
+		}
+
+		let stash = create_funded_user::<T>("newcomer", USER_SEED, 100);
+		let bond = low_weight.saturating_mul(2u32.into());
+	}: {
+		Staking::<T>::evict_lowest_nominator(&stash, bond)?;
+	}
+	verify {
+		assert!(!Nominators::<T>::contains_key(&lowest));
+	}
+
 	chill {
 		// clean up any existing state.
 		clear_validators_and_nominators::<T>();
@@ -464,6 +690,27 @@ benchmarks! {
 		assert!(!T::VoterList::contains(&stash));
 	}
 
+	drop_nomination {
+		// clean up any existing state.
+		clear_validators_and_nominators::<T>();
+
+		let origin_weight = MinNominatorBond::<T>::get().max(T::Currency::minimum_balance());
+
+		// dropping the only nomination of the stash chills it, with a worst case removal from the
+		// list.
+		let scenario = ListScenario::<T>::new(origin_weight, true)?;
+		let controller = scenario.origin_controller1.clone();
+		let stash = scenario.origin_stash1;
+		let target: T::AccountId = account("random_validator", 0, SEED);
+		assert!(T::VoterList::contains(&stash));
+
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller), T::Lookup::unlookup(target.clone()))
+	verify {
+		assert!(!T::VoterList::contains(&stash));
+		assert!(NominationExits::<T>::contains_key(&stash, &target));
+	}
+
 	set_payee {
 		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
 		assert_eq!(Payee::<T>::get(&stash), RewardDestination::Staked);
@@ -538,18 +785,49 @@ benchmarks! {
 
 	cancel_deferred_slash {
 		let s in 1 .. MAX_SLASHES;
-		let mut unapplied_slashes = Vec::new();
 		let era = EraIndex::one();
 		let dummy = || T::AccountId::decode(&mut TrailingZeroInput::zeroes()).unwrap();
-		for _ in 0 .. MAX_SLASHES {
-			unapplied_slashes.push(UnappliedSlash::<T::AccountId, BalanceOf<T>>::default_from(dummy()));
+		for id in 0 .. MAX_SLASHES {
+			let slash = UnappliedSlash::<T::AccountId, BalanceOf<T>>::default_from(dummy());
+			UnappliedSlashes::<T>::insert(era, id, slash);
 		}
-		UnappliedSlashes::<T>::insert(era, &unapplied_slashes);
 
 		let slash_indices: Vec<u32> = (0 .. s).collect();
 	}: _(RawOrigin::Root, era, slash_indices)
 	verify {
-		assert_eq!(UnappliedSlashes::<T>::get(&era).len(), (MAX_SLASHES - s) as usize);
+		assert_eq!(UnappliedSlashes::<T>::iter_prefix(&era).count(), (MAX_SLASHES - s) as usize);
+	}
+
+	reduce_deferred_slash {
+		let n in 0 .. T::MaxNominatorRewardedPerValidator::get() as u32;
+		let (validator, _) = create_validator_with_nominators::<T>(
+			n,
+			T::MaxNominatorRewardedPerValidator::get() as u32,
+			false,
+			true,
+			RewardDestination::Staked,
+		)?;
+
+		let slash_era = CurrentEra::<T>::get().unwrap();
+		let exposure = Staking::<T>::eras_stakers(slash_era, &validator);
+		let (unapplied, _) = crate::slashing::compute_slash::<T>(crate::slashing::SlashParams {
+			stash: &validator,
+			slash: Perbill::from_percent(10),
+			exposure: &exposure,
+			slash_era,
+			window_start: slash_era,
+			now: slash_era,
+			reward_proportion: SlashRewardFraction::<T>::get(),
+			disable_strategy: DisableStrategy::Never,
+		});
+		let era = slash_era + Staking::<T>::slash_defer_duration() + 1;
+		UnappliedSlashes::<T>::insert(era, 0, unapplied.unwrap());
+	}: _(RawOrigin::Root, era, 0, Perbill::from_percent(5))
+	verify {
+		assert_eq!(
+			ValidatorSlashInEra::<T>::get(slash_era, &validator).map(|(fraction, _)| fraction),
+			Some(Perbill::from_percent(5)),
+		);
 	}
 
 	payout_stakers_dead_controller {
@@ -690,7 +968,7 @@ benchmarks! {
 			active: T::Currency::minimum_balance() - One::one(),
 			total: T::Currency::minimum_balance() - One::one(),
 			unlocking: Default::default(),
-			claimed_rewards: Default::default(),
+			legacy_claimed_rewards: Default::default(),
 		};
 		Ledger::<T>::insert(&controller, l);
 
@@ -940,6 +1218,93 @@ benchmarks! {
 		assert_eq!(MinCommission::<T>::get(), Perbill::from_percent(100));
 	}
 
+	set_commission_tiers {
+		let t in 0 .. T::MaxCommissionTiers::get();
+		let tiers = (0..t)
+			.map(|i| (Perbill::from_parts(2 * i), Perbill::from_parts(2 * i + 1)))
+			.collect::<Vec<_>>();
+		let tiers = BoundedVec::<_, T::MaxCommissionTiers>::truncate_from(tiers);
+	}: _(RawOrigin::Root, tiers.clone())
+	verify {
+		assert_eq!(CommissionTiers::<T>::get(), tiers);
+	}
+
+	set_slash_destination {
+		let burned = Perbill::from_percent(50);
+	}: _(RawOrigin::Root, burned)
+	verify {
+		assert_eq!(SlashDestination::<T>::get(), burned);
+	}
+
+	re_enable_validator {
+		T::SessionInterface::disable_validator(0);
+		OffendingValidators::<T>::put(vec![(0, true)]);
+	}: _(RawOrigin::Root, 0)
+	verify {
+		assert_eq!(OffendingValidators::<T>::get(), vec![(0, false)]);
+	}
+
+	replace_validator {
+		let r in 1 .. T::MaxRunnersUp::get().max(1);
+		if T::MaxRunnersUp::get() == 0 {
+			// no validator can be replaced without runners-up.
+			return Err(BenchmarkError::Weightless)
+		}
+		let stash = T::SessionInterface::validators()
+			.first()
+			.cloned()
+			.ok_or("no session validator to replace")?;
+		let era = CurrentEra::<T>::get().unwrap_or_default();
+		CurrentEra::<T>::put(era);
+		ActiveEra::<T>::put(ActiveEraInfo { index: era, start: None });
+
+		// only the last runner-up is still a validator, so that all of them are looked at, and it
+		// is backed by as many nominators as can be exposed.
+		let (substitute, controller) =
+			create_stash_controller::<T>(0, 100, RewardDestination::Staked)?;
+		Staking::<T>::validate(RawOrigin::Signed(controller).into(), ValidatorPrefs::default())?;
+		let n = T::MaxNominatorRewardedPerValidator::get();
+		let value = T::Currency::minimum_balance();
+		let exposure = Exposure {
+			total: value.saturating_mul((n + 1).into()),
+			own: value,
+			others: (0..n)
+				.map(|i| IndividualExposure { who: account("nominator", i, SEED), value })
+				.collect(),
+		};
+		let mut runners_up = (1..r)
+			.map(|i| (account("runner-up", i, SEED), Exposure::default()))
+			.collect::<Vec<_>>();
+		runners_up.push((substitute.clone(), exposure));
+		ErasRunnersUp::<T>::insert(era, BoundedVec::truncate_from(runners_up));
+	}: _(RawOrigin::Root, T::Lookup::unlookup(stash.clone()))
+	verify {
+		assert_eq!(ReplacedValidators::<T>::get().into_inner(), vec![(stash, substitute)]);
+	}
+
+	set_offence_penalty_tiers {
+		let t in 0 .. T::MaxOffencePenaltyTiers::get();
+		let tiers = (0..t).map(|i| (Perbill::from_parts(i), i)).collect::<Vec<_>>();
+		let tiers = BoundedVec::<_, T::MaxOffencePenaltyTiers>::truncate_from(tiers);
+	}: _(RawOrigin::Root, tiers.clone())
+	verify {
+		assert_eq!(OffencePenaltyTiers::<T>::get(), tiers);
+	}
+
+	set_payee_splits {
+		let (stash, controller) = create_stash_controller::<T>(USER_SEED, 100, Default::default())?;
+		let share = Perbill::from_rational(1, MAX_PAYEE_SPLITS);
+		let splits = (0..MAX_PAYEE_SPLITS)
+			.map(|i| (account::<T::AccountId>("split", i, SEED), share))
+			.collect::<Vec<_>>();
+		let splits = PayeeSplitsOf::<T>::truncate_from(splits);
+		whitelist_account!(controller);
+	}: _(RawOrigin::Signed(controller), splits.clone())
+	verify {
+		assert_eq!(Payee::<T>::get(&stash), RewardDestination::Split);
+		assert_eq!(PayeeSplits::<T>::get(&stash), splits);
+	}
+
 	impl_benchmark_test_suite!(
 		Staking,
 		crate::mock::ExtBuilder::default().has_stakers(true),
@@ -44,7 +44,7 @@
 //!
 //! // fit as many voters in the vec as the bounds permit.
 //! for v in sorted_voters {
-//!     let voter = (v, weight_of(&v), targets_of(&v));
+//!     let voter = Voter::new(v, weight_of(&v), targets_of(&v));
 //!     if size_tracker.try_register_voter(&voter, &voter_bounds).is_err() {
 //!         // voter bounds size exhausted
 //!         break;
@@ -73,7 +73,7 @@
 use codec::Encode;
 use frame_election_provider_support::{
 	bounds::{DataProviderBounds, SizeBound},
-	ElectionDataProvider, VoterOf,
+	ElectionDataProvider, Voter, VoterOf,
 };
 
 /// Keeps track of the SCALE encoded byte length of the snapshot's voters or targets.
@@ -123,12 +123,11 @@ where
 
 	/// Calculates the size of the voter to register based on [`Encode::size_hint`].
 	fn voter_size_hint(voter: &VoterOf<DataProvider>) -> usize {
-		let (voter_account, vote_weight, targets) = voter;
+		let Voter { who, weight, targets } = voter;
 
-		voter_account
-			.size_hint()
-			.saturating_add(vote_weight.size_hint())
-			.saturating_add(voter_account.size_hint().saturating_mul(targets.len()))
+		who.size_hint()
+			.saturating_add(weight.size_hint())
+			.saturating_add(who.size_hint().saturating_mul(targets.len()))
 	}
 
 	/// Tries to register a new target.
@@ -155,6 +154,25 @@ where
 		}
 	}
 
+	/// Calculates the final size in bytes of a snapshot made of `count` copies of each of the
+	/// given voters, without registering them.
+	pub fn voters_byte_size_of(voters: &[(VoterOf<DataProvider>, usize)]) -> usize {
+		let (num_voters, size) =
+			voters.iter().fold((0usize, 0usize), |(num, size), (voter, count)| {
+				(
+					num.saturating_add(*count),
+					size.saturating_add(Self::voter_size_hint(voter).saturating_mul(*count)),
+				)
+			});
+		Self::final_byte_size_of(num_voters, size)
+	}
+
+	/// Calculates the final size in bytes of a snapshot made of `count` copies of `target`,
+	/// without registering them.
+	pub fn targets_byte_size_of(target: &DataProvider::AccountId, count: usize) -> usize {
+		Self::final_byte_size_of(count, target.size_hint().saturating_mul(count))
+	}
+
 	/// Size of the SCALE encoded prefix with a given length.
 	#[inline]
 	fn length_prefix(len: usize) -> usize {
@@ -171,23 +189,18 @@ where
 #[cfg(test)]
 mod tests {
 	use super::*;
-	use crate::{
-		mock::{AccountId, Staking, Test},
-		BoundedVec, MaxNominationsOf,
-	};
+	use crate::mock::Staking;
 	use frame_election_provider_support::bounds::ElectionBoundsBuilder;
 	use sp_core::bounded_vec;
 
-	type Voters = BoundedVec<AccountId, MaxNominationsOf<Test>>;
-
 	#[test]
 	pub fn election_size_tracker_works() {
-		let mut voters: Vec<(u64, u64, Voters)> = vec![];
+		let mut voters: Vec<VoterOf<Staking>> = vec![];
 		let mut size_tracker = StaticTracker::<Staking>::default();
 		let voter_bounds = ElectionBoundsBuilder::default().voters_size(1_50.into()).build().voters;
 
 		// register 1 voter with 1 vote.
-		let voter = (1, 10, bounded_vec![2]);
+		let voter = Voter::new(1, 10, bounded_vec![2]);
 		assert!(size_tracker.try_register_voter(&voter, &voter_bounds).is_ok());
 		voters.push(voter);
 
@@ -197,7 +210,7 @@ mod tests {
 		);
 
 		// register another voter, now with 3 votes.
-		let voter = (2, 20, bounded_vec![3, 4, 5]);
+		let voter = Voter::new(2, 20, bounded_vec![3, 4, 5]);
 		assert!(size_tracker.try_register_voter(&voter, &voter_bounds).is_ok());
 		voters.push(voter);
 
@@ -207,7 +220,7 @@ mod tests {
 		);
 
 		// register noop vote (unlikely to happen).
-		let voter = (3, 30, bounded_vec![]);
+		let voter = Voter::new(3, 30, bounded_vec![]);
 		assert!(size_tracker.try_register_voter(&voter, &voter_bounds).is_ok());
 		voters.push(voter);
 
@@ -219,11 +232,11 @@ mod tests {
 
 	#[test]
 	pub fn election_size_tracker_bounds_works() {
-		let mut voters: Vec<(u64, u64, Voters)> = vec![];
+		let mut voters: Vec<VoterOf<Staking>> = vec![];
 		let mut size_tracker = StaticTracker::<Staking>::default();
 		let voter_bounds = ElectionBoundsBuilder::default().voters_size(1_00.into()).build().voters;
 
-		let voter = (1, 10, bounded_vec![2]);
+		let voter = Voter::new(1, 10, bounded_vec![2]);
 		assert!(size_tracker.try_register_voter(&voter, &voter_bounds).is_ok());
 		voters.push(voter);
 
@@ -236,7 +249,7 @@ mod tests {
 		let size_before_overflow = size_tracker.size;
 
 		// try many voters that will overflow the tracker's buffer.
-		let voter = (2, 10, bounded_vec![2, 3, 4, 5, 6, 7, 8, 9]);
+		let voter = Voter::new(2, 10, bounded_vec![2, 3, 4, 5, 6, 7, 8, 9]);
 		voters.push(voter.clone());
 
 		assert!(size_tracker.try_register_voter(&voter, &voter_bounds).is_err());
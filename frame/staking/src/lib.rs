@@ -286,6 +286,8 @@
 pub mod benchmarking;
 #[cfg(any(feature = "runtime-benchmarks", test))]
 pub mod testing_utils;
+#[cfg(feature = "testing-utils")]
+pub mod time_travel;
 
 #[cfg(test)]
 pub(crate) mod mock;
@@ -295,6 +297,7 @@ mod tests;
 pub mod election_size_tracker;
 pub mod inflation;
 pub mod migrations;
+pub mod participation;
 pub mod slashing;
 pub mod weights;
 
@@ -309,14 +312,14 @@ use frame_support::{
 use scale_info::TypeInfo;
 use sp_runtime::{
 	curve::PiecewiseLinear,
-	traits::{AtLeast32BitUnsigned, Convert, Saturating, StaticLookup, Zero},
+	traits::{AtLeast32BitUnsigned, CheckedSub, Convert, Saturating, StaticLookup, Zero},
 	Perbill, Perquintill, Rounding, RuntimeDebug,
 };
-pub use sp_staking::StakerStatus;
 use sp_staking::{
 	offence::{Offence, OffenceError, ReportOffence},
 	EraIndex, OnStakingUpdate, SessionIndex,
 };
+pub use sp_staking::{StakerRole, StakerStatus};
 use sp_std::{collections::btree_map::BTreeMap, prelude::*};
 pub use weights::WeightInfo;
 
@@ -324,17 +327,75 @@ pub use pallet::{pallet::*, UseNominatorsAndValidatorsMap, UseValidatorsMap};
 
 pub(crate) const LOG_TARGET: &str = "runtime::staking";
 
-// syntactic sugar for logging.
+/// The default [`Config::LogTarget`], `runtime::staking`.
+pub struct DefaultLogTarget;
+impl Get<&'static str> for DefaultLogTarget {
+	fn get() -> &'static str {
+		LOG_TARGET
+	}
+}
+
+/// The most verbose level that each subsystem of the pallet logs at, see [`Config::LogLevels`].
+///
+/// The default lets every message through, leaving the filtering to the node.
+#[derive(PartialEq, Eq, Clone, Copy, RuntimeDebug)]
+pub struct LogLevels {
+	/// Elections, and the snapshots taken for them.
+	pub election: log::LevelFilter,
+	/// Payouts of the era rewards.
+	pub payout: log::LevelFilter,
+	/// Slashes, from the offence report until they are applied.
+	pub slashing: log::LevelFilter,
+}
+
+impl Default for LogLevels {
+	fn default() -> Self {
+		Self {
+			election: log::LevelFilter::Trace,
+			payout: log::LevelFilter::Trace,
+			slashing: log::LevelFilter::Trace,
+		}
+	}
+}
+
+// syntactic sugar for logging. Prefix the level with a subsystem of [`LogLevels`], as in
+// `log!(election: info, ..)`, to only log up to the level configured for it.
 #[macro_export]
 macro_rules! log {
+	(@level error) => { log::Level::Error };
+	(@level warn) => { log::Level::Warn };
+	(@level info) => { log::Level::Info };
+	(@level debug) => { log::Level::Debug };
+	(@level trace) => { log::Level::Trace };
+	($subsystem:ident : $level:tt, $patter:expr $(, $values:expr)* $(,)?) => {
+		if $crate::log!(@level $level) <=
+			<<T as crate::Config>::LogLevels as frame_support::traits::Get<_>>::get().$subsystem
+		{
+			$crate::log!($level, $patter $(, $values)*)
+		}
+	};
 	($level:tt, $patter:expr $(, $values:expr)* $(,)?) => {
 		log::$level!(
-			target: crate::LOG_TARGET,
+			target: <<T as crate::Config>::LogTarget as frame_support::traits::Get<_>>::get(),
 			concat!("[{:?}] 💸 ", $patter), <frame_system::Pallet<T>>::block_number() $(, $values)*
 		)
 	};
 }
 
+// syntactic sugar for tracing, a no-op without the `tracing` feature. The span lasts until the end
+// of the current scope. Spans need a constant target, so this always uses `runtime::staking`.
+#[macro_export]
+macro_rules! trace_span {
+	($name:expr $(, $($fields:tt)+)?) => {
+		#[cfg(feature = "tracing")]
+		sp_tracing::enter_span!(sp_tracing::debug_span!(
+			target: crate::LOG_TARGET,
+			$name
+			$(, $($fields)+)?
+		));
+	};
+}
+
 /// Maximum number of winners (aka. active validators), as defined in the election provider of this
 /// pallet.
 pub type MaxWinnersOf<T> = <<T as Config>::ElectionProvider as frame_election_provider_support::ElectionProviderBase>::MaxWinners;
@@ -370,6 +431,38 @@ pub struct ActiveEraInfo {
 	start: Option<u64>,
 }
 
+/// What happened to an account in an era, as recorded in [`pallet::AccountEraActivity`].
+#[derive(
+	PartialEq, Eq, Clone, Copy, Default, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen,
+)]
+pub struct AccountActivity(u8);
+
+impl AccountActivity {
+	/// Rewarded for the era.
+	pub const REWARDED: Self = Self(1 << 0);
+	/// Slashed while the era was active.
+	pub const SLASHED: Self = Self(1 << 1);
+	/// Elected as a validator for the era.
+	pub const ELECTED: Self = Self(1 << 2);
+	/// Chilled while the era was active.
+	pub const CHILLED: Self = Self(1 << 3);
+
+	/// Whether all of `other` happened.
+	pub fn contains(self, other: Self) -> bool {
+		self.0 & other.0 == other.0
+	}
+
+	/// Record that `other` happened as well.
+	pub fn insert(&mut self, other: Self) {
+		self.0 |= other.0;
+	}
+
+	/// Whether nothing happened.
+	pub fn is_empty(self) -> bool {
+		self.0 == 0
+	}
+}
+
 /// Reward points of an era. Used to split era total payout between validators.
 ///
 /// This points will be used to reward validators and their respective nominators.
@@ -387,6 +480,51 @@ impl<AccountId: Ord> Default for EraRewardPoints<AccountId> {
 	}
 }
 
+/// The number of consecutive eras for which a nominator has been backing the same validator.
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct LoyaltyStreak {
+	/// The last era in which the nominator was exposed to the validator.
+	pub last_era: EraIndex,
+	/// The number of consecutive eras up to and including `last_era`.
+	pub eras: u32,
+}
+
+impl LoyaltyStreak {
+	/// The streak after the nominator has been exposed to the validator in `era`, saturating at
+	/// `max_eras`.
+	///
+	/// `max_eras` should exceed the longest streak that is rewarded by at least the number of
+	/// eras that can still be paid out, so that [`Self::at`] is exact for all of them.
+	fn extended(maybe_streak: Option<Self>, era: EraIndex, max_eras: u32) -> Self {
+		let eras = match maybe_streak {
+			Some(streak) if streak.last_era.saturating_add(1) == era =>
+				streak.eras.saturating_add(1),
+			Some(streak) if streak.last_era == era => streak.eras,
+			_ => 1,
+		};
+		LoyaltyStreak { last_era: era, eras: eras.min(max_eras) }
+	}
+
+	/// The streak as it stood in `era`, or zero if it had not started yet.
+	///
+	/// A streak that was broken and restarted after `era` no longer accounts for `era`.
+	fn at(&self, era: EraIndex) -> u32 {
+		if era > self.last_era {
+			return 0
+		}
+		self.eras.saturating_sub(self.last_era - era)
+	}
+}
+
+/// The maximum number of accounts that a reward can be split across, see
+/// [`RewardDestination::Split`].
+pub const MAX_PAYEE_SPLITS: u32 = 8;
+
+/// The accounts that a reward is split across, each with its share of it, see
+/// [`RewardDestination::Split`].
+pub type PayeeSplitsOf<T> =
+	BoundedVec<(<T as frame_system::Config>::AccountId, Perbill), ConstU32<MAX_PAYEE_SPLITS>>;
+
 /// A destination account for payment.
 #[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum RewardDestination<AccountId> {
@@ -400,6 +538,15 @@ pub enum RewardDestination<AccountId> {
 	Account(AccountId),
 	/// Receive no reward.
 	None,
+	/// Split the reward across the accounts in [`PayeeSplits`], each receiving its share of it,
+	/// see [`Pallet::set_payee_splits`].
+	///
+	/// The first account receives whatever is left once the others are paid, including any
+	/// rounding remainder, hence never less than its own share.
+	Split,
+	/// Pay the `staked` share of the reward into the stash account, increasing the amount at
+	/// stake accordingly, and the rest into `account`.
+	StakedSplit { staked: Perbill, account: AccountId },
 }
 
 impl<AccountId> Default for RewardDestination<AccountId> {
@@ -408,6 +555,25 @@ impl<AccountId> Default for RewardDestination<AccountId> {
 	}
 }
 
+/// A filter of the nominators to kick with [`Pallet::kick_by`].
+#[derive(PartialEq, Eq, Copy, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum KickFilter<Balance> {
+	/// The nominators whose active bond is below the given amount.
+	BondBelow(Balance),
+	/// The nominators whose nominations were submitted after the given era.
+	SubmittedAfter(EraIndex),
+}
+
+impl<Balance: PartialOrd> KickFilter<Balance> {
+	/// Whether a nominator with `active` bond and `submitted_in` nominations is to be kicked.
+	pub fn matches(&self, active: &Balance, submitted_in: EraIndex) -> bool {
+		match self {
+			KickFilter::BondBelow(bond) => active < bond,
+			KickFilter::SubmittedAfter(era) => submitted_in > *era,
+		}
+	}
+}
+
 /// Preference of what happens regarding validation.
 #[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, Default, MaxEncodedLen)]
 pub struct ValidatorPrefs {
@@ -419,6 +585,35 @@ pub struct ValidatorPrefs {
 	/// who is not already nominating this validator may nominate them. By default, validators
 	/// are accepting nominations.
 	pub blocked: bool,
+	/// Share of the reward for the validator's own stake that the validator forfeits to their
+	/// nominators, pro rata to their stake, in order to attract nominations. Only applies if the
+	/// validator has any nominators.
+	#[codec(compact)]
+	pub nominator_bonus: Perbill,
+	/// The most that the validator commits to raise its commission by within an era, if any.
+	pub max_commission_increase_per_era: Option<Perbill>,
+	/// The number of blocks that the validator commits to leave between two changes of its
+	/// commission or of these commitments.
+	#[codec(compact)]
+	pub min_blocks_between_changes: u32,
+}
+
+/// The last change of the commission of a validator, along with the commitments of
+/// [`ValidatorPrefs`] that stay in effect until the end of its era.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub struct CommissionChange<BlockNumber> {
+	/// The active era of the change.
+	pub era: EraIndex,
+	/// The commission before the first change in `era`.
+	pub base: Perbill,
+	/// The block of the change.
+	pub at: BlockNumber,
+	/// The commission set by the change.
+	pub commission: Perbill,
+	/// The strictest [`ValidatorPrefs::max_commission_increase_per_era`] in effect in `era`.
+	pub max_commission_increase_per_era: Option<Perbill>,
+	/// The strictest [`ValidatorPrefs::min_blocks_between_changes`] in effect in `era`.
+	pub min_blocks_between_changes: u32,
 }
 
 /// Just a Balance/BlockNumber tuple to encode when a chunk of funds will be unlocked.
@@ -459,9 +654,12 @@ pub struct StakingLedger<T: Config> {
 	/// (assuming it doesn't get slashed first). It is assumed that this will be treated as a first
 	/// in, first out queue where the new (higher value) eras get pushed on the back.
 	pub unlocking: BoundedVec<UnlockChunk<BalanceOf<T>>, T::MaxUnlockingChunks>,
-	/// List of eras for which the stakers behind a validator have claimed rewards. Only updated
-	/// for validators.
-	pub claimed_rewards: BoundedVec<EraIndex, T::HistoryDepth>,
+	/// List of eras for which the stakers behind a validator have claimed rewards, before these
+	/// were tracked in [`ClaimedRewards`].
+	///
+	/// Kept, rather than migrated, so that page 0 of these eras is not paid out twice. Never
+	/// added to, and pruned past [`Config::HistoryDepth`] upon payouts.
+	pub legacy_claimed_rewards: BoundedVec<EraIndex, T::HistoryDepth>,
 }
 
 impl<T: Config> StakingLedger<T> {
@@ -472,7 +670,7 @@ impl<T: Config> StakingLedger<T> {
 			total: Zero::zero(),
 			active: Zero::zero(),
 			unlocking: Default::default(),
-			claimed_rewards: Default::default(),
+			legacy_claimed_rewards: Default::default(),
 		}
 	}
 
@@ -502,7 +700,7 @@ impl<T: Config> StakingLedger<T> {
 			total,
 			active: self.active,
 			unlocking,
-			claimed_rewards: self.claimed_rewards,
+			legacy_claimed_rewards: self.legacy_claimed_rewards,
 		}
 	}
 
@@ -571,8 +769,9 @@ impl<T: Config> StakingLedger<T> {
 		let pre_slash_total = self.total;
 
 		// for a `slash_era = x`, any chunk that is scheduled to be unlocked at era `x + 28`
-		// (assuming 28 is the bonding duration) onwards should be slashed.
-		let slashable_chunks_start = slash_era + T::BondingDuration::get();
+		// (assuming 28 is the bonding duration, or the min unbonding duration if the unbonding
+		// goes through a queue) onwards should be slashed.
+		let slashable_chunks_start = slash_era + Pallet::<T>::slashing_window();
 
 		// `Some(ratio)` if this is proportional, with `ratio`, `None` otherwise. In both cases, we
 		// slash first the active chunk, and then `slash_chunks_priority`.
@@ -614,7 +813,7 @@ impl<T: Config> StakingLedger<T> {
 
 		// Helper to update `target` and the ledgers total after accounting for slashing `target`.
 		log!(
-			debug,
+			slashing: debug,
 			"slashing {:?} for era {:?} out of {:?}, priority: {:?}, proportional = {:?}",
 			slash_amount,
 			slash_era,
@@ -697,7 +896,19 @@ pub struct Nominations<T: Config> {
 }
 
 /// The amount of exposure (to slashing) than an individual nominator has.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	serde::Serialize,
+	serde::Deserialize,
+)]
 pub struct IndividualExposure<AccountId, Balance: HasCompact> {
 	/// The stash account of the nominator in question.
 	pub who: AccountId,
@@ -707,7 +918,19 @@ pub struct IndividualExposure<AccountId, Balance: HasCompact> {
 }
 
 /// A snapshot of the stake backing a single validator in the system.
-#[derive(PartialEq, Eq, PartialOrd, Ord, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[derive(
+	PartialEq,
+	Eq,
+	PartialOrd,
+	Ord,
+	Clone,
+	Encode,
+	Decode,
+	RuntimeDebug,
+	TypeInfo,
+	serde::Serialize,
+	serde::Deserialize,
+)]
 pub struct Exposure<AccountId, Balance: HasCompact> {
 	/// The total balance backing this validator.
 	#[codec(compact)]
@@ -725,6 +948,81 @@ impl<AccountId, Balance: Default + HasCompact> Default for Exposure<AccountId, B
 	}
 }
 
+/// The stake of a nominator in a [`CompactExposure`].
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+pub struct CompactIndividualExposure<AccountId, Balance: HasCompact> {
+	/// The stash account of the nominator.
+	pub who: AccountId,
+	/// The stake of the nominator, as the difference with the stake of the previous nominator
+	/// of the exposure, or the whole stake for the first one.
+	#[codec(compact)]
+	pub delta: Balance,
+}
+
+/// A page of an [`Exposure`] in the form that it is stored in, which is a lot smaller for the
+/// large ones, with at most `MaxNominators` nominators.
+///
+/// The nominators are sorted by decreasing stake, so that each stake can be stored as the
+/// difference with the previous one.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[scale_info(skip_type_params(MaxNominators))]
+pub struct CompactExposure<AccountId, Balance: HasCompact, MaxNominators: Get<u32>> {
+	/// The total balance backing this validator.
+	#[codec(compact)]
+	pub total: Balance,
+	/// The validator's own stash that is exposed.
+	#[codec(compact)]
+	pub own: Balance,
+	/// The portions of nominators stashes that are exposed.
+	pub others: BoundedVec<CompactIndividualExposure<AccountId, Balance>, MaxNominators>,
+}
+
+impl<AccountId, Balance: HasCompact + AtLeast32BitUnsigned + Copy, MaxNominators: Get<u32>>
+	CompactExposure<AccountId, Balance, MaxNominators>
+{
+	/// Compress `exposure`.
+	///
+	/// Returns `None` if it has more than `MaxNominators` nominators.
+	pub fn compress(mut exposure: Exposure<AccountId, Balance>) -> Option<Self> {
+		if exposure.others.len() > MaxNominators::get() as usize {
+			return None
+		}
+		exposure.others.sort_by(|a, b| b.value.cmp(&a.value));
+		let mut previous = None;
+		let others = exposure
+			.others
+			.into_iter()
+			.map(|nominator| {
+				let delta = previous.map_or(nominator.value, |p: Balance| p - nominator.value);
+				previous = Some(nominator.value);
+				CompactIndividualExposure { who: nominator.who, delta }
+			})
+			.collect::<Vec<_>>();
+		let others = BoundedVec::truncate_from(others);
+		Some(Self { total: exposure.total, own: exposure.own, others })
+	}
+
+	/// Decompress into an [`Exposure`].
+	///
+	/// Returns `None` if the stakes do not add up.
+	pub fn decompress(self) -> Option<Exposure<AccountId, Balance>> {
+		let mut previous = None;
+		let others = self
+			.others
+			.into_iter()
+			.map(|nominator| {
+				let value = match previous {
+					Some(p) => p.checked_sub(&nominator.delta)?,
+					None => nominator.delta,
+				};
+				previous = Some(value);
+				Some(IndividualExposure { who: nominator.who, value })
+			})
+			.collect::<Option<Vec<_>>>()?;
+		Some(Exposure { total: self.total, own: self.own, others })
+	}
+}
+
 /// A pending slash record. The value of the slash has been computed but not applied yet,
 /// rather deferred for several eras.
 #[derive(Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -791,10 +1089,28 @@ pub trait SessionInterface<AccountId> {
 	/// Disable the validator at the given index, returns `false` if the validator was already
 	/// disabled or the index is out of bounds.
 	fn disable_validator(validator_index: u32) -> bool;
+	/// Re-enable the validator at the given index, returns `false` if the validator was not
+	/// disabled.
+	///
+	/// By default, validators cannot be re-enabled, and `false` is returned.
+	fn enable_validator(validator_index: u32) -> bool {
+		let _ = validator_index;
+		false
+	}
 	/// Get the validators from session.
 	fn validators() -> Vec<AccountId>;
+	/// Get the validators of the currently active session. Same as [`Self::validators`].
+	fn active_validators() -> Vec<AccountId> {
+		Self::validators()
+	}
+	/// Get the validators that session has queued for the next session.
+	fn queued_validators() -> Vec<AccountId>;
 	/// Prune historical session tries up to but not including the given index.
 	fn prune_historical_up_to(up_to: SessionIndex);
+	/// Whether `validator` has set session keys.
+	fn has_keys(_validator: &AccountId) -> bool {
+		true
+	}
 }
 
 impl<T: Config> SessionInterface<<T as frame_system::Config>::AccountId> for T
@@ -815,27 +1131,130 @@ where
 		<pallet_session::Pallet<T>>::disable_index(validator_index)
 	}
 
+	fn enable_validator(validator_index: u32) -> bool {
+		<pallet_session::Pallet<T>>::enable_index(validator_index)
+	}
+
 	fn validators() -> Vec<<T as frame_system::Config>::AccountId> {
 		<pallet_session::Pallet<T>>::validators()
 	}
 
+	fn queued_validators() -> Vec<<T as frame_system::Config>::AccountId> {
+		<pallet_session::Pallet<T>>::queued_keys().into_iter().map(|(v, _)| v).collect()
+	}
+
 	fn prune_historical_up_to(up_to: SessionIndex) {
 		<pallet_session::historical::Pallet<T>>::prune_up_to(up_to);
 	}
+
+	fn has_keys(validator: &<T as frame_system::Config>::AccountId) -> bool {
+		<pallet_session::NextKeys<T>>::contains_key(validator)
+	}
 }
 
 impl<AccountId> SessionInterface<AccountId> for () {
 	fn disable_validator(_: u32) -> bool {
 		true
 	}
+	fn enable_validator(_: u32) -> bool {
+		true
+	}
 	fn validators() -> Vec<AccountId> {
 		Vec::new()
 	}
+	fn queued_validators() -> Vec<AccountId> {
+		Vec::new()
+	}
 	fn prune_historical_up_to(_: SessionIndex) {
 		()
 	}
 }
 
+/// A source of liveness for the validators, e.g. their session key heartbeats.
+///
+/// Used at the start of each era to keep the winners that are known to be dead out of it, see
+/// [`Config::ValidatorLiveness`].
+pub trait ValidatorLiveness<AccountId> {
+	/// Whether `who` is known to be dead, and should not take a seat in the next era.
+	fn is_dead(who: &AccountId) -> bool;
+}
+
+impl<AccountId> ValidatorLiveness<AccountId> for () {
+	fn is_dead(_: &AccountId) -> bool {
+		false
+	}
+}
+
+/// Decides whether an offending validator is disabled for the rest of the era.
+///
+/// Consulted by the slashing for each offence, see [`Config::DisablingStrategy`].
+pub trait DisablingStrategy {
+	/// Whether the offender is disabled.
+	///
+	/// `slash` is the fraction of the offence, `asked` whether the offence asks for disabling,
+	/// and `disabled` how many of the `validators` of the session are disabled already.
+	fn decision(slash: Perbill, asked: bool, disabled: u32, validators: u32) -> bool;
+
+	/// The indices of the offenders that are disabled again at the start of each session, as the
+	/// session forgets them.
+	///
+	/// `offending` are the offenders of the era, sorted by index, with whether they are disabled,
+	/// out of the `validators` of the session. By default, the disabled ones stay disabled.
+	fn disabled_at_new_session(offending: &[(u32, bool)], validators: u32) -> Vec<u32> {
+		let _ = validators;
+		offending
+			.iter()
+			.filter(|(_, disabled)| *disabled)
+			.map(|(index, _)| *index)
+			.collect()
+	}
+}
+
+/// Disable the offenders whenever the offence asks for it.
+pub struct DisableWhenAsked;
+impl DisablingStrategy for DisableWhenAsked {
+	fn decision(_: Perbill, asked: bool, _: u32, _: u32) -> bool {
+		asked
+	}
+}
+
+/// Disable the offenders when asked, as long as at most `Share` of the validators are disabled.
+pub struct DisableUpTo<Share>(sp_std::marker::PhantomData<Share>);
+impl<Share: Get<Perbill>> DisablingStrategy for DisableUpTo<Share> {
+	fn decision(_: Perbill, asked: bool, disabled: u32, validators: u32) -> bool {
+		asked && disabled < Share::get() * validators
+	}
+
+	fn disabled_at_new_session(offending: &[(u32, bool)], validators: u32) -> Vec<u32> {
+		offending
+			.iter()
+			.filter(|(_, disabled)| *disabled)
+			.map(|(index, _)| *index)
+			.take((Share::get() * validators) as usize)
+			.collect()
+	}
+}
+
+/// Disable the offenders when asked, only for the offences of at least `MinSlash`.
+pub struct DisableSeriousOffences<MinSlash>(sp_std::marker::PhantomData<MinSlash>);
+impl<MinSlash: Get<Perbill>> DisablingStrategy for DisableSeriousOffences<MinSlash> {
+	fn decision(slash: Perbill, asked: bool, _: u32, _: u32) -> bool {
+		asked && slash >= MinSlash::get()
+	}
+}
+
+/// Never disable the offenders.
+pub struct NeverDisable;
+impl DisablingStrategy for NeverDisable {
+	fn decision(_: Perbill, _: bool, _: u32, _: u32) -> bool {
+		false
+	}
+
+	fn disabled_at_new_session(_: &[(u32, bool)], _: u32) -> Vec<u32> {
+		Vec::new()
+	}
+}
+
 /// Handler for determining how much of a balance should be paid out on the current era.
 pub trait EraPayout<Balance> {
 	/// Determine the payout for this era.
@@ -847,6 +1266,88 @@ pub trait EraPayout<Balance> {
 		total_issuance: Balance,
 		era_duration_millis: u64,
 	) -> (Balance, Balance);
+
+	/// Determine the payout for this era, given the performance of its validators.
+	///
+	/// This is what the staking pallet calls. By default, the performance is ignored and this
+	/// falls back to [`Self::era_payout`].
+	fn era_payout_with_performance(
+		total_staked: Balance,
+		total_issuance: Balance,
+		era_duration_millis: u64,
+		_performance: EraPerformance,
+	) -> (Balance, Balance) {
+		Self::era_payout(total_staked, total_issuance, era_duration_millis)
+	}
+}
+
+/// Aggregate of the performance of the validators over an era, given to [`EraPayout`].
+#[derive(PartialEq, Eq, Clone, Copy, Default, RuntimeDebug)]
+pub struct EraPerformance {
+	/// The total reward points earned by the validators in the era.
+	pub total_reward_points: RewardPoint,
+	/// The number of blocks authored in the era.
+	pub authored_blocks: u32,
+}
+
+/// The maximum number of accounts the remainder of an era payout can be split across, in
+/// [`RemainderPolicy::Split`].
+pub const MAX_REMAINDER_SINKS: u32 = 8;
+
+/// How the remainder of an era payout (the "rest" of [`EraPayout`]) is routed.
+#[derive(PartialEq, Eq, Clone, RuntimeDebug)]
+pub enum RemainderPolicy<AccountId> {
+	/// Issue all of it to [`Config::RewardRemainder`].
+	Issue,
+	/// Do not issue any of it.
+	Burn,
+	/// Add all of it to the validator payout of the next era.
+	CarryOver,
+	/// Issue the `issue` portion to [`Config::RewardRemainder`], the portion of each of the
+	/// `sinks` to its account, add the `carry_over` portion to the validator payout of the next
+	/// era, and burn whatever is left.
+	///
+	/// The portions are taken in that order, each capped to what the previous ones left.
+	Split {
+		issue: Perbill,
+		sinks: BoundedVec<(AccountId, Perbill), ConstU32<MAX_REMAINDER_SINKS>>,
+		carry_over: Perbill,
+	},
+}
+
+impl<AccountId> RemainderPolicy<AccountId> {
+	/// Split `remainder` into the amounts that are issued, sunk into each account, carried over
+	/// and burnt, in that order.
+	pub fn route<Balance: AtLeast32BitUnsigned + Copy>(
+		self,
+		remainder: Balance,
+	) -> (Balance, BoundedVec<(AccountId, Balance), ConstU32<MAX_REMAINDER_SINKS>>, Balance, Balance)
+	{
+		let mut left = remainder;
+		let mut take = |portion: Perbill| {
+			let amount = (portion * remainder).min(left);
+			left = left.saturating_sub(amount);
+			amount
+		};
+		let (issued, sunk, carried_over) = match self {
+			RemainderPolicy::Issue => (take(Perbill::one()), Vec::new(), Zero::zero()),
+			RemainderPolicy::Burn => (Zero::zero(), Vec::new(), Zero::zero()),
+			RemainderPolicy::CarryOver => (Zero::zero(), Vec::new(), take(Perbill::one())),
+			RemainderPolicy::Split { issue, sinks, carry_over } => {
+				let issued = take(issue);
+				let sunk = sinks.into_iter().map(|(who, portion)| (who, take(portion))).collect();
+				(issued, sunk, take(carry_over))
+			},
+		};
+		// as many as there are sinks, which are bounded alike.
+		(issued, BoundedVec::truncate_from(sunk), carried_over, left)
+	}
+}
+
+impl<AccountId> Default for RemainderPolicy<AccountId> {
+	fn default() -> Self {
+		RemainderPolicy::Issue
+	}
 }
 
 impl<Balance: Default> EraPayout<Balance> for () {
@@ -915,6 +1416,25 @@ impl Default for Forcing {
 	}
 }
 
+/// How the length of an era is measured.
+#[derive(Copy, Clone, PartialEq, Eq, RuntimeDebug)]
+pub enum EraLength<BlockNumber> {
+	/// An era lasts [`Config::SessionsPerEra`] sessions.
+	SessionsPerEra,
+	/// An era lasts at least the given number of blocks, counted from the block in which it was
+	/// planned. It ends with the first session that ends after that.
+	///
+	/// Useful for chains whose session length is dictated by consensus, and can't be tuned for
+	/// the staking cadence.
+	BlocksPerEra(BlockNumber),
+}
+
+impl<BlockNumber> Default for EraLength<BlockNumber> {
+	fn default() -> Self {
+		EraLength::SessionsPerEra
+	}
+}
+
 /// A `Convert` implementation that finds the stash of the given controller account,
 /// if any.
 pub struct StashOf<T>(sp_std::marker::PhantomData<T>);
@@ -941,6 +1461,17 @@ impl<T: Config> Convert<T::AccountId, Option<Exposure<T::AccountId, BalanceOf<T>
 	}
 }
 
+/// The maximum number of nominators in a page of an exposure, see
+/// [`Pallet::exposure_page_size`].
+#[derive(Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct ExposurePageSizeOf<T>(sp_std::marker::PhantomData<T>);
+
+impl<T: Config> Get<u32> for ExposurePageSizeOf<T> {
+	fn get() -> u32 {
+		T::MaxNominatorRewardedPerValidator::get().max(1)
+	}
+}
+
 /// Filter historical offences out and only allow those from the bonding period.
 pub struct FilterHistoricalOffences<T, R> {
 	_inner: sp_std::marker::PhantomData<(T, R)>,
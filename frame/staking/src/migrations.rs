@@ -17,11 +17,12 @@
 //! Storage migrations for the Staking pallet.
 
 use super::*;
-use frame_election_provider_support::SortedListProvider;
+use frame_election_provider_support::{bounds::ElectionBounds, SortedListProvider};
 use frame_support::{
 	dispatch::GetStorageVersion, pallet_prelude::ValueQuery, storage_alias,
 	traits::OnRuntimeUpgrade,
 };
+use sp_runtime::FixedU128;
 
 #[cfg(feature = "try-runtime")]
 use frame_support::ensure;
@@ -57,6 +58,550 @@ impl Default for ObsoleteReleases {
 #[storage_alias]
 type StorageVersion<T: Config> = StorageValue<Pallet<T>, ObsoleteReleases, ValueQuery>;
 
+/// Not a migration: fails the `pre_upgrade` check if the snapshot `Bounds` of the upgraded
+/// runtime can not fit all the current validators, and `NominatorsMultiple` times the current
+/// nominators, in the next elections.
+///
+/// Meant to be kept in the runtime migrations, so that a change of the election bounds or of
+/// `MaxNominations` that would silently truncate the snapshots is caught by `try-runtime`. See
+/// [`Pallet::ensure_snapshot_bounds_fit`].
+pub struct CheckSnapshotBounds<T, Bounds, NominatorsMultiple>(
+	sp_std::marker::PhantomData<(T, Bounds, NominatorsMultiple)>,
+);
+impl<T, Bounds, NominatorsMultiple> OnRuntimeUpgrade
+	for CheckSnapshotBounds<T, Bounds, NominatorsMultiple>
+where
+	T: Config,
+	Bounds: Get<ElectionBounds>,
+	NominatorsMultiple: Get<FixedU128>,
+{
+	fn on_runtime_upgrade() -> Weight {
+		Weight::zero()
+	}
+
+	#[cfg(feature = "try-runtime")]
+	fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+		Pallet::<T>::ensure_snapshot_bounds_fit(Bounds::get(), NominatorsMultiple::get())?;
+		Ok(Vec::new())
+	}
+}
+
+pub mod v20 {
+	use super::*;
+
+	/// Injects all the validators into [`Config::TargetList`], scored by their own stake, as
+	/// only the validators that were added or updated since it was introduced are in it.
+	pub struct InjectValidatorsIntoTargetList<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for InjectValidatorsIntoTargetList<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 19,
+				"Required v19 before upgrading to v20"
+			);
+			Ok(Default::default())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 19 {
+				let prev_count = T::TargetList::count();
+				for (validator, _) in Validators::<T>::iter() {
+					if T::TargetList::contains(&validator) {
+						continue
+					}
+					let score = Pallet::<T>::slashable_balance_of(&validator);
+					let _ = T::TargetList::on_insert(validator.clone(), score).map_err(|err| {
+						log!(warn, "failed to insert {:?} into TargetList: {:?}", validator, err)
+					});
+				}
+				frame_support::traits::StorageVersion::new(20).put::<Pallet<T>>();
+
+				log!(
+					info,
+					"v20 applied successfully, prev count: {} next count: {}",
+					prev_count,
+					T::TargetList::count(),
+				);
+				T::BlockWeights::get().max_block
+			} else {
+				log!(warn, "Skipping v20, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 20,
+				"v20 not applied"
+			);
+			frame_support::ensure!(
+				T::TargetList::count() == Validators::<T>::count(),
+				"`TargetList` count after the migration must equal the number of validators"
+			);
+			Ok(())
+		}
+	}
+}
+
+pub mod v19 {
+	use super::*;
+
+	/// The full exposures, before they were only stored in [`ErasStakersPaged`].
+	#[storage_alias]
+	pub type ErasStakers<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		frame_support::Twox64Concat,
+		EraIndex,
+		frame_support::Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Exposure<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// The exposures clipped to their top nominators, which were page 0 of the exposures.
+	#[storage_alias]
+	pub type ErasStakersClipped<T: Config> = StorageDoubleMap<
+		Pallet<T>,
+		frame_support::Twox64Concat,
+		EraIndex,
+		frame_support::Twox64Concat,
+		<T as frame_system::Config>::AccountId,
+		Exposure<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		ValueQuery,
+	>;
+
+	/// Splits the stored [`ErasStakers`] in pages of [`ErasStakersPaged`], page 0 included, and
+	/// removes them along with the [`ErasStakersClipped`].
+	pub struct MigrateToV19<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV19<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 18,
+				"Required v18 before upgrading to v19"
+			);
+
+			Ok((ErasStakers::<T>::iter_keys().count() as u32).encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 18 {
+				let (mut exposures, mut pages) = (0u64, 0u64);
+				for (era, stash, exposure) in ErasStakers::<T>::drain().collect::<Vec<_>>() {
+					exposures += 1;
+					pages += Pallet::<T>::exposure_page_count(exposure.others.len() as u32) as u64;
+					Pallet::<T>::store_exposure(era, &stash, exposure);
+				}
+				let clipped = ErasStakersClipped::<T>::clear(u32::MAX, None).unique as u64;
+				frame_support::traits::StorageVersion::new(19).put::<Pallet<T>>();
+
+				log!(info, "v19 applied successfully, {} exposures in {} pages", exposures, pages);
+				T::DbWeight::get()
+					.reads_writes(exposures + clipped + 1, exposures * 2 + pages + clipped + 1)
+			} else {
+				log!(warn, "Skipping v19, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 19,
+				"v19 not applied"
+			);
+
+			let count = u32::decode(&mut &state[..]).expect("encoded in pre_upgrade; qed");
+			frame_support::ensure!(
+				ErasStakers::<T>::iter_keys().count() == 0 &&
+					ErasStakersClipped::<T>::iter_keys().count() == 0,
+				"exposures left behind"
+			);
+			frame_support::ensure!(
+				ErasStakersTotals::<T>::iter_keys().count() as u32 >= count,
+				"exposures lost during the migration"
+			);
+
+			Ok(())
+		}
+	}
+}
+
+pub mod v18 {
+	use super::*;
+
+	/// [`crate::UnappliedSlashes`] before the slashes of an era were stored one by one.
+	#[storage_alias]
+	pub type UnappliedSlashes<T: Config> = StorageMap<
+		Pallet<T>,
+		frame_support::Twox64Concat,
+		EraIndex,
+		Vec<UnappliedSlash<<T as frame_system::Config>::AccountId, BalanceOf<T>>>,
+		ValueQuery,
+	>;
+
+	/// Gives each deferred slash an id in [`crate::UnappliedSlashes`], its position in its era,
+	/// and builds the [`PendingSlashes`] of their stakers.
+	pub struct MigrateToV18<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV18<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 17,
+				"Required v17 before upgrading to v18"
+			);
+
+			let slashes = UnappliedSlashes::<T>::iter_values().map(|s| s.len() as u32).sum::<u32>();
+			Ok(slashes.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 17 {
+				let (mut eras, mut slashes, mut stakers) = (0u64, 0u64, 0u64);
+				let mut next_id = 0u32;
+				for (era, era_slashes) in UnappliedSlashes::<T>::drain().collect::<Vec<_>>() {
+					eras += 1;
+					for slash in era_slashes {
+						slashes += 1;
+						stakers += 1 + slash.others.len() as u64;
+						Pallet::<T>::note_pending_slash(era, &slash, true);
+						crate::UnappliedSlashes::<T>::insert(era, next_id, slash);
+						next_id = next_id.wrapping_add(1);
+					}
+				}
+				NextUnappliedSlashId::<T>::put(next_id);
+				frame_support::traits::StorageVersion::new(18).put::<Pallet<T>>();
+
+				log!(info, "v18 applied successfully, {} slashes of {} eras", slashes, eras);
+				T::DbWeight::get().reads_writes(eras + stakers + 1, eras + slashes + stakers + 2)
+			} else {
+				log!(warn, "Skipping v18, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 18,
+				"v18 not applied"
+			);
+
+			let slashes = u32::decode(&mut &state[..]).expect("encoded in pre_upgrade; qed");
+			frame_support::ensure!(
+				crate::UnappliedSlashes::<T>::iter_keys().count() as u32 == slashes,
+				"slashes lost during the migration"
+			);
+
+			Ok(())
+		}
+	}
+}
+
+pub mod v17 {
+	use super::*;
+
+	/// [`ValidatorPrefs`] before the commission commitments were added.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct OldValidatorPrefs {
+		#[codec(compact)]
+		pub commission: Perbill,
+		pub blocked: bool,
+		#[codec(compact)]
+		pub nominator_bonus: Perbill,
+	}
+
+	impl OldValidatorPrefs {
+		fn upgraded(self) -> ValidatorPrefs {
+			ValidatorPrefs {
+				commission: self.commission,
+				blocked: self.blocked,
+				nominator_bonus: self.nominator_bonus,
+				max_commission_increase_per_era: None,
+				min_blocks_between_changes: 0,
+			}
+		}
+	}
+
+	/// Adds no commission commitments to all stored [`ValidatorPrefs`].
+	pub struct MigrateToV17<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV17<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 16,
+				"Required v16 before upgrading to v17"
+			);
+
+			Ok(Validators::<T>::count().encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 16 {
+				let mut translated = 0u64;
+				Validators::<T>::translate::<OldValidatorPrefs, _>(|_, prefs| {
+					translated += 1;
+					Some(prefs.upgraded())
+				});
+				ErasValidatorPrefs::<T>::translate::<OldValidatorPrefs, _>(|_, _, prefs| {
+					translated += 1;
+					Some(prefs.upgraded())
+				});
+				frame_support::traits::StorageVersion::new(17).put::<Pallet<T>>();
+
+				log!(
+					info,
+					"v17 applied successfully, {} validator preferences migrated",
+					translated
+				);
+				T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+			} else {
+				log!(warn, "Skipping v17, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 17,
+				"v17 not applied"
+			);
+
+			let count = u32::decode(&mut &state[..]).expect("encoded in pre_upgrade; qed");
+			frame_support::ensure!(
+				Validators::<T>::count() == count,
+				"validators lost during the migration"
+			);
+
+			Ok(())
+		}
+	}
+}
+
+pub mod v16 {
+	use super::*;
+
+	/// Populates [`Status`] for every bonded stash, based on [`Validators`] and [`Nominators`],
+	/// and indexes the targets of all the [`Nominators`] in [`NominatorsOf`].
+	pub struct MigrateToV16<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV16<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 15,
+				"Required v15 before upgrading to v16"
+			);
+
+			let edges: u32 = Nominators::<T>::iter_values()
+				.map(|nominations| nominations.targets.len() as u32)
+				.sum();
+			Ok(edges.encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 15 {
+				let (mut stashes, mut edges) = (0u64, 0u64);
+				Bonded::<T>::iter_keys().for_each(|stash| {
+					let role = if Validators::<T>::contains_key(&stash) {
+						StakerRole::Validator
+					} else if let Some(nominations) = Nominators::<T>::get(&stash) {
+						for target in nominations.targets {
+							edges += 1;
+							NominatorsOf::<T>::insert(target, &stash, ());
+						}
+						StakerRole::Nominator
+					} else {
+						StakerRole::Idle
+					};
+					Status::<T>::insert(&stash, role);
+					stashes += 1;
+				});
+				frame_support::traits::StorageVersion::new(16).put::<Pallet<T>>();
+
+				log!(
+					info,
+					"v16 applied successfully, {} staker roles set, {} nominations indexed",
+					stashes,
+					edges,
+				);
+				T::DbWeight::get().reads_writes(3 * stashes + 1, stashes + edges + 1)
+			} else {
+				log!(warn, "Skipping v16, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 16,
+				"v16 not applied"
+			);
+
+			let edges = u32::decode(&mut &state[..]).expect("encoded in pre_upgrade; qed");
+			frame_support::ensure!(
+				NominatorsOf::<T>::iter_keys().count() as u32 == edges,
+				"nominations not indexed by the migration"
+			);
+
+			for stash in Bonded::<T>::iter_keys() {
+				frame_support::ensure!(
+					Status::<T>::contains_key(&stash),
+					"staker role not set during the migration"
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
+
+pub mod v15 {
+	use super::*;
+
+	/// Populates the [`StakeOf`] cache from all the stored [`StakingLedger`]s.
+	pub struct MigrateToV15<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV15<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 14,
+				"Required v14 before upgrading to v15"
+			);
+
+			Ok(Vec::new())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 14 {
+				let mut cached = 0u64;
+				Ledger::<T>::iter_values().for_each(|ledger| {
+					StakeOf::<T>::insert(&ledger.stash, (ledger.active, ledger.total));
+					cached += 1;
+				});
+				frame_support::traits::StorageVersion::new(15).put::<Pallet<T>>();
+
+				log!(info, "v15 applied successfully, {} stakes cached", cached);
+				T::DbWeight::get().reads_writes(cached + 1, cached + 1)
+			} else {
+				log!(warn, "Skipping v15, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(_state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 15,
+				"v15 not applied"
+			);
+
+			for ledger in Ledger::<T>::iter_values() {
+				frame_support::ensure!(
+					StakeOf::<T>::get(&ledger.stash) == Some((ledger.active, ledger.total)),
+					"stake not cached during the migration"
+				);
+			}
+
+			Ok(())
+		}
+	}
+}
+
+pub mod v14 {
+	use super::*;
+
+	/// [`ValidatorPrefs`] before `nominator_bonus` was added.
+	#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct OldValidatorPrefs {
+		#[codec(compact)]
+		pub commission: Perbill,
+		pub blocked: bool,
+	}
+
+	impl OldValidatorPrefs {
+		fn upgraded(self) -> ValidatorPrefs {
+			ValidatorPrefs {
+				commission: self.commission,
+				blocked: self.blocked,
+				nominator_bonus: Perbill::zero(),
+				..Default::default()
+			}
+		}
+	}
+
+	/// Adds a zero `nominator_bonus` to all stored [`ValidatorPrefs`].
+	pub struct MigrateToV14<T>(sp_std::marker::PhantomData<T>);
+	impl<T: Config> OnRuntimeUpgrade for MigrateToV14<T> {
+		#[cfg(feature = "try-runtime")]
+		fn pre_upgrade() -> Result<Vec<u8>, TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 13,
+				"Required v13 before upgrading to v14"
+			);
+
+			Ok(Validators::<T>::count().encode())
+		}
+
+		fn on_runtime_upgrade() -> Weight {
+			let onchain = Pallet::<T>::on_chain_storage_version();
+
+			if onchain == 13 {
+				let mut translated = 0u64;
+				Validators::<T>::translate::<OldValidatorPrefs, _>(|_, prefs| {
+					translated += 1;
+					Some(prefs.upgraded())
+				});
+				ErasValidatorPrefs::<T>::translate::<OldValidatorPrefs, _>(|_, _, prefs| {
+					translated += 1;
+					Some(prefs.upgraded())
+				});
+				frame_support::traits::StorageVersion::new(14).put::<Pallet<T>>();
+
+				log!(
+					info,
+					"v14 applied successfully, {} validator preferences migrated",
+					translated
+				);
+				T::DbWeight::get().reads_writes(translated + 1, translated + 1)
+			} else {
+				log!(warn, "Skipping v14, should be removed");
+				T::DbWeight::get().reads(1)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn post_upgrade(state: Vec<u8>) -> Result<(), TryRuntimeError> {
+			frame_support::ensure!(
+				Pallet::<T>::on_chain_storage_version() == 14,
+				"v14 not applied"
+			);
+
+			let count = u32::decode(&mut &state[..]).expect("encoded in pre_upgrade; qed");
+			frame_support::ensure!(
+				Validators::<T>::count() == count,
+				"validators lost during the migration"
+			);
+
+			Ok(())
+		}
+	}
+}
+
 pub mod v13 {
 	use super::*;
 
@@ -255,7 +800,7 @@ pub mod v11 {
 }
 
 pub mod v10 {
-	use super::*;
+	use super::{v20::UnappliedSlashes, *};
 	use frame_support::storage_alias;
 
 	#[storage_alias]
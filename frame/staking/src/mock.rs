@@ -25,10 +25,10 @@ use frame_election_provider_support::{
 use frame_support::{
 	assert_ok, ord_parameter_types, parameter_types,
 	traits::{
-		ConstU32, ConstU64, Currency, EitherOfDiverse, FindAuthor, Get, Hooks, Imbalance,
-		OnUnbalanced, OneSessionHandler,
+		ConstU32, ConstU64, Currency, EitherOfDiverse, EstimateNextNewSession, FindAuthor, Get,
+		Hooks, Imbalance, OnUnbalanced, OneSessionHandler,
 	},
-	weights::constants::RocksDbWeight,
+	weights::{constants::RocksDbWeight, Weight},
 };
 use frame_system::{EnsureRoot, EnsureSignedBy};
 use sp_core::H256;
@@ -39,7 +39,10 @@ use sp_runtime::{
 	traits::{IdentityLookup, Zero},
 	BuildStorage,
 };
-use sp_staking::offence::{DisableStrategy, OffenceDetails, OnOffenceHandler};
+use sp_staking::{
+	currency_to_vote::{CurrencyToVote, U128CurrencyToVote},
+	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
+};
 
 pub const INIT_TIMESTAMP: u64 = 30_000;
 pub const BLOCK_TIME: u64 = 1000;
@@ -115,6 +118,8 @@ impl FindAuthor<AccountId> for Author11 {
 
 parameter_types! {
 	pub static SessionsPerEra: SessionIndex = 3;
+	pub static EraLengthMode: EraLength<BlockNumber> = EraLength::SessionsPerEra;
+	pub static ExternalExposures: bool = false;
 	pub static ExistentialDeposit: Balance = 1;
 	pub static SlashDeferDuration: EraIndex = 0;
 	pub static Period: BlockNumber = 5;
@@ -158,8 +163,8 @@ impl pallet_balances::Config for Test {
 	type WeightInfo = ();
 	type FreezeIdentifier = ();
 	type MaxFreezes = ();
-	type RuntimeHoldReason = ();
-	type MaxHolds = ();
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxHolds = frame_support::traits::ConstU32<1>;
 }
 
 sp_runtime::impl_opaque_keys! {
@@ -235,8 +240,38 @@ parameter_types! {
 	pub static MaxUnlockingChunks: u32 = 32;
 	pub static RewardOnUnbalanceWasCalled: bool = false;
 	pub static MaxWinners: u32 = 100;
+	pub static MaxBackersPerWinner: u32 = u32::MAX;
 	pub static ElectionsBounds: ElectionBounds = ElectionBoundsBuilder::default().build();
 	pub static AbsoluteMaxNominations: u32 = 16;
+	pub static DeductPendingSlashes: bool = false;
+	pub static DeferOverweightElection: bool = false;
+	pub static MaxElectionDeferrals: u32 = 2;
+	pub static LoyaltyBonus: Perbill = Perbill::zero();
+	pub static MaxLoyaltyEras: u32 = 4;
+	pub static MaxExitsPerEra: Option<u32> = None;
+	pub static ValidatorWaitlistSessions: SessionIndex = 0;
+	pub static VoterSamplingSeed: Option<H256> = None;
+	pub static ValidatorShuffleSeed: Option<H256> = None;
+	pub static EraRemainderPolicy: RemainderPolicy<AccountId> = RemainderPolicy::Issue;
+	// disabled by default, as many tests report the same offence more than once.
+	pub static MaxRememberedOffences: u32 = 0;
+	pub static MaxSlashesPerBlock: u32 = u32::MAX;
+	pub static MinNominatorBondShare: Option<Perbill> = None;
+	pub static EvictLowestNominators: bool = false;
+	pub static MaxEvictionScan: u32 = 8;
+	pub static AbsoluteMaxSnapshot: Option<u32> = None;
+	pub static MaxAutoPayoutsPerBlock: u32 = 0;
+	pub static MaxVetoedWinners: u32 = 0;
+	pub static MinNominatorSlash: Balance = 0;
+	pub static MaxRunnersUp: u32 = 0;
+	pub static ReplaceDisabledValidators: bool = false;
+	pub static MinUnbondingDuration: Option<EraIndex> = None;
+	pub static UnbondingQueueSaturation: Perbill = Perbill::from_percent(10);
+	pub static DeadValidators: Vec<AccountId> = vec![];
+	pub static MinValidatorBondShare: Option<Perbill> = None;
+	pub static IssuanceBasedCurrencyToVote: bool = false;
+	pub static NextSessionUnknown: bool = false;
+	pub static NextSessionFallback: Option<BlockNumber> = None;
 }
 
 type VoterBagsListInstance = pallet_bags_list::Instance1;
@@ -256,7 +291,32 @@ impl onchain::Config for OnChainSeqPhragmen {
 	type DataProvider = Staking;
 	type WeightInfo = ();
 	type MaxWinners = MaxWinners;
+	type MaxBackersPerWinner = MaxBackersPerWinner;
 	type Bounds = ElectionsBounds;
+	type SolverBudget = ();
+}
+
+pub struct MockLiveness;
+impl ValidatorLiveness<AccountId> for MockLiveness {
+	fn is_dead(who: &AccountId) -> bool {
+		DeadValidators::get().contains(who)
+	}
+}
+
+/// Estimates the next session as the session pallet does, unless `NextSessionUnknown` is set.
+pub struct MockNextNewSession;
+impl EstimateNextNewSession<BlockNumber> for MockNextNewSession {
+	fn average_session_length() -> BlockNumber {
+		Session::average_session_length()
+	}
+
+	fn estimate_next_new_session(now: BlockNumber) -> (Option<BlockNumber>, Weight) {
+		if NextSessionUnknown::get() {
+			(None, Weight::zero())
+		} else {
+			Session::estimate_next_new_session(now)
+		}
+	}
 }
 
 pub struct MockReward {}
@@ -283,11 +343,32 @@ impl OnStakingUpdate<AccountId, Balance> for EventListenerMock {
 	}
 }
 
+/// Saturates by default, and scales with the total issuance if `IssuanceBasedCurrencyToVote` is
+/// set.
+pub struct MockCurrencyToVote;
+impl CurrencyToVote<Balance> for MockCurrencyToVote {
+	fn to_vote(value: Balance, issuance: Balance) -> u64 {
+		if IssuanceBasedCurrencyToVote::get() {
+			U128CurrencyToVote::to_vote(value, issuance)
+		} else {
+			<()>::to_vote(value, issuance)
+		}
+	}
+
+	fn to_currency(value: u128, issuance: Balance) -> Balance {
+		if IssuanceBasedCurrencyToVote::get() {
+			U128CurrencyToVote::to_currency(value, issuance)
+		} else {
+			<()>::to_currency(value, issuance)
+		}
+	}
+}
+
 impl crate::pallet::pallet::Config for Test {
 	type Currency = Balances;
 	type CurrencyBalance = <Self as pallet_balances::Config>::Balance;
 	type UnixTime = Timestamp;
-	type CurrencyToVote = ();
+	type CurrencyToVote = MockCurrencyToVote;
 	type RewardRemainder = RewardRemainderMock;
 	type RuntimeEvent = RuntimeEvent;
 	type Slash = ();
@@ -298,7 +379,7 @@ impl crate::pallet::pallet::Config for Test {
 	type BondingDuration = BondingDuration;
 	type SessionInterface = Self;
 	type EraPayout = ConvertCurve<RewardCurve>;
-	type NextNewSession = Session;
+	type NextNewSession = MockNextNewSession;
 	type MaxNominatorRewardedPerValidator = ConstU32<64>;
 	type OffendingValidatorsThreshold = OffendingValidatorsThreshold;
 	type ElectionProvider = onchain::OnChainExecution<OnChainSeqPhragmen>;
@@ -310,6 +391,44 @@ impl crate::pallet::pallet::Config for Test {
 	type MaxUnlockingChunks = MaxUnlockingChunks;
 	type HistoryDepth = HistoryDepth;
 	type EventListeners = EventListenerMock;
+	type DeductPendingSlashes = DeductPendingSlashes;
+	type DeferOverweightElection = DeferOverweightElection;
+	type LoyaltyBonus = LoyaltyBonus;
+	type MaxLoyaltyEras = MaxLoyaltyEras;
+	type MaxExitsPerEra = MaxExitsPerEra;
+	type ValidatorWaitlistSessions = ValidatorWaitlistSessions;
+	type VoterSamplingSeed = VoterSamplingSeed;
+	type ValidatorShuffleSeed = ValidatorShuffleSeed;
+	type WeightAccountant = System;
+	type RemainderPolicy = EraRemainderPolicy;
+	type MaxRememberedOffences = MaxRememberedOffences;
+	type MaxSlashesPerBlock = MaxSlashesPerBlock;
+	type MinNominatorBondShare = MinNominatorBondShare;
+	type MinValidatorBondShare = MinValidatorBondShare;
+	type EvictLowestNominators = EvictLowestNominators;
+	type LogTarget = DefaultLogTarget;
+	type LogLevels = ();
+	type AbsoluteMaxSnapshot = AbsoluteMaxSnapshot;
+	type MaxAutoPayoutsPerBlock = MaxAutoPayoutsPerBlock;
+	type EraLength = EraLengthMode;
+	type ExternalExposures = ExternalExposures;
+	type ExposureOracle = EnsureRoot<AccountId>;
+	type ValidatorLiveness = MockLiveness;
+	type MaxVetoedWinners = MaxVetoedWinners;
+	type MinNominatorSlash = MinNominatorSlash;
+	type MaxRunnersUp = MaxRunnersUp;
+	type DisablingStrategy = DisableWhenAsked;
+	type ReplaceDisabledValidators = ReplaceDisabledValidators;
+	type MinUnbondingDuration = MinUnbondingDuration;
+	type UnbondingQueueSaturation = UnbondingQueueSaturation;
+	type HoldCurrency = Balances;
+	type RuntimeHoldReason = RuntimeHoldReason;
+	type MaxElectionDeferrals = MaxElectionDeferrals;
+	type MaxEvictionScan = MaxEvictionScan;
+	type MaxCommissionTiers = ConstU32<16>;
+	type MaxOffencePenaltyTiers = ConstU32<16>;
+	type MaxPayoutsPerBatch = ConstU32<64>;
+	type NextSessionFallback = NextSessionFallback;
 	type BenchmarkingConfig = TestBenchmarkingConfig;
 	type WeightInfo = ();
 }
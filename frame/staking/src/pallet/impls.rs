@@ -18,15 +18,20 @@
 //! Implementations for the Staking FRAME Pallet.
 
 use frame_election_provider_support::{
-	bounds::{CountBound, SizeBound},
-	data_provider, BoundedSupportsOf, DataProviderBounds, ElectionDataProvider, ElectionProvider,
-	ScoreProvider, SortedListProvider, VoteWeight, VoterOf,
+	bounds::{CountBound, ElectionBounds, SizeBound},
+	data_provider, score_of, validate_supports, BoundedSupport, BoundedSupportsOf,
+	DataProviderBounds, ElectionDataProvider, ElectionProvider, ElectionProviderBase,
+	ElectionSchedule, ElectionScore, ExtendedBalance, InstantElectionProvider, PageIndex,
+	RunnersUpOf, ScoreProvider, SortedListProvider, VoteWeight, Voter, VoterOf,
 };
 use frame_support::{
 	defensive,
 	dispatch::WithPostDispatchInfo,
 	pallet_prelude::*,
+	storage::with_storage_layer,
 	traits::{
+		fungible::hold::Mutate as FunHoldMutate,
+		tokens::{Fortitude, Precision, Restriction},
 		Currency, Defensive, DefensiveResult, EstimateNextNewSession, Get, Imbalance,
 		LockableCurrency, OnUnbalanced, TryCollect, UnixTime, WithdrawReasons,
 	},
@@ -35,25 +40,39 @@ use frame_support::{
 use frame_system::{pallet_prelude::BlockNumberFor, RawOrigin};
 use pallet_session::historical;
 use sp_runtime::{
-	traits::{Bounded, Convert, One, SaturatedConversion, Saturating, StaticLookup, Zero},
-	Perbill,
+	traits::{
+		Bounded, Convert, Hash, One, SaturatedConversion, Saturating, StaticLookup,
+		TrailingZeroInput, Zero,
+	},
+	FixedPointNumber, FixedU128, Perbill,
 };
 use sp_staking::{
 	currency_to_vote::CurrencyToVote,
-	offence::{DisableStrategy, OffenceDetails, OnOffenceHandler},
-	EraIndex, SessionIndex, Stake, StakingInterface,
+	offence::{DisableStrategy, Kind, OffenceDetails, OnOffenceHandler},
+	ElectionHealthReport, EraIndex, EraInflation, SessionIndex, SnapshotUtilization, Stake,
+	StakingInterface,
+};
+use sp_std::{
+	borrow::Borrow,
+	cell::Cell,
+	collections::{btree_map::BTreeMap, btree_set::BTreeSet},
+	prelude::*,
 };
-use sp_std::prelude::*;
 
 use crate::{
-	election_size_tracker::StaticTracker, log, slashing, weights::WeightInfo, ActiveEraInfo,
-	BalanceOf, EraPayout, Exposure, ExposureOf, Forcing, IndividualExposure, MaxNominationsOf,
-	MaxWinnersOf, Nominations, NominationsQuota, PositiveImbalanceOf, RewardDestination,
-	SessionInterface, StakingLedger, ValidatorPrefs,
+	election_size_tracker::StaticTracker, log, slashing, trace_span, weights::WeightInfo,
+	AccountActivity, ActiveEraInfo, BalanceOf, CommissionChange, CompactExposure, EraLength,
+	EraPayout, EraPerformance, Exposure, ExposureOf, ExposurePageSizeOf, Forcing,
+	IndividualExposure, LoyaltyStreak, MaxNominationsOf, MaxWinnersOf, Nominations,
+	NominationsQuota, PositiveImbalanceOf, RemainderPolicy, RewardDestination, RewardPoint,
+	SessionInterface, StakerRole, StakingLedger, UnappliedSlash, UnlockChunk, ValidatorLiveness,
+	ValidatorPrefs, MAX_PAYEE_SPLITS,
 };
 
 use super::{pallet::*, STAKING_ID};
 
+#[cfg(feature = "runtime-benchmarks")]
+use crate::benchmarking::{Setup, StakeConversion};
 #[cfg(feature = "try-runtime")]
 use frame_support::ensure;
 #[cfg(any(test, feature = "try-runtime"))]
@@ -67,11 +86,217 @@ use sp_runtime::TryRuntimeError;
 /// times and then give up.
 const NPOS_MAX_ITERATIONS_COEFFICIENT: u32 = 2;
 
+/// A page of an election snapshot, collected by iterating over a sorted list.
+struct SnapshotPage<T: Config, Item> {
+	/// The items of the page.
+	items: Vec<Item>,
+	/// The last account of the sorted list that was looked at, or `None` if the end of the list
+	/// was reached.
+	last_seen: Option<T::AccountId>,
+}
+
+/// The voters that are eligible for the npos election among a sorted list of accounts, within
+/// some bounds, produced one at a time.
+///
+/// Shared by [`Pallet::get_npos_voters`], [`Pallet::npos_voters_iter`] and the paged voter
+/// snapshot. The weight of each voter is registered as it is produced.
+struct NposVoters<T: Config> {
+	bounds: DataProviderBounds,
+	sorted_voters: Box<dyn Iterator<Item = T::AccountId>>,
+	weight_of: Box<dyn Fn(&T::AccountId) -> VoteWeight>,
+	voters_size_tracker: StaticTracker<Pallet<T>>,
+	final_predicted_len: u32,
+	voters_seen: u32,
+	validators_taken: u32,
+	nominators_taken: u32,
+	min_active_stake: VoteWeight,
+	last_seen: Option<T::AccountId>,
+	/// Whether the end of `sorted_voters` was reached.
+	exhausted: bool,
+	/// Whether no more voters will be produced.
+	done: bool,
+	/// Whether to set `MinimumActiveStake` once done.
+	set_min_active_stake: bool,
+}
+
+impl<T: Config> NposVoters<T> {
+	fn new(
+		bounds: DataProviderBounds,
+		sorted_voters: Box<dyn Iterator<Item = T::AccountId>>,
+	) -> Self {
+		let final_predicted_len = {
+			let all_voter_count = T::VoterList::count();
+			bounds.count.unwrap_or(all_voter_count.into()).min(all_voter_count.into()).0
+		};
+		Pallet::<T>::register_weight(T::WeightInfo::get_npos_voters(0, 0));
+
+		Self {
+			bounds,
+			sorted_voters,
+			weight_of: Pallet::<T>::weight_of_fn(),
+			voters_size_tracker: StaticTracker::default(),
+			final_predicted_len,
+			voters_seen: 0,
+			validators_taken: 0,
+			nominators_taken: 0,
+			min_active_stake: u64::MAX,
+			last_seen: None,
+			exhausted: false,
+			done: false,
+			set_min_active_stake: false,
+		}
+	}
+
+	/// Set `MinimumActiveStake` once all the voters are produced.
+	fn setting_min_active_stake(mut self) -> Self {
+		self.set_min_active_stake = true;
+		self
+	}
+
+	/// The minimum active stake among the nominators produced so far.
+	fn min_active_stake(&self) -> BalanceOf<T> {
+		if self.validators_taken + self.nominators_taken == 0 {
+			Zero::zero()
+		} else {
+			self.min_active_stake.into()
+		}
+	}
+
+	/// Take `voter`, as one more validator or nominator, if it fits in the bounds, and register
+	/// its weight.
+	fn try_take(&mut self, voter: &VoterOf<Pallet<T>>, is_validator: bool) -> bool {
+		let (validators_taken, nominators_taken) = (self.validators_taken, self.nominators_taken);
+		let weight_before = T::WeightInfo::get_npos_voters(validators_taken, nominators_taken);
+		let weight_after = if is_validator {
+			T::WeightInfo::get_npos_voters(validators_taken + 1, nominators_taken)
+		} else {
+			T::WeightInfo::get_npos_voters(validators_taken, nominators_taken + 1)
+		};
+		if self.bounds.weight_exhausted(weight_after) {
+			// no more weight left for the election snapshot, stop iterating.
+			Pallet::<T>::deposit_event(Event::<T>::SnapshotVotersWeightExceeded {
+				weight: weight_before,
+			});
+			return false
+		}
+		if self.voters_size_tracker.try_register_voter(voter, &self.bounds).is_err() {
+			// no more space left for the election snapshot, stop iterating.
+			Pallet::<T>::deposit_event(Event::<T>::SnapshotVotersSizeExceeded {
+				size: self.voters_size_tracker.size as u32,
+			});
+			return false
+		}
+
+		Pallet::<T>::register_weight(weight_after.saturating_sub(weight_before));
+		if is_validator {
+			self.validators_taken.saturating_inc();
+		} else {
+			self.nominators_taken.saturating_inc();
+		}
+		true
+	}
+
+	fn next_voter(&mut self) -> Option<VoterOf<Pallet<T>>> {
+		while self.validators_taken + self.nominators_taken < self.final_predicted_len &&
+			self.voters_seen < NPOS_MAX_ITERATIONS_COEFFICIENT * self.final_predicted_len
+		{
+			let voter = match self.sorted_voters.next() {
+				Some(voter) => {
+					self.voters_seen.saturating_inc();
+					voter
+				},
+				None => {
+					self.exhausted = true;
+					return None
+				},
+			};
+
+			let voter_weight = (self.weight_of)(&voter);
+			// if voter weight is zero, do not consider this voter for the snapshot.
+			if voter_weight.is_zero() {
+				log!(election: debug, "voter's active balance is 0. skip this voter.");
+				self.last_seen = Some(voter);
+				continue
+			}
+
+			if let Some(Nominations { targets, .. }) = <Nominators<T>>::get(&voter) {
+				if targets.is_empty() {
+					// technically should never happen, but not much we can do about it.
+					self.last_seen = Some(voter);
+					self.min_active_stake = self.min_active_stake.min(voter_weight);
+					continue
+				}
+
+				// Note on lazy nomination quota: we do not check the nomination quota of the
+				// voter at this point and accept all the current nominations. The nomination
+				// quota is only enforced at `nominate` time.
+				let voter = Voter::new(voter, voter_weight, targets);
+				if !self.try_take(&voter, false) {
+					return None
+				}
+				self.last_seen = Some(voter.who.clone());
+				self.min_active_stake = self.min_active_stake.min(voter_weight);
+				return Some(voter)
+			} else if Validators::<T>::contains_key(&voter) {
+				// if this voter is a validator:
+				let self_vote = Voter::new(
+					voter.clone(),
+					voter_weight,
+					vec![voter.clone()]
+						.try_into()
+						.expect("`MaxVotesPerVoter` must be greater than or equal to 1"),
+				);
+				if !self.try_take(&self_vote, true) {
+					return None
+				}
+				self.last_seen = Some(voter);
+				return Some(self_vote)
+			} else {
+				// this can only happen if: 1. there a bug in the bags-list (or whatever is the
+				// sorted list) logic and the state of the two pallets is no longer compatible, or
+				// because the nominators is not decodable since they have more nomination than
+				// `T::NominationsQuota::get_quota`. The latter can rarely happen, and is not
+				// really an emergency or bug if it does.
+				defensive!(
+					"DEFENSIVE: invalid item in `VoterList`: {:?}, this nominator probably has too many nominations now",
+					voter,
+				);
+				self.last_seen = Some(voter);
+			}
+		}
+		None
+	}
+}
+
+impl<T: Config> Iterator for NposVoters<T> {
+	type Item = VoterOf<Pallet<T>>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		if self.done {
+			return None
+		}
+		let maybe_voter = self.next_voter();
+		if maybe_voter.is_none() {
+			self.done = true;
+			log!(
+				election: info,
+				"generated {} npos voters, {} from validators and {} nominators",
+				self.validators_taken + self.nominators_taken,
+				self.validators_taken,
+				self.nominators_taken
+			);
+			if self.set_min_active_stake {
+				MinimumActiveStake::<T>::put(self.min_active_stake());
+			}
+		}
+		maybe_voter
+	}
+}
+
 impl<T: Config> Pallet<T> {
 	/// The total balance that can be slashed from a stash account as of right now.
 	pub fn slashable_balance_of(stash: &T::AccountId) -> BalanceOf<T> {
-		// Weight note: consider making the stake accessible through stash.
-		Self::bonded(stash).and_then(Self::ledger).map(|l| l.active).unwrap_or_default()
+		Self::stake_of(stash).map(|(active, _)| active).unwrap_or_default()
 	}
 
 	/// Internal impl of [`Self::slashable_balance_of`] that returns [`VoteWeight`].
@@ -82,31 +307,116 @@ impl<T: Config> Pallet<T> {
 		T::CurrencyToVote::to_vote(Self::slashable_balance_of(stash), issuance)
 	}
 
+	/// The sum of all pending slashes in [`PendingSlashes`], per slashed staker.
+	///
+	/// Returns an empty map if [`Config::DeductPendingSlashes`] is disabled. Otherwise, the weight
+	/// of reading them all is registered.
+	pub(crate) fn pending_slashes() -> BTreeMap<T::AccountId, BalanceOf<T>> {
+		let mut pending = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+		if !T::DeductPendingSlashes::get() {
+			return pending
+		}
+
+		let mut reads = 0u64;
+		for (who, _, value) in PendingSlashes::<T>::iter() {
+			reads.saturating_inc();
+			let total = pending.entry(who).or_default();
+			*total = total.saturating_add(value);
+		}
+		Self::register_weight(T::DbWeight::get().reads(reads));
+		pending
+	}
+
+	/// The sum of all pending slashes of `who` in [`PendingSlashes`].
+	///
+	/// Always zero if [`Config::DeductPendingSlashes`] is disabled.
+	pub(crate) fn pending_slash_of(who: &T::AccountId) -> BalanceOf<T> {
+		if !T::DeductPendingSlashes::get() {
+			return Zero::zero()
+		}
+
+		PendingSlashes::<T>::iter_prefix_values(who)
+			.fold(Zero::zero(), |acc: BalanceOf<T>, value| acc.saturating_add(value))
+	}
+
+	/// Add `slash`, deferred to `era`, to the [`PendingSlashes`] of its stakers if `pending`, or
+	/// take it out of them otherwise.
+	pub(crate) fn note_pending_slash(
+		era: EraIndex,
+		slash: &UnappliedSlash<T::AccountId, BalanceOf<T>>,
+		pending: bool,
+	) {
+		let stakers = sp_std::iter::once((&slash.validator, slash.own))
+			.chain(slash.others.iter().map(|(nominator, value)| (nominator, *value)));
+		for (who, value) in stakers.filter(|(_, value)| !value.is_zero()) {
+			PendingSlashes::<T>::mutate_exists(who, era, |total| {
+				let old = total.unwrap_or_default();
+				let new =
+					if pending { old.saturating_add(value) } else { old.saturating_sub(value) };
+				*total = Some(new).filter(|new| !new.is_zero());
+			});
+		}
+	}
+
 	/// Returns a closure around `slashable_balance_of_vote_weight` that can be passed around.
 	///
 	/// This prevents call sites from repeatedly requesting `total_issuance` from backend. But it is
 	/// important to be only used while the total issuance is not changing.
+	///
+	/// If [`Config::DeductPendingSlashes`] is enabled, the pending slashes are read once and
+	/// deducted from the slashable balance of each staker.
 	pub fn weight_of_fn() -> Box<dyn Fn(&T::AccountId) -> VoteWeight> {
+		Self::weight_of_fn_with_refresh(0)
+	}
+
+	/// Same as [`Self::weight_of_fn`], but re-reads the total issuance once every `every_n_calls`
+	/// calls of the returned closure.
+	///
+	/// Meant for closures that live across blocks, such as in multi-block snapshot processes, so
+	/// that vote weights are not computed against an issuance that has since changed materially.
+	/// An `every_n_calls` of zero never refreshes the issuance.
+	pub fn weight_of_fn_with_refresh(
+		every_n_calls: u32,
+	) -> Box<dyn Fn(&T::AccountId) -> VoteWeight> {
 		// NOTE: changing this to unboxed `impl Fn(..)` return type and the pallet will still
 		// compile, while some types in mock fail to resolve.
-		let issuance = T::Currency::total_issuance();
+		let issuance = Cell::new(T::Currency::total_issuance());
+		let calls_since_refresh = Cell::new(0u32);
+		let pending_slashes = Self::pending_slashes();
 		Box::new(move |who: &T::AccountId| -> VoteWeight {
-			Self::slashable_balance_of_vote_weight(who, issuance)
+			if every_n_calls > 0 && calls_since_refresh.get() >= every_n_calls {
+				issuance.set(T::Currency::total_issuance());
+				calls_since_refresh.set(0);
+			}
+			calls_since_refresh.set(calls_since_refresh.get().saturating_add(1));
+
+			match pending_slashes.get(who) {
+				Some(pending) => T::CurrencyToVote::to_vote(
+					Self::slashable_balance_of(who).saturating_sub(*pending),
+					issuance.get(),
+				),
+				None => Self::slashable_balance_of_vote_weight(who, issuance.get()),
+			}
 		})
 	}
 
 	/// Same as `weight_of_fn`, but made for one time use.
 	pub fn weight_of(who: &T::AccountId) -> VoteWeight {
 		let issuance = T::Currency::total_issuance();
-		Self::slashable_balance_of_vote_weight(who, issuance)
+		let stake = Self::slashable_balance_of(who).saturating_sub(Self::pending_slash_of(who));
+		T::CurrencyToVote::to_vote(stake, issuance)
 	}
 
 	pub(super) fn do_withdraw_unbonded(
 		controller: &T::AccountId,
 		num_slashing_spans: u32,
 	) -> Result<Weight, DispatchError> {
-		let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+		let mut ledger = Self::try_ledger(&controller)?;
 		let (stash, old_total) = (ledger.stash.clone(), ledger.total);
+		// the chunks slashed by the deferred slashes being applied must not be withdrawn meanwhile.
+		if let Some(era) = UnappliedSlashesCursor::<T>::get() {
+			ensure!(!PendingSlashes::<T>::contains_key(&stash, era), Error::<T>::SlashBeingApplied);
+		}
 		if let Some(current_era) = Self::current_era() {
 			ledger = ledger.consolidate_unlocked(current_era)
 		}
@@ -144,53 +454,291 @@ impl<T: Config> Pallet<T> {
 		validator_stash: T::AccountId,
 		era: EraIndex,
 	) -> DispatchResultWithPostInfo {
+		Self::do_payout_stakers_by_page(validator_stash, era, 0)
+	}
+
+	/// The pages of the exposure of `validator_stash` in `era` that have been paid out, sorted.
+	///
+	/// Includes page 0 if it was claimed before [`ClaimedRewards`], see
+	/// [`StakingLedger::legacy_claimed_rewards`].
+	pub(crate) fn claimed_pages(era: EraIndex, validator_stash: &T::AccountId) -> Vec<u32> {
+		match Self::bonded(&Self::migrated_stash(validator_stash)).and_then(|c| Self::ledger(&c)) {
+			Some(ledger) => Self::claimed_pages_of(era, validator_stash, &ledger),
+			None => <ClaimedRewards<T>>::get(era, validator_stash),
+		}
+	}
+
+	/// Same as [`Self::claimed_pages`], with the `ledger` of `validator_stash` at hand.
+	fn claimed_pages_of(
+		era: EraIndex,
+		validator_stash: &T::AccountId,
+		ledger: &StakingLedger<T>,
+	) -> Vec<u32> {
+		let mut claimed = <ClaimedRewards<T>>::get(era, validator_stash);
+		if claimed.first() != Some(&0) && ledger.legacy_claimed_rewards.contains(&era) {
+			claimed.insert(0, 0);
+		}
+		claimed
+	}
+
+	/// Pay out the validator and the nominators in `page` of its exposure in `era`.
+	///
+	/// Page 0 holds the top [`Config::MaxNominatorRewardedPerValidator`] nominators, and is the
+	/// only page that pays the validator itself. Any further page holds the next ones, see
+	/// [`ErasStakersPaged`].
+	pub(super) fn do_payout_stakers_by_page(
+		validator_stash: T::AccountId,
+		era: EraIndex,
+		page: u32,
+	) -> DispatchResultWithPostInfo {
+		trace_span!("payout_stakers", era, page, validator = ?validator_stash);
 		// Validate input data
 		let current_era = CurrentEra::<T>::get().ok_or_else(|| {
-			Error::<T>::InvalidEraToReward
-				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+			Error::<T>::EraInFuture.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
 		})?;
-		let history_depth = T::HistoryDepth::get();
+		let oldest_claimable = current_era.saturating_sub(T::HistoryDepth::get());
 		ensure!(
-			era <= current_era && era >= current_era.saturating_sub(history_depth),
-			Error::<T>::InvalidEraToReward
+			era <= current_era,
+			Error::<T>::EraInFuture.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		);
+		ensure!(
+			era >= oldest_claimable,
+			Error::<T>::EraTooOld { oldest_claimable }
 				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
 		);
 
 		// Note: if era has no reward to be claimed, era may be future. better not to update
-		// `ledger.claimed_rewards` in this case.
+		// `ClaimedRewards` in this case.
 		let era_payout = <ErasValidatorReward<T>>::get(&era).ok_or_else(|| {
-			Error::<T>::InvalidEraToReward
+			Error::<T>::RewardsNotRecordedYet
 				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
 		})?;
 
-		let controller = Self::bonded(&validator_stash).ok_or_else(|| {
-			Error::<T>::NotStash.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		// the bond of a migrated stash is still rewarded for the eras it was exposed in.
+		let controller =
+			Self::bonded(&Self::migrated_stash(&validator_stash)).ok_or_else(|| {
+				Error::<T>::NotStash.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+			})?;
+		let mut ledger = Self::try_ledger(&controller)?;
+
+		let mut claimed = Self::claimed_pages_of(era, &validator_stash, &ledger);
+		let pos = claimed.binary_search(&page).err().ok_or_else(|| {
+			Error::<T>::AlreadyClaimed.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		})?;
+		// a validator that was not elected in `era` has no page to claim.
+		let exposure = Self::eras_stakers_paged(era, &validator_stash, page).ok_or_else(|| {
+			Error::<T>::InvalidPage.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
 		})?;
-		let mut ledger = <Ledger<T>>::get(&controller).ok_or(Error::<T>::NotController)?;
 
-		ledger
-			.claimed_rewards
-			.retain(|&x| x >= current_era.saturating_sub(history_depth));
+		// Input data seems good, no errors allowed after this point
 
-		match ledger.claimed_rewards.binary_search(&era) {
-			Ok(_) =>
-				return Err(Error::<T>::AlreadyClaimed
-					.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))),
-			Err(pos) => ledger
-				.claimed_rewards
-				.try_insert(pos, era)
-				// Since we retain era entries in `claimed_rewards` only upto
-				// `HistoryDepth`, following bound is always expected to be
-				// satisfied.
-				.defensive_map_err(|_| Error::<T>::BoundNotMet)?,
+		claimed.insert(pos, page);
+		<ClaimedRewards<T>>::insert(&era, &validator_stash, claimed);
+		if ledger
+			.legacy_claimed_rewards
+			.first()
+			.map_or(false, |era| *era < oldest_claimable)
+		{
+			ledger.legacy_claimed_rewards.retain(|era| *era >= oldest_claimable);
+			<Ledger<T>>::insert(&controller, &ledger);
 		}
+		// the nominators that already claimed their own reward out of this page.
+		let claimed_by_nominators =
+			<ClaimedNominatorRewards<T>>::take((era, &validator_stash, page));
+
+		let (validator_payout, nominator_rewards) =
+			match Self::page_rewards(&validator_stash, era, era_payout, &exposure) {
+				Some(rewards) => rewards,
+				// Nothing to do if they have no reward points.
+				None => return Ok(Some(T::WeightInfo::payout_stakers_alive_staked(0)).into()),
+			};
 
-		let exposure = <ErasStakersClipped<T>>::get(&era, &ledger.stash);
+		Self::deposit_event(Event::<T>::PayoutStarted {
+			era_index: era,
+			validator_stash: validator_stash.clone(),
+		});
 
-		// Input data seems good, no errors allowed after this point
+		let mut total_imbalance = PositiveImbalanceOf::<T>::zero();
+		// the weight of the payouts split across accounts, on top of the benchmarked ones.
+		let mut split_weight = Weight::zero();
+		// We can now make total validator payout, only once and on the first page:
+		if page.is_zero() {
+			let (imbalance, weight) = Self::make_payout(&ledger.stash, validator_payout);
+			split_weight.saturating_accrue(weight);
+			if let Some(imbalance) = imbalance {
+				Self::note_activity(era, &ledger.stash, AccountActivity::REWARDED);
+				Self::deposit_event(Event::<T>::Rewarded {
+					stash: ledger.stash.clone(),
+					amount: imbalance.peek(),
+				});
+				total_imbalance.subsume(imbalance);
+			}
+		}
+
+		// Track the number of payout ops to nominators. Note:
+		// `WeightInfo::payout_stakers_alive_staked` always assumes at least a validator is paid
+		// out, so we do not need to count their payout op.
+		let mut nominator_payout_count: u32 = 0;
 
-		<Ledger<T>>::insert(&controller, &ledger);
+		// Reward only the exposures of this page. Note this is not necessarily sorted.
+		for (index, (nominator, nominator_reward)) in
+			exposure.others.iter().zip(nominator_rewards).enumerate()
+		{
+			if claimed_by_nominators
+				.get(index / 8)
+				.map_or(false, |byte| byte & (1 << (index % 8)) != 0)
+			{
+				continue
+			}
+			// We can now make nominator payout, to the bond of the nominator if it was migrated:
+			let stash = Self::migrated_stash(&nominator.who);
+			let (imbalance, weight) = Self::make_payout(&stash, nominator_reward);
+			split_weight.saturating_accrue(weight);
+			if let Some(imbalance) = imbalance {
+				// Note: this logic does not count payouts for `RewardDestination::None`.
+				nominator_payout_count += 1;
+				Self::note_activity(era, &stash, AccountActivity::REWARDED);
+				let e = Event::<T>::Rewarded { stash, amount: imbalance.peek() };
+				Self::deposit_event(e);
+				total_imbalance.subsume(imbalance);
+			}
+		}
 
+		T::Reward::on_unbalanced(total_imbalance);
+		debug_assert!(nominator_payout_count <= T::MaxNominatorRewardedPerValidator::get());
+		let exposed = exposure.others.len() as u32;
+		log!(
+			payout: debug,
+			"paid out page {} of {:?} for era {:?} to {} nominators",
+			page,
+			validator_stash,
+			era,
+			nominator_payout_count,
+		);
+		Ok(Some(
+			Self::payout_page_weight(nominator_payout_count, exposed).saturating_add(split_weight),
+		)
+		.into())
+	}
+
+	/// The weight of paying out a page of `nominators` nominators, `paid` of which are paid.
+	pub(crate) fn payout_page_weight(paid: u32, nominators: u32) -> Weight {
+		T::WeightInfo::payout_stakers_alive_staked(paid)
+			// the `NominationExits` of each nominator, to check whether it forfeited its reward,
+			// and its `MigratedStashes`, to find its bond.
+			.saturating_add(T::DbWeight::get().reads(2 * nominators as u64))
+			// the `AccountEraActivity` of the validator and of each paid nominator.
+			.saturating_add(T::DbWeight::get().reads_writes(paid as u64 + 1, paid as u64 + 1))
+	}
+
+	/// The weight of paying a reward into the `accounts` accounts of a
+	/// [`RewardDestination::Split`], beyond the single payment of the benchmarked payouts.
+	pub(crate) fn split_payout_weight(accounts: u32) -> Weight {
+		// the `PayeeSplits`, and each of the accounts.
+		T::DbWeight::get().reads_writes(accounts as u64 + 1, accounts as u64)
+	}
+
+	/// The weight of paying out a full page, with the reward of each staker split across as many
+	/// accounts as possible.
+	pub(crate) fn max_payout_page_weight() -> Weight {
+		let max = T::MaxNominatorRewardedPerValidator::get();
+		Self::payout_page_weight(max, max).saturating_add(
+			Self::split_payout_weight(MAX_PAYEE_SPLITS).saturating_mul(max as u64 + 1),
+		)
+	}
+
+	/// Pay out the reward of `nominator` alone, out of the page of the exposure of
+	/// `validator_stash` in `era` that it is in.
+	///
+	/// The nominator is marked in [`ClaimedNominatorRewards`], and skipped when the rest of the
+	/// page is paid out.
+	pub(super) fn do_claim_nominator_reward(
+		nominator: T::AccountId,
+		validator_stash: T::AccountId,
+		era: EraIndex,
+		page: u32,
+	) -> DispatchResultWithPostInfo {
+		trace_span!("claim_nominator_reward", era, page, validator = ?validator_stash);
+		let current_era = CurrentEra::<T>::get().ok_or_else(|| {
+			Error::<T>::EraInFuture.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		})?;
+		let oldest_claimable = current_era.saturating_sub(T::HistoryDepth::get());
+		ensure!(
+			era <= current_era,
+			Error::<T>::EraInFuture.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		);
+		ensure!(
+			era >= oldest_claimable,
+			Error::<T>::EraTooOld { oldest_claimable }
+				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		);
+		let era_payout = <ErasValidatorReward<T>>::get(&era).ok_or_else(|| {
+			Error::<T>::RewardsNotRecordedYet
+				.with_weight(T::WeightInfo::payout_stakers_alive_staked(0))
+		})?;
+
+		// the nominator is exposed under the stash it was migrated from, if any.
+		let exposed_as = MigratedFrom::<T>::get(&nominator);
+		let exposure =
+			Self::eras_stakers_paged(era, &validator_stash, page).ok_or(Error::<T>::NotExposed)?;
+		let index = exposure
+			.others
+			.iter()
+			.position(|n| n.who == nominator || Some(&n.who) == exposed_as.as_ref())
+			.ok_or(Error::<T>::NotExposed)?;
+
+		ensure!(
+			!Self::claimed_pages(era, &validator_stash).contains(&page),
+			Error::<T>::AlreadyClaimed
+		);
+		let mut claimed_by_nominators =
+			<ClaimedNominatorRewards<T>>::get((era, &validator_stash, page));
+		if claimed_by_nominators.len() <= index / 8 {
+			claimed_by_nominators.resize(index / 8 + 1, 0);
+		}
+		ensure!(
+			claimed_by_nominators[index / 8] & (1 << (index % 8)) == 0,
+			Error::<T>::AlreadyClaimed
+		);
+		claimed_by_nominators[index / 8] |= 1 << (index % 8);
+		<ClaimedNominatorRewards<T>>::insert((era, &validator_stash, page), claimed_by_nominators);
+
+		let reward = match Self::page_rewards(&validator_stash, era, era_payout, &exposure) {
+			Some((_, nominator_rewards)) => nominator_rewards[index],
+			// Nothing to do if the validator has no reward points.
+			None => return Ok(().into()),
+		};
+		if let (Some(imbalance), _) = Self::make_payout(&nominator, reward) {
+			Self::note_activity(era, &nominator, AccountActivity::REWARDED);
+			Self::deposit_event(Event::<T>::Rewarded {
+				stash: nominator.clone(),
+				amount: imbalance.peek(),
+			});
+			T::Reward::on_unbalanced(imbalance);
+		}
+		log!(
+			payout: debug,
+			"paid out {:?} alone, out of page {} of {:?} for era {:?}",
+			nominator,
+			page,
+			validator_stash,
+			era,
+		);
+		Ok(().into())
+	}
+
+	/// The reward of `validator_stash`, and of each of the nominators of `exposure`, one of the
+	/// pages of its exposure in `era`, out of the `era_payout`.
+	///
+	/// The reward of the validator is only due on page 0, and nominators that dropped the
+	/// validator before `era` get nothing. Returns `None` if the validator has no reward points in
+	/// `era`.
+	fn page_rewards(
+		validator_stash: &T::AccountId,
+		era: EraIndex,
+		era_payout: BalanceOf<T>,
+		exposure: &Exposure<T::AccountId, BalanceOf<T>>,
+	) -> Option<(BalanceOf<T>, Vec<BalanceOf<T>>)> {
 		// Get Era reward points. It has TOTAL and INDIVIDUAL
 		// Find the fraction of the era reward that belongs to the validator
 		// Take that fraction of the eras rewards to split to nominator and validator
@@ -202,13 +750,12 @@ impl<T: Config> Pallet<T> {
 		let total_reward_points = era_reward_points.total;
 		let validator_reward_points = era_reward_points
 			.individual
-			.get(&ledger.stash)
+			.get(validator_stash)
 			.copied()
 			.unwrap_or_else(Zero::zero);
 
-		// Nothing to do if they have no reward points.
 		if validator_reward_points.is_zero() {
-			return Ok(Some(T::WeightInfo::payout_stakers_alive_staked(0)).into())
+			return None
 		}
 
 		// This is the fraction of the total reward that the validator and the
@@ -219,7 +766,7 @@ impl<T: Config> Pallet<T> {
 		// This is how much validator + nominators are entitled to.
 		let validator_total_payout = validator_total_reward_part * era_payout;
 
-		let validator_prefs = Self::eras_validator_prefs(&era, &validator_stash);
+		let validator_prefs = Self::eras_validator_prefs(&era, validator_stash);
 		// Validator first gets a cut off the top.
 		let validator_commission = validator_prefs.commission;
 		let validator_commission_payout = validator_commission * validator_total_payout;
@@ -228,90 +775,556 @@ impl<T: Config> Pallet<T> {
 		// Now let's calculate how this is split to the validator.
 		let validator_exposure_part = Perbill::from_rational(exposure.own, exposure.total);
 		let validator_staking_payout = validator_exposure_part * validator_leftover_payout;
+		// The validator may forfeit part of that to their nominators, across all pages.
+		let all_nominators_stake = exposure.total.saturating_sub(exposure.own);
+		let nominator_bonus_payout = if all_nominators_stake.is_zero() {
+			Zero::zero()
+		} else {
+			validator_prefs.nominator_bonus * validator_staking_payout
+		};
+		let validator_staking_payout =
+			validator_staking_payout.saturating_sub(nominator_bonus_payout);
 
-		Self::deposit_event(Event::<T>::PayoutStarted {
-			era_index: era,
-			validator_stash: ledger.stash.clone(),
+		// The part of the bonus that goes to the nominators of this page.
+		let nominators_stake = exposure
+			.others
+			.iter()
+			.fold(BalanceOf::<T>::zero(), |acc, n| acc.saturating_add(n.value));
+		let nominator_bonus_payout = if nominators_stake == all_nominators_stake {
+			nominator_bonus_payout
+		} else {
+			Perbill::from_rational(nominators_stake, all_nominators_stake) * nominator_bonus_payout
+		};
+
+		// Loyal nominators may get a larger part of what is left for all nominators.
+		let loyalty_weights = Self::loyalty_weights(&exposure.others, validator_stash, era);
+		let nominators_leftover_payout =
+			Perbill::from_rational(nominators_stake, exposure.total) * validator_leftover_payout;
+
+		// Lets now calculate how this is split to the nominators.
+		let nominator_rewards = exposure
+			.others
+			.iter()
+			.enumerate()
+			.map(|(index, nominator)| {
+				// the reward is forfeited along with the slashes.
+				if Self::dropped_before(&nominator.who, validator_stash, era) {
+					return Zero::zero()
+				}
+				let nominator_leftover_payout = match loyalty_weights {
+					Some((ref weights, total_weight)) if !total_weight.is_zero() =>
+						Perbill::from_rational(weights[index], total_weight) *
+							nominators_leftover_payout,
+					_ =>
+						Perbill::from_rational(nominator.value, exposure.total) *
+							validator_leftover_payout,
+				};
+				let nominator_bonus_part =
+					Perbill::from_rational(nominator.value, nominators_stake);
+
+				nominator_leftover_payout
+					.saturating_add(nominator_bonus_part * nominator_bonus_payout)
+			})
+			.collect();
+
+		Some((validator_staking_payout + validator_commission_payout, nominator_rewards))
+	}
+
+	/// The stake of each of the `nominators` of `validator` in `era`, scaled up by their loyalty
+	/// bonus, along with the sum of all of them.
+	///
+	/// Returns `None` if [`Config::LoyaltyBonus`] is zero, in which case the nominators are paid
+	/// out pro rata to their stake.
+	fn loyalty_weights(
+		nominators: &[IndividualExposure<T::AccountId, BalanceOf<T>>],
+		validator: &T::AccountId,
+		era: EraIndex,
+	) -> Option<(Vec<BalanceOf<T>>, BalanceOf<T>)> {
+		let bonus = T::LoyaltyBonus::get();
+		if bonus.is_zero() {
+			return None
+		}
+
+		let max_eras = T::MaxLoyaltyEras::get().max(1);
+		let weights = nominators
+			.iter()
+			.map(|nominator| {
+				let streak = Self::nominator_loyalty(&nominator.who, validator)
+					.map_or(0, |streak| streak.at(era))
+					.min(max_eras);
+				let loyalty = Perbill::from_rational(streak, max_eras);
+				nominator.value.saturating_add(loyalty * (bonus * nominator.value))
+			})
+			.collect::<Vec<_>>();
+		let total_weight =
+			weights.iter().fold(BalanceOf::<T>::zero(), |acc, w| acc.saturating_add(*w));
+		Some((weights, total_weight))
+	}
+
+	/// The eras within the history depth in which the stakers behind `validator_stash` can be paid
+	/// out and have not been yet, oldest first.
+	///
+	/// Only the first page of each era is considered, see [`Call::payout_stakers_by_page`].
+	pub fn unclaimed_eras(validator_stash: &T::AccountId) -> Vec<EraIndex> {
+		let current_era = match CurrentEra::<T>::get() {
+			Some(current_era) => current_era,
+			None => return Vec::new(),
+		};
+		(current_era.saturating_sub(T::HistoryDepth::get())..=current_era)
+			.filter(|era| {
+				<ErasValidatorReward<T>>::contains_key(era) &&
+					<ErasStakersTotals<T>>::contains_key(era, validator_stash) &&
+					!Self::claimed_pages(*era, validator_stash).contains(&0)
+			})
+			.collect()
+	}
+
+	/// Pay out each of `payouts`, reporting and skipping the failed ones, and return the weight
+	/// consumed.
+	pub(super) fn do_payout_stakers_batch(payouts: Vec<(T::AccountId, EraIndex)>) -> Weight {
+		payouts.into_iter().fold(Weight::zero(), |total, (validator_stash, era)| {
+			total.saturating_add(Self::do_payout_page_or_report(validator_stash, era, 0))
+		})
+	}
+
+	/// Pay out `page` of `validator_stash` in `era`, reporting a failure with a
+	/// [`Event::PayoutFailed`], and return the weight consumed.
+	fn do_payout_page_or_report(validator_stash: T::AccountId, era: EraIndex, page: u32) -> Weight {
+		let max_weight = Self::max_payout_page_weight();
+		// a failed payout must not leave any of its changes behind.
+		let actual_weight = match with_storage_layer(|| {
+			Self::do_payout_stakers_by_page(validator_stash.clone(), era, page)
+		}) {
+			Ok(info) => info.actual_weight,
+			Err(e) => {
+				if let Some(oldest_claimable) =
+					Self::api_oldest_claimable_era().filter(|oldest| era < *oldest)
+				{
+					Self::deposit_event(Event::<T>::PayoutEraTooOld {
+						validator_stash: validator_stash.clone(),
+						era,
+						oldest_claimable,
+					});
+				}
+				Self::deposit_event(Event::<T>::PayoutFailed {
+					validator_stash,
+					era,
+					error: e.error,
+				});
+				e.post_info.actual_weight
+			},
+		};
+		actual_weight.unwrap_or(max_weight)
+	}
+
+	/// Pay out up to `budget` pages of the oldest unclaimed rewards, within `remaining_weight`,
+	/// and return the weight consumed.
+	///
+	/// The eras are paid out from the oldest within the history depth, and the validators of an
+	/// era by decreasing stake. The pages left to pay out of the era in progress are kept in
+	/// [`AutoPayoutPages`], one per entry.
+	pub(crate) fn do_auto_payouts(remaining_weight: Weight, budget: u32) -> Weight {
+		// paying a page out also takes it out of the queue.
+		let page_weight =
+			Self::max_payout_page_weight().saturating_add(T::DbWeight::get().reads_writes(1, 1));
+		// reading the eras and the queued era, and writing the queued era back.
+		let mut consumed = T::DbWeight::get().reads_writes(3, 1);
+		// queueing an era reads the totals and the claims of all its validators, and writes all
+		// their pages, after removing the pages left of a stale era.
+		let max_pages = MaxWinnersOf::<T>::get().saturating_mul(Self::exposure_page_count(
+			<T::ElectionProvider as ElectionProviderBase>::MaxBackersPerWinner::get(),
+		)) as u64;
+		let queue_weight = T::DbWeight::get()
+			.reads_writes(2 * MaxWinnersOf::<T>::get() as u64, max_pages.saturating_mul(2));
+		if remaining_weight.any_lt(consumed) {
+			return Weight::zero()
+		}
+
+		let (current_era, active_era) = match (CurrentEra::<T>::get(), Self::active_era()) {
+			(Some(current_era), Some(active_era)) => (current_era, active_era.index),
+			_ => return T::DbWeight::get().reads(2),
+		};
+		let oldest_era = current_era.saturating_sub(T::HistoryDepth::get());
+		let (mut era, mut left) = match AutoPayoutEra::<T>::get() {
+			Some((era, left)) if era >= oldest_era => (era, left),
+			queued if oldest_era < active_era => {
+				if remaining_weight.any_lt(consumed.saturating_add(queue_weight)) {
+					return consumed
+				}
+				consumed.saturating_accrue(queue_weight);
+				let stale = queued.map_or(0, |(_, left)| left);
+				(oldest_era, Self::queue_auto_payout_pages(oldest_era, stale))
+			},
+			_ => return T::DbWeight::get().reads(3),
+		};
+
+		let mut paid = 0u32;
+		while paid < budget {
+			if left > 0 {
+				if remaining_weight.any_lt(consumed.saturating_add(page_weight)) {
+					break
+				}
+				left -= 1;
+				consumed.saturating_accrue(T::DbWeight::get().reads_writes(1, 1));
+				if let Some((validator_stash, page)) =
+					AutoPayoutPages::<T>::take(left).defensive_proof("pages are queued up to left")
+				{
+					consumed.saturating_accrue(Self::do_payout_page_or_report(
+						validator_stash,
+						era,
+						page,
+					));
+				}
+				paid.saturating_inc();
+			} else {
+				// move on to the next era, once it has ended.
+				let next_era = era.saturating_add(1);
+				if next_era >= active_era ||
+					remaining_weight.any_lt(consumed.saturating_add(queue_weight))
+				{
+					break
+				}
+				consumed.saturating_accrue(queue_weight);
+				era = next_era;
+				left = Self::queue_auto_payout_pages(next_era, 0);
+			}
+		}
+
+		AutoPayoutEra::<T>::put((era, left));
+		consumed
+	}
+
+	/// Queue the pages of `era` that have not been paid out yet in [`AutoPayoutPages`], in the
+	/// order in which [`Self::do_auto_payouts`] takes them: by decreasing stake of their
+	/// validator. The `stale` pages left from a previous era are removed first.
+	///
+	/// Returns the number of pages queued.
+	fn queue_auto_payout_pages(era: EraIndex, stale: u32) -> u32 {
+		for index in 0..stale {
+			AutoPayoutPages::<T>::remove(index);
+		}
+
+		let mut validators = <ErasStakersTotals<T>>::iter_prefix(era).collect::<Vec<_>>();
+		validators.sort_by(|(_, (_, a, _)), (_, (_, b, _))| a.cmp(b));
+		let mut queued = 0u32;
+		for (validator_stash, (_, _, nominator_count)) in validators {
+			let claimed = Self::claimed_pages(era, &validator_stash);
+			for page in (0..Self::exposure_page_count(nominator_count)).rev() {
+				if claimed.binary_search(&page).is_err() {
+					AutoPayoutPages::<T>::insert(queued, (validator_stash.clone(), page));
+					queued.saturating_inc();
+				}
+			}
+		}
+		queued
+	}
+
+	/// Update the ledger for a controller.
+	///
+	/// This will also update the stash lock and the cached [`StakeOf`] of the stash.
+	pub(crate) fn update_ledger(controller: &T::AccountId, ledger: &StakingLedger<T>) {
+		T::Currency::set_lock(STAKING_ID, &ledger.stash, ledger.total, WithdrawReasons::all());
+		<Ledger<T>>::insert(controller, ledger);
+		<StakeOf<T>>::insert(&ledger.stash, (ledger.active, ledger.total));
+	}
+
+	/// Chill a stash account.
+	pub(crate) fn chill_stash(stash: &T::AccountId) {
+		let chilled_as_validator = Self::do_remove_validator(stash);
+		let chilled_as_nominator = Self::do_remove_nominator(stash);
+		if chilled_as_validator || chilled_as_nominator {
+			Self::note_active_activity(stash, AccountActivity::CHILLED);
+			Self::deposit_event(Event::<T>::Chilled { stash: stash.clone() });
+		}
+	}
+
+	/// Chill the nominator at the tail of [`Config::VoterList`] to make room for `stash`.
+	///
+	/// Fails with `TooManyNominators` if the lowest nominator has at least `bond` at stake, or if
+	/// none is found within the last [`Config::MaxEvictionScan`] voters.
+	pub(crate) fn evict_lowest_nominator(
+		stash: &T::AccountId,
+		bond: BalanceOf<T>,
+	) -> DispatchResult {
+		let lowest = T::VoterList::iter_rev()
+			.take(T::MaxEvictionScan::get() as usize)
+			.find(|voter| Nominators::<T>::contains_key(voter))
+			.ok_or(Error::<T>::TooManyNominators)?;
+		ensure!(Self::slashable_balance_of(&lowest) < bond, Error::<T>::TooManyNominators);
+
+		Self::chill_stash(&lowest);
+		Self::deposit_event(Event::<T>::NominatorKicked { stash: lowest, by: stash.clone() });
+		Ok(())
+	}
+
+	/// Check that `prefs` keep the commitments in effect for `stash`, if any, and record them as
+	/// its new commission change if they change anything.
+	///
+	/// Stricter commitments take effect right away, looser ones only from the next era: until
+	/// then, the commitments in effect at the last change keep being enforced.
+	pub(crate) fn note_commission_change(
+		stash: &T::AccountId,
+		prefs: &ValidatorPrefs,
+	) -> DispatchResult {
+		let last = CommissionChanges::<T>::get(stash);
+		let declared = Validators::<T>::try_get(stash).ok();
+		let unchanged = last.is_some() &&
+			declared.as_ref().map_or(false, |declared| {
+				declared.commission == prefs.commission &&
+					declared.max_commission_increase_per_era ==
+						prefs.max_commission_increase_per_era &&
+					declared.min_blocks_between_changes == prefs.min_blocks_between_changes
+			});
+		if unchanged {
+			return Ok(())
+		}
+
+		let now = frame_system::Pallet::<T>::block_number();
+		let era = Self::active_era().map(|e| e.index).unwrap_or(0);
+		let (base, max_increase, min_blocks) = match last {
+			Some(last) => {
+				// the commitments recorded at the last change hold until the end of its era, after
+				// which the ones declared by the validator take over.
+				let (max_increase, min_blocks) = match declared {
+					Some(declared) if last.era != era => (
+						declared.max_commission_increase_per_era,
+						declared.min_blocks_between_changes,
+					),
+					_ => (last.max_commission_increase_per_era, last.min_blocks_between_changes),
+				};
+				ensure!(
+					now >= last.at.saturating_add(min_blocks.into()),
+					Error::<T>::CommissionChangeTooSoon
+				);
+				let base = if last.era == era { last.base } else { last.commission };
+				if let Some(max_increase) = max_increase {
+					ensure!(
+						prefs.commission <= base.saturating_add(max_increase),
+						Error::<T>::CommissionIncreaseTooHigh
+					);
+				}
+				(base, max_increase, min_blocks)
+			},
+			None => (
+				prefs.commission,
+				prefs.max_commission_increase_per_era,
+				prefs.min_blocks_between_changes,
+			),
+		};
+
+		CommissionChanges::<T>::insert(
+			stash,
+			CommissionChange {
+				era,
+				base,
+				at: now,
+				commission: prefs.commission,
+				max_commission_increase_per_era: match (
+					max_increase,
+					prefs.max_commission_increase_per_era,
+				) {
+					(Some(current), Some(new)) => Some(current.min(new)),
+					(current, new) => current.or(new),
+				},
+				min_blocks_between_changes: min_blocks.max(prefs.min_blocks_between_changes),
+			},
+		);
+		Ok(())
+	}
+
+	/// Remove `stash` from the targets of `nominator`, if it nominates it.
+	pub(crate) fn kick_nominator(stash: &T::AccountId, nominator: &T::AccountId) {
+		Nominators::<T>::mutate(nominator, |maybe_nom| {
+			if let Some(ref mut nom) = maybe_nom {
+				if let Some(pos) = nom.targets.iter().position(|v| v == stash) {
+					nom.targets.swap_remove(pos);
+					NominatorsOf::<T>::remove(stash, nominator);
+					Self::deposit_event(Event::<T>::Kicked {
+						nominator: nominator.clone(),
+						stash: stash.clone(),
+					});
+				}
+			}
 		});
+	}
+
+	/// Record `activity` of `who` in `era`, see [`AccountEraActivity`].
+	pub(crate) fn note_activity(era: EraIndex, who: &T::AccountId, activity: AccountActivity) {
+		AccountEraActivity::<T>::mutate(era, who, |recorded| recorded.insert(activity));
+	}
 
-		let mut total_imbalance = PositiveImbalanceOf::<T>::zero();
-		// We can now make total validator payout:
-		if let Some(imbalance) =
-			Self::make_payout(&ledger.stash, validator_staking_payout + validator_commission_payout)
-		{
-			Self::deposit_event(Event::<T>::Rewarded {
-				stash: ledger.stash,
-				amount: imbalance.peek(),
-			});
-			total_imbalance.subsume(imbalance);
+	/// Record `activity` of `who` in the active era, if any.
+	pub(crate) fn note_active_activity(who: &T::AccountId, activity: AccountActivity) {
+		if let Some(active_era) = Self::active_era() {
+			Self::note_activity(active_era.index, who, activity);
 		}
+	}
 
-		// Track the number of payout ops to nominators. Note:
-		// `WeightInfo::payout_stakers_alive_staked` always assumes at least a validator is paid
-		// out, so we do not need to count their payout op.
-		let mut nominator_payout_count: u32 = 0;
+	/// Put the validator `stash` at the end of [`ExitQueue`], unless it is already in it.
+	pub(crate) fn queue_exit(stash: &T::AccountId) {
+		if ExitTurns::<T>::contains_key(stash) {
+			return
+		}
 
-		// Lets now calculate how this is split to the nominators.
-		// Reward only the clipped exposures. Note this is not necessarily sorted.
-		for nominator in exposure.others.iter() {
-			let nominator_exposure_part = Perbill::from_rational(nominator.value, exposure.total);
-
-			let nominator_reward: BalanceOf<T> =
-				nominator_exposure_part * validator_leftover_payout;
-			// We can now make nominator payout:
-			if let Some(imbalance) = Self::make_payout(&nominator.who, nominator_reward) {
-				// Note: this logic does not count payouts for `RewardDestination::None`.
-				nominator_payout_count += 1;
-				let e =
-					Event::<T>::Rewarded { stash: nominator.who.clone(), amount: imbalance.peek() };
-				Self::deposit_event(e);
-				total_imbalance.subsume(imbalance);
+		let (front, turn) = ExitQueueRange::<T>::get();
+		ExitQueue::<T>::insert(turn, stash);
+		ExitTurns::<T>::insert(stash, turn);
+		ExitQueueRange::<T>::put((front, turn.saturating_add(1)));
+		Self::deposit_event(Event::<T>::ExitQueued { stash: stash.clone() });
+	}
+
+	/// Remove `stash` from [`ExitQueue`], if it is in it.
+	///
+	/// The turn of `stash` is left empty, unless it is at either end of the queue.
+	pub(crate) fn dequeue_exit(stash: &T::AccountId) {
+		if let Some(turn) = ExitTurns::<T>::take(stash) {
+			ExitQueue::<T>::remove(turn);
+			let (front, next) = ExitQueueRange::<T>::get();
+			let range = if turn == front {
+				(front.saturating_add(1), next)
+			} else if turn.saturating_add(1) == next {
+				(front, turn)
+			} else {
+				return
+			};
+			if range.0 == range.1 {
+				ExitQueueRange::<T>::kill();
+			} else {
+				ExitQueueRange::<T>::put(range);
 			}
 		}
+	}
+
+	/// Put the new validator `stash` in [`ValidatorWaitlist`], if new validators have to wait.
+	pub(crate) fn waitlist_validator(stash: &T::AccountId) {
+		let wait = T::ValidatorWaitlistSessions::get();
+		if wait.is_zero() {
+			return
+		}
 
-		T::Reward::on_unbalanced(total_imbalance);
-		debug_assert!(nominator_payout_count <= T::MaxNominatorRewardedPerValidator::get());
-		Ok(Some(T::WeightInfo::payout_stakers_alive_staked(nominator_payout_count)).into())
+		let until = Self::current_planned_session().saturating_add(wait);
+		ValidatorWaitlist::<T>::insert(stash, until);
+		Self::deposit_event(Event::<T>::ValidatorWaitlisted { stash: stash.clone(), until });
 	}
 
-	/// Update the ledger for a controller.
+	/// Whether the validator `stash` is still in [`ValidatorWaitlist`].
 	///
-	/// This will also update the stash lock.
-	pub(crate) fn update_ledger(controller: &T::AccountId, ledger: &StakingLedger<T>) {
-		T::Currency::set_lock(STAKING_ID, &ledger.stash, ledger.total, WithdrawReasons::all());
-		<Ledger<T>>::insert(controller, ledger);
+	/// The entry of a validator whose wait is over is removed.
+	fn is_waitlisted(stash: &T::AccountId) -> bool {
+		match ValidatorWaitlist::<T>::get(stash) {
+			Some(until) if until > Self::current_planned_session() => true,
+			Some(_) => {
+				ValidatorWaitlist::<T>::remove(stash);
+				false
+			},
+			None => false,
+		}
 	}
 
-	/// Chill a stash account.
-	pub(crate) fn chill_stash(stash: &T::AccountId) {
-		let chilled_as_validator = Self::do_remove_validator(stash);
-		let chilled_as_nominator = Self::do_remove_nominator(stash);
-		if chilled_as_validator || chilled_as_nominator {
-			Self::deposit_event(Event::<T>::Chilled { stash: stash.clone() });
+	/// Chill the validators at the front of [`ExitQueue`], at most [`Config::MaxExitsPerEra`] of
+	/// them.
+	///
+	/// The turns left empty at the front of the queue are skipped over.
+	fn process_exit_queue() {
+		let (mut front, next) = ExitQueueRange::<T>::get();
+		if front == next {
+			return
+		}
+
+		// if the queue has been disabled, let everyone out.
+		let max_exits = T::MaxExitsPerEra::get().unwrap_or(u32::MAX);
+		let (mut exits, mut turns) = (0u32, 0u64);
+		while front < next && exits < max_exits {
+			if let Some(stash) = ExitQueue::<T>::take(front) {
+				ExitTurns::<T>::remove(&stash);
+				Self::chill_stash(&stash);
+				exits += 1;
+			}
+			front += 1;
+			turns += 1;
 		}
+
+		if front == next {
+			ExitQueueRange::<T>::kill();
+		} else {
+			ExitQueueRange::<T>::put((front, next));
+		}
+		Self::register_weight(
+			T::WeightInfo::chill()
+				.saturating_mul(exits.into())
+				.saturating_add(T::DbWeight::get().reads_writes(turns + 1, turns + 1)),
+		);
 	}
 
 	/// Actually make a payment to a staker. This uses the currency's reward function
 	/// to pay the right payee for the given staker account.
-	fn make_payout(stash: &T::AccountId, amount: BalanceOf<T>) -> Option<PositiveImbalanceOf<T>> {
+	///
+	/// Also returns the weight of splitting the payment across accounts, if it was, see
+	/// [`Self::split_payout_weight`].
+	fn make_payout(
+		stash: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> (Option<PositiveImbalanceOf<T>>, Weight) {
 		let dest = Self::payee(stash);
-		match dest {
+		let imbalance = match dest {
 			RewardDestination::Controller => Self::bonded(stash)
 				.map(|controller| T::Currency::deposit_creating(&controller, amount)),
 			RewardDestination::Stash => T::Currency::deposit_into_existing(stash, amount).ok(),
-			RewardDestination::Staked => Self::bonded(stash)
-				.and_then(|c| Self::ledger(&c).map(|l| (c, l)))
-				.and_then(|(controller, mut l)| {
-					l.active += amount;
-					l.total += amount;
-					let r = T::Currency::deposit_into_existing(stash, amount).ok();
-					Self::update_ledger(&controller, &l);
-					r
-				}),
+			RewardDestination::Staked => Self::make_staked_payout(stash, amount),
 			RewardDestination::Account(dest_account) =>
 				Some(T::Currency::deposit_creating(&dest_account, amount)),
 			RewardDestination::None => None,
+			RewardDestination::Split => {
+				let splits = PayeeSplits::<T>::get(stash);
+				let weight = Self::split_payout_weight(splits.len() as u32);
+				return (Self::make_split_payout(&splits, amount), weight)
+			},
+			RewardDestination::StakedSplit { staked, account } => {
+				let staked_amount = staked * amount;
+				Self::make_staked_payout(stash, staked_amount).map(|mut imbalance| {
+					imbalance.subsume(T::Currency::deposit_creating(
+						&account,
+						amount.saturating_sub(staked_amount),
+					));
+					imbalance
+				})
+			},
+		};
+		(imbalance, Weight::zero())
+	}
+
+	/// Pay `amount` across the accounts of `splits`, the first one receiving whatever is left once
+	/// the others are paid their share.
+	fn make_split_payout(
+		splits: &[(T::AccountId, Perbill)],
+		amount: BalanceOf<T>,
+	) -> Option<PositiveImbalanceOf<T>> {
+		let ((first, _), others) = splits.split_first()?;
+		let mut imbalance = PositiveImbalanceOf::<T>::zero();
+		let mut rest = amount;
+		for (who, share) in others {
+			let part = (*share * amount).min(rest);
+			rest -= part;
+			imbalance.subsume(T::Currency::deposit_creating(who, part));
 		}
+		imbalance.subsume(T::Currency::deposit_creating(first, rest));
+		Some(imbalance)
+	}
+
+	/// Pay `amount` into `stash`, and bond it.
+	fn make_staked_payout(
+		stash: &T::AccountId,
+		amount: BalanceOf<T>,
+	) -> Option<PositiveImbalanceOf<T>> {
+		Self::bonded(stash).and_then(|c| Self::ledger(&c).map(|l| (c, l))).and_then(
+			|(controller, mut l)| {
+				l.active += amount;
+				l.total += amount;
+				let r = T::Currency::deposit_into_existing(stash, amount).ok();
+				Self::update_ledger(&controller, &l);
+				r
+			},
+		)
 	}
 
 	/// Plan a new session potentially trigger a new era.
@@ -319,6 +1332,12 @@ impl<T: Config> Pallet<T> {
 		session_index: SessionIndex,
 		is_genesis: bool,
 	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
+		trace_span!("new_session", session = session_index, is_genesis);
+		// the next era was already planned by a deferred election.
+		if let Some(validators) = DeferredElectionWinners::<T>::take() {
+			return Some(validators)
+		}
+
 		if let Some(current_era) = Self::current_era() {
 			// Initial era has been set.
 			let current_era_start_session_index = Self::eras_start_session_index(current_era)
@@ -327,28 +1346,26 @@ impl<T: Config> Pallet<T> {
 					0
 				});
 
-			let era_length = session_index.saturating_sub(current_era_start_session_index); // Must never happen.
-
 			match ForceEra::<T>::get() {
 				// Will be set to `NotForcing` again if a new era has been triggered.
 				Forcing::ForceNew => (),
 				// Short circuit to `try_trigger_new_era`.
 				Forcing::ForceAlways => (),
-				// Only go to `try_trigger_new_era` if deadline reached.
-				Forcing::NotForcing if era_length >= T::SessionsPerEra::get() => (),
+				// Only go to `try_trigger_new_era` if deadline, including any offset, reached.
+				Forcing::NotForcing
+					if Self::era_length_reached(session_index, current_era_start_session_index) =>
+					(),
 				_ => {
 					// Either `Forcing::ForceNone`,
-					// or `Forcing::NotForcing if era_length >= T::SessionsPerEra::get()`.
-					return None
+					// or `Forcing::NotForcing` before the end of the era.
+					return Self::queued_replacements()
 				},
 			}
 
 			// New era.
 			let maybe_new_era_validators = Self::try_trigger_new_era(session_index, is_genesis);
-			if maybe_new_era_validators.is_some() &&
-				matches!(ForceEra::<T>::get(), Forcing::ForceNew)
-			{
-				Self::set_force_era(Forcing::NotForcing);
+			if maybe_new_era_validators.is_some() {
+				Self::note_era_planned();
 			}
 
 			maybe_new_era_validators
@@ -359,6 +1376,119 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Reset the forcing and the offset of the era that has just been planned.
+	fn note_era_planned() {
+		ElectionSessionOffset::<T>::kill();
+		if matches!(ForceEra::<T>::get(), Forcing::ForceNew) {
+			Self::set_force_era(Forcing::NotForcing);
+		}
+	}
+
+	/// Retry the election of the next era if it was deferred, and it is due at block `now`.
+	///
+	/// The era it plans starts with the session after the last planned one, to which its
+	/// validators are handed through [`DeferredElectionWinners`]. If the supports of the retried
+	/// election were not all converted into exposures, the conversion is resumed instead. Returns
+	/// the weight consumed.
+	pub(crate) fn retry_deferred_election(now: BlockNumberFor<T>) -> Weight {
+		match DeferredElection::<T>::get() {
+			Some((at, _)) if now >= at => (),
+			_ => return Weight::zero(),
+		}
+
+		let start_session_index = CurrentPlannedSession::<T>::get().saturating_add(1);
+		let validators = if ExposureCollection::<T>::exists() {
+			Self::resume_exposure_collection(start_session_index)
+		} else {
+			Self::try_trigger_new_era(start_session_index, false)
+		};
+		if let Some(validators) = validators {
+			Self::note_era_planned();
+			DeferredElectionWinners::<T>::put(validators);
+		}
+		<T::ElectionProvider>::estimate_elect_weight()
+			.saturating_add(T::DbWeight::get().reads_writes(2, 3))
+	}
+
+	/// The validators of the active session with the substitutes of [`ReplacedValidators`], if
+	/// some are yet to be handed to the session.
+	fn queued_replacements() -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
+		if !ReplacementsQueued::<T>::take() {
+			return None
+		}
+		let replaced = ReplacedValidators::<T>::get();
+		let validators = T::SessionInterface::validators()
+			.into_iter()
+			.map(|validator| {
+				replaced
+					.iter()
+					.find(|(who, _)| *who == validator)
+					.map_or(validator, |(_, substitute)| substitute.clone())
+			})
+			.collect::<Vec<_>>();
+		Some(BoundedVec::truncate_from(validators))
+	}
+
+	/// Whether the current era, which started with `current_era_start_session_index`, has lasted
+	/// long enough to end before the session `session_index`, including any election offset.
+	fn era_length_reached(
+		session_index: SessionIndex,
+		current_era_start_session_index: SessionIndex,
+	) -> bool {
+		match T::EraLength::get() {
+			EraLength::SessionsPerEra =>
+				session_index.saturating_sub(current_era_start_session_index) >=
+					T::SessionsPerEra::get().saturating_add(ElectionSessionOffset::<T>::get()),
+			EraLength::BlocksPerEra(blocks) =>
+				frame_system::Pallet::<T>::block_number() >= Self::era_end_block(blocks),
+		}
+	}
+
+	/// The first block in which the current era can end, if an era lasts `blocks` blocks.
+	///
+	/// The election offset is converted to blocks with the average session length.
+	fn era_end_block(blocks: BlockNumberFor<T>) -> BlockNumberFor<T> {
+		let offset = T::NextNewSession::average_session_length()
+			.saturating_mul(ElectionSessionOffset::<T>::get().into());
+		CurrentEraStartBlock::<T>::get()
+			.map_or_else(Zero::zero, |start| start.saturating_add(blocks).saturating_add(offset))
+	}
+
+	/// The number of sessions, after the one ending at `session_end`, until the current era can
+	/// end.
+	fn sessions_left_in_era(
+		session_end: BlockNumberFor<T>,
+		session_length: BlockNumberFor<T>,
+	) -> BlockNumberFor<T> {
+		match T::EraLength::get() {
+			EraLength::SessionsPerEra => {
+				let current_era = Self::current_era().unwrap_or(0);
+				let current_session = Self::current_planned_session();
+				let current_era_start_session_index =
+					Self::eras_start_session_index(current_era).unwrap_or(0);
+				let sessions_per_era =
+					T::SessionsPerEra::get().saturating_add(ElectionSessionOffset::<T>::get());
+				// Number of session in the current era or the maximum session per era if reached.
+				let era_progress = current_session
+					.saturating_sub(current_era_start_session_index)
+					.min(sessions_per_era);
+
+				sessions_per_era
+					.saturating_sub(era_progress)
+					// One session is computed in this_session_end.
+					.saturating_sub(1)
+					.into()
+			},
+			EraLength::BlocksPerEra(blocks) => {
+				let blocks_left = Self::era_end_block(blocks).saturating_sub(session_end);
+				let session_length = session_length.max(One::one());
+				// round up to whole sessions.
+				blocks_left.saturating_add(session_length.saturating_sub(One::one())) /
+					session_length
+			},
+		}
+	}
+
 	/// Start a session potentially starting an era.
 	fn start_session(start_session: SessionIndex) {
 		let next_active_era = Self::active_era().map(|e| e.index + 1).unwrap_or(0);
@@ -378,10 +1508,31 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 
-		// disable all offending validators that have been disabled for the whole era
-		for (index, disabled) in <OffendingValidators<T>>::get() {
-			if disabled {
-				T::SessionInterface::disable_validator(index);
+		Self::reapply_disabling();
+	}
+
+	/// Disable again the offending validators that [`Config::DisablingStrategy`] keeps disabled,
+	/// as the session forgets them at each new session.
+	///
+	/// The validators re-enabled by the `AdminOrigin` are not flagged anymore, and the replaced
+	/// validators stay disabled until their substitute takes their seat.
+	fn reapply_disabling() {
+		let offending = <OffendingValidators<T>>::get();
+		let replaced = ReplacedValidators::<T>::get();
+		if offending.is_empty() && replaced.is_empty() {
+			return
+		}
+
+		let validators = T::SessionInterface::validators();
+		for index in
+			T::DisablingStrategy::disabled_at_new_session(&offending, validators.len() as u32)
+		{
+			T::SessionInterface::disable_validator(index);
+		}
+
+		for (who, _) in replaced {
+			if let Some(index) = validators.iter().position(|validator| *validator == who) {
+				T::SessionInterface::disable_validator(index as u32);
 			}
 		}
 	}
@@ -393,12 +1544,96 @@ impl<T: Config> Pallet<T> {
 				Self::eras_start_session_index(active_era.index + 1)
 			{
 				if next_active_era_start_session_index == session_index + 1 {
+					Self::check_session_validators(
+						active_era.index + 1,
+						T::SessionInterface::queued_validators(),
+						true,
+					);
 					Self::end_era(active_era, session_index);
 				}
 			}
 		}
 	}
 
+	/// The era until which `value`, unbonded now, remains bonded, which is noted in
+	/// [`ErasUnbonding`].
+	///
+	/// See [`Config::MinUnbondingDuration`].
+	pub(crate) fn unbond_until(value: BalanceOf<T>) -> EraIndex {
+		// Note: in case there is no current era it is fine to bond one era more.
+		let current_era = Self::current_era().unwrap_or(0);
+		let max_duration = T::BondingDuration::get();
+		let min_duration = match T::MinUnbondingDuration::get() {
+			Some(min_duration) if min_duration < max_duration => min_duration,
+			_ => return current_era + max_duration,
+		};
+
+		let exiting = (current_era.saturating_add(1)..=current_era.saturating_add(max_duration))
+			.fold(value, |exiting, era| exiting.saturating_add(ErasUnbonding::<T>::get(era)));
+		let staked = Self::active_era()
+			.map_or_else(Zero::zero, |active_era| ErasTotalStake::<T>::get(active_era.index));
+		let saturation = T::UnbondingQueueSaturation::get() * staked;
+		let stretch = if saturation.is_zero() {
+			Perbill::one()
+		} else {
+			Perbill::from_rational(exiting, saturation)
+		};
+
+		let era = current_era
+			.saturating_add(min_duration)
+			.saturating_add(stretch * (max_duration - min_duration));
+		ErasUnbonding::<T>::mutate(era, |unbonding| *unbonding = unbonding.saturating_add(value));
+		era
+	}
+
+	/// Take the funds taken out of the `unlocking` chunks, which are now `remaining`, from
+	/// [`ErasUnbonding`], as they do not exit anymore once rebonded or slashed.
+	pub(crate) fn note_unlocking_taken(
+		unlocking: &[UnlockChunk<BalanceOf<T>>],
+		remaining: &[UnlockChunk<BalanceOf<T>>],
+	) {
+		if T::MinUnbondingDuration::get().is_none() {
+			return
+		}
+		let current_era = Self::current_era().unwrap_or(0);
+		// chunks are matched by era, as emptied ones are removed.
+		let unlocking_in = |chunks: &[UnlockChunk<BalanceOf<T>>], era| {
+			chunks
+				.iter()
+				.filter(|chunk| chunk.era == era)
+				.fold(BalanceOf::<T>::zero(), |sum, chunk| sum.saturating_add(chunk.value))
+		};
+		let mut eras = unlocking.iter().map(|chunk| chunk.era).collect::<Vec<_>>();
+		eras.sort_unstable();
+		eras.dedup();
+		for era in eras.into_iter().filter(|era| *era > current_era) {
+			let taken = unlocking_in(unlocking, era).saturating_sub(unlocking_in(remaining, era));
+			if !taken.is_zero() {
+				ErasUnbonding::<T>::mutate(era, |unbonding| {
+					*unbonding = unbonding.saturating_sub(taken)
+				});
+			}
+		}
+	}
+
+	/// The number of eras after which offences can no longer be reported, as the funds unbonded
+	/// in the era of the offence may have been released by then.
+	///
+	/// This is [`Config::MinUnbondingDuration`] if set, capped by [`Config::BondingDuration`].
+	pub fn slashing_window() -> EraIndex {
+		let bonding_duration = T::BondingDuration::get();
+		T::MinUnbondingDuration::get()
+			.map_or(bonding_duration, |min_duration| min_duration.min(bonding_duration))
+	}
+
+	/// The weight of going through the unbonding queue when unbonding, if any.
+	pub(crate) fn unbonding_queue_weight() -> Weight {
+		if T::MinUnbondingDuration::get().is_none() {
+			return Weight::zero()
+		}
+		T::DbWeight::get().reads_writes(T::BondingDuration::get().saturating_add(1).into(), 1)
+	}
+
 	/// Start a new era. It does:
 	///
 	/// * Increment `active_era.index`,
@@ -414,6 +1649,9 @@ impl<T: Config> Pallet<T> {
 			});
 			new_index
 		});
+		trace_span!("start_era", era = active_era, session = start_session);
+		// the funds released in the era are no longer exiting.
+		ErasUnbonding::<T>::remove(active_era);
 
 		let bonding_duration = T::BondingDuration::get();
 
@@ -438,20 +1676,87 @@ impl<T: Config> Pallet<T> {
 			}
 		});
 
-		Self::apply_unapplied_slashes(active_era);
+		Self::check_session_validators(active_era, T::SessionInterface::active_validators(), false);
+		// defensive: the slashes of the previous era should long have been applied.
+		if let Some(era) = UnappliedSlashesCursor::<T>::get() {
+			log!(slashing: warn, "slashes of era {:?} still pending at the start of a new era", era);
+			Self::apply_unapplied_slashes(era, u32::MAX);
+		}
+		Self::apply_unapplied_slashes(active_era, T::MaxSlashesPerBlock::get());
+		Self::process_exit_queue();
+		Self::update_min_bonds();
+	}
+
+	/// Recompute the minimum bonds that are set as a share of the total issuance, as per
+	/// [`Config::MinNominatorBondShare`] and [`Config::MinValidatorBondShare`].
+	fn update_min_bonds() {
+		let issuance = T::Currency::total_issuance();
+		if let Some(share) = T::MinNominatorBondShare::get() {
+			MinNominatorBond::<T>::put(share * issuance);
+		}
+		if let Some(share) = T::MinValidatorBondShare::get() {
+			MinValidatorBond::<T>::put(share * issuance);
+		}
+	}
+
+	/// Cross-check the validators elected for `era` against the validators that the session
+	/// pallet reports for it, either as `queued` or as active.
+	///
+	/// A mismatch, e.g. because some elected validators have no session keys, is not fatal but
+	/// degrades block production, hence it is reported through
+	/// [`Event::SessionValidatorsMismatch`].
+	fn check_session_validators(era: EraIndex, mut session: Vec<T::AccountId>, queued: bool) {
+		let mut elected = ErasStakersTotals::<T>::iter_key_prefix(era).collect::<Vec<_>>();
+		if elected.is_empty() {
+			// no exposures, e.g. the initial era without stakers, where session is expected to
+			// choose a sensible validator set.
+			return
+		}
+
+		elected.sort();
+		session.sort();
+		if elected != session {
+			log!(
+				warn,
+				"session {} validators for era {:?} do not match the elected ones ({} elected, {} in session)",
+				if queued { "queued" } else { "active" },
+				era,
+				elected.len(),
+				session.len(),
+			);
+			Self::deposit_event(Event::<T>::SessionValidatorsMismatch {
+				era_index: era,
+				queued,
+				elected: elected.len() as u32,
+				session: session.len() as u32,
+			});
+		}
 	}
 
 	/// Compute payout for era.
 	fn end_era(active_era: ActiveEraInfo, _session_index: SessionIndex) {
+		trace_span!("end_era", era = active_era.index, session = _session_index);
 		// Note: active_era_start can be None if end era is called during genesis config.
 		if let Some(active_era_start) = active_era.start {
 			let now_as_millis_u64 = T::UnixTime::now().as_millis().saturated_into::<u64>();
 
 			let era_duration = (now_as_millis_u64 - active_era_start).saturated_into::<u64>();
+			LastEraDuration::<T>::put(era_duration);
 			let staked = Self::eras_total_stake(&active_era.index);
 			let issuance = T::Currency::total_issuance();
-			let (validator_payout, remainder) =
-				T::EraPayout::era_payout(staked, issuance, era_duration);
+			let performance = EraPerformance {
+				total_reward_points: Self::eras_reward_points(&active_era.index).total,
+				authored_blocks: ErasAuthoredBlocks::<T>::get(&active_era.index),
+			};
+			let (validator_payout, remainder) = T::EraPayout::era_payout_with_performance(
+				staked,
+				issuance,
+				era_duration,
+				performance,
+			);
+			// what was carried over from the last era is paid on top.
+			let validator_payout =
+				validator_payout.saturating_add(CarriedOverRemainder::<T>::take());
 
 			Self::deposit_event(Event::<T>::EraPaid {
 				era_index: active_era.index,
@@ -461,10 +1766,37 @@ impl<T: Config> Pallet<T> {
 
 			// Set ending era reward.
 			<ErasValidatorReward<T>>::insert(&active_era.index, validator_payout);
-			T::RewardRemainder::on_unbalanced(T::Currency::issue(remainder));
+			ErasInflation::<T>::insert(
+				&active_era.index,
+				EraInflation { staked, issuance, validator_payout, remainder },
+			);
+
+			let policy = T::RemainderPolicy::get();
+			// issuing all of it is what `EraPaid` already implies.
+			let report = policy != RemainderPolicy::Issue;
+			let (issued, mut sunk, carried_over, mut burnt) = policy.route(remainder);
+			T::RewardRemainder::on_unbalanced(T::Currency::issue(issued));
+			for (who, amount) in sunk.iter_mut() {
+				// what cannot be deposited, e.g. as it is below the existential deposit, is burnt.
+				let deposited = T::Currency::deposit_creating(who, *amount).peek();
+				burnt = burnt.saturating_add(amount.saturating_sub(deposited));
+				*amount = deposited;
+			}
+			CarriedOverRemainder::<T>::put(carried_over);
+			if report {
+				Self::deposit_event(Event::<T>::RemainderRouted {
+					era_index: active_era.index,
+					issued,
+					sunk,
+					carried_over,
+					burnt,
+				});
+			}
 
-			// Clear offending validators.
+			// Clear offending and replaced validators.
 			<OffendingValidators<T>>::kill();
+			<ReplacedValidators<T>>::kill();
+			<ReplacementsQueued<T>>::kill();
 		}
 	}
 
@@ -483,12 +1815,14 @@ impl<T: Config> Pallet<T> {
 			MaxWinnersOf<T>,
 		>,
 	) -> BoundedVec<T::AccountId, MaxWinnersOf<T>> {
+		trace_span!("trigger_new_era", session = start_session_index, exposures = exposures.len());
 		// Increment or set current era.
 		let new_planned_era = CurrentEra::<T>::mutate(|s| {
 			*s = Some(s.map(|s| s + 1).unwrap_or(0));
 			s.unwrap()
 		});
 		ErasStartSessionIndex::<T>::insert(&new_planned_era, &start_session_index);
+		CurrentEraStartBlock::<T>::put(frame_system::Pallet::<T>::block_number());
 
 		// Clean old era information.
 		if let Some(old_era) = new_planned_era.checked_sub(T::HistoryDepth::get() + 1) {
@@ -496,7 +1830,30 @@ impl<T: Config> Pallet<T> {
 		}
 
 		// Set staking information for the new era.
-		Self::store_stakers_info(exposures, new_planned_era)
+		let elected_stashes = Self::store_stakers_info(exposures, new_planned_era);
+		match T::ValidatorShuffleSeed::get() {
+			Some(seed) => Self::shuffle_validators(elected_stashes, seed, new_planned_era),
+			None => elected_stashes,
+		}
+	}
+
+	/// Shuffle `validators` with randomness derived from `seed` and `era`.
+	///
+	/// This is a Fisher-Yates shuffle, where each swap index is drawn from the hash of `seed`,
+	/// `era` and the position being swapped.
+	pub(crate) fn shuffle_validators(
+		validators: BoundedVec<T::AccountId, MaxWinnersOf<T>>,
+		seed: T::Hash,
+		era: EraIndex,
+	) -> BoundedVec<T::AccountId, MaxWinnersOf<T>> {
+		let mut validators = validators.into_inner();
+		for i in (1..validators.len()).rev() {
+			let hash = (seed, era, i as u32).using_encoded(T::Hashing::hash);
+			let random = u32::decode(&mut TrailingZeroInput::new(hash.as_ref()))
+				.expect("input is padded with zeroes; qed");
+			validators.swap(i, random as usize % (i + 1));
+		}
+		validators.try_into().expect("shuffling does not change the length; qed")
 	}
 
 	/// Potentially plan a new era.
@@ -509,32 +1866,150 @@ impl<T: Config> Pallet<T> {
 		start_session_index: SessionIndex,
 		is_genesis: bool,
 	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
-		let election_result: BoundedVec<_, MaxWinnersOf<T>> = if is_genesis {
-			let result = <T::GenesisElectionProvider>::elect().map_err(|e| {
-				log!(warn, "genesis election provider failed due to {:?}", e);
-				Self::deposit_event(Event::StakingElectionFailed);
-			});
+		trace_span!("try_trigger_new_era", session = start_session_index, is_genesis);
+		let mut runners_up = Vec::new();
+		let (score, exposures) = if !is_genesis && T::ExternalExposures::get() {
+			Self::take_submitted_exposures()?
+		} else {
+			let (election_result, deferrals): (BoundedVec<_, MaxWinnersOf<T>>, _) = if is_genesis {
+				// the genesis stakers are few, and are all taken in.
+				let voters = Self::get_npos_voters(DataProviderBounds::default());
+				let targets = Self::get_npos_targets(DataProviderBounds::default());
+				let result = <T::GenesisElectionProvider>::instant_elect_with_inputs(
+					voters,
+					targets,
+					Self::validator_count(),
+				)
+				.map_err(|e| {
+					log!(election: warn, "genesis election provider failed due to {:?}", e);
+					Self::note_election_failure();
+				});
+
+				let winners = result
+					.ok()?
+					.into_iter()
+					// the backers are re-bounded, truncating them if the genesis bound is larger.
+					.map(|(who, support)| {
+						(who, BoundedSupport::sorted_truncate_from(support.into()).0)
+					})
+					.collect::<Vec<_>>()
+					.try_into()
+					// both bounds checked in integrity test to be equal
+					.defensive_unwrap_or_default();
+				(winners, 0)
+			} else {
+				let deferrals = DeferredElection::<T>::take().map_or(0, |(_, deferrals)| deferrals);
+				if T::DeferOverweightElection::get() && deferrals < T::MaxElectionDeferrals::get() {
+					let estimated_weight = <T::ElectionProvider>::estimate_elect_weight();
+					let remaining_weight = Self::remaining_block_weight();
+					if !estimated_weight.all_lte(remaining_weight) {
+						log!(
+							election: warn,
+							"deferring election, estimated weight {:?} exceeds remaining weight {:?}",
+							estimated_weight,
+							remaining_weight,
+						);
+						let retry_at =
+							frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+						DeferredElection::<T>::put((retry_at, deferrals.saturating_add(1)));
+						Self::deposit_event(Event::ElectionDeferred {
+							estimated_weight,
+							remaining_weight,
+						});
+						return None
+					}
+				}
+
+				let result = <T::ElectionProvider>::elect_with_runners_up(T::MaxRunnersUp::get())
+					.map_err(|e| {
+						log!(election: warn, "election provider failed due to {:?}", e);
+						Self::note_election_failure();
+					});
+				let (winners, others) = result.ok()?;
+				runners_up = others;
+				(winners, deferrals)
+			};
+
+			let score = score_of(election_result.as_slice());
+			if deferrals == 0 || deferrals >= T::MaxElectionDeferrals::get() {
+				(score, Self::collect_exposures(election_result))
+			} else {
+				// a deferred election is retried in `on_initialize`, with the conversion of its
+				// supports spread over the next blocks if the rest of this one does not fit it.
+				let (consumed, exposures) = Self::collect_exposures_paged(
+					Some(election_result),
+					Self::remaining_block_weight(),
+				);
+				Self::register_weight(consumed);
+				match exposures {
+					Some(exposures) => (score, exposures),
+					None => {
+						Self::store_runners_up(runners_up);
+						Self::defer_exposure_collection(deferrals);
+						return None
+					},
+				}
+			}
+		};
+		Self::plan_era(start_session_index, is_genesis, score, exposures, runners_up)
+	}
 
-			result
-				.ok()?
-				.into_inner()
-				.try_into()
-				// both bounds checked in integrity test to be equal
-				.defensive_unwrap_or_default()
+	/// Resume the conversion of the supports of a deferred election, planning the era that starts
+	/// with `start_session_index` once it is done.
+	///
+	/// Once the election was deferred [`Config::MaxElectionDeferrals`] times, the conversion is
+	/// finished regardless of the remaining block weight.
+	fn resume_exposure_collection(
+		start_session_index: SessionIndex,
+	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
+		let deferrals = DeferredElection::<T>::take().map_or(0, |(_, deferrals)| deferrals);
+		let remaining_weight = if deferrals < T::MaxElectionDeferrals::get() {
+			Self::remaining_block_weight()
 		} else {
-			let result = <T::ElectionProvider>::elect().map_err(|e| {
-				log!(warn, "election provider failed due to {:?}", e);
-				Self::deposit_event(Event::StakingElectionFailed);
-			});
-			result.ok()?
+			Weight::MAX
 		};
+		let (consumed, exposures) = Self::collect_exposures_paged(None, remaining_weight);
+		Self::register_weight(consumed);
+		match exposures {
+			Some(exposures) => {
+				let score = Self::score_of_exposures(&exposures);
+				Self::plan_era(start_session_index, false, score, exposures, Vec::new())
+			},
+			None => {
+				Self::defer_exposure_collection(deferrals);
+				None
+			},
+		}
+	}
+
+	/// Retry the conversion of the supports of the deferred election in the next block, counting
+	/// it as another deferral.
+	fn defer_exposure_collection(deferrals: u32) {
+		log!(election: warn, "deferring the collection of the exposures to the next block");
+		let retry_at = frame_system::Pallet::<T>::block_number().saturating_add(One::one());
+		DeferredElection::<T>::put((retry_at, deferrals.saturating_add(1)));
+	}
 
-		let exposures = Self::collect_exposures(election_result);
+	/// Plan the era that starts with `start_session_index` with the elected `exposures`, if they
+	/// are enough.
+	fn plan_era(
+		start_session_index: SessionIndex,
+		is_genesis: bool,
+		score: ElectionScore,
+		exposures: BoundedVec<
+			(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>),
+			MaxWinnersOf<T>,
+		>,
+		runners_up: RunnersUpOf<T::ElectionProvider>,
+	) -> Option<BoundedVec<T::AccountId, MaxWinnersOf<T>>> {
+		// the runners-up substitute the vetoed winners.
+		Self::store_runners_up(runners_up);
+		let exposures = if is_genesis { exposures } else { Self::veto_dead_winners(exposures) };
 		if (exposures.len() as u32) < Self::minimum_validator_count().max(1) {
 			// Session will panic if we ever return an empty validator set, thus max(1) ^^.
 			match CurrentEra::<T>::get() {
 				Some(current_era) if current_era > 0 => log!(
-					warn,
+					election: warn,
 					"chain does not have enough staking candidates to operate for era {:?} ({} \
 					elected, minimum is {})",
 					CurrentEra::<T>::get().unwrap_or(0),
@@ -548,16 +2023,308 @@ impl<T: Config> Pallet<T> {
 					// TODO: this should be simplified #8911
 					CurrentEra::<T>::put(0);
 					ErasStartSessionIndex::<T>::insert(&0, &start_session_index);
+					CurrentEraStartBlock::<T>::put(frame_system::Pallet::<T>::block_number());
 				},
 				_ => (),
 			}
 
-			Self::deposit_event(Event::StakingElectionFailed);
-			return None
+			Self::note_election_failure();
+			return None
+		}
+
+		ConsecutiveElectionFailures::<T>::kill();
+		LastSuccessfulElection::<T>::put(frame_system::Pallet::<T>::block_number());
+		Self::deposit_event(Event::StakersElected { score });
+		Some(Self::trigger_new_era(start_session_index, exposures))
+	}
+
+	/// Store the `runners_up` of the election of the next era in [`ErasRunnersUp`], with the
+	/// exposure they would have had.
+	fn store_runners_up(runners_up: RunnersUpOf<T::ElectionProvider>) {
+		if runners_up.is_empty() {
+			return
+		}
+		let era = CurrentEra::<T>::get().map_or(0, |era| era.saturating_add(1));
+		let total_issuance = T::Currency::total_issuance();
+		let runners_up = runners_up
+			.into_iter()
+			.take(T::MaxRunnersUp::get() as usize)
+			.map(|(who, support)| {
+				let exposure = Self::exposure_of_support(&who, support, total_issuance);
+				(who, exposure)
+			})
+			.collect::<Vec<_>>();
+		Self::register_weight(T::DbWeight::get().writes(1));
+		ErasRunnersUp::<T>::insert(era, BoundedVec::truncate_from(runners_up));
+	}
+
+	/// Replace `stash` for the rest of the active era by the best runner-up of the era that is
+	/// still a validator, with the exposure it had in the election.
+	///
+	/// The substitute is handed to the session at the next new session, and `stash` is disabled
+	/// until it takes its seat. If `stash` offended, it keeps counting towards
+	/// [`Config::OffendingValidatorsThreshold`], but its seat is not disabled for the substitute.
+	///
+	/// Returns the weight consumed.
+	pub(crate) fn do_replace_validator(stash: &T::AccountId) -> Result<Weight, DispatchError> {
+		let era = Self::active_era().map(|era| era.index).ok_or(Error::<T>::NotActiveValidator)?;
+		ensure!(Self::current_era() == Some(era), Error::<T>::NextEraPlanned);
+
+		let validators = T::SessionInterface::validators();
+		let index = validators
+			.iter()
+			.position(|validator| validator == stash)
+			.ok_or(Error::<T>::NotActiveValidator)? as u32;
+		let mut replaced = ReplacedValidators::<T>::get();
+		ensure!(!replaced.iter().any(|(who, _)| who == stash), Error::<T>::NotActiveValidator);
+
+		let mut runners_up = ErasRunnersUp::<T>::get(era);
+		let position = runners_up
+			.iter()
+			.position(|(who, _)| Validators::<T>::contains_key(who) && !validators.contains(who))
+			.ok_or(Error::<T>::NoRunnerUp)?;
+		let (substitute, exposure) = runners_up.remove(position);
+		replaced
+			.try_push((stash.clone(), substitute.clone()))
+			.map_err(|_| Error::<T>::NoRunnerUp)?;
+
+		let substitute_total = exposure.total;
+		let mut weight = Self::store_elected_exposure(era, &substitute, exposure);
+		<ErasTotalStake<T>>::mutate(era, |total| *total = total.saturating_add(substitute_total));
+
+		ErasRunnersUp::<T>::insert(era, runners_up);
+		ReplacedValidators::<T>::put(replaced);
+		ReplacementsQueued::<T>::put(true);
+		OffendingValidators::<T>::mutate(|offending| {
+			if let Ok(position) = offending.binary_search_by_key(&index, |(i, _)| *i) {
+				offending[position].1 = false;
+			}
+		});
+		T::SessionInterface::disable_validator(index);
+		// the eras, the session validators, the replaced validators, the runners-up and whether
+		// they are still validators, the total stake, the offenders and the disabled validators.
+		weight.saturating_accrue(
+			T::DbWeight::get().reads_writes((T::MaxRunnersUp::get() as u64).saturating_add(8), 6),
+		);
+
+		log!(info, "validator {:?} replaced by {:?} in era {:?}", stash, substitute, era);
+		Self::deposit_event(Event::<T>::ValidatorReplaced {
+			era,
+			replaced: stash.clone(),
+			substitute,
+		});
+		Ok(weight)
+	}
+
+	/// Keep the winners known to be dead by [`Config::ValidatorLiveness`] out of the next era, up
+	/// to [`Config::MaxVetoedWinners`] of them.
+	///
+	/// Each vetoed winner is substituted by the best runner-up of the election in
+	/// [`ErasRunnersUp`] that is still a validator, neither elected nor dead, with the exposure it
+	/// had in the election. The substitutes are taken out of the runners-up, and a vetoed winner
+	/// without a substitute is just left out.
+	fn veto_dead_winners(
+		mut exposures: BoundedVec<
+			(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>),
+			MaxWinnersOf<T>,
+		>,
+	) -> BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), MaxWinnersOf<T>> {
+		let max_vetoed = T::MaxVetoedWinners::get();
+		if max_vetoed.is_zero() {
+			return exposures
+		}
+
+		let era = CurrentEra::<T>::get().map_or(0, |era| era.saturating_add(1));
+		let mut runners_up = ErasRunnersUp::<T>::get(era);
+		let mut vetoed = 0u32;
+		let mut index = 0;
+		while index < exposures.len() && vetoed < max_vetoed {
+			if !T::ValidatorLiveness::is_dead(&exposures[index].0) {
+				index += 1;
+				continue
+			}
+			vetoed.saturating_inc();
+
+			let position = runners_up.iter().position(|(who, _)| {
+				Validators::<T>::contains_key(who) &&
+					!T::ValidatorLiveness::is_dead(who) &&
+					!exposures.iter().any(|(winner, _)| winner == who)
+			});
+			let (winner, substitute) = match (position, exposures.get_mut(index)) {
+				(Some(position), Some(seat)) => {
+					index += 1;
+					let runner_up = runners_up.remove(position);
+					let substitute = runner_up.0.clone();
+					(sp_std::mem::replace(seat, runner_up).0, Some(substitute))
+				},
+				_ => (exposures.remove(index).0, None),
+			};
+			log!(election: info, "winner {:?} vetoed for era {:?}", winner, era);
+			Self::deposit_event(Event::<T>::WinnerVetoed { era, vetoed: winner, substitute });
+		}
+
+		// the liveness of the winners, and the runners-up with whether they are still validators.
+		let mut weight = T::DbWeight::get().reads(
+			(exposures.len() as u64)
+				.saturating_add(1)
+				.saturating_add((vetoed as u64).saturating_mul(2 * T::MaxRunnersUp::get() as u64)),
+		);
+		if !vetoed.is_zero() {
+			ErasRunnersUp::<T>::insert(era, runners_up);
+			weight.saturating_accrue(T::DbWeight::get().writes(1));
+		}
+		Self::register_weight(weight);
+		exposures
+	}
+
+	/// The exposures submitted for the next era with [`Call::submit_exposures`], along with their
+	/// score.
+	///
+	/// Counts as a failed election if none were submitted for the next era.
+	fn take_submitted_exposures() -> Option<(
+		ElectionScore,
+		BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), MaxWinnersOf<T>>,
+	)> {
+		let next_era = CurrentEra::<T>::get().map_or(0, |era| era.saturating_add(1));
+		let submitted_for = SubmittedExposuresEra::<T>::take();
+		let exposures = SubmittedExposures::<T>::drain().collect::<Vec<_>>();
+		let count = exposures.len() as u64;
+		Self::register_weight(T::DbWeight::get().reads_writes(2 + count, 1 + count));
+		if submitted_for != Some(next_era) {
+			log!(election: warn, "no exposures submitted for era {:?}", next_era);
+			Self::note_election_failure();
+			return None
+		}
+
+		let exposures = BoundedVec::truncate_from(exposures);
+		Some((Self::score_of_exposures(&exposures), exposures))
+	}
+
+	/// The score of an election electing `exposures`.
+	fn score_of_exposures(
+		exposures: &[(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)],
+	) -> ElectionScore {
+		let total_issuance = T::Currency::total_issuance();
+		let stakes = exposures
+			.iter()
+			.map(|(_, exposure)| {
+				ExtendedBalance::from(T::CurrencyToVote::to_vote(exposure.total, total_issuance))
+			})
+			.collect::<Vec<_>>();
+		ElectionScore {
+			minimal_stake: stakes.iter().min().copied().unwrap_or_default(),
+			sum_stake: stakes.iter().fold(0, |sum, stake| sum.saturating_add(*stake)),
+			sum_stake_squared: stakes
+				.iter()
+				.fold(0, |sum, stake| sum.saturating_add(stake.saturating_mul(*stake))),
+		}
+	}
+
+	/// Check the exposures submitted for `era`, and keep them for when it is planned.
+	pub(super) fn do_submit_exposures(
+		era: EraIndex,
+		exposures: Vec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)>,
+	) -> DispatchResult {
+		ensure!(T::ExternalExposures::get(), Error::<T>::ExternalExposuresDisabled);
+		let next_era = CurrentEra::<T>::get().map_or(0, |era| era.saturating_add(1));
+		ensure!(era == next_era, Error::<T>::InvalidExposures);
+		let exposures: BoundedVec<_, MaxWinnersOf<T>> =
+			exposures.try_into().map_err(|_| Error::<T>::InvalidExposures)?;
+		let max_backers =
+			<T::ElectionProvider as ElectionProviderBase>::MaxBackersPerWinner::get() as usize;
+
+		// the stake each staker backs the validators with, in total.
+		let mut backing = BTreeMap::<T::AccountId, BalanceOf<T>>::new();
+		let mut validators = BTreeSet::new();
+		for (validator, exposure) in exposures.iter() {
+			ensure!(
+				Validators::<T>::contains_key(validator) && validators.insert(validator),
+				Error::<T>::InvalidExposures
+			);
+			ensure!(exposure.others.len() <= max_backers, Error::<T>::InvalidExposures);
+			// each nominator backs the validator once, and only if it nominates it.
+			let mut nominators = BTreeSet::new();
+			ensure!(
+				exposure.others.iter().all(|other| {
+					nominators.insert(&other.who) &&
+						Nominators::<T>::get(&other.who)
+							.map_or(false, |nominations| nominations.targets.contains(validator))
+				}),
+				Error::<T>::InvalidExposures
+			);
+			let others = exposure
+				.others
+				.iter()
+				.fold(BalanceOf::<T>::zero(), |sum, other| sum.saturating_add(other.value));
+			ensure!(
+				exposure.own.saturating_add(others) == exposure.total,
+				Error::<T>::InvalidExposures
+			);
+
+			let stakes = sp_std::iter::once((validator, exposure.own))
+				.chain(exposure.others.iter().map(|other| (&other.who, other.value)));
+			for (who, value) in stakes {
+				let stake = backing.entry(who.clone()).or_insert_with(Zero::zero);
+				*stake = stake.saturating_add(value);
+			}
+		}
+		ensure!(
+			backing.iter().all(|(who, stake)| *stake <= Self::slashable_balance_of(who)),
+			Error::<T>::InvalidExposures
+		);
+
+		let validators = exposures.len() as u32;
+		let _ = SubmittedExposures::<T>::clear(MaxWinnersOf::<T>::get(), None);
+		for (validator, exposure) in exposures {
+			SubmittedExposures::<T>::insert(validator, exposure);
+		}
+		SubmittedExposuresEra::<T>::put(era);
+		Self::deposit_event(Event::<T>::ExposuresSubmitted { era, validators });
+		Ok(())
+	}
+
+	/// Count a failed election, and deposit [`Event::StakingElectionFailed`].
+	fn note_election_failure() {
+		ConsecutiveElectionFailures::<T>::mutate(|failures| *failures = failures.saturating_add(1));
+		Self::deposit_event(Event::StakingElectionFailed);
+	}
+
+	/// The utilization of a snapshot (page) of `size` items taken from a list of `list_count`
+	/// items, with the given bounds.
+	///
+	/// The utilization of the previous pages of the same snapshot, if any, is accumulated.
+	fn snapshot_utilization(
+		previous: Option<SnapshotUtilization>,
+		size: usize,
+		bounds: DataProviderBounds,
+		list_count: u32,
+	) -> SnapshotUtilization {
+		let page_bound = bounds.count.map(|count| count.0);
+		let (size, bound) = match previous {
+			None => (size as u32, page_bound),
+			Some(previous) => (
+				previous.size.saturating_add(size as u32),
+				previous.bound.zip(page_bound).map(|(b, p)| b.saturating_add(p)),
+			),
+		};
+		SnapshotUtilization { size, bound, excluded: list_count.saturating_sub(size) }
+	}
+
+	/// Record the last of `voters` as the [`VoterSnapshotCutoff`], unless the snapshot is
+	/// `complete` or sampled.
+	fn note_voter_snapshot_cutoff(voters: &[VoterOf<Self>], complete: bool) {
+		if complete || T::VoterSamplingSeed::get().is_some() {
+			VoterSnapshotCutoff::<T>::kill();
+		} else if let Some(last) = voters.last() {
+			VoterSnapshotCutoff::<T>::put((last.who.clone(), last.weight));
 		}
+	}
 
-		Self::deposit_event(Event::StakersElected);
-		Some(Self::trigger_new_era(start_session_index, exposures))
+	/// The weight left in the current block before reaching the maximum block weight.
+	fn remaining_block_weight() -> Weight {
+		T::BlockWeights::get()
+			.max_block
+			.saturating_sub(frame_system::Pallet::<T>::block_weight().total())
 	}
 
 	/// Process the output of the election.
@@ -580,31 +2347,35 @@ impl<T: Config> Pallet<T> {
 
 		// Populate stakers, exposures, and the snapshot of validator prefs.
 		let mut total_stake: BalanceOf<T> = Zero::zero();
+		let (mut self_backed, mut nominator_backed) = (0u32, 0u32);
+		// the `ErasTotalStake` of the era, on top of what each elected exposure costs.
+		let mut weight = T::DbWeight::get().writes(1);
 		exposures.into_iter().for_each(|(stash, exposure)| {
 			total_stake = total_stake.saturating_add(exposure.total);
-			<ErasStakers<T>>::insert(new_planned_era, &stash, &exposure);
-
-			let mut exposure_clipped = exposure;
-			let clipped_max_len = T::MaxNominatorRewardedPerValidator::get() as usize;
-			if exposure_clipped.others.len() > clipped_max_len {
-				exposure_clipped.others.sort_by(|a, b| a.value.cmp(&b.value).reverse());
-				exposure_clipped.others.truncate(clipped_max_len);
+			if Self::is_self_backed(&exposure) {
+				self_backed.saturating_inc();
+			} else {
+				nominator_backed.saturating_inc();
 			}
-			<ErasStakersClipped<T>>::insert(&new_planned_era, &stash, exposure_clipped);
+			weight.saturating_accrue(Self::store_elected_exposure(
+				new_planned_era,
+				&stash,
+				exposure,
+			));
 		});
+		Self::register_weight(weight);
 
 		// Insert current era staking information
 		<ErasTotalStake<T>>::insert(&new_planned_era, total_stake);
-
-		// Collect the pref of all winners.
-		for stash in &elected_stashes {
-			let pref = Self::validators(stash);
-			<ErasValidatorPrefs<T>>::insert(&new_planned_era, stash, pref);
-		}
+		Self::deposit_event(Event::<T>::ExposureDominance {
+			era_index: new_planned_era,
+			self_backed,
+			nominator_backed,
+		});
 
 		if new_planned_era > 0 {
 			log!(
-				info,
+				election: info,
 				"new validator set of size {:?} has been processed for era {:?}",
 				elected_stashes.len(),
 				new_planned_era,
@@ -614,43 +2385,259 @@ impl<T: Config> Pallet<T> {
 		elected_stashes
 	}
 
+	/// Store the `exposure` of `stash`, elected in `era`, along with what comes with its election:
+	/// its activity, the loyalty streaks of its nominators and a snapshot of its preferences.
+	///
+	/// Returns the weight consumed.
+	fn store_elected_exposure(
+		era: EraIndex,
+		stash: &T::AccountId,
+		exposure: Exposure<T::AccountId, BalanceOf<T>>,
+	) -> Weight {
+		let nominators = exposure.others.len() as u32;
+		Self::note_activity(era, stash, AccountActivity::ELECTED);
+		let streaks = if T::LoyaltyBonus::get().is_zero() {
+			0
+		} else {
+			// keep counting past `MaxLoyaltyEras`, so that the streak is still exact for all the
+			// eras that can be paid out.
+			let max_streak = T::MaxLoyaltyEras::get().saturating_add(T::HistoryDepth::get());
+			for nominator in exposure.others.iter() {
+				NominatorLoyalty::<T>::mutate(&nominator.who, stash, |streak| {
+					*streak = Some(LoyaltyStreak::extended(*streak, era, max_streak));
+				});
+			}
+			nominators as u64
+		};
+		<ErasValidatorPrefs<T>>::insert(era, stash, Self::validators(stash));
+		Self::store_exposure(era, stash, exposure);
+
+		// the activity, the streaks and the prefs are read, the pages and totals only written.
+		let pages = Self::exposure_page_count(nominators) as u64;
+		T::DbWeight::get().reads_writes(2 + streaks, 3 + streaks + pages)
+	}
+
+	/// The fraction of the `total` backing of a validator that comes from its `own` stash.
+	pub(crate) fn own_exposure_ratio(own: BalanceOf<T>, total: BalanceOf<T>) -> Perbill {
+		Perbill::from_rational(own, total)
+	}
+
+	/// Whether the validator behind `exposure` is predominantly backed by its own stash, i.e. more
+	/// than half of its total backing is self-stake.
+	pub(crate) fn is_self_backed(exposure: &Exposure<T::AccountId, BalanceOf<T>>) -> bool {
+		Self::own_exposure_ratio(exposure.own, exposure.total) > Perbill::from_percent(50)
+	}
+
+	/// The `(own, total, nominator_count)` of the exposure of `validator` in `era`, or zeros if
+	/// it was not exposed.
+	pub fn eras_stakers_totals(
+		era: EraIndex,
+		validator: &T::AccountId,
+	) -> (BalanceOf<T>, BalanceOf<T>, u32) {
+		ErasStakersTotals::<T>::get(era, validator).unwrap_or_default()
+	}
+
+	/// The number of nominators in each page of [`ErasStakersPaged`].
+	pub fn exposure_page_size() -> u32 {
+		ExposurePageSizeOf::<T>::get()
+	}
+
+	/// The number of pages of an exposure with `nominator_count` nominators, which is never zero
+	/// as page 0 pays the validator itself.
+	pub fn exposure_page_count(nominator_count: u32) -> u32 {
+		nominator_count.saturating_sub(1) / Self::exposure_page_size() + 1
+	}
+
+	/// Store the `exposure` of `stash` in `era`, in [`ErasStakersTotals`] and in pages of
+	/// [`ErasStakersPaged`] sorted by decreasing stake.
+	pub fn store_exposure(
+		era: EraIndex,
+		stash: &T::AccountId,
+		mut exposure: Exposure<T::AccountId, BalanceOf<T>>,
+	) {
+		let nominator_count = exposure.others.len() as u32;
+		<ErasStakersTotals<T>>::insert(era, stash, (exposure.own, exposure.total, nominator_count));
+
+		exposure.others.sort_by(|a, b| a.value.cmp(&b.value).reverse());
+		let page_size = Self::exposure_page_size() as usize;
+		for page in 0..Self::exposure_page_count(nominator_count) {
+			let start = (page as usize).saturating_mul(page_size);
+			let others = exposure
+				.others
+				.get(start..start.saturating_add(page_size).min(exposure.others.len()))
+				.unwrap_or_default()
+				.to_vec();
+			let compact = CompactExposure::compress(Exposure {
+				total: exposure.total,
+				own: exposure.own,
+				others,
+			})
+			.defensive_proof("a page holds at most the page size; qed");
+			if let Some(compact) = compact {
+				<ErasStakersPaged<T>>::insert((era, stash, page), compact);
+			}
+		}
+	}
+
+	/// The full exposure of `validator` in `era`, gathered from all its pages, or an empty
+	/// exposure if it was not exposed.
+	pub fn eras_stakers(
+		era: EraIndex,
+		validator: impl Borrow<T::AccountId>,
+	) -> Exposure<T::AccountId, BalanceOf<T>> {
+		let validator = validator.borrow();
+		let (own, total, nominator_count) = match ErasStakersTotals::<T>::get(era, validator) {
+			Some(totals) => totals,
+			None => return Default::default(),
+		};
+		let others = (0..Self::exposure_page_count(nominator_count))
+			.filter_map(|page| Self::eras_stakers_paged(era, validator, page))
+			.flat_map(|page| page.others)
+			.collect();
+		Exposure { total, own, others }
+	}
+
 	/// Consume a set of [`BoundedSupports`] from [`sp_npos_elections`] and collect them into a
 	/// [`Exposure`].
 	fn collect_exposures(
 		supports: BoundedSupportsOf<T::ElectionProvider>,
 	) -> BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), MaxWinnersOf<T>> {
-		let total_issuance = T::Currency::total_issuance();
-		let to_currency = |e: frame_election_provider_support::ExtendedBalance| {
-			T::CurrencyToVote::to_currency(e, total_issuance)
-		};
+		// defensive: the election provider is trusted, but its outcome might be stale, e.g. if
+		// the validator count or set changed while the election was ongoing.
+		Self::register_weight(T::DbWeight::get().reads(1 + supports.len() as u64));
+		if let Err(e) = validate_supports(&supports, Self::validator_count(), |who| {
+			Validators::<T>::contains_key(who)
+		}) {
+			log!(election: warn, "election result failed validation: {:?}", e);
+		}
 
+		let total_issuance = T::Currency::total_issuance();
 		supports
 			.into_iter()
 			.map(|(validator, support)| {
-				// Build `struct exposure` from `support`.
-				let mut others = Vec::with_capacity(support.voters.len());
-				let mut own: BalanceOf<T> = Zero::zero();
-				let mut total: BalanceOf<T> = Zero::zero();
-				support
-					.voters
-					.into_iter()
-					.map(|(nominator, weight)| (nominator, to_currency(weight)))
-					.for_each(|(nominator, stake)| {
-						if nominator == validator {
-							own = own.saturating_add(stake);
-						} else {
-							others.push(IndividualExposure { who: nominator, value: stake });
-						}
-						total = total.saturating_add(stake);
-					});
-
-				let exposure = Exposure { own, others, total };
+				let exposure = Self::exposure_of_support(&validator, support, total_issuance);
 				(validator, exposure)
 			})
 			.try_collect()
 			.expect("we only map through support vector which cannot change the size; qed")
 	}
 
+	/// Same as [`Self::collect_exposures`], but converting the supports into exposures one winner
+	/// at a time, as far as `remaining_weight` allows, so that it can be spread over several
+	/// blocks. Each winner is kept under its own key in [`ExposureCollectionSupports`] until it is
+	/// converted into [`CollectedExposures`], so that a step only reads and writes what it
+	/// converts.
+	///
+	/// `Some(supports)` starts a new collection, discarding any one in progress, while `None`
+	/// resumes the one in [`ExposureCollection`]. Starting a collection always stores the
+	/// supports, regardless of `remaining_weight`. Returns the consumed weight, along with the
+	/// exposures once all the supports are converted.
+	pub fn collect_exposures_paged(
+		supports: Option<BoundedSupportsOf<T::ElectionProvider>>,
+		remaining_weight: Weight,
+	) -> (
+		Weight,
+		Option<BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), MaxWinnersOf<T>>>,
+	) {
+		let db = T::DbWeight::get();
+		// reading and writing the progress.
+		let mut consumed = db.reads_writes(1, 1);
+		let (winners, mut converted) = match supports {
+			Some(supports) => {
+				// a collection in progress is stale once a new one starts.
+				consumed.saturating_accrue(Self::kill_exposure_collection());
+				consumed.saturating_accrue(
+					db.reads_writes(1 + supports.len() as u64, supports.len() as u64),
+				);
+				if let Err(e) = validate_supports(&supports, Self::validator_count(), |who| {
+					Validators::<T>::contains_key(who)
+				}) {
+					log!(election: warn, "election result failed validation: {:?}", e);
+				}
+				let winners = supports.len() as u32;
+				for (index, support) in supports.into_iter().enumerate() {
+					ExposureCollectionSupports::<T>::insert(index as u32, support);
+				}
+				(winners, 0)
+			},
+			None => match ExposureCollection::<T>::get() {
+				Some(progress) => progress,
+				None => return (db.reads(1), None),
+			},
+		};
+
+		// taking the support of a winner, and writing its exposure.
+		let per_winner = db.reads_writes(1, 2);
+		let total_issuance = T::Currency::total_issuance();
+		while converted < winners && !remaining_weight.any_lt(consumed.saturating_add(per_winner)) {
+			consumed.saturating_accrue(per_winner);
+			if let Some((validator, support)) = ExposureCollectionSupports::<T>::take(converted) {
+				let exposure = Self::exposure_of_support(&validator, support, total_issuance);
+				CollectedExposures::<T>::insert(converted, (validator, exposure));
+			}
+			converted.saturating_inc();
+		}
+
+		// taking the exposures of all the winners.
+		let finish = db.reads_writes(winners.into(), winners.into());
+		if converted < winners || remaining_weight.any_lt(consumed.saturating_add(finish)) {
+			ExposureCollection::<T>::put((winners, converted));
+			return (consumed, None)
+		}
+		consumed.saturating_accrue(finish);
+		ExposureCollection::<T>::kill();
+		let exposures = (0..winners)
+			.filter_map(CollectedExposures::<T>::take)
+			.collect::<Vec<_>>()
+			.try_into()
+			.expect("at most as many exposures as supports, which are bounded alike; qed");
+		(consumed, Some(exposures))
+	}
+
+	/// Clear the collection in progress in [`ExposureCollection`], if any. Returns the consumed
+	/// weight.
+	fn kill_exposure_collection() -> Weight {
+		let (winners, converted) = match ExposureCollection::<T>::take() {
+			Some(progress) => progress,
+			None => return Weight::zero(),
+		};
+		for index in 0..winners {
+			if index < converted {
+				CollectedExposures::<T>::remove(index);
+			} else {
+				ExposureCollectionSupports::<T>::remove(index);
+			}
+		}
+		T::DbWeight::get().writes(1 + winners as u64)
+	}
+
+	/// Build the [`Exposure`] of `validator` from its `support`.
+	fn exposure_of_support<Bound: Get<u32>>(
+		validator: &T::AccountId,
+		support: BoundedSupport<T::AccountId, Bound>,
+		total_issuance: BalanceOf<T>,
+	) -> Exposure<T::AccountId, BalanceOf<T>> {
+		let mut others = Vec::with_capacity(support.voters.len());
+		let mut own: BalanceOf<T> = Zero::zero();
+		let mut total: BalanceOf<T> = Zero::zero();
+		support
+			.voters
+			.into_iter()
+			.map(|(nominator, weight)| {
+				(nominator, T::CurrencyToVote::to_currency(weight, total_issuance))
+			})
+			.for_each(|(nominator, stake)| {
+				if nominator == *validator {
+					own = own.saturating_add(stake);
+				} else {
+					others.push(IndividualExposure { who: nominator, value: stake });
+				}
+				total = total.saturating_add(stake);
+			});
+
+		Exposure { own, others, total }
+	}
+
 	/// Remove all associated data of a stash account from the staking system.
 	///
 	/// Assumes storage is upgraded before calling.
@@ -659,49 +2646,364 @@ impl<T: Config> Pallet<T> {
 	/// - after a `withdraw_unbonded()` call that frees all of a stash's bonded balance.
 	/// - through `reap_stash()` if the balance has fallen to zero (through slashing).
 	pub(crate) fn kill_stash(stash: &T::AccountId, num_slashing_spans: u32) -> DispatchResult {
-		let controller = <Bonded<T>>::get(stash).ok_or(Error::<T>::NotStash)?;
+		let controller = Self::try_bonded(stash)?;
 
 		slashing::clear_stash_metadata::<T>(stash, num_slashing_spans)?;
 
 		<Bonded<T>>::remove(stash);
 		<Ledger<T>>::remove(&controller);
+		<StakeOf<T>>::remove(stash);
 
 		<Payee<T>>::remove(stash);
+		PayeeSplits::<T>::remove(stash);
+		CommissionChanges::<T>::remove(stash);
 		Self::do_remove_validator(stash);
 		Self::do_remove_nominator(stash);
+		Status::<T>::remove(stash);
 
 		frame_system::Pallet::<T>::dec_consumers(stash);
 
 		Ok(())
 	}
 
+	/// Migrate the bond, the funds and the role of `old` to `new`, see
+	/// [`Call::migrate_stash`].
+	pub(super) fn do_migrate_stash(
+		controller: &T::AccountId,
+		old: &T::AccountId,
+		new: &T::AccountId,
+	) -> DispatchResult {
+		let old_controller = Self::try_bonded(old)?;
+		ensure!(&old_controller == controller, Error::<T>::NotController);
+		ensure!(
+			!<Bonded<T>>::contains_key(new) && !MigratedStashes::<T>::contains_key(new),
+			Error::<T>::AlreadyBonded
+		);
+		ensure!(!<Ledger<T>>::contains_key(new), Error::<T>::AlreadyPaired);
+		ensure!(!MigratedFrom::<T>::contains_key(old), Error::<T>::StashMigratedRecently);
+		let mut ledger = Self::try_ledger(controller)?;
+
+		let prefs = Validators::<T>::contains_key(old).then(|| Validators::<T>::get(old));
+		let nominations = Nominators::<T>::get(old);
+		// a validator without session keys would drop out of the validator set.
+		ensure!(prefs.is_none() || T::SessionInterface::has_keys(new), Error::<T>::NoSessionKeys);
+		Self::do_remove_validator(old);
+		Self::do_remove_nominator(old);
+
+		// move the funds on hold, so that they are never free to be spent in between, along
+		// with the consumer reference of the bond.
+		let reason = HoldReason::StashMigration.into();
+		T::Currency::remove_lock(STAKING_ID, old);
+		frame_system::Pallet::<T>::dec_consumers(old);
+		T::HoldCurrency::hold(&reason, old, ledger.total)?;
+		T::HoldCurrency::transfer_on_hold(
+			&reason,
+			old,
+			new,
+			ledger.total,
+			Precision::Exact,
+			Restriction::Free,
+			Fortitude::Polite,
+		)?;
+		frame_system::Pallet::<T>::inc_consumers(new).map_err(|_| Error::<T>::BadState)?;
+
+		<Bonded<T>>::remove(old);
+		<Ledger<T>>::remove(controller);
+		<StakeOf<T>>::remove(old);
+		<Bonded<T>>::insert(new, new);
+		ledger.stash = new.clone();
+		Self::update_ledger(new, &ledger);
+
+		<Payee<T>>::insert(new, <Payee<T>>::take(old));
+		if let Some(changes) = CommissionChanges::<T>::take(old) {
+			CommissionChanges::<T>::insert(new, changes);
+		}
+		Status::<T>::remove(old);
+
+		if let Some(prefs) = prefs {
+			Self::do_add_validator(new, prefs);
+			StashMigrationQueue::<T>::insert(old, new);
+		} else if let Some(nominations) = nominations {
+			Self::do_add_nominator(new, nominations);
+		} else {
+			Status::<T>::insert(new, StakerRole::Idle);
+		}
+		Invulnerables::<T>::mutate(|invulnerables| {
+			invulnerables
+				.iter_mut()
+				.filter(|who| *who == old)
+				.for_each(|who| *who = new.clone())
+		});
+
+		let era = Self::current_era().unwrap_or(0);
+		MigratedStashes::<T>::insert(old, (new.clone(), era));
+		MigratedFrom::<T>::insert(new, old);
+		ErasStashMigrations::<T>::insert(era, old, ());
+		Self::deposit_event(Event::<T>::StashMigrated {
+			old_stash: old.clone(),
+			new_stash: new.clone(),
+		});
+		Ok(())
+	}
+
+	/// The stash that now holds the bond of `stash`, if it was migrated through
+	/// [`Pallet::migrate_stash`], see [`MigratedStashes`].
+	///
+	/// This is `stash` itself if it has not been migrated. A stash that was migrated to cannot be
+	/// migrated again while this is kept, so this reads a single entry.
+	pub fn migrated_stash(stash: &T::AccountId) -> T::AccountId {
+		MigratedStashes::<T>::get(stash).map_or_else(|| stash.clone(), |(new, _)| new)
+	}
+
+	/// Point the nominations of the stashes in [`StashMigrationQueue`] to their new stash, one
+	/// at a time, for as many nominators as `remaining_weight` allows.
+	///
+	/// Returns the consumed weight.
+	pub(crate) fn do_migrate_nominations(remaining_weight: Weight) -> Weight {
+		// reading a nominator, and writing it along with the cursor.
+		let per_nominator = T::DbWeight::get().reads_writes(1, 4);
+		let mut consumed = T::DbWeight::get().reads(1);
+		if remaining_weight.any_lt(consumed) {
+			return Weight::zero()
+		}
+		let (old, new, cursor) = match NominationsMigrationCursor::<T>::get() {
+			Some(in_progress) => in_progress,
+			None => {
+				// taking the next migration out of the queue.
+				consumed.saturating_accrue(T::DbWeight::get().reads_writes(1, 2));
+				if remaining_weight.any_lt(consumed) {
+					return T::DbWeight::get().reads(1)
+				}
+				match StashMigrationQueue::<T>::iter().next() {
+					Some((old, new)) => {
+						StashMigrationQueue::<T>::remove(&old);
+						NominationsMigrationCursor::<T>::put((&old, &new, None::<T::AccountId>));
+						(old, new, None)
+					},
+					None => return T::DbWeight::get().reads(2),
+				}
+			},
+		};
+
+		let mut iter = match cursor {
+			Some(cursor) => Nominators::<T>::iter_from(Nominators::<T>::hashed_key_for(cursor)),
+			None => Nominators::<T>::iter(),
+		};
+		loop {
+			if remaining_weight.any_lt(consumed.saturating_add(per_nominator)) {
+				return consumed
+			}
+			consumed.saturating_accrue(per_nominator);
+			let (nominator, mut nominations) = match iter.next() {
+				Some(next) => next,
+				None => break,
+			};
+
+			if let Some(index) = nominations.targets.iter().position(|target| target == &old) {
+				if nominations.targets.contains(&new) {
+					nominations.targets.remove(index);
+				} else if let Some(target) = nominations.targets.get_mut(index) {
+					*target = new.clone();
+				}
+				Nominators::<T>::insert(&nominator, nominations);
+				NominatorsOf::<T>::remove(&old, &nominator);
+				NominatorsOf::<T>::insert(&new, &nominator, ());
+			}
+			NominationsMigrationCursor::<T>::put((&old, &new, Some(&nominator)));
+		}
+
+		NominationsMigrationCursor::<T>::kill();
+		consumed.saturating_accrue(T::DbWeight::get().writes(1));
+		Self::deposit_event(Event::<T>::NominationsMigrated { old_stash: old, new_stash: new });
+		consumed
+	}
+
+	/// Remove the [`NominatorLoyalty`] streaks that ended more than `HISTORY_DEPTH` eras ago, once
+	/// per active era, for as many streaks as `remaining_weight` allows.
+	///
+	/// Such a streak is not accounted for by any era that can still be paid out, and starts over
+	/// if the nominator backs the validator again. Returns the consumed weight.
+	pub(crate) fn do_prune_loyalty(remaining_weight: Weight) -> Weight {
+		// reading a streak, and removing it along with writing the cursor.
+		let per_streak = T::DbWeight::get().reads_writes(1, 2);
+		let mut consumed = T::DbWeight::get().reads(2);
+		if remaining_weight.any_lt(consumed) {
+			return Weight::zero()
+		}
+		let era = match Self::active_era() {
+			Some(era) => era.index,
+			None => return consumed,
+		};
+		let cursor = match LoyaltyPruningCursor::<T>::get() {
+			Some((swept, None)) if swept >= era => return consumed,
+			Some((_, cursor)) => cursor,
+			None => None,
+		};
+
+		let mut iter = match cursor {
+			Some((nominator, validator)) => NominatorLoyalty::<T>::iter_from(
+				NominatorLoyalty::<T>::hashed_key_for(nominator, validator),
+			),
+			None => NominatorLoyalty::<T>::iter(),
+		};
+		let history_depth = T::HistoryDepth::get();
+		loop {
+			if remaining_weight.any_lt(consumed.saturating_add(per_streak)) {
+				return consumed
+			}
+			consumed.saturating_accrue(per_streak);
+			let (nominator, validator, streak) = match iter.next() {
+				Some(next) => next,
+				None => break,
+			};
+
+			if streak.last_era.saturating_add(history_depth) < era {
+				NominatorLoyalty::<T>::remove(&nominator, &validator);
+			}
+			LoyaltyPruningCursor::<T>::put((era, Some((nominator, validator))));
+		}
+
+		LoyaltyPruningCursor::<T>::put((era, None::<(T::AccountId, T::AccountId)>));
+		consumed.saturating_accrue(T::DbWeight::get().writes(1));
+		consumed
+	}
+
+	/// The page `page` of the exposure of `validator` in `era`, out of [`ErasStakersPaged`].
+	pub fn eras_stakers_paged(
+		era: EraIndex,
+		validator: &T::AccountId,
+		page: u32,
+	) -> Option<Exposure<T::AccountId, BalanceOf<T>>> {
+		<ErasStakersPaged<T>>::get((era, validator, page))?.decompress()
+	}
+
 	/// Clear all era information for given era.
 	pub(crate) fn clear_era_information(era_index: EraIndex) {
-		let mut cursor = <ErasStakers<T>>::clear_prefix(era_index, u32::MAX, None);
+		let mut cursor = <ErasStakersPaged<T>>::clear_prefix((era_index,), u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ClaimedRewards<T>>::clear_prefix(era_index, u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <ClaimedNominatorRewards<T>>::clear_prefix((era_index,), u32::MAX, None);
 		debug_assert!(cursor.maybe_cursor.is_none());
-		cursor = <ErasStakersClipped<T>>::clear_prefix(era_index, u32::MAX, None);
+		cursor = <ErasStakersTotals<T>>::clear_prefix(era_index, u32::MAX, None);
 		debug_assert!(cursor.maybe_cursor.is_none());
 		cursor = <ErasValidatorPrefs<T>>::clear_prefix(era_index, u32::MAX, None);
 		debug_assert!(cursor.maybe_cursor.is_none());
+		cursor = <AccountEraActivity<T>>::clear_prefix(era_index, u32::MAX, None);
+		debug_assert!(cursor.maybe_cursor.is_none());
 		<ErasValidatorReward<T>>::remove(era_index);
+		ErasInflation::<T>::remove(era_index);
+		ErasRunnersUp::<T>::remove(era_index);
 		<ErasRewardPoints<T>>::remove(era_index);
+		<ErasAuthoredBlocks<T>>::remove(era_index);
 		<ErasTotalStake<T>>::remove(era_index);
 		ErasStartSessionIndex::<T>::remove(era_index);
+
+		// the exits and the migrations recorded while `exposed_era` was planned are needed until
+		// it can no longer be slashed either.
+		if let Some(exposed_era) = era_index.checked_sub(T::BondingDuration::get()) {
+			for ((nominator, validator), exit) in
+				ErasNominationExits::<T>::drain_prefix(exposed_era)
+			{
+				if NominationExits::<T>::get(&nominator, &validator) == Some(exit) {
+					NominationExits::<T>::remove(&nominator, &validator);
+				}
+			}
+			for (old, ()) in ErasStashMigrations::<T>::drain_prefix(exposed_era) {
+				if let Some((new, _)) = MigratedStashes::<T>::take(&old) {
+					MigratedFrom::<T>::remove(&new);
+				}
+			}
+		}
+	}
+
+	/// The number of eras that slashes are deferred by.
+	pub fn slash_defer_duration() -> EraIndex {
+		SlashDeferDurationOverride::<T>::get().unwrap_or_else(T::SlashDeferDuration::get)
+	}
+
+	/// Set the number of eras that slashes are deferred by, or fall back to
+	/// [`Config::SlashDeferDuration`] if `None`.
+	///
+	/// Deferred slashes are rescheduled so that they are applied `duration` eras after the era of
+	/// their offence, which cannot happen if that is earlier than they are already scheduled for.
+	pub(crate) fn set_slash_defer_duration(maybe_duration: Option<EraIndex>) -> DispatchResult {
+		let old_duration = Self::slash_defer_duration();
+		let new_duration = maybe_duration.unwrap_or_else(T::SlashDeferDuration::get);
+		ensure!(
+			new_duration < Self::slashing_window() || T::BondingDuration::get() == 0,
+			Error::<T>::InvalidSlashDeferDuration
+		);
+
+		if new_duration < old_duration {
+			ensure!(
+				UnappliedSlashes::<T>::iter_keys().next().is_none(),
+				Error::<T>::SlashesPending
+			);
+		} else if new_duration > old_duration {
+			// keep `slash_era = apply_era - duration` valid for the slashes already deferred, but
+			// those being applied.
+			let delay = new_duration - old_duration;
+			let applying = UnappliedSlashesCursor::<T>::get();
+			let pending = UnappliedSlashes::<T>::drain().collect::<Vec<_>>();
+			for (apply_era, id, slash) in pending {
+				let apply_era =
+					if Some(apply_era) == applying { apply_era } else { apply_era + delay };
+				UnappliedSlashes::<T>::insert(apply_era, id, slash);
+			}
+			let pending = PendingSlashes::<T>::drain().collect::<Vec<_>>();
+			for (who, apply_era, total) in pending {
+				let apply_era =
+					if Some(apply_era) == applying { apply_era } else { apply_era + delay };
+				PendingSlashes::<T>::insert(who, apply_era, total);
+			}
+		}
+
+		match maybe_duration {
+			Some(duration) => SlashDeferDurationOverride::<T>::put(duration),
+			None => SlashDeferDurationOverride::<T>::kill(),
+		}
+		Ok(())
 	}
 
 	/// Apply previously-unapplied slashes on the beginning of a new era, after a delay.
-	fn apply_unapplied_slashes(active_era: EraIndex) {
-		let era_slashes = UnappliedSlashes::<T>::take(&active_era);
+	///
+	/// At most `limit` of the slashes deferred to `era` are applied, and the weight consumed is
+	/// returned. If any are left, [`UnappliedSlashesCursor`] points to `era`, such that they are
+	/// applied in the next blocks, see [`Config::MaxSlashesPerBlock`].
+	pub(crate) fn apply_unapplied_slashes(era: EraIndex, limit: u32) -> Weight {
+		let to_apply = UnappliedSlashes::<T>::iter_prefix(&era)
+			.take(limit.max(1) as usize)
+			.collect::<Vec<_>>();
+		trace_span!("apply_unapplied_slashes", era, to_apply = to_apply.len());
 		log!(
-			debug,
-			"found {} slashes scheduled to be executed in era {:?}",
-			era_slashes.len(),
-			active_era,
+			slashing: debug,
+			"applying {} of the slashes scheduled to be executed in era {:?}",
+			to_apply.len(),
+			era,
 		);
-		for slash in era_slashes {
-			let slash_era = active_era.saturating_sub(T::SlashDeferDuration::get());
+
+		let mut weight = T::DbWeight::get().reads_writes(1 + to_apply.len() as u64, 1);
+		let slash_era = era.saturating_sub(Self::slash_defer_duration());
+		for (id, slash) in to_apply {
+			// same estimate as when applying a slash right away, along with the pending slashes.
+			let stakers = 1 + slash.others.len() as u64;
+			let reporters = slash.reporters.len() as u64;
+			weight.saturating_accrue(
+				T::DbWeight::get()
+					.reads_writes(9 * stakers + 2 * reporters, 7 * stakers + 2 * reporters + 1),
+			);
+			UnappliedSlashes::<T>::remove(&era, id);
+			Self::note_pending_slash(era, &slash, false);
 			slashing::apply_slash::<T>(slash, slash_era);
 		}
+
+		if UnappliedSlashes::<T>::iter_key_prefix(&era).next().is_none() {
+			UnappliedSlashesCursor::<T>::kill();
+		} else {
+			UnappliedSlashesCursor::<T>::put(era);
+		}
+		weight
 	}
 
 	/// Add reward points to validators using their stash account ID.
@@ -726,6 +3028,61 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Remove reward points from validators, e.g. for their offences, see
+	/// [`OffencePenaltyTiers`]. The opposite of [`Self::reward_by_ids`].
+	///
+	/// For each element in the iterator the given number of points in u32 is removed from the
+	/// validator, thus duplicates are handled. A validator never loses more points than it has in
+	/// the active era.
+	pub fn penalize_by_ids(validators_points: impl IntoIterator<Item = (T::AccountId, u32)>) {
+		if let Some(active_era) = Self::active_era() {
+			Self::penalize_in_era(active_era.index, validators_points);
+		}
+	}
+
+	/// Remove reward points from validators in `era`, as [`Self::penalize_by_ids`] does in the
+	/// active era.
+	///
+	/// Returns the number of points actually removed.
+	fn penalize_in_era(
+		era: EraIndex,
+		validators_points: impl IntoIterator<Item = (T::AccountId, u32)>,
+	) -> RewardPoint {
+		<ErasRewardPoints<T>>::mutate(era, |era_rewards| {
+			let mut removed: RewardPoint = 0;
+			for (validator, points) in validators_points.into_iter() {
+				if let Some(earned) = era_rewards.individual.get_mut(&validator) {
+					let points = points.min(*earned);
+					*earned -= points;
+					era_rewards.total = era_rewards.total.saturating_sub(points);
+					removed = removed.saturating_add(points);
+				}
+			}
+			removed
+		})
+	}
+
+	/// Whether none of the rewards of `era` have been paid out yet.
+	///
+	/// The points of a paid era are left alone, or the validators paid later would get a larger
+	/// share of the payout than those paid before.
+	fn era_unpaid(era: EraIndex) -> bool {
+		ClaimedRewards::<T>::iter_prefix(era).next().is_none()
+	}
+
+	/// The era points that an offence of `slash_fraction` costs, as of the first of the `tiers`
+	/// that covers it.
+	fn offence_penalty(
+		slash_fraction: Perbill,
+		tiers: &[(Perbill, RewardPoint)],
+	) -> Option<RewardPoint> {
+		tiers
+			.iter()
+			.find(|(max_fraction, _)| slash_fraction <= *max_fraction)
+			.map(|(_, points)| *points)
+			.filter(|points| !points.is_zero())
+	}
+
 	/// Helper to set a new `ForceEra` mode.
 	pub(crate) fn set_force_era(mode: Forcing) {
 		log!(info, "Setting force era mode {:?}.", mode);
@@ -747,12 +3104,18 @@ impl<T: Config> Pallet<T> {
 		stash: T::AccountId,
 		exposure: Exposure<T::AccountId, BalanceOf<T>>,
 	) {
-		<ErasStakers<T>>::insert(&current_era, &stash, &exposure);
+		Setup::<T>::new()
+			.era_stakers(current_era, stash, exposure)
+			.build()
+			.expect("exposures need no conversion; qed");
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	pub fn set_slash_reward_fraction(fraction: Perbill) {
-		SlashRewardFraction::<T>::put(fraction);
+		Setup::<T>::new()
+			.slash_reward_fraction(fraction)
+			.build()
+			.expect("fractions need no conversion; qed");
 	}
 
 	/// Get all of the voters that are eligible for the npos election.
@@ -764,121 +3127,237 @@ impl<T: Config> Pallet<T> {
 	///
 	/// This function is self-weighing as [`DispatchClass::Mandatory`].
 	pub fn get_npos_voters(bounds: DataProviderBounds) -> Vec<VoterOf<Self>> {
-		let mut voters_size_tracker: StaticTracker<Self> = StaticTracker::default();
+		Self::npos_voters_iter(bounds).collect()
+	}
 
+	/// Reorder the top of [`Config::VoterList`] so that nominators are sampled with a probability
+	/// proportional to their stake, using `seed`.
+	///
+	/// Among the voters that [`Self::npos_voters_from`] would consider, all the validators and a
+	/// sample of the nominators, as large as the room left by the validators in `bounds`, come
+	/// first, in their original order. The other voters follow, to fill any room left. If all the
+	/// considered voters fit in `bounds`, their order is unchanged.
+	///
+	/// The sampling is systematic: the stakes of the nominators are laid end to end, and picked
+	/// at evenly spaced points, shifted by an offset derived from `seed` and the current era. A
+	/// nominator is thus picked with a probability proportional to its stake, up to one.
+	fn stake_weighted_voters(
+		bounds: DataProviderBounds,
+		seed: T::Hash,
+	) -> Box<dyn Iterator<Item = T::AccountId>> {
 		let final_predicted_len = {
 			let all_voter_count = T::VoterList::count();
 			bounds.count.unwrap_or(all_voter_count.into()).min(all_voter_count.into()).0
 		};
 
-		let mut all_voters = Vec::<_>::with_capacity(final_predicted_len as usize);
+		let considered = T::VoterList::iter()
+			.take((NPOS_MAX_ITERATIONS_COEFFICIENT * final_predicted_len) as usize)
+			.collect::<Vec<_>>();
+		if considered.len() <= final_predicted_len as usize {
+			return Box::new(considered.into_iter())
+		}
 
-		// cache a few things.
+		// `None` for validators, which are never left out of the sample.
 		let weight_of = Self::weight_of_fn();
+		let stakes = considered
+			.iter()
+			.map(|voter| (!Validators::<T>::contains_key(voter)).then(|| weight_of(voter)))
+			.collect::<Vec<_>>();
+		Self::register_weight(T::DbWeight::get().reads(2 * considered.len() as u64));
+
+		let validators = stakes.iter().filter(|stake| stake.is_none()).count() as u32;
+		let sample_len = final_predicted_len.saturating_sub(validators).max(1);
+		let total_stake = stakes.iter().flatten().map(|stake| *stake as u128).sum::<u128>();
+		let step = (total_stake / sample_len as u128).max(1);
+		let offset = {
+			let subject = (seed, Self::current_era().unwrap_or_default());
+			let hash = subject.using_encoded(T::Hashing::hash);
+			u128::decode(&mut TrailingZeroInput::new(hash.as_ref()))
+				.expect("input is padded with zeroes; qed") %
+				step
+		};
 
-		let mut voters_seen = 0u32;
-		let mut validators_taken = 0u32;
-		let mut nominators_taken = 0u32;
-		let mut min_active_stake = u64::MAX;
-
-		let mut sorted_voters = T::VoterList::iter();
-		while all_voters.len() < final_predicted_len as usize &&
-			voters_seen < (NPOS_MAX_ITERATIONS_COEFFICIENT * final_predicted_len as u32)
-		{
-			let voter = match sorted_voters.next() {
-				Some(voter) => {
-					voters_seen.saturating_inc();
-					voter
-				},
-				None => break,
-			};
-
-			let voter_weight = weight_of(&voter);
-			// if voter weight is zero, do not consider this voter for the snapshot.
-			if voter_weight.is_zero() {
-				log!(debug, "voter's active balance is 0. skip this voter.");
-				continue
-			}
-
-			if let Some(Nominations { targets, .. }) = <Nominators<T>>::get(&voter) {
-				if !targets.is_empty() {
-					// Note on lazy nomination quota: we do not check the nomination quota of the
-					// voter at this point and accept all the current nominations. The nomination
-					// quota is only enforced at `nominate` time.
-
-					let voter = (voter, voter_weight, targets);
-					if voters_size_tracker.try_register_voter(&voter, &bounds).is_err() {
-						// no more space left for the election result, stop iterating.
-						Self::deposit_event(Event::<T>::SnapshotVotersSizeExceeded {
-							size: voters_size_tracker.size as u32,
-						});
-						break
+		let mut picked = Vec::with_capacity(final_predicted_len as usize);
+		let mut others = Vec::with_capacity(considered.len());
+		let mut next_point = offset;
+		let mut line_len = 0u128;
+		for (voter, maybe_stake) in considered.into_iter().zip(stakes) {
+			let is_picked = match maybe_stake {
+				None => true,
+				Some(stake) => {
+					line_len = line_len.saturating_add(stake as u128);
+					let is_picked = next_point < line_len;
+					while next_point < line_len {
+						next_point = next_point.saturating_add(step);
 					}
-
-					all_voters.push(voter);
-					nominators_taken.saturating_inc();
-				} else {
-					// technically should never happen, but not much we can do about it.
-				}
-				min_active_stake =
-					if voter_weight < min_active_stake { voter_weight } else { min_active_stake };
-			} else if Validators::<T>::contains_key(&voter) {
-				// if this voter is a validator:
-				let self_vote = (
-					voter.clone(),
-					voter_weight,
-					vec![voter.clone()]
-						.try_into()
-						.expect("`MaxVotesPerVoter` must be greater than or equal to 1"),
-				);
-
-				if voters_size_tracker.try_register_voter(&self_vote, &bounds).is_err() {
-					// no more space left for the election snapshot, stop iterating.
-					Self::deposit_event(Event::<T>::SnapshotVotersSizeExceeded {
-						size: voters_size_tracker.size as u32,
-					});
-					break
-				}
-				all_voters.push(self_vote);
-				validators_taken.saturating_inc();
-			} else {
-				// this can only happen if: 1. there a bug in the bags-list (or whatever is the
-				// sorted list) logic and the state of the two pallets is no longer compatible, or
-				// because the nominators is not decodable since they have more nomination than
-				// `T::NominationsQuota::get_quota`. The latter can rarely happen, and is not
-				// really an emergency or bug if it does.
-				defensive!(
-				    "DEFENSIVE: invalid item in `VoterList`: {:?}, this nominator probably has too many nominations now",
-                    voter,
-                );
+					is_picked
+				},
+			};
+			if is_picked {
+				picked.push(voter);
+			} else {
+				others.push(voter);
 			}
 		}
 
+		log!(
+			election: debug,
+			"sampled {} npos voters out of {}, with a stake step of {}",
+			picked.len(),
+			picked.len() + others.len(),
+			step,
+		);
+
+		Box::new(picked.into_iter().chain(others))
+	}
+
+	/// Lazily iterate over the voters that are eligible for the npos election, within `bounds`.
+	///
+	/// Yields the same voters as [`Self::get_npos_voters`], and sets `MinimumActiveStake` in the
+	/// same way once it is consumed to the end.
+	///
+	/// This function is self-weighing as [`DispatchClass::Mandatory`]: the weight of each voter is
+	/// registered as it is produced.
+	pub fn npos_voters_iter(bounds: DataProviderBounds) -> Box<dyn Iterator<Item = VoterOf<Self>>> {
+		let sorted_voters = match T::VoterSamplingSeed::get() {
+			Some(seed) => Self::stake_weighted_voters(bounds, seed),
+			None => T::VoterList::iter(),
+		};
+		Box::new(NposVoters::<T>::new(bounds, sorted_voters).setting_min_active_stake())
+	}
+
+	/// Get the voters that are eligible for the npos election among `sorted_voters`, within
+	/// `bounds`, along with the minimum active nominator stake among them.
+	///
+	/// This function is self-weighing as [`DispatchClass::Mandatory`].
+	fn npos_voters_from(
+		bounds: DataProviderBounds,
+		sorted_voters: Box<dyn Iterator<Item = T::AccountId>>,
+	) -> (SnapshotPage<T, VoterOf<Self>>, BalanceOf<T>) {
+		let mut voters = NposVoters::<T>::new(bounds, sorted_voters);
+		let mut all_voters = Vec::<_>::with_capacity(voters.final_predicted_len as usize);
+		all_voters.extend(voters.by_ref());
+
 		// all_voters should have not re-allocated.
-		debug_assert!(all_voters.capacity() == final_predicted_len as usize);
+		debug_assert!(all_voters.capacity() == voters.final_predicted_len as usize);
 
-		Self::register_weight(T::WeightInfo::get_npos_voters(validators_taken, nominators_taken));
+		let page = SnapshotPage {
+			items: all_voters,
+			last_seen: if voters.exhausted { None } else { voters.last_seen.take() },
+		};
+		(page, voters.min_active_stake())
+	}
 
-		let min_active_stake: T::CurrencyBalance =
-			if all_voters.is_empty() { Zero::zero() } else { min_active_stake.into() };
+	/// Ensure that a snapshot within `bounds`, out of a list of `list_count` items, does not read
+	/// more than [`Config::AbsoluteMaxSnapshot`] items of the list.
+	///
+	/// A snapshot may read up to `NPOS_MAX_ITERATIONS_COEFFICIENT` times as many items as it
+	/// returns, so this is checked before reading any of them: an over-sized snapshot is then a
+	/// recoverable election failure rather than running out of memory or time halfway.
+	fn ensure_snapshot_within_cap(
+		list_count: u32,
+		bounds: DataProviderBounds,
+	) -> data_provider::Result<()> {
+		let cap = match T::AbsoluteMaxSnapshot::get() {
+			Some(cap) => cap,
+			None => return Ok(()),
+		};
+		let predicted_len = bounds.count.map_or(list_count, |count| count.0.min(list_count));
+		let max_reads =
+			NPOS_MAX_ITERATIONS_COEFFICIENT.saturating_mul(predicted_len).min(list_count);
+		if max_reads > cap {
+			log!(
+				election: error,
+				"snapshot may read {} items, more than the absolute maximum of {}",
+				max_reads,
+				cap,
+			);
+			return Err("Snapshot exceeds the absolute maximum number of reads")
+		}
+		Ok(())
+	}
 
-		MinimumActiveStake::<T>::put(min_active_stake);
+	/// Ensure that `bounds` fit a target snapshot of all the current validators, and a voter
+	/// snapshot of all the current validators and `nominators_multiple` times the current
+	/// nominators.
+	///
+	/// Nominators are assumed to use all of their [`MaxNominationsOf`] votes. Meant to catch,
+	/// before a runtime upgrade, bounds that would silently truncate the next elections. See
+	/// [`crate::migrations::CheckSnapshotBounds`].
+	pub fn ensure_snapshot_bounds_fit(
+		bounds: ElectionBounds,
+		nominators_multiple: FixedU128,
+	) -> Result<(), &'static str> {
+		let validators = Validators::<T>::count();
+		let nominators = nominators_multiple.saturating_mul_int(Nominators::<T>::count());
+
+		let who = T::AccountId::decode(&mut TrailingZeroInput::zeroes())
+			.map_err(|_| "Account ids can not be decoded from zeroes")?;
+		let targets_size = StaticTracker::<Self>::targets_byte_size_of(&who, validators as usize);
+		let self_vote = BoundedVec::truncate_from(vec![who.clone()]);
+		let votes =
+			BoundedVec::truncate_from(vec![who.clone(); MaxNominationsOf::<T>::get() as usize]);
+		let voters_size = StaticTracker::<Self>::voters_byte_size_of(&[
+			(Voter::new(who.clone(), 0, self_vote), validators as usize),
+			(Voter::new(who, 0, votes), nominators as usize),
+		]);
+
+		bounds.ensure_voters_limits(
+			CountBound(validators.saturating_add(nominators)),
+			SizeBound(voters_size.saturated_into()),
+		)?;
+		bounds
+			.ensure_targets_limits(CountBound(validators), SizeBound(targets_size.saturated_into()))
+	}
 
-		log!(
-			info,
-			"generated {} npos voters, {} from validators and {} nominators",
-			all_voters.len(),
-			validators_taken,
-			nominators_taken
-		);
+	/// Drop the last items of a snapshot until it fits in `bounds`, and return how many were
+	/// dropped. If any were, the event built by `truncated_event` out of their number is
+	/// deposited.
+	///
+	/// Snapshots are built within their bounds, so this should never drop anything. It is kept as
+	/// a safety measure, so that a bug in the snapshot logic does not result in an over-sized
+	/// snapshot in production.
+	pub(crate) fn truncate_snapshot_to_bounds<I: Encode>(
+		items: &mut Vec<I>,
+		bounds: DataProviderBounds,
+		truncated_event: impl FnOnce(u32) -> Event<T>,
+	) -> u32 {
+		let initial_len = items.len();
+		if let Some(count) = bounds.count {
+			items.truncate(count.0 as usize);
+		}
 
-		all_voters
+		// the length prefix can only shrink as items are dropped, so the size is over-estimated.
+		let mut size = items.encoded_size();
+		while bounds.size_exhausted(SizeBound(size as u32)) {
+			match items.pop() {
+				Some(item) => size = size.saturating_sub(item.encoded_size()),
+				None => break,
+			}
+		}
+
+		let removed = initial_len.saturating_sub(items.len()) as u32;
+		if removed > 0 {
+			log!(election: error, "snapshot exceeded its bounds, {} items were dropped", removed);
+			Self::deposit_event(truncated_event(removed));
+		}
+		removed
 	}
 
 	/// Get the targets for an upcoming npos election.
 	///
 	/// This function is self-weighing as [`DispatchClass::Mandatory`].
 	pub fn get_npos_targets(bounds: DataProviderBounds) -> Vec<T::AccountId> {
+		Self::npos_targets_from(bounds, T::TargetList::iter()).items
+	}
+
+	/// Get the targets for an upcoming npos election among `targets_iter`, within `bounds`.
+	///
+	/// This function is self-weighing as [`DispatchClass::Mandatory`].
+	fn npos_targets_from(
+		bounds: DataProviderBounds,
+		mut targets_iter: Box<dyn Iterator<Item = T::AccountId>>,
+	) -> SnapshotPage<T, T::AccountId> {
 		let mut targets_size_tracker: StaticTracker<Self> = StaticTracker::default();
 
 		let final_predicted_len = {
@@ -888,8 +3367,9 @@ impl<T: Config> Pallet<T> {
 
 		let mut all_targets = Vec::<T::AccountId>::with_capacity(final_predicted_len as usize);
 		let mut targets_seen = 0;
+		let mut last_seen = None;
+		let mut exhausted = false;
 
-		let mut targets_iter = T::TargetList::iter();
 		while all_targets.len() < final_predicted_len as usize &&
 			targets_seen < (NPOS_MAX_ITERATIONS_COEFFICIENT * final_predicted_len as u32)
 		{
@@ -898,9 +3378,20 @@ impl<T: Config> Pallet<T> {
 					targets_seen.saturating_inc();
 					target
 				},
-				None => break,
+				None => {
+					exhausted = true;
+					break
+				},
 			};
 
+			let weight_after = T::WeightInfo::get_npos_targets(all_targets.len() as u32 + 1);
+			if bounds.weight_exhausted(weight_after) {
+				// no more weight left for the election snapshot, stop iterating.
+				Self::deposit_event(Event::<T>::SnapshotTargetsWeightExceeded {
+					weight: T::WeightInfo::get_npos_targets(all_targets.len() as u32),
+				});
+				break
+			}
 			if targets_size_tracker.try_register_target(target.clone(), &bounds).is_err() {
 				// no more space left for the election snapshot, stop iterating.
 				Self::deposit_event(Event::<T>::SnapshotTargetsSizeExceeded {
@@ -909,15 +3400,20 @@ impl<T: Config> Pallet<T> {
 				break
 			}
 
-			if Validators::<T>::contains_key(&target) {
+			last_seen = Some(target.clone());
+			if Validators::<T>::contains_key(&target) && !Self::is_waitlisted(&target) {
 				all_targets.push(target);
 			}
 		}
 
 		Self::register_weight(T::WeightInfo::get_npos_targets(all_targets.len() as u32));
-		log!(info, "generated {} npos targets", all_targets.len());
+		// the waitlist is checked, and possibly cleaned, for each target.
+		Self::register_weight(
+			T::DbWeight::get().reads_writes(targets_seen.into(), targets_seen.into()),
+		);
+		log!(election: info, "generated {} npos targets", all_targets.len());
 
-		all_targets
+		SnapshotPage { items: all_targets, last_seen: if exhausted { None } else { last_seen } }
 	}
 
 	/// This function will add a nominator to the `Nominators` storage map,
@@ -929,12 +3425,18 @@ impl<T: Config> Pallet<T> {
 	/// to `Nominators` or `VoterList` outside of this function is almost certainly
 	/// wrong.
 	pub fn do_add_nominator(who: &T::AccountId, nominations: Nominations<T>) {
-		if !Nominators::<T>::contains_key(who) {
-			// maybe update sorted list.
-			let _ = T::VoterList::on_insert(who.clone(), Self::weight_of(who))
-				.defensive_unwrap_or_default();
-		}
+		let old_targets = match Nominators::<T>::get(who) {
+			Some(old) => old.targets.into_inner(),
+			None => {
+				// maybe update sorted list.
+				let _ = T::VoterList::on_insert(who.clone(), Self::weight_of(who))
+					.defensive_unwrap_or_default();
+				Vec::new()
+			},
+		};
+		Self::note_nominated_targets(who, &old_targets, &nominations.targets);
 		Nominators::<T>::insert(who, nominations);
+		Status::<T>::insert(who, StakerRole::Nominator);
 
 		debug_assert_eq!(
 			Nominators::<T>::count() + Validators::<T>::count(),
@@ -951,9 +3453,10 @@ impl<T: Config> Pallet<T> {
 	/// `Nominators` or `VoterList` outside of this function is almost certainly
 	/// wrong.
 	pub fn do_remove_nominator(who: &T::AccountId) -> bool {
-		let outcome = if Nominators::<T>::contains_key(who) {
-			Nominators::<T>::remove(who);
+		let outcome = if let Some(nominations) = Nominators::<T>::take(who) {
+			Self::note_nominated_targets(who, &nominations.targets, &[]);
 			let _ = T::VoterList::on_remove(who).defensive();
+			Status::<T>::insert(who, StakerRole::Idle);
 			true
 		} else {
 			false
@@ -967,6 +3470,17 @@ impl<T: Config> Pallet<T> {
 		outcome
 	}
 
+	/// Keep [`NominatorsOf`] in line with `who` changing its nominations from the `old` targets
+	/// to the `new` ones.
+	fn note_nominated_targets(who: &T::AccountId, old: &[T::AccountId], new: &[T::AccountId]) {
+		for target in old.iter().filter(|target| !new.contains(target)) {
+			NominatorsOf::<T>::remove(target, who);
+		}
+		for target in new.iter().filter(|target| !old.contains(target)) {
+			NominatorsOf::<T>::insert(target, who, ());
+		}
+	}
+
 	/// This function will add a validator to the `Validators` storage map.
 	///
 	/// If the validator already exists, their preferences will be updated.
@@ -976,16 +3490,20 @@ impl<T: Config> Pallet<T> {
 	/// wrong.
 	pub fn do_add_validator(who: &T::AccountId, prefs: ValidatorPrefs) {
 		if !Validators::<T>::contains_key(who) {
-			// maybe update sorted list.
+			// maybe update sorted lists.
 			let _ = T::VoterList::on_insert(who.clone(), Self::weight_of(who))
 				.defensive_unwrap_or_default();
+			let _ = T::TargetList::on_insert(who.clone(), Self::slashable_balance_of(who))
+				.defensive_unwrap_or_default();
 		}
 		Validators::<T>::insert(who, prefs);
+		Status::<T>::insert(who, StakerRole::Validator);
 
 		debug_assert_eq!(
 			Nominators::<T>::count() + Validators::<T>::count(),
 			T::VoterList::count()
 		);
+		debug_assert_eq!(Validators::<T>::count(), T::TargetList::count());
 	}
 
 	/// This function will remove a validator from the `Validators` storage map.
@@ -999,6 +3517,10 @@ impl<T: Config> Pallet<T> {
 		let outcome = if Validators::<T>::contains_key(who) {
 			Validators::<T>::remove(who);
 			let _ = T::VoterList::on_remove(who).defensive();
+			let _ = T::TargetList::on_remove(who).defensive();
+			Self::dequeue_exit(who);
+			ValidatorWaitlist::<T>::remove(who);
+			Status::<T>::insert(who, StakerRole::Idle);
 			true
 		} else {
 			false
@@ -1008,18 +3530,16 @@ impl<T: Config> Pallet<T> {
 			Nominators::<T>::count() + Validators::<T>::count(),
 			T::VoterList::count()
 		);
+		debug_assert_eq!(Validators::<T>::count(), T::TargetList::count());
 
 		outcome
 	}
 
-	/// Register some amount of weight directly with the system pallet.
+	/// Register some amount of weight with [`Config::WeightAccountant`].
 	///
 	/// This is always mandatory weight.
 	fn register_weight(weight: Weight) {
-		<frame_system::Pallet<T>>::register_extra_weight_unchecked(
-			weight,
-			DispatchClass::Mandatory,
-		);
+		T::WeightAccountant::register_weight(weight);
 	}
 }
 
@@ -1030,6 +3550,163 @@ impl<T: Config> Pallet<T> {
 	pub fn api_nominations_quota(balance: BalanceOf<T>) -> u32 {
 		T::NominationsQuota::get_quota(balance)
 	}
+
+	/// Returns the role currently played by `who`, or `None` if it is not a bonded stash.
+	///
+	/// Used by the runtime API.
+	pub fn api_staker_status(who: T::AccountId) -> Option<StakerRole> {
+		Status::<T>::get(who)
+	}
+
+	/// Whether `nominator` dropped `validator` through [`Pallet::drop_nomination`] before `era`,
+	/// in which case it is neither rewarded nor slashed for its exposure to `validator` in `era`.
+	pub(crate) fn dropped_before(
+		nominator: &T::AccountId,
+		validator: &T::AccountId,
+		era: EraIndex,
+	) -> bool {
+		NominationExits::<T>::get(nominator, validator).map_or(false, |exit| exit < era)
+	}
+
+	/// Returns the era after which `nominator` is no longer exposed to the slashes of
+	/// `validator`, if it dropped it through [`Pallet::drop_nomination`].
+	///
+	/// Used by the runtime API.
+	pub fn api_nomination_exit(
+		nominator: T::AccountId,
+		validator: T::AccountId,
+	) -> Option<EraIndex> {
+		NominationExits::<T>::get(nominator, validator)
+	}
+
+	/// Returns the oldest era whose rewards can still be paid out, if any era has been planned.
+	///
+	/// Used by the runtime API.
+	pub fn api_oldest_claimable_era() -> Option<EraIndex> {
+		Self::current_era().map(|era| era.saturating_sub(T::HistoryDepth::get()))
+	}
+
+	/// Returns a summary of the health of the elections.
+	///
+	/// Used by the runtime API.
+	pub fn api_election_health_report() -> ElectionHealthReport<BlockNumberFor<T>> {
+		let last_success = LastSuccessfulElection::<T>::get();
+		ElectionHealthReport {
+			voters: LastVoterSnapshot::<T>::get(),
+			targets: LastTargetSnapshot::<T>::get(),
+			consecutive_failures: ConsecutiveElectionFailures::<T>::get(),
+			last_success,
+			blocks_since_last_success: last_success
+				.map(|at| frame_system::Pallet::<T>::block_number().saturating_sub(at)),
+			lists_consistent: T::VoterList::count() ==
+				Nominators::<T>::count() + Validators::<T>::count() &&
+				T::TargetList::count() == Validators::<T>::count(),
+		}
+	}
+
+	/// Returns the inflation of the eras still in [`ErasInflation`], oldest first.
+	///
+	/// Used by the runtime API.
+	pub fn api_eras_inflation() -> Vec<(EraIndex, EraInflation<BalanceOf<T>>)> {
+		let mut inflation = ErasInflation::<T>::iter().collect::<Vec<_>>();
+		inflation.sort_by_key(|(era, _)| *era);
+		inflation
+	}
+
+	/// Returns the last voter included in the last voter snapshot, with its vote weight, if the
+	/// snapshot left some voters out.
+	///
+	/// Used by the runtime API.
+	pub fn api_inclusion_cutoff() -> Option<(T::AccountId, VoteWeight)> {
+		VoterSnapshotCutoff::<T>::get()
+	}
+
+	/// Returns whether `who` made it into the last voter snapshot, going by its score in
+	/// [`Config::VoterList`] against the vote weight of the [`VoterSnapshotCutoff`].
+	///
+	/// Voters tied with the cutoff are reported as included, and so are voters whose score grew
+	/// past it since the snapshot was taken.
+	///
+	/// Used by the runtime API.
+	pub fn api_was_included(who: T::AccountId) -> bool {
+		if !T::VoterList::contains(&who) {
+			return false
+		}
+		match VoterSnapshotCutoff::<T>::get() {
+			Some((cutoff, cutoff_weight)) =>
+				who == cutoff ||
+					T::VoterList::get_score(&who).map_or(false, |score| score >= cutoff_weight),
+			None => true,
+		}
+	}
+
+	/// Returns the projected rewards of `stash` in each of the next `eras_ahead` eras, out of
+	/// [`Config::EraPayout`].
+	///
+	/// Assumes constant conditions: the exposures and commissions of the active era, eras as long
+	/// as the last one, reward points evenly spread across the validators, and the total issuance
+	/// only growing by the payouts. Rewards are pro rata to the stake, without any loyalty or
+	/// nominator bonus. Empty until an era has ended. At most [`Config::HistoryDepth`] eras are
+	/// projected.
+	///
+	/// Used by the runtime API.
+	pub fn api_project_rewards(stash: T::AccountId, eras_ahead: u32) -> Vec<BalanceOf<T>> {
+		let eras_ahead = eras_ahead.min(T::HistoryDepth::get());
+		let (era, era_duration) = match (Self::active_era(), LastEraDuration::<T>::get()) {
+			(Some(era), Some(duration)) => (era.index, duration),
+			_ => return Vec::new(),
+		};
+		let mut validators = 0u32;
+		// the commission of each validator `stash` is exposed to, whether it is `stash` itself,
+		// and the part of what is left after the commission that goes to `stash`.
+		let parts = ErasStakersTotals::<T>::iter_key_prefix(era)
+			.map(|validator| {
+				let exposure = Self::eras_stakers(era, &validator);
+				(validator, exposure)
+			})
+			.filter_map(|(validator, exposure)| {
+				validators.saturating_inc();
+				let own = if validator == stash { exposure.own } else { Zero::zero() };
+				let stake = exposure
+					.others
+					.iter()
+					.filter(|other| other.who == stash)
+					.fold(own, |stake, other| stake.saturating_add(other.value));
+				(validator == stash || !stake.is_zero()).then(|| {
+					let commission = Self::eras_validator_prefs(era, &validator).commission;
+					(commission, validator == stash, Perbill::from_rational(stake, exposure.total))
+				})
+			})
+			.collect::<Vec<_>>();
+
+		let staked = Self::eras_total_stake(era);
+		let mut issuance = T::Currency::total_issuance();
+		(0..eras_ahead)
+			.map(|_| {
+				let (validator_payout, _) =
+					T::EraPayout::era_payout(staked, issuance, era_duration);
+				issuance = issuance.saturating_add(validator_payout);
+				let validator_total_payout = validator_payout / validators.max(1).into();
+				parts
+					.iter()
+					.fold(Zero::zero(), |reward: BalanceOf<T>, (commission, own, part)| {
+						let commission_payout = *commission * validator_total_payout;
+						let leftover = validator_total_payout.saturating_sub(commission_payout);
+						let commission_payout = if *own { commission_payout } else { Zero::zero() };
+						reward.saturating_add(commission_payout).saturating_add(*part * leftover)
+					})
+			})
+			.collect()
+	}
+
+	/// Returns the ratio of own stake to total backing of each validator exposed in `era`.
+	///
+	/// Used by the runtime API.
+	pub fn api_exposure_own_ratios(era: EraIndex) -> Vec<(T::AccountId, Perbill)> {
+		ErasStakersTotals::<T>::iter_prefix(era)
+			.map(|(stash, (own, total, _))| (stash, Self::own_exposure_ratio(own, total)))
+			.collect()
+	}
 }
 
 impl<T: Config> ElectionDataProvider for Pallet<T> {
@@ -1043,19 +3720,25 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 	}
 
 	fn electing_voters(bounds: DataProviderBounds) -> data_provider::Result<Vec<VoterOf<Self>>> {
-		// This can never fail -- if `maybe_max_len` is `Some(_)` we handle it.
-		let voters = Self::get_npos_voters(bounds);
+		Self::ensure_snapshot_within_cap(T::VoterList::count(), bounds)?;
+		let mut voters = Self::get_npos_voters(bounds);
 
-		debug_assert!(!bounds.exhausted(
-			SizeBound(voters.encoded_size() as u32).into(),
-			CountBound(voters.len() as u32).into()
-		));
+		Self::truncate_snapshot_to_bounds(&mut voters, bounds, |removed| {
+			Event::<T>::SnapshotVotersTruncated { removed }
+		});
 
+		Self::register_weight(T::DbWeight::get().reads_writes(1, 2));
+		let utilization =
+			Self::snapshot_utilization(None, voters.len(), bounds, T::VoterList::count());
+		VoterSnapshotCutoff::<T>::kill();
+		Self::note_voter_snapshot_cutoff(&voters, utilization.excluded.is_zero());
+		LastVoterSnapshot::<T>::put(utilization);
 		Ok(voters)
 	}
 
 	fn electable_targets(bounds: DataProviderBounds) -> data_provider::Result<Vec<T::AccountId>> {
-		let targets = Self::get_npos_targets(bounds);
+		Self::ensure_snapshot_within_cap(T::TargetList::count(), bounds)?;
+		let mut targets = Self::get_npos_targets(bounds);
 
 		// We can't handle this case yet -- return an error. WIP to improve handling this case in
 		// <https://github.com/paritytech/substrate/pull/13195>.
@@ -1063,40 +3746,115 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 			return Err("Target snapshot too big")
 		}
 
-		debug_assert!(!bounds.exhausted(
-			SizeBound(targets.encoded_size() as u32).into(),
-			CountBound(targets.len() as u32).into()
-		));
+		Self::truncate_snapshot_to_bounds(&mut targets, bounds, |removed| {
+			Event::<T>::SnapshotTargetsTruncated { removed }
+		});
 
+		Self::register_weight(T::DbWeight::get().reads_writes(1, 1));
+		LastTargetSnapshot::<T>::put(Self::snapshot_utilization(
+			None,
+			targets.len(),
+			bounds,
+			T::TargetList::count(),
+		));
 		Ok(targets)
 	}
 
-	fn next_election_prediction(now: BlockNumberFor<T>) -> BlockNumberFor<T> {
-		let current_era = Self::current_era().unwrap_or(0);
-		let current_session = Self::current_planned_session();
-		let current_era_start_session_index =
-			Self::eras_start_session_index(current_era).unwrap_or(0);
-		// Number of session in the current era or the maximum session per era if reached.
-		let era_progress = current_session
-			.saturating_sub(current_era_start_session_index)
-			.min(T::SessionsPerEra::get());
-
-		let until_this_session_end = T::NextNewSession::estimate_next_new_session(now)
-			.0
-			.unwrap_or_default()
-			.saturating_sub(now);
+	fn voters_iter(
+		bounds: DataProviderBounds,
+	) -> data_provider::Result<Box<dyn Iterator<Item = VoterOf<Self>>>> {
+		Self::ensure_snapshot_within_cap(T::VoterList::count(), bounds)?;
+		Ok(Self::npos_voters_iter(bounds))
+	}
+
+	fn voters_page(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<VoterOf<Self>>> {
+		Self::ensure_snapshot_within_cap(T::VoterList::count(), bounds)?;
+		let sorted_voters = match (page, VoterSnapshotCursor::<T>::get()) {
+			(0, _) => T::VoterList::iter(),
+			(_, Some(cursor)) => T::VoterList::iter_from(&cursor)
+				.map_err(|_| "Voter snapshot cursor no longer in the voter list")?,
+			// the snapshot is complete.
+			(_, None) => return Ok(Vec::new()),
+		};
+
+		let (voters, min_active_stake) = Self::npos_voters_from(bounds, sorted_voters);
+		VoterSnapshotCursor::<T>::set(voters.last_seen);
+		if page == 0 {
+			MinimumActiveStake::<T>::put(min_active_stake);
+		} else if !voters.items.is_empty() {
+			MinimumActiveStake::<T>::mutate(|min| *min = (*min).min(min_active_stake));
+		}
+		if page == 0 {
+			VoterSnapshotCutoff::<T>::kill();
+		}
+		Self::note_voter_snapshot_cutoff(&voters.items, voters.last_seen.is_none());
+		Self::register_weight(T::DbWeight::get().reads_writes(2, 2));
+		LastVoterSnapshot::<T>::put(Self::snapshot_utilization(
+			(page != 0).then(LastVoterSnapshot::<T>::get),
+			voters.items.len(),
+			bounds,
+			T::VoterList::count(),
+		));
+
+		Ok(voters.items)
+	}
+
+	fn targets_page(
+		bounds: DataProviderBounds,
+		page: PageIndex,
+	) -> data_provider::Result<Vec<T::AccountId>> {
+		Self::ensure_snapshot_within_cap(T::TargetList::count(), bounds)?;
+		let targets_iter = match (page, TargetSnapshotCursor::<T>::get()) {
+			(0, _) => T::TargetList::iter(),
+			(_, Some(cursor)) => T::TargetList::iter_from(&cursor)
+				.map_err(|_| "Target snapshot cursor no longer in the target list")?,
+			// the snapshot is complete.
+			(_, None) => return Ok(Vec::new()),
+		};
+
+		let targets = Self::npos_targets_from(bounds, targets_iter);
+		TargetSnapshotCursor::<T>::set(targets.last_seen);
+		Self::register_weight(T::DbWeight::get().reads_writes(2, 1));
+		LastTargetSnapshot::<T>::put(Self::snapshot_utilization(
+			(page != 0).then(LastTargetSnapshot::<T>::get),
+			targets.items.len(),
+			bounds,
+			T::TargetList::count(),
+		));
+
+		Ok(targets.items)
+	}
 
+	fn next_election_prediction(now: BlockNumberFor<T>) -> BlockNumberFor<T> {
 		let session_length = T::NextNewSession::average_session_length();
 
+		let until_this_session_end = match T::NextNewSession::estimate_next_new_session(now).0 {
+			Some(next_session) => next_session.saturating_sub(now),
+			None => {
+				// Without an estimate we would predict an election at `now`, which makes stateful
+				// election providers start a new election every block. Assume instead that the
+				// current session ends within the configured fallback.
+				let fallback = T::NextSessionFallback::get().unwrap_or(session_length);
+				log!(
+					warn,
+					"next session estimation unavailable, falling back to {:?} blocks",
+					fallback,
+				);
+				Self::deposit_event(Event::<T>::NextSessionUnknown { fallback });
+				fallback
+			},
+		};
+
 		let sessions_left: BlockNumberFor<T> = match ForceEra::<T>::get() {
 			Forcing::ForceNone => Bounded::max_value(),
 			Forcing::ForceNew | Forcing::ForceAlways => Zero::zero(),
-			Forcing::NotForcing if era_progress >= T::SessionsPerEra::get() => Zero::zero(),
-			Forcing::NotForcing => T::SessionsPerEra::get()
-				.saturating_sub(era_progress)
-				// One session is computed in this_session_end.
-				.saturating_sub(1)
-				.into(),
+			Forcing::NotForcing => Self::sessions_left_in_era(
+				now.saturating_add(until_this_session_end),
+				session_length,
+			),
 		};
 
 		now.saturating_add(
@@ -1109,43 +3867,21 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		voter: T::AccountId,
 		weight: VoteWeight,
 		targets: BoundedVec<T::AccountId, Self::MaxVotesPerVoter>,
-	) {
-		let stake = <BalanceOf<T>>::try_from(weight).unwrap_or_else(|_| {
-			panic!("cannot convert a VoteWeight into BalanceOf, benchmark needs reconfiguring.")
-		});
-		<Bonded<T>>::insert(voter.clone(), voter.clone());
-		<Ledger<T>>::insert(
-			voter.clone(),
-			StakingLedger {
-				stash: voter.clone(),
-				active: stake,
-				total: stake,
-				unlocking: Default::default(),
-				claimed_rewards: Default::default(),
-			},
-		);
-
-		Self::do_add_nominator(&voter, Nominations { targets, submitted_in: 0, suppressed: false });
+	) -> data_provider::Result<()> {
+		// weights saturate, so that setups written for `u64` weights also work with small balance
+		// types.
+		Setup::<T>::new()
+			.conversion(StakeConversion::Saturating)
+			.voter(voter, weight, targets)
+			.build()
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
 	fn add_target(target: T::AccountId) {
-		let stake = MinValidatorBond::<T>::get() * 100u32.into();
-		<Bonded<T>>::insert(target.clone(), target.clone());
-		<Ledger<T>>::insert(
-			target.clone(),
-			StakingLedger {
-				stash: target.clone(),
-				active: stake,
-				total: stake,
-				unlocking: Default::default(),
-				claimed_rewards: Default::default(),
-			},
-		);
-		Self::do_add_validator(
-			&target,
-			ValidatorPrefs { commission: Perbill::zero(), blocked: false },
-		);
+		Setup::<T>::new()
+			.target(target, None)
+			.build()
+			.expect("the default target stake needs no conversion; qed");
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1155,11 +3891,16 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		#[allow(deprecated)]
 		<Ledger<T>>::remove_all(None);
 		#[allow(deprecated)]
+		<StakeOf<T>>::remove_all(None);
+		#[allow(deprecated)]
 		<Validators<T>>::remove_all();
 		#[allow(deprecated)]
 		<Nominators<T>>::remove_all();
+		#[allow(deprecated)]
+		<NominatorsOf<T>>::remove_all(None);
 
 		T::VoterList::unsafe_clear();
+		T::TargetList::unsafe_clear();
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -1167,48 +3908,39 @@ impl<T: Config> ElectionDataProvider for Pallet<T> {
 		voters: Vec<VoterOf<Self>>,
 		targets: Vec<T::AccountId>,
 		target_stake: Option<VoteWeight>,
-	) {
-		targets.into_iter().for_each(|v| {
-			let stake: BalanceOf<T> = target_stake
-				.and_then(|w| <BalanceOf<T>>::try_from(w).ok())
-				.unwrap_or_else(|| MinNominatorBond::<T>::get() * 100u32.into());
-			<Bonded<T>>::insert(v.clone(), v.clone());
-			<Ledger<T>>::insert(
-				v.clone(),
-				StakingLedger {
-					stash: v.clone(),
-					active: stake,
-					total: stake,
-					unlocking: Default::default(),
-					claimed_rewards: Default::default(),
-				},
-			);
-			Self::do_add_validator(
-				&v,
-				ValidatorPrefs { commission: Perbill::zero(), blocked: false },
-			);
-		});
-
-		voters.into_iter().for_each(|(v, s, t)| {
-			let stake = <BalanceOf<T>>::try_from(s).unwrap_or_else(|_| {
-				panic!("cannot convert a VoteWeight into BalanceOf, benchmark needs reconfiguring.")
+	) -> data_provider::Result<()> {
+		let setup = targets
+			.into_iter()
+			.fold(Setup::<T>::new().conversion(StakeConversion::Saturating), |setup, t| {
+				setup.target(t, target_stake)
 			});
-			<Bonded<T>>::insert(v.clone(), v.clone());
-			<Ledger<T>>::insert(
-				v.clone(),
-				StakingLedger {
-					stash: v.clone(),
-					active: stake,
-					total: stake,
-					unlocking: Default::default(),
-					claimed_rewards: Default::default(),
-				},
-			);
-			Self::do_add_nominator(
-				&v,
-				Nominations { targets: t, submitted_in: 0, suppressed: false },
-			);
-		});
+		voters
+			.into_iter()
+			.fold(setup, |setup, voter| setup.voter(voter.who, voter.weight, voter.targets))
+			.build()
+	}
+}
+
+/// Elections happen at the start of the eras, which are delayed in whole sessions.
+impl<T: Config> ElectionSchedule for Pallet<T> {
+	type BlockNumber = BlockNumberFor<T>;
+
+	fn next_election() -> BlockNumberFor<T> {
+		Self::next_election_prediction(<frame_system::Pallet<T>>::block_number())
+	}
+
+	fn force_election() {
+		Self::set_force_era(Forcing::ForceNew);
+	}
+
+	fn set_election_offset(blocks: BlockNumberFor<T>) {
+		let session_length = T::NextNewSession::average_session_length().max(One::one());
+		// round up to whole sessions.
+		let sessions = (blocks.saturating_add(session_length.saturating_sub(One::one())) /
+			session_length)
+			.saturated_into::<SessionIndex>();
+		log!(election: info, "delaying the next election by {} sessions", sessions);
+		ElectionSessionOffset::<T>::put(sessions);
 	}
 }
 
@@ -1292,6 +4024,9 @@ where
 	T: Config + pallet_authorship::Config + pallet_session::Config,
 {
 	fn note_author(author: T::AccountId) {
+		if let Some(active_era) = Self::active_era() {
+			ErasAuthoredBlocks::<T>::mutate(active_era.index, |blocks| blocks.saturating_inc());
+		}
 		Self::reward_by_ids(vec![(author, 20)])
 	}
 }
@@ -1322,6 +4057,57 @@ where
 		slash_session: SessionIndex,
 		disable_strategy: DisableStrategy,
 	) -> Weight {
+		Self::handle_offence(None, offenders, slash_fraction, slash_session, disable_strategy)
+	}
+
+	fn on_offence_of_kind(
+		kind: &Kind,
+		time_slot: &[u8],
+		offenders: &[OffenceDetails<
+			T::AccountId,
+			pallet_session::historical::IdentificationTuple<T>,
+		>],
+		slash_fraction: &[Perbill],
+		slash_session: SessionIndex,
+		disable_strategy: DisableStrategy,
+	) -> Weight {
+		Self::handle_offence(
+			Some((kind, time_slot)),
+			offenders,
+			slash_fraction,
+			slash_session,
+			disable_strategy,
+		)
+	}
+}
+
+impl<T: Config> Pallet<T>
+where
+	T: pallet_session::Config<ValidatorId = <T as frame_system::Config>::AccountId>,
+	T: pallet_session::historical::Config<
+		FullIdentification = Exposure<<T as frame_system::Config>::AccountId, BalanceOf<T>>,
+		FullIdentificationOf = ExposureOf<T>,
+	>,
+	T::SessionHandler: pallet_session::SessionHandler<<T as frame_system::Config>::AccountId>,
+	T::SessionManager: pallet_session::SessionManager<<T as frame_system::Config>::AccountId>,
+	T::ValidatorIdOf: Convert<
+		<T as frame_system::Config>::AccountId,
+		Option<<T as frame_system::Config>::AccountId>,
+	>,
+{
+	/// Handle the offence of `offenders`, identified by its kind and encoded time slot if they
+	/// are given, in which case an offence that was already reported is ignored.
+	fn handle_offence(
+		offence: Option<(&Kind, &[u8])>,
+		offenders: &[OffenceDetails<
+			T::AccountId,
+			pallet_session::historical::IdentificationTuple<T>,
+		>],
+		slash_fraction: &[Perbill],
+		slash_session: SessionIndex,
+		disable_strategy: DisableStrategy,
+	) -> Weight {
+		trace_span!("on_offence", session = slash_session, offenders = offenders.len());
 		let reward_proportion = SlashRewardFraction::<T>::get();
 		let mut consumed_weight = Weight::from_parts(0, 0);
 		let mut add_db_reads_writes = |reads, writes| {
@@ -1344,7 +4130,7 @@ where
 			});
 		add_db_reads_writes(1, 0);
 
-		let window_start = active_era.saturating_sub(T::BondingDuration::get());
+		let window_start = active_era.saturating_sub(Self::slashing_window());
 
 		// Fast path for active-era report - most likely.
 		// `slash_session` cannot be in a future active era. It must be in `active_era` or before.
@@ -1361,14 +4147,25 @@ where
 				None => return consumed_weight,
 			}
 		};
+		// the funds at stake in `slash_era` may have been released already.
+		if slash_era < window_start {
+			return consumed_weight
+		}
 
 		add_db_reads_writes(1, 1);
 
-		let slash_defer_duration = T::SlashDeferDuration::get();
+		let slash_defer_duration = Self::slash_defer_duration();
 
 		let invulnerables = Self::invulnerables();
 		add_db_reads_writes(1, 0);
 
+		let penalty_tiers = OffencePenaltyTiers::<T>::get();
+		add_db_reads_writes(1, 0);
+
+		let mut reported = ReportedOffences::<T>::get();
+		let mut newly_reported = false;
+		add_db_reads_writes(1, 0);
+
 		for (details, slash_fraction) in offenders.iter().zip(slash_fraction) {
 			let (stash, exposure) = &details.offender;
 
@@ -1377,16 +4174,59 @@ where
 				continue
 			}
 
-			let unapplied = slashing::compute_slash::<T>(slashing::SlashParams {
-				stash,
-				slash: *slash_fraction,
-				exposure,
-				slash_era,
-				window_start,
-				now: active_era,
-				reward_proportion,
-				disable_strategy,
-			});
+			// Skip if the very same offence was already reported, e.g. before a re-org. The earlier
+			// offenders of a kind and time slot are reported again along with the new ones, with
+			// an escalated slash fraction that must still be handled, so the fraction is part of
+			// the identity of the offence.
+			if let Some((kind, time_slot)) = offence {
+				let offence =
+					(T::Hashing::hash_of(&(kind, time_slot, stash, slash_fraction)), slash_session);
+				if reported.contains(&offence) {
+					Self::deposit_event(Event::<T>::OffenceAlreadyReported {
+						validator: stash.clone(),
+						session: slash_session,
+					});
+					continue
+				}
+				if reported.is_full() && !reported.is_empty() {
+					reported.remove(0);
+				}
+				newly_reported |= reported.try_push(offence).is_ok();
+			}
+
+			// the points of the era of the offence are taken, as long as they are not paid out.
+			if let Some(points) = Self::offence_penalty(*slash_fraction, &penalty_tiers) {
+				add_db_reads_writes(1, 0);
+				if Self::era_unpaid(slash_era) {
+					let points = Self::penalize_in_era(
+						slash_era,
+						sp_std::iter::once((stash.clone(), points)),
+					);
+					add_db_reads_writes(1, 1);
+					if !points.is_zero() {
+						Self::deposit_event(Event::<T>::OffencePenalized {
+							validator: stash.clone(),
+							era: slash_era,
+							points,
+						});
+					}
+				}
+			}
+
+			// `ErasStakersTotals`.
+			add_db_reads_writes(1, 0);
+			let (unapplied, replacement_weight) =
+				slashing::compute_slash::<T>(slashing::SlashParams {
+					stash,
+					slash: *slash_fraction,
+					exposure,
+					slash_era,
+					window_start,
+					now: active_era,
+					reward_proportion,
+					disable_strategy,
+				});
+			consumed_weight.saturating_accrue(replacement_weight);
 
 			Self::deposit_event(Event::<T>::SlashReported {
 				validator: stash.clone(),
@@ -1408,7 +4248,9 @@ where
 					// Apply right away.
 					slashing::apply_slash::<T>(unapplied, slash_era);
 					{
-						let slash_cost = (6, 5);
+						// including the `ActiveEra` and `AccountEraActivity` of each slashed
+						// staker.
+						let slash_cost = (8, 6);
 						let reward_cost = (2, 2);
 						add_db_reads_writes(
 							(1 + nominators_len) * slash_cost.0 + reward_cost.0 * reporters_len,
@@ -1418,24 +4260,40 @@ where
 				} else {
 					// Defer to end of some `slash_defer_duration` from now.
 					log!(
-						debug,
+						slashing: debug,
 						"deferring slash of {:?}% happened in {:?} (reported in {:?}) to {:?}",
 						slash_fraction,
 						slash_era,
 						active_era,
 						slash_era + slash_defer_duration + 1,
 					);
-					UnappliedSlashes::<T>::mutate(
-						slash_era.saturating_add(slash_defer_duration).saturating_add(One::one()),
-						move |for_later| for_later.push(unapplied),
-					);
-					add_db_reads_writes(1, 1);
+					let era =
+						slash_era.saturating_add(slash_defer_duration).saturating_add(One::one());
+					let id = NextUnappliedSlashId::<T>::mutate(|next| {
+						let id = *next;
+						*next = next.wrapping_add(1);
+						id
+					});
+					Self::note_pending_slash(era, &unapplied, true);
+					UnappliedSlashes::<T>::insert(era, id, unapplied);
+					Self::deposit_event(Event::<T>::SlashDeferred {
+						validator: stash.clone(),
+						slash_era,
+						era,
+						id,
+					});
+					add_db_reads_writes(2 + nominators_len, 3 + nominators_len);
 				}
 			} else {
 				add_db_reads_writes(4 /* fetch_spans */, 5 /* kick_out_if_recent */)
 			}
 		}
 
+		if newly_reported {
+			ReportedOffences::<T>::put(reported);
+			add_db_reads_writes(0, 1);
+		}
+
 		consumed_weight
 	}
 }
@@ -1451,23 +4309,21 @@ impl<T: Config> ScoreProvider<T::AccountId> for Pallet<T> {
 	fn set_score_of(who: &T::AccountId, weight: Self::Score) {
 		// this will clearly results in an inconsistent state, but it should not matter for a
 		// benchmark.
-		let active: BalanceOf<T> = weight.try_into().map_err(|_| ()).unwrap();
+		let active = Setup::<T>::stake_of(weight, StakeConversion::Strict).unwrap();
 		let mut ledger = match Self::ledger(who) {
 			None => StakingLedger::default_from(who.clone()),
 			Some(l) => l,
 		};
 		ledger.active = active;
 
-		<Ledger<T>>::insert(who, ledger);
+		<Ledger<T>>::insert(who, &ledger);
+		<StakeOf<T>>::insert(who, (ledger.active, ledger.total));
 		<Bonded<T>>::insert(who, who);
 
 		// also, we play a trick to make sure that a issuance based-`CurrencyToVote` behaves well:
 		// This will make sure that total issuance is zero, thus the currency to vote will be a 1-1
 		// conversion.
-		let imbalance = T::Currency::burn(T::Currency::total_issuance());
-		// kinda ugly, but gets the job done. The fact that this works here is a HUGE exception.
-		// Don't try this pattern in other places.
-		sp_std::mem::forget(imbalance);
+		Setup::<T>::burn_total_issuance();
 	}
 }
 
@@ -1609,6 +4465,8 @@ impl<T: Config> SortedListProvider<T::AccountId> for UseNominatorsAndValidatorsM
 		#[allow(deprecated)]
 		Nominators::<T>::remove_all();
 		#[allow(deprecated)]
+		NominatorsOf::<T>::remove_all(None);
+		#[allow(deprecated)]
 		Validators::<T>::remove_all();
 	}
 
@@ -1651,9 +4509,9 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn is_exposed_in_era(who: &Self::AccountId, era: &EraIndex) -> bool {
-		ErasStakers::<T>::iter_prefix(era).any(|(validator, exposures)| {
-			validator == *who || exposures.others.iter().any(|i| i.who == *who)
-		})
+		ErasStakersTotals::<T>::contains_key(era, who) ||
+			ErasStakersPaged::<T>::iter_prefix_values((*era,))
+				.any(|page| page.others.iter().any(|i| i.who == *who))
 	}
 
 	fn bonding_duration() -> EraIndex {
@@ -1665,9 +4523,8 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn stake(who: &Self::AccountId) -> Result<Stake<BalanceOf<T>>, DispatchError> {
-		Self::bonded(who)
-			.and_then(|c| Self::ledger(c))
-			.map(|l| Stake { total: l.total, active: l.active })
+		Self::stake_of(who)
+			.map(|(active, total)| Stake { total, active })
 			.ok_or(Error::<T>::NotStash.into())
 	}
 
@@ -1676,7 +4533,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn unbond(who: &Self::AccountId, value: Self::Balance) -> DispatchResult {
-		let ctrl = Self::bonded(who).ok_or(Error::<T>::NotStash)?;
+		let ctrl = Self::try_bonded(who)?;
 		Self::unbond(RawOrigin::Signed(ctrl).into(), value)
 			.map_err(|with_post| with_post.error)
 			.map(|_| ())
@@ -1685,7 +4542,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	fn chill(who: &Self::AccountId) -> DispatchResult {
 		// defensive-only: any account bonded via this interface has the stash set as the
 		// controller, but we have to be sure. Same comment anywhere else that we read this.
-		let ctrl = Self::bonded(who).ok_or(Error::<T>::NotStash)?;
+		let ctrl = Self::try_bonded(who)?;
 		Self::chill(RawOrigin::Signed(ctrl).into())
 	}
 
@@ -1693,7 +4550,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 		who: Self::AccountId,
 		num_slashing_spans: u32,
 	) -> Result<bool, DispatchError> {
-		let ctrl = Self::bonded(who).ok_or(Error::<T>::NotStash)?;
+		let ctrl = Self::try_bonded(&who)?;
 		Self::withdraw_unbonded(RawOrigin::Signed(ctrl.clone()).into(), num_slashing_spans)
 			.map(|_| !Ledger::<T>::contains_key(&ctrl))
 			.map_err(|with_post| with_post.error)
@@ -1712,7 +4569,7 @@ impl<T: Config> StakingInterface for Pallet<T> {
 	}
 
 	fn nominate(who: &Self::AccountId, targets: Vec<Self::AccountId>) -> DispatchResult {
-		let ctrl = Self::bonded(who).ok_or(Error::<T>::NotStash)?;
+		let ctrl = Self::try_bonded(who)?;
 		let targets = targets.into_iter().map(T::Lookup::unlookup).collect::<Vec<_>>();
 		Self::nominate(RawOrigin::Signed(ctrl).into(), targets)
 	}
@@ -1757,7 +4614,10 @@ impl<T: Config> StakingInterface for Pallet<T> {
 				.map(|(who, value)| IndividualExposure { who: who.clone(), value: value.clone() })
 				.collect::<Vec<_>>();
 			let exposure = Exposure { total: Default::default(), own: Default::default(), others };
-			<ErasStakers<T>>::insert(&current_era, &stash, &exposure);
+			Setup::<T>::new()
+				.era_stakers(*current_era, stash.clone(), exposure)
+				.build()
+				.expect("exposures need no conversion; qed");
 		}
 
 		fn set_current_era(era: EraIndex) {
@@ -1778,9 +4638,41 @@ impl<T: Config> Pallet<T> {
 		Self::check_nominators()?;
 		Self::check_exposures()?;
 		Self::check_ledgers()?;
+		Self::check_exit_queue()?;
+		Self::check_status()?;
 		Self::check_count()
 	}
 
+	fn check_exit_queue() -> Result<(), TryRuntimeError> {
+		ensure!(
+			ExitQueue::<T>::iter_values().all(|stash| Validators::<T>::contains_key(stash)),
+			"ExitQueue contains a non-validator"
+		);
+		let (front, next) = ExitQueueRange::<T>::get();
+		ensure!(
+			ExitTurns::<T>::iter().all(|(stash, turn)| {
+				(front..next).contains(&turn) && ExitQueue::<T>::get(turn) == Some(stash)
+			}),
+			"ExitTurns out of sync with ExitQueue"
+		);
+		Ok(())
+	}
+
+	fn check_status() -> Result<(), TryRuntimeError> {
+		for (stash, role) in Status::<T>::iter() {
+			ensure!(Bonded::<T>::contains_key(&stash), "Status contains a non-bonded stash");
+			let expected = if Validators::<T>::contains_key(&stash) {
+				StakerRole::Validator
+			} else if Nominators::<T>::contains_key(&stash) {
+				StakerRole::Nominator
+			} else {
+				StakerRole::Idle
+			};
+			ensure!(role == expected, "Status out of sync with Validators and Nominators");
+		}
+		Ok(())
+	}
+
 	fn check_count() -> Result<(), TryRuntimeError> {
 		ensure!(
 			<T as Config>::VoterList::count() ==
@@ -1809,8 +4701,9 @@ impl<T: Config> Pallet<T> {
 	fn check_exposures() -> Result<(), TryRuntimeError> {
 		// a check per validator to ensure the exposure struct is always sane.
 		let era = Self::active_era().unwrap().index;
-		ErasStakers::<T>::iter_prefix_values(era)
-			.map(|expo| {
+		ErasStakersTotals::<T>::iter_key_prefix(era)
+			.map(|validator| {
+				let expo = Self::eras_stakers(era, &validator);
 				ensure!(
 					expo.total ==
 						expo.own +
@@ -1879,6 +4772,10 @@ impl<T: Config> Pallet<T> {
 		let real_total: BalanceOf<T> =
 			ledger.unlocking.iter().fold(ledger.active, |a, c| a + c.value);
 		ensure!(real_total == ledger.total, "ledger.total corrupt");
+		ensure!(
+			Self::stake_of(&ledger.stash) == Some((ledger.active, ledger.total)),
+			"cached stake of the ledger corrupt"
+		);
 
 		Ok(())
 	}
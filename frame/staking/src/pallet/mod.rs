@@ -18,14 +18,15 @@
 //! Staking FRAME Pallet.
 
 use frame_election_provider_support::{
-	ElectionProvider, ElectionProviderBase, SortedListProvider, VoteWeight,
+	BoundedSupport, ElectionProvider, ElectionProviderBase, ElectionSchedule, ElectionScore,
+	InstantElectionProvider, SortedListProvider, VoteWeight, WeightAccountant,
 };
 use frame_support::{
 	dispatch::Codec,
 	pallet_prelude::*,
 	traits::{
-		Currency, Defensive, DefensiveResult, DefensiveSaturating, EnsureOrigin,
-		EstimateNextNewSession, Get, LockIdentifier, LockableCurrency, OnUnbalanced, TryCollect,
+		fungible::hold::Mutate as FunHoldMutate, Currency, Defensive, DefensiveSaturating,
+		EnsureOrigin, EstimateNextNewSession, Get, LockIdentifier, LockableCurrency, OnUnbalanced,
 		UnixTime,
 	},
 	weights::Weight,
@@ -33,10 +34,10 @@ use frame_support::{
 };
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
 use sp_runtime::{
-	traits::{CheckedSub, SaturatedConversion, StaticLookup, Zero},
+	traits::{CheckedSub, SaturatedConversion, Saturating, StaticLookup, Zero},
 	ArithmeticError, Perbill, Percent,
 };
-use sp_staking::{EraIndex, SessionIndex};
+use sp_staking::{EraIndex, EraInflation, SessionIndex, SnapshotUtilization};
 use sp_std::prelude::*;
 
 mod impls;
@@ -44,10 +45,12 @@ mod impls;
 pub use impls::*;
 
 use crate::{
-	slashing, weights::WeightInfo, AccountIdLookupOf, ActiveEraInfo, BalanceOf, EraPayout,
-	EraRewardPoints, Exposure, Forcing, MaxNominationsOf, NegativeImbalanceOf, Nominations,
-	NominationsQuota, PositiveImbalanceOf, RewardDestination, SessionInterface, StakingLedger,
-	UnappliedSlash, UnlockChunk, ValidatorPrefs,
+	slashing, weights::WeightInfo, AccountActivity, AccountIdLookupOf, ActiveEraInfo, BalanceOf,
+	CommissionChange, CompactExposure, DisablingStrategy, EraLength, EraPayout, EraRewardPoints,
+	Exposure, ExposurePageSizeOf, Forcing, KickFilter, LoyaltyStreak, MaxNominationsOf,
+	NegativeImbalanceOf, Nominations, NominationsQuota, PayeeSplitsOf, PositiveImbalanceOf,
+	RemainderPolicy, RewardDestination, RewardPoint, SessionInterface, StakerRole, StakingLedger,
+	UnappliedSlash, UnlockChunk, ValidatorLiveness, ValidatorPrefs, MAX_REMAINDER_SINKS,
 };
 
 const STAKING_ID: LockIdentifier = *b"staking ";
@@ -65,7 +68,7 @@ pub mod pallet {
 	use super::*;
 
 	/// The current storage version.
-	const STORAGE_VERSION: StorageVersion = StorageVersion::new(13);
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(20);
 
 	#[pallet::pallet]
 	#[pallet::storage_version(STORAGE_VERSION)]
@@ -101,6 +104,17 @@ pub mod pallet {
 			+ From<u64>
 			+ TypeInfo
 			+ MaxEncodedLen;
+
+		/// The currency through which [`Pallet::migrate_stash`] moves a bond, on hold. This
+		/// should be the same as [`Config::Currency`].
+		type HoldCurrency: FunHoldMutate<
+			Self::AccountId,
+			Balance = Self::CurrencyBalance,
+			Reason = Self::RuntimeHoldReason,
+		>;
+
+		/// The overarching hold reason.
+		type RuntimeHoldReason: From<HoldReason>;
 		/// Time used for computing era duration.
 		///
 		/// It is guaranteed to start being called from the first `on_finalize`. Thus value at
@@ -123,22 +137,66 @@ pub mod pallet {
 			DataProvider = Pallet<Self>,
 		>;
 		/// Something that provides the election functionality at genesis.
-		type GenesisElectionProvider: ElectionProvider<
+		///
+		/// The genesis era is elected out of the genesis stakers, with
+		/// [`InstantElectionProvider::instant_elect_with_inputs`].
+		type GenesisElectionProvider: InstantElectionProvider<
 			AccountId = Self::AccountId,
 			BlockNumber = BlockNumberFor<Self>,
 			DataProvider = Pallet<Self>,
 		>;
 
+		/// Whether the exposures of each new era are submitted by [`Config::ExposureOracle`], with
+		/// [`Call::submit_exposures`], rather than computed out of an election.
+		///
+		/// The genesis era is still elected by [`Config::GenesisElectionProvider`].
+		type ExternalExposures: Get<bool>;
+
+		/// The origin which can submit the exposures of the next era, if
+		/// [`Config::ExternalExposures`] is set.
+		type ExposureOracle: EnsureOrigin<Self::RuntimeOrigin>;
+
+		/// The source of liveness of the validators, which can veto the winners of an election
+		/// that are known to be dead.
+		///
+		/// A vetoed winner is substituted by the best runner-up of the election that is still
+		/// available, see [`Config::MaxRunnersUp`].
+		type ValidatorLiveness: ValidatorLiveness<Self::AccountId>;
+
+		/// The maximum number of winners that [`Config::ValidatorLiveness`] can veto in an era.
+		///
+		/// Set to 0 to never veto any.
+		type MaxVetoedWinners: Get<u32>;
+
+		/// The smallest slash that a nominator can get for the offence of a validator.
+		///
+		/// The nominators that would be slashed less are left alone, as if they did not back the
+		/// validator, which spares both them and the slashing records the dust.
+		type MinNominatorSlash: Get<BalanceOf<Self>>;
+
+		/// The maximum number of runners-up of each election kept in [`ErasRunnersUp`].
+		///
+		/// They are asked from [`Config::ElectionProvider`], which may not provide any: only the
+		/// on-chain election provider does, and an election provider like the multi-phase one
+		/// leaves [`ErasRunnersUp`] empty, so that no validator can be substituted. Zero spares
+		/// the on-chain provider from solving the election a second time.
+		#[pallet::constant]
+		type MaxRunnersUp: Get<u32>;
+
+		/// Whether the validators disabled for an offence are replaced by the best runner-up of the
+		/// active era, as with [`Call::replace_validator`].
+		type ReplaceDisabledValidators: Get<bool>;
+
 		/// Something that defines the maximum number of nominations per nominator.
 		type NominationsQuota: NominationsQuota<BalanceOf<Self>>;
 
 		/// Number of eras to keep in history.
 		///
 		/// Following information is kept for eras in `[current_era -
-		/// HistoryDepth, current_era]`: `ErasStakers`, `ErasStakersClipped`,
+		/// HistoryDepth, current_era]`: `ErasStakersTotals`, `ErasStakersPaged`,
 		/// `ErasValidatorPrefs`, `ErasValidatorReward`, `ErasRewardPoints`,
-		/// `ErasTotalStake`, `ErasStartSessionIndex`,
-		/// `StakingLedger.claimed_rewards`.
+		/// `ErasAuthoredBlocks`, `AccountEraActivity`, `ErasTotalStake`, `ErasStartSessionIndex`,
+		/// `ClaimedRewards`.
 		///
 		/// Must be more than the number of eras delayed by session.
 		/// I.e. active era must always be in history. I.e. `active_era >
@@ -146,12 +204,6 @@ pub mod pallet {
 		///
 		/// If migrating an existing pallet from storage value to config value,
 		/// this should be set to same value or greater as in storage.
-		///
-		/// Note: `HistoryDepth` is used as the upper bound for the `BoundedVec`
-		/// item `StakingLedger.claimed_rewards`. Setting this value lower than
-		/// the existing value can lead to inconsistencies in the
-		/// `StakingLedger` and will need to be handled properly in a migration.
-		/// The test `reducing_history_depth_abrupt` shows this effect.
 		#[pallet::constant]
 		type HistoryDepth: Get<u32>;
 
@@ -159,6 +211,23 @@ pub mod pallet {
 		/// See [Era payout](./index.html#era-payout).
 		type RewardRemainder: OnUnbalanced<NegativeImbalanceOf<Self>>;
 
+		/// How the remainder of each era payout is routed between [`Config::RewardRemainder`],
+		/// the validator payout of the next era, and burning.
+		type RemainderPolicy: Get<RemainderPolicy<Self::AccountId>>;
+
+		/// The maximum number of reported offences that are remembered, so that the same offence
+		/// reported again, e.g. after a re-org, is ignored.
+		///
+		/// Once full, the oldest offence is forgotten.
+		#[pallet::constant]
+		type MaxRememberedOffences: Get<u32>;
+
+		/// The maximum number of deferred slashes applied in a single block.
+		///
+		/// The slashes of an era in excess of this are applied in the following blocks.
+		#[pallet::constant]
+		type MaxSlashesPerBlock: Get<u32>;
+
 		/// The overarching event type.
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 
@@ -174,6 +243,10 @@ pub mod pallet {
 		#[pallet::constant]
 		type SessionsPerEra: Get<SessionIndex>;
 
+		/// How the length of an era is measured: in sessions, following
+		/// [`Config::SessionsPerEra`], or directly in blocks.
+		type EraLength: Get<EraLength<BlockNumberFor<Self>>>;
+
 		/// Number of eras that staked funds must remain bonded for.
 		#[pallet::constant]
 		type BondingDuration: Get<EraIndex>;
@@ -182,9 +255,30 @@ pub mod pallet {
 		///
 		/// This should be less than the bonding duration. Set to 0 if slashes
 		/// should be applied immediately, without opportunity for intervention.
+		///
+		/// This is only the default, which can be overridden through
+		/// [`Call::set_slashing_params`].
 		#[pallet::constant]
 		type SlashDeferDuration: Get<EraIndex>;
 
+		/// The least number of eras that unbonded funds remain bonded for, if unbonding goes
+		/// through a queue.
+		///
+		/// If `Some`, funds unbonded while little stake is exiting are released after this many
+		/// eras, and the more stake is exiting, see [`ErasUnbonding`], the closer it gets to
+		/// [`Config::BondingDuration`], which is reached when [`Config::UnbondingQueueSaturation`]
+		/// of the total stake is. If `None`, unbonded funds always remain bonded for
+		/// [`Config::BondingDuration`].
+		///
+		/// Offences can then only be reported for this many eras, as the funds unbonded in the
+		/// era of an offence may be released after that. This should be more than the slash
+		/// defer duration, so that slashes are applied before the funds are released.
+		type MinUnbondingDuration: Get<Option<EraIndex>>;
+
+		/// The share of the total stake exiting at which unbonding takes the whole
+		/// [`Config::BondingDuration`], see [`Config::MinUnbondingDuration`].
+		type UnbondingQueueSaturation: Get<Perbill>;
+
 		/// The origin which can manage less critical staking parameters that does not require root.
 		///
 		/// Supported actions: (1) cancel deferred slash, (2) set minimum commission.
@@ -199,8 +293,17 @@ pub mod pallet {
 
 		/// Something that can estimate the next session change, accurately or as a best effort
 		/// guess.
+		///
+		/// If no estimate of the next session change is available, the next election is predicted
+		/// with [`Config::NextSessionFallback`] instead.
 		type NextNewSession: EstimateNextNewSession<BlockNumberFor<Self>>;
 
+		/// The number of blocks until the end of the current session to assume when
+		/// [`Config::NextNewSession`] cannot estimate it, in predicting the next election.
+		///
+		/// If `None`, [`EstimateNextNewSession::average_session_length`] blocks are assumed.
+		type NextSessionFallback: Get<Option<BlockNumberFor<Self>>>;
+
 		/// The maximum number of nominators rewarded for each validator.
 		///
 		/// For each validator only the `$MaxNominatorRewardedPerValidator` biggest stakers can
@@ -212,6 +315,11 @@ pub mod pallet {
 		/// After the threshold is reached a new era will be forced.
 		type OffendingValidatorsThreshold: Get<Perbill>;
 
+		/// Decides which of the offending validators are disabled for the rest of the era, see
+		/// [`crate::DisableWhenAsked`], [`crate::DisableUpTo`],
+		/// [`crate::DisableSeriousOffences`] and [`crate::NeverDisable`].
+		type DisablingStrategy: DisablingStrategy;
+
 		/// Something that provides a best-effort sorted list of voters aka electing nominators,
 		/// used for NPoS election.
 		///
@@ -225,26 +333,15 @@ pub mod pallet {
 		/// Invariant: what comes out of this list will always be a nominator.
 		type VoterList: SortedListProvider<Self::AccountId, Score = VoteWeight>;
 
-		/// WIP: This is a noop as of now, the actual business logic that's described below is going
-		/// to be introduced in a follow-up PR.
-		///
 		/// Something that provides a best-effort sorted list of targets aka electable validators,
 		/// used for NPoS election.
 		///
-		/// The changes to the approval stake of each validator are reported to this. This means any
-		/// change to:
-		/// 1. The stake of any validator or nominator.
-		/// 2. The targets of any nominator
-		/// 3. The role of any staker (e.g. validator -> chilled, nominator -> validator, etc)
-		///
-		/// Unlike `VoterList`, the values in this list are always kept up to date with reward and
-		/// slash as well, and thus represent the accurate approval stake of all account being
-		/// nominated by nominators.
+		/// Validators are inserted into and removed from this list as they start and stop
+		/// validating, scored by their own active stake. The score is updated whenever a validator
+		/// bonds, unbonds or rebonds, but, just like `VoterList`, not on rewards or slashes.
 		///
-		/// Note that while at the time of nomination, all targets are checked to be real
-		/// validators, they can chill at any point, and their approval stakes will still be
-		/// recorded. This implies that what comes out of iterating this list MIGHT NOT BE AN ACTIVE
-		/// VALIDATOR.
+		/// The target snapshot is taken from the head of this list, so if it does not fit in the
+		/// snapshot bounds, the validators with the least stake are left out.
 		type TargetList: SortedListProvider<Self::AccountId, Score = BalanceOf<Self>>;
 
 		/// The maximum number of `unlocking` chunks a [`StakingLedger`] can
@@ -266,6 +363,148 @@ pub mod pallet {
 		/// WARNING: this only reports slashing events for the time being.
 		type EventListeners: sp_staking::OnStakingUpdate<Self::AccountId, BalanceOf<Self>>;
 
+		/// The target that the pallet logs to.
+		///
+		/// [`crate::DefaultLogTarget`] is `runtime::staking`.
+		type LogTarget: Get<&'static str>;
+
+		/// The most verbose level that each subsystem of the pallet logs at.
+		///
+		/// `()` lets every message through, leaving the filtering to the node.
+		type LogLevels: Get<crate::LogLevels>;
+
+		/// The maximum number of items that a single election snapshot may read out of
+		/// [`Config::VoterList`] or [`Config::TargetList`], if any.
+		///
+		/// A snapshot that could read more fails before reading anything, even if its bounds are
+		/// unbounded. This guards against the snapshot running out of memory or time.
+		type AbsoluteMaxSnapshot: Get<Option<u32>>;
+
+		/// The maximum number of pages of rewards that are paid out in a block, with the weight
+		/// left over in `on_idle`.
+		///
+		/// The oldest unclaimed rewards within the history depth are paid out first, so that they
+		/// are not lost if nobody claims them. Zero disables the automatic payouts.
+		type MaxAutoPayoutsPerBlock: Get<u32>;
+
+		/// Whether the pending, deferred slashes of a staker in [`UnappliedSlashes`] should be
+		/// deducted from their vote weight.
+		///
+		/// If `true`, a validator or nominator awaiting a deferred slash does not participate in
+		/// the next elections with the stake that they are about to lose.
+		type DeductPendingSlashes: Get<bool>;
+
+		/// Whether an election whose estimated weight does not fit into the remaining weight of
+		/// the current block should be deferred.
+		///
+		/// If `true`, [`Config::ElectionProvider`] is asked for an estimate of the election weight
+		/// before a new era is planned. If it does not fit, the election is retried at the start of
+		/// the next block, see [`DeferredElection`]. The era it plans starts one session later than
+		/// it would have. A retried election whose supports cannot all be converted into exposures
+		/// in the rest of the block converts them over the next blocks, each one counting as
+		/// another deferral.
+		type DeferOverweightElection: Get<bool>;
+
+		/// The maximum number of times in a row that an overweight election is deferred, after
+		/// which it is run regardless of its estimated weight.
+		#[pallet::constant]
+		type MaxElectionDeferrals: Get<u32>;
+
+		/// The extra share of the nominators' payout that a nominator earns for having backed the
+		/// same validator for [`Config::MaxLoyaltyEras`] consecutive eras.
+		///
+		/// The extra share grows linearly with the length of the streak, and is paid for by the
+		/// other nominators of the same validator, so the total payout is not affected. If zero,
+		/// streaks are not tracked at all.
+		type LoyaltyBonus: Get<Perbill>;
+
+		/// The length of a loyalty streak, in eras, after which [`Config::LoyaltyBonus`] no
+		/// longer grows.
+		type MaxLoyaltyEras: Get<u32>;
+
+		/// The maximum number of validators that leave the electable set in an era, if any.
+		///
+		/// If `Some`, a validator that calls [`Pallet::chill`] is put in [`ExitQueue`] and stays
+		/// electable until its turn comes: at the start of each era, at most this many validators
+		/// of the queue are chilled, in the order in which they asked to. If `None`, validators
+		/// are chilled right away.
+		type MaxExitsPerEra: Get<Option<u32>>;
+
+		/// The number of sessions that a new validator has to wait for before it becomes
+		/// electable.
+		///
+		/// The wait starts when a stash that is not a validator calls [`Pallet::validate`], and is
+		/// tracked in [`ValidatorWaitlist`]. If zero, new validators are electable right away.
+		type ValidatorWaitlistSessions: Get<SessionIndex>;
+
+		/// The seed with which nominators are sampled into the election snapshot, if any.
+		///
+		/// If `Some`, and not all of [`Config::VoterList`] fits in the snapshot bounds, nominators
+		/// are picked with a probability proportional to their stake instead of strictly from the
+		/// top of the list, so that mid-size nominators are not always left out by slightly larger
+		/// ones. The seed should come from on-chain randomness that does not change within an
+		/// election round, e.g. that of the previous epoch. If `None`, the top of the list is
+		/// taken. Paged snapshots always take the top of the list.
+		type VoterSamplingSeed: Get<Option<Self::Hash>>;
+
+		/// The seed with which the elected validators are shuffled at the start of each era, if
+		/// any.
+		///
+		/// If `Some`, the order of the validator set handed to the session pallet is shuffled with
+		/// randomness derived from the seed and the new era, so that session handlers that assign
+		/// responsibilities by index do not see a predictable order. If `None`, the validators
+		/// keep the order of the election result.
+		type ValidatorShuffleSeed: Get<Option<Self::Hash>>;
+
+		/// The share of the total issuance that [`MinNominatorBond`] is set to, if any.
+		///
+		/// If `Some`, [`MinNominatorBond`] is recomputed at the start of each era, so that it
+		/// follows the growth of the token supply, and any value set through
+		/// [`Pallet::set_staking_configs`] only holds until then. If `None`, [`MinNominatorBond`]
+		/// is a fixed balance.
+		type MinNominatorBondShare: Get<Option<Perbill>>;
+
+		/// The share of the total issuance that [`MinValidatorBond`] is set to, if any.
+		///
+		/// Same as [`Config::MinNominatorBondShare`], for [`MinValidatorBond`].
+		type MinValidatorBondShare: Get<Option<Perbill>>;
+
+		/// Whether a new nominator can take the place of the lowest-staked one once
+		/// [`MaxNominatorsCount`] is reached.
+		///
+		/// If `true`, a new nominator whose active bond exceeds that of the nominator at the tail
+		/// of [`Config::VoterList`] chills it instead of failing with `TooManyNominators`. If
+		/// `false`, nominators are admitted on a first-come-first-served basis.
+		///
+		/// Only effective with a [`Config::VoterList`] that can be walked from its tail, see
+		/// [`SortedListProvider::iter_rev`].
+		type EvictLowestNominators: Get<bool>;
+
+		/// The maximum number of voters at the tail of [`Config::VoterList`] looked at to find the
+		/// lowest-staked nominator to evict, see [`Config::EvictLowestNominators`].
+		///
+		/// Validators are in the list as well, and are skipped over.
+		#[pallet::constant]
+		type MaxEvictionScan: Get<u32>;
+
+		/// The maximum number of commission tiers that can be set, see [`CommissionTiers`].
+		#[pallet::constant]
+		type MaxCommissionTiers: Get<u32>;
+
+		/// The maximum number of offence penalty tiers that can be set, see
+		/// [`OffencePenaltyTiers`].
+		#[pallet::constant]
+		type MaxOffencePenaltyTiers: Get<u32>;
+
+		/// The maximum number of payouts in a single call of
+		/// [`Call::payout_stakers_batch`].
+		#[pallet::constant]
+		type MaxPayoutsPerBatch: Get<u32>;
+
+		/// Something that accounts for the weight registered by the pallet outside of its
+		/// dispatches, e.g. while creating the election snapshot. Usually the system pallet.
+		type WeightAccountant: WeightAccountant;
+
 		/// Some parameters of the benchmarking.
 		type BenchmarkingConfig: BenchmarkingConfig;
 
@@ -275,19 +514,16 @@ pub mod pallet {
 
 	/// The ideal number of active validators.
 	#[pallet::storage]
-	#[pallet::getter(fn validator_count)]
 	pub type ValidatorCount<T> = StorageValue<_, u32, ValueQuery>;
 
 	/// Minimum number of staking participants before emergency conditions are imposed.
 	#[pallet::storage]
-	#[pallet::getter(fn minimum_validator_count)]
 	pub type MinimumValidatorCount<T> = StorageValue<_, u32, ValueQuery>;
 
 	/// Any validators that may never be slashed or forcibly kicked. It's a Vec since they're
 	/// easy to initialize and the performance hit is minimal (we expect no more than four
 	/// invulnerables) and restricted to testnets.
 	#[pallet::storage]
-	#[pallet::getter(fn invulnerables)]
 	#[pallet::unbounded]
 	pub type Invulnerables<T: Config> = StorageValue<_, Vec<T::AccountId>, ValueQuery>;
 
@@ -295,14 +531,26 @@ pub mod pallet {
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
 	#[pallet::storage]
-	#[pallet::getter(fn bonded)]
 	pub type Bonded<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
 
+	/// The role currently played by each bonded stash.
+	///
+	/// Kept in sync with [`Bonded`], [`Validators`] and [`Nominators`], so that the role of a
+	/// stash can be read with a single lookup.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type Status<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, StakerRole>;
+
 	/// The minimum active bond to become and maintain the role of a nominator.
+	///
+	/// Recomputed at the start of each era if [`Config::MinNominatorBondShare`] is set.
 	#[pallet::storage]
 	pub type MinNominatorBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
 	/// The minimum active bond to become and maintain the role of a validator.
+	///
+	/// Recomputed at the start of each era if [`Config::MinValidatorBondShare`] is set.
 	#[pallet::storage]
 	pub type MinValidatorBond<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
@@ -310,30 +558,115 @@ pub mod pallet {
 	#[pallet::storage]
 	pub type MinimumActiveStake<T> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
+	/// The last voter of [`Config::VoterList`] that was looked at by a paged voter snapshot, if
+	/// one is in progress.
+	///
+	/// See [`ElectionDataProvider::voters_page`].
+	#[pallet::storage]
+	pub type VoterSnapshotCursor<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// The last target of [`Config::TargetList`] that was looked at by a paged target snapshot, if
+	/// one is in progress.
+	///
+	/// See [`ElectionDataProvider::targets_page`].
+	#[pallet::storage]
+	pub type TargetSnapshotCursor<T: Config> = StorageValue<_, T::AccountId>;
+
+	/// The validators that asked to be chilled and are still electable, by their turn to leave,
+	/// in the order in which they asked.
+	///
+	/// See [`Config::MaxExitsPerEra`].
+	#[pallet::storage]
+	pub type ExitQueue<T: Config> = StorageMap<_, Twox64Concat, u32, T::AccountId>;
+
+	/// The turn of each validator in [`ExitQueue`].
+	#[pallet::storage]
+	pub type ExitTurns<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32>;
+
+	/// The turn at the front of [`ExitQueue`], and the turn of the next validator to be queued.
+	///
+	/// The turns in between are either queued, or left empty by the validators that validated
+	/// again or were removed meanwhile.
+	#[pallet::storage]
+	pub type ExitQueueRange<T> = StorageValue<_, (u32, u32), ValueQuery>;
+
+	/// The new validators that are not electable yet, mapped to the planned session from which
+	/// they are.
+	///
+	/// See [`Config::ValidatorWaitlistSessions`].
+	#[pallet::storage]
+	pub type ValidatorWaitlist<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, SessionIndex>;
+
 	/// The minimum amount of commission that validators can set.
 	///
 	/// If set to `0`, no limit exists.
 	#[pallet::storage]
 	pub type MinCommission<T: Config> = StorageValue<_, Perbill, ValueQuery>;
 
+	/// The inclusive ranges of commission that validators can choose from, sorted and not
+	/// overlapping.
+	///
+	/// If empty, any commission of at least [`MinCommission`] is allowed.
+	#[pallet::storage]
+	pub type CommissionTiers<T: Config> =
+		StorageValue<_, BoundedVec<(Perbill, Perbill), T::MaxCommissionTiers>, ValueQuery>;
+
+	/// The era points that the offenders lose for their offences, by severity.
+	///
+	/// Each tier is the largest slash fraction of the offences it covers, with the points they
+	/// cost, sorted by slash fraction. An offence falls in the first tier that covers it, if any,
+	/// on top of being slashed. The points are taken from the era of the offence, unless some of
+	/// its rewards have been paid out already. See [`Pallet::penalize_by_ids`].
+	#[pallet::storage]
+	pub type OffencePenaltyTiers<T: Config> =
+		StorageValue<_, BoundedVec<(Perbill, RewardPoint), T::MaxOffencePenaltyTiers>, ValueQuery>;
+
+	/// The share of the slashed funds that is burned.
+	///
+	/// The rest pays the reporters of the offence, and what they do not get goes to
+	/// [`Config::Slash`].
+	#[pallet::storage]
+	pub type SlashDestination<T> = StorageValue<_, Perbill, ValueQuery>;
+
+	/// The unbonded funds that are released in each era, i.e. that exit the staking system then.
+	///
+	/// Only tracked if [`Config::MinUnbondingDuration`] is set, and pruned when the era becomes
+	/// active.
+	#[pallet::storage]
+	pub type ErasUnbonding<T: Config> =
+		StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, ValueQuery>;
+
 	/// Map from all (unlocked) "controller" accounts to the info regarding the staking.
 	#[pallet::storage]
-	#[pallet::getter(fn ledger)]
 	pub type Ledger<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, StakingLedger<T>>;
 
+	/// The `(active, total)` balance of the [`StakingLedger`] of each stash.
+	///
+	/// Kept in sync with [`Ledger`], so that the stake of a stash can be read without going
+	/// through its controller or decoding its full ledger.
+	#[pallet::storage]
+	pub type StakeOf<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (BalanceOf<T>, BalanceOf<T>)>;
+
 	/// Where the reward payment should be made. Keyed by stash.
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
 	#[pallet::storage]
-	#[pallet::getter(fn payee)]
 	pub type Payee<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, RewardDestination<T::AccountId>, ValueQuery>;
 
+	/// The accounts that the reward of a stash is split across, if its [`Payee`] is
+	/// [`RewardDestination::Split`]. Keyed by stash.
+	///
+	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
+	#[pallet::storage]
+	pub type PayeeSplits<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, PayeeSplitsOf<T>, ValueQuery>;
+
 	/// The map from (wannabe) validator stash key to the preferences of that validator.
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
 	#[pallet::storage]
-	#[pallet::getter(fn validators)]
 	pub type Validators<T: Config> =
 		CountedStorageMap<_, Twox64Concat, T::AccountId, ValidatorPrefs, ValueQuery>;
 
@@ -363,10 +696,22 @@ pub mod pallet {
 	///
 	/// TWOX-NOTE: SAFE since `AccountId` is a secure hash.
 	#[pallet::storage]
-	#[pallet::getter(fn nominators)]
 	pub type Nominators<T: Config> =
 		CountedStorageMap<_, Twox64Concat, T::AccountId, Nominations<T>>;
 
+	/// The nominators of each target, as per [`Nominators`], such that they can be looked up
+	/// without going through all the nominators.
+	#[pallet::storage]
+	pub type NominatorsOf<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		(),
+		OptionQuery,
+	>;
+
 	/// The maximum nominator count before we stop allowing new validators to join.
 	///
 	/// When this value is not set, no limits are enforced.
@@ -378,7 +723,6 @@ pub mod pallet {
 	/// This is the latest planned era, depending on how the Session pallet queues the validator
 	/// set, it might be active or not.
 	#[pallet::storage]
-	#[pallet::getter(fn current_era)]
 	pub type CurrentEra<T> = StorageValue<_, EraIndex>;
 
 	/// The active era information, it holds index and start.
@@ -386,7 +730,6 @@ pub mod pallet {
 	/// The active era is the era being currently rewarded. Validator set of this era must be
 	/// equal to [`SessionInterface::validators`].
 	#[pallet::storage]
-	#[pallet::getter(fn active_era)]
 	pub type ActiveEra<T> = StorageValue<_, ActiveEraInfo>;
 
 	/// The session index at which the era start for the last `HISTORY_DEPTH` eras.
@@ -394,60 +737,122 @@ pub mod pallet {
 	/// Note: This tracks the starting session (i.e. session index when era start being active)
 	/// for the eras in `[CurrentEra - HISTORY_DEPTH, CurrentEra]`.
 	#[pallet::storage]
-	#[pallet::getter(fn eras_start_session_index)]
 	pub type ErasStartSessionIndex<T> = StorageMap<_, Twox64Concat, EraIndex, SessionIndex>;
 
-	/// Exposure of validator at era.
+	/// The `(own, total, nominator_count)` of the exposure of a validator at era.
+	///
+	/// The nominators themselves are in the [`ErasStakersPaged`] of the validator, whose number
+	/// of pages follows from `nominator_count`, see [`Pallet::exposure_page_count`].
 	///
 	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
 	///
 	/// Is it removed after `HISTORY_DEPTH` eras.
-	/// If stakers hasn't been set or has been removed then empty exposure is returned.
 	#[pallet::storage]
-	#[pallet::getter(fn eras_stakers)]
-	#[pallet::unbounded]
-	pub type ErasStakers<T: Config> = StorageDoubleMap<
+	pub type ErasStakersTotals<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
 		EraIndex,
 		Twox64Concat,
 		T::AccountId,
-		Exposure<T::AccountId, BalanceOf<T>>,
-		ValueQuery,
+		(BalanceOf<T>, BalanceOf<T>, u32),
 	>;
 
-	/// Clipped Exposure of validator at era.
+	/// The nominators of a validator at era, in pages of at most
+	/// `T::MaxNominatorRewardedPerValidator`, sorted by stake.
+	///
+	/// A validator that is exposed in an era has at least page 0, which is the only page that
+	/// pays the validator itself. Each page carries the `own` and `total` of the whole exposure.
 	///
-	/// This is similar to [`ErasStakers`] but number of nominators exposed is reduced to the
-	/// `T::MaxNominatorRewardedPerValidator` biggest stakers.
-	/// (Note: the field `total` and `own` of the exposure remains unchanged).
-	/// This is used to limit the i/o cost for the nominator payout.
+	/// The pages are compressed, use [`Pallet::eras_stakers_paged`] to read them.
+	///
+	/// This is keyed first by the era index to allow bulk deletion, then the stash account and
+	/// the page index.
+	///
+	/// Is it removed after `HISTORY_DEPTH` eras.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type ErasStakersPaged<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, EraIndex>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, u32>,
+		),
+		CompactExposure<T::AccountId, BalanceOf<T>, ExposurePageSizeOf<T>>,
+		OptionQuery,
+	>;
+
+	/// The era whose rewards are being paid out automatically, along with the number of its
+	/// pages left to pay out in [`AutoPayoutPages`], see [`Config::MaxAutoPayoutsPerBlock`].
+	#[pallet::storage]
+	pub type AutoPayoutEra<T: Config> = StorageValue<_, (EraIndex, u32), OptionQuery>;
+
+	/// The pages of the [`AutoPayoutEra`] left to pay out, by index. They are paid out from the
+	/// highest index down.
+	#[pallet::storage]
+	pub type AutoPayoutPages<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (T::AccountId, u32), OptionQuery>;
+
+	/// The pages of the exposure of a validator at era that have been paid out, sorted.
 	///
-	/// This is keyed fist by the era index to allow bulk deletion and then the stash account.
+	/// The pages themselves are in [`ErasStakersPaged`].
 	///
 	/// Is it removed after `HISTORY_DEPTH` eras.
-	/// If stakers hasn't been set or has been removed then empty exposure is returned.
 	#[pallet::storage]
 	#[pallet::unbounded]
-	#[pallet::getter(fn eras_stakers_clipped)]
-	pub type ErasStakersClipped<T: Config> = StorageDoubleMap<
+	pub type ClaimedRewards<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
 		EraIndex,
 		Twox64Concat,
 		T::AccountId,
-		Exposure<T::AccountId, BalanceOf<T>>,
+		Vec<u32>,
+		ValueQuery,
+	>;
+
+	/// The nominators of a page of the exposure of a validator at era that have claimed their own
+	/// reward with [`Call::claim_my_reward`], as a bitmap of their positions in the page.
+	///
+	/// Removed once the rest of the page is paid out, see [`ClaimedRewards`], and after
+	/// `HISTORY_DEPTH` eras.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type ClaimedNominatorRewards<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, EraIndex>,
+			NMapKey<Twox64Concat, T::AccountId>,
+			NMapKey<Twox64Concat, u32>,
+		),
+		Vec<u8>,
 		ValueQuery,
 	>;
 
-	/// Similar to `ErasStakers`, this holds the preferences of validators.
+	/// The era that the exposures in [`SubmittedExposures`] are for.
+	#[pallet::storage]
+	pub type SubmittedExposuresEra<T> = StorageValue<_, EraIndex, OptionQuery>;
+
+	/// The exposures submitted with [`Call::submit_exposures`], by validator.
+	///
+	/// Taken in place of an election when the era of [`SubmittedExposuresEra`] is planned. There
+	/// are at most `MaxWinners` of them, each with at most `MaxBackersPerWinner` nominators.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type SubmittedExposures<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Exposure<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	/// Similar to `ErasStakersTotals`, this holds the preferences of validators.
 	///
 	/// This is keyed first by the era index to allow bulk deletion and then the stash account.
 	///
 	/// Is it removed after `HISTORY_DEPTH` eras.
 	// If prefs hasn't been set or has been removed then 0 commission is returned.
 	#[pallet::storage]
-	#[pallet::getter(fn eras_validator_prefs)]
 	pub type ErasValidatorPrefs<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
@@ -458,57 +863,260 @@ pub mod pallet {
 		ValueQuery,
 	>;
 
+	/// The [`LoyaltyStreak`] of a nominator towards each validator that they have been exposed to.
+	///
+	/// This is keyed first by the nominator stash and then by the validator stash. Only updated
+	/// if [`Config::LoyaltyBonus`] is not zero.
+	///
+	/// The streaks that no era within `HISTORY_DEPTH` accounts for anymore are pruned in
+	/// `on_idle`, see [`LoyaltyPruningCursor`].
+	#[pallet::storage]
+	pub type NominatorLoyalty<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		LoyaltyStreak,
+		OptionQuery,
+	>;
+
+	/// The progress of the pruning of [`NominatorLoyalty`], as the active era of the last sweep,
+	/// and the last streak visited as `(nominator, validator)` while the sweep is not over.
+	#[pallet::storage]
+	pub(crate) type LoyaltyPruningCursor<T: Config> =
+		StorageValue<_, (EraIndex, Option<(T::AccountId, T::AccountId)>)>;
+
+	/// The era in which a nominator dropped a validator through [`Pallet::drop_nomination`],
+	/// unless it nominated the validator again since.
+	///
+	/// This is keyed first by the nominator stash and then by the validator stash. The nominator
+	/// is neither rewarded nor slashed for its exposure to the validator in later eras, even if it
+	/// is in an exposure of the validator that was computed before the drop.
+	///
+	/// Entries are pruned through [`ErasNominationExits`].
+	#[pallet::storage]
+	pub type NominationExits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		T::AccountId,
+		EraIndex,
+		OptionQuery,
+	>;
+
+	/// The entries of [`NominationExits`], keyed by the last era whose exposures may include the
+	/// nominator, which is the era planned at the time of the drop.
+	///
+	/// The entries of an era are pruned, along with the matching [`NominationExits`], once that
+	/// era can neither be paid out nor slashed anymore.
+	#[pallet::storage]
+	pub type ErasNominationExits<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		Twox64Concat,
+		(T::AccountId, T::AccountId),
+		EraIndex,
+		OptionQuery,
+	>;
+
+	/// The last change of the commission of each validator stash.
+	///
+	/// Kept when the validator is chilled, so that the commitments still hold if it validates
+	/// again, and removed once the stash is reaped.
+	#[pallet::storage]
+	pub type CommissionChanges<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, CommissionChange<BlockNumberFor<T>>, OptionQuery>;
+
+	/// The last nominator visited by [`Pallet::kick_by`] for each validator stash and filter, if
+	/// it has not gone through all the nominators of the validator yet.
+	#[pallet::storage]
+	pub type KickCursor<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
+		KickFilter<BalanceOf<T>>,
+		T::AccountId,
+		OptionQuery,
+	>;
+
 	/// The total validator era payout for the last `HISTORY_DEPTH` eras.
 	///
 	/// Eras that haven't finished yet or has been removed doesn't have reward.
 	#[pallet::storage]
-	#[pallet::getter(fn eras_validator_reward)]
 	pub type ErasValidatorReward<T: Config> = StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>>;
 
 	/// Rewards for the last `HISTORY_DEPTH` eras.
 	/// If reward hasn't been set or has been removed then 0 reward is returned.
 	#[pallet::storage]
 	#[pallet::unbounded]
-	#[pallet::getter(fn eras_reward_points)]
 	pub type ErasRewardPoints<T: Config> =
 		StorageMap<_, Twox64Concat, EraIndex, EraRewardPoints<T::AccountId>, ValueQuery>;
 
+	/// The number of blocks authored in the last `HISTORY_DEPTH` eras.
+	#[pallet::storage]
+	pub type ErasAuthoredBlocks<T: Config> = StorageMap<_, Twox64Concat, EraIndex, u32, ValueQuery>;
+
+	/// The most recently reported offences, as the hash of the kind and time slot of the offence
+	/// with the offender and its slash fraction, and the session of the offence.
+	///
+	/// Used to ignore offences that are reported more than once.
+	#[pallet::storage]
+	pub type ReportedOffences<T: Config> =
+		StorageValue<_, BoundedVec<(T::Hash, SessionIndex), T::MaxRememberedOffences>, ValueQuery>;
+
+	/// The part of the remainder of the last era payout that is added to the validator payout of
+	/// the next one, as per [`Config::RemainderPolicy`].
+	#[pallet::storage]
+	pub type CarriedOverRemainder<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
+
+	/// The duration of the last era that ended, in milliseconds.
+	///
+	/// Used to project the rewards of the next eras, see [`Pallet::api_project_rewards`].
+	#[pallet::storage]
+	pub type LastEraDuration<T> = StorageValue<_, u64>;
+
+	/// The inflation of the last `HISTORY_DEPTH` eras, recorded when they end.
+	#[pallet::storage]
+	pub type ErasInflation<T: Config> =
+		StorageMap<_, Twox64Concat, EraIndex, EraInflation<BalanceOf<T>>>;
+
+	/// The runners-up of the election of each of the last `HISTORY_DEPTH` eras, best first, with
+	/// the exposure they would have had.
+	///
+	/// See [`Config::MaxRunnersUp`]. The runners-up that substitute a validator with
+	/// [`Call::replace_validator`] are taken out.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub type ErasRunnersUp<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		BoundedVec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>), T::MaxRunnersUp>,
+		ValueQuery,
+	>;
+
+	/// The validators of the active era replaced by a runner-up, with their substitute.
+	///
+	/// A substitute takes the seat of the replaced validator from the session after the next one,
+	/// and the replaced validator is kept disabled until then.
+	#[pallet::storage]
+	pub type ReplacedValidators<T: Config> =
+		StorageValue<_, BoundedVec<(T::AccountId, T::AccountId), T::MaxRunnersUp>, ValueQuery>;
+
+	/// Whether some substitutes of [`ReplacedValidators`] are yet to be handed to the session.
+	#[pallet::storage]
+	pub type ReplacementsQueued<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// What happened to each account in the last `HISTORY_DEPTH` eras, recorded along with the
+	/// corresponding events, so that it can be queried without scanning them.
+	///
+	/// This is keyed first by the era index to allow bulk deletion and then the account.
+	#[pallet::storage]
+	pub type AccountEraActivity<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		Twox64Concat,
+		T::AccountId,
+		AccountActivity,
+		ValueQuery,
+	>;
+
 	/// The total amount staked for the last `HISTORY_DEPTH` eras.
 	/// If total hasn't been set or has been removed then 0 stake is returned.
 	#[pallet::storage]
-	#[pallet::getter(fn eras_total_stake)]
 	pub type ErasTotalStake<T: Config> =
 		StorageMap<_, Twox64Concat, EraIndex, BalanceOf<T>, ValueQuery>;
 
 	/// Mode of era forcing.
 	#[pallet::storage]
-	#[pallet::getter(fn force_era)]
 	pub type ForceEra<T> = StorageValue<_, Forcing, ValueQuery>;
 
+	/// The number of sessions by which the next era, and thus the next election, is delayed.
+	///
+	/// Cleared once the next era is planned. See [`ElectionSchedule::set_election_offset`].
+	#[pallet::storage]
+	pub type ElectionSessionOffset<T> = StorageValue<_, SessionIndex, ValueQuery>;
+
+	/// The block from which the deferred election of the next era is retried, and the number of
+	/// times it has been deferred so far, see [`Config::DeferOverweightElection`].
+	#[pallet::storage]
+	pub type DeferredElection<T: Config> = StorageValue<_, (BlockNumberFor<T>, u32)>;
+
+	/// The validators elected by a deferred election, which are handed to the session when the
+	/// next session is planned.
+	#[pallet::storage]
+	pub type DeferredElectionWinners<T: Config> =
+		StorageValue<_, BoundedVec<T::AccountId, MaxWinnersOf<T>>>;
+
+	/// The block in which the current era was planned.
+	///
+	/// Only used when [`Config::EraLength`] is [`EraLength::BlocksPerEra`]. If missing, the
+	/// current era ends with the next session.
+	#[pallet::storage]
+	pub type CurrentEraStartBlock<T: Config> = StorageValue<_, BlockNumberFor<T>>;
+
 	/// The percentage of the slash that is distributed to reporters.
 	///
 	/// The rest of the slashed value is handled by the `Slash`.
 	#[pallet::storage]
-	#[pallet::getter(fn slash_reward_fraction)]
 	pub type SlashRewardFraction<T> = StorageValue<_, Perbill, ValueQuery>;
 
+	/// The number of eras that slashes are deferred by, if it differs from
+	/// [`Config::SlashDeferDuration`].
+	#[pallet::storage]
+	pub type SlashDeferDurationOverride<T> = StorageValue<_, EraIndex>;
+
 	/// The amount of currency given to reporters of a slash event which was
 	/// canceled by extraordinary circumstances (e.g. governance).
 	#[pallet::storage]
-	#[pallet::getter(fn canceled_payout)]
 	pub type CanceledSlashPayout<T: Config> = StorageValue<_, BalanceOf<T>, ValueQuery>;
 
-	/// All unapplied slashes that are queued for later.
+	/// All unapplied slashes that are queued for later, per era they are applied in and id.
+	///
+	/// The id of a slash is announced by `SlashDeferred`, and never changes.
 	#[pallet::storage]
 	#[pallet::unbounded]
-	pub type UnappliedSlashes<T: Config> = StorageMap<
+	pub type UnappliedSlashes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		EraIndex,
+		Twox64Concat,
+		u32,
+		UnappliedSlash<T::AccountId, BalanceOf<T>>,
+	>;
+
+	/// The id of the next slash deferred to [`UnappliedSlashes`].
+	#[pallet::storage]
+	pub type NextUnappliedSlashId<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The total of the deferred slashes of each staker, per era they are applied in.
+	///
+	/// Mirrors [`UnappliedSlashes`], such that the pending slashes of a staker can be looked up
+	/// without going through all of them.
+	#[pallet::storage]
+	pub type PendingSlashes<T: Config> = StorageDoubleMap<
 		_,
 		Twox64Concat,
+		T::AccountId,
+		Twox64Concat,
 		EraIndex,
-		Vec<UnappliedSlash<T::AccountId, BalanceOf<T>>>,
+		BalanceOf<T>,
 		ValueQuery,
 	>;
 
+	/// The era whose deferred slashes are still being applied, in the following blocks.
+	///
+	/// Meanwhile, only the slashes not applied yet are left in [`UnappliedSlashes`] for that era,
+	/// and the stakers with a pending slash in it cannot withdraw. See
+	/// [`Config::MaxSlashesPerBlock`].
+	#[pallet::storage]
+	pub type UnappliedSlashesCursor<T> = StorageValue<_, EraIndex>;
+
 	/// A mapping from still-bonded eras to the first session index of that era.
 	///
 	/// Must contains information for eras for the range:
@@ -537,7 +1145,6 @@ pub mod pallet {
 
 	/// Slashing spans for stash accounts.
 	#[pallet::storage]
-	#[pallet::getter(fn slashing_spans)]
 	#[pallet::unbounded]
 	pub type SlashingSpans<T: Config> =
 		StorageMap<_, Twox64Concat, T::AccountId, slashing::SlashingSpans>;
@@ -557,7 +1164,6 @@ pub mod pallet {
 	///
 	/// This is basically in sync with the call to [`pallet_session::SessionManager::new_session`].
 	#[pallet::storage]
-	#[pallet::getter(fn current_planned_session)]
 	pub type CurrentPlannedSession<T> = StorageValue<_, SessionIndex, ValueQuery>;
 
 	/// Indices of validators that have offended in the active era and whether they are currently
@@ -571,7 +1177,6 @@ pub mod pallet {
 	/// the era ends.
 	#[pallet::storage]
 	#[pallet::unbounded]
-	#[pallet::getter(fn offending_validators)]
 	pub type OffendingValidators<T: Config> = StorageValue<_, Vec<(u32, bool)>, ValueQuery>;
 
 	/// The threshold for when users can start calling `chill_other` for other validators /
@@ -580,6 +1185,91 @@ pub mod pallet {
 	#[pallet::storage]
 	pub(crate) type ChillThreshold<T: Config> = StorageValue<_, Percent, OptionQuery>;
 
+	/// The stashes that have been migrated through [`Pallet::migrate_stash`], with the stash they
+	/// have been migrated to and the era of the migration.
+	///
+	/// Kept so that the rewards and the slashes of the eras in which the old stash was exposed
+	/// keep landing on its bond, and pruned through [`ErasStashMigrations`] along with
+	/// [`MigratedFrom`] once these eras can neither be paid out nor slashed anymore. An old stash
+	/// cannot be bonded again until then.
+	#[pallet::storage]
+	pub type MigratedStashes<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, (T::AccountId, EraIndex)>;
+
+	/// The reverse of [`MigratedStashes`]: the stash that each new stash was migrated from.
+	///
+	/// A new stash cannot be migrated again while it is here, so that [`MigratedStashes`] never
+	/// has to be followed more than once.
+	#[pallet::storage]
+	pub type MigratedFrom<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+	/// The old stashes of [`MigratedStashes`], keyed by the era of their migration.
+	#[pallet::storage]
+	pub type ErasStashMigrations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, EraIndex, Twox64Concat, T::AccountId, ()>;
+
+	/// The validator stashes migrated through [`Pallet::migrate_stash`] whose nominators still
+	/// have to be pointed to the new stash, from the old stash to the new one.
+	#[pallet::storage]
+	pub(crate) type StashMigrationQueue<T: Config> =
+		StorageMap<_, Twox64Concat, T::AccountId, T::AccountId>;
+
+	/// The migration taken out of [`StashMigrationQueue`] whose nominators are being pointed to
+	/// the new stash, as `(old, new, last nominator visited)`.
+	#[pallet::storage]
+	pub(crate) type NominationsMigrationCursor<T: Config> =
+		StorageValue<_, (T::AccountId, T::AccountId, Option<T::AccountId>)>;
+
+	/// The progress of [`Pallet::collect_exposures_paged`], as the number of winners whose
+	/// supports are collected and the number of them converted so far.
+	#[pallet::storage]
+	pub(crate) type ExposureCollection<T> = StorageValue<_, (u32, u32)>;
+
+	/// The supports left to convert by [`Pallet::collect_exposures_paged`], by winner index.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub(crate) type ExposureCollectionSupports<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		u32,
+		(
+			T::AccountId,
+			BoundedSupport<
+				T::AccountId,
+				<T::ElectionProvider as ElectionProviderBase>::MaxBackersPerWinner,
+			>,
+		),
+	>;
+
+	/// The exposures converted so far by [`Pallet::collect_exposures_paged`], by winner index.
+	#[pallet::storage]
+	#[pallet::unbounded]
+	pub(crate) type CollectedExposures<T: Config> =
+		StorageMap<_, Twox64Concat, u32, (T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)>;
+
+	/// The utilization of the last voter snapshot.
+	#[pallet::storage]
+	pub type LastVoterSnapshot<T> = StorageValue<_, SnapshotUtilization, ValueQuery>;
+
+	/// The last voter included in the last voter snapshot, with its vote weight, if the snapshot
+	/// left some voters of [`Config::VoterList`] out.
+	///
+	/// Not recorded when the voters are sampled, see [`Config::VoterSamplingSeed`].
+	#[pallet::storage]
+	pub type VoterSnapshotCutoff<T: Config> = StorageValue<_, (T::AccountId, VoteWeight)>;
+
+	/// The utilization of the last target snapshot.
+	#[pallet::storage]
+	pub type LastTargetSnapshot<T> = StorageValue<_, SnapshotUtilization, ValueQuery>;
+
+	/// The number of elections that failed in a row since the last successful one.
+	#[pallet::storage]
+	pub type ConsecutiveElectionFailures<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// The block of the last successful election.
+	#[pallet::storage]
+	pub type LastSuccessfulElection<T: Config> = StorageValue<_, BlockNumberFor<T>, OptionQuery>;
+
 	#[pallet::genesis_config]
 	#[derive(frame_support::DefaultNoBound)]
 	pub struct GenesisConfig<T: Config> {
@@ -674,8 +1364,8 @@ pub mod pallet {
 		/// An old slashing report from a prior era was discarded because it could
 		/// not be processed.
 		OldSlashingReportDiscarded { session_index: SessionIndex },
-		/// A new set of stakers was elected.
-		StakersElected,
+		/// A new set of stakers was elected, with the given election `score`.
+		StakersElected { score: ElectionScore },
 		/// An account has bonded this amount. \[stash, amount\]
 		///
 		/// NOTE: This event is only emitted when funds are bonded via a dispatchable. Notably,
@@ -702,6 +1392,87 @@ pub mod pallet {
 		SnapshotTargetsSizeExceeded { size: u32 },
 		/// A new force era mode was set.
 		ForceEra { mode: Forcing },
+		/// Summary of how the validators elected for `era_index` are backed. A validator is
+		/// counted as `self_backed` if more than half of its exposure is its own stake, and as
+		/// `nominator_backed` otherwise.
+		ExposureDominance { era_index: EraIndex, self_backed: u32, nominator_backed: u32 },
+		/// The validator set that the session pallet has queued (if `queued`) or activated for
+		/// `era_index` does not match the validators elected for that era.
+		SessionValidatorsMismatch { era_index: EraIndex, queued: bool, elected: u32, session: u32 },
+		/// The election was deferred to the next block because its estimated weight exceeds the
+		/// weight remaining in the block.
+		ElectionDeferred { estimated_weight: Weight, remaining_weight: Weight },
+		/// The payout of `validator_stash` for `era` in a batch of payouts failed with `error`.
+		PayoutFailed { validator_stash: T::AccountId, era: EraIndex, error: DispatchError },
+		/// The slashing parameters have been updated, to the given values.
+		SlashingParamsSet { defer_duration: EraIndex, reward_fraction: Perbill },
+		/// The validator `stash` asked to be chilled, and has been put in the exit queue.
+		ExitQueued { stash: T::AccountId },
+		/// The new validator `stash` is not electable before the planned session `until`.
+		ValidatorWaitlisted { stash: T::AccountId, until: SessionIndex },
+		/// Voters weight limit reached, after consuming `weight`.
+		SnapshotVotersWeightExceeded { weight: Weight },
+		/// Targets weight limit reached, after consuming `weight`.
+		SnapshotTargetsWeightExceeded { weight: Weight },
+		/// The voters snapshot exceeded its bounds, and `removed` voters were dropped from it.
+		SnapshotVotersTruncated { removed: u32 },
+		/// The targets snapshot exceeded its bounds, and `removed` targets were dropped from it.
+		SnapshotTargetsTruncated { removed: u32 },
+		/// The remainder of the payout of `era_index` was routed as per
+		/// [`Config::RemainderPolicy`].
+		RemainderRouted {
+			era_index: EraIndex,
+			issued: BalanceOf<T>,
+			sunk: BoundedVec<(T::AccountId, BalanceOf<T>), ConstU32<MAX_REMAINDER_SINKS>>,
+			carried_over: BalanceOf<T>,
+			burnt: BalanceOf<T>,
+		},
+		/// An offence of `validator` in `session` was reported again, and ignored.
+		OffenceAlreadyReported { validator: T::AccountId, session: SessionIndex },
+		/// The nominator `stash` dropped `target` from its nominations, and is no longer
+		/// rewarded nor slashed for its exposure to `target` after `era`.
+		NominationDropped { stash: T::AccountId, target: T::AccountId, era: EraIndex },
+		/// The commission tiers have been set to `tiers`.
+		CommissionTiersSet { tiers: BoundedVec<(Perbill, Perbill), T::MaxCommissionTiers> },
+		/// The nominator `stash` has been chilled to make room for `by`, which has a higher bond.
+		NominatorKicked { stash: T::AccountId, by: T::AccountId },
+		/// The exposures of `validators` validators have been submitted for `era`.
+		ExposuresSubmitted { era: EraIndex, validators: u32 },
+		/// The bond of `old_stash` has been migrated to `new_stash`.
+		StashMigrated { old_stash: T::AccountId, new_stash: T::AccountId },
+		/// All the nominations of `old_stash` have been moved to `new_stash`.
+		NominationsMigrated { old_stash: T::AccountId, new_stash: T::AccountId },
+		/// The slash of `validator` for `slash_era` has been deferred to `era`, as `id` in
+		/// [`UnappliedSlashes`].
+		SlashDeferred { validator: T::AccountId, slash_era: EraIndex, era: EraIndex, id: u32 },
+		/// The deferred slash `id` of `validator` in the [`UnappliedSlashes`] of `era` has been
+		/// cancelled.
+		SlashCancelled { validator: T::AccountId, era: EraIndex, id: u32 },
+		/// The deferred slash `id` of `validator` in the [`UnappliedSlashes`] of `era` has been
+		/// reduced to a slash of `fraction`.
+		SlashReduced { validator: T::AccountId, era: EraIndex, id: u32, fraction: Perbill },
+		/// The winner `vetoed` was kept out of `era` as it is known to be dead, and its seat was
+		/// given to `substitute`, if any runner-up was found.
+		WinnerVetoed { era: EraIndex, vetoed: T::AccountId, substitute: Option<T::AccountId> },
+		/// The share of the slashed funds that is burned has been set to `burned`.
+		SlashDestinationSet { burned: Perbill },
+		/// The disabled validator at `index` of the session has been re-enabled.
+		ValidatorReEnabled { index: u32 },
+		/// The validator `replaced` has been replaced for the rest of `era` by the runner-up
+		/// `substitute`.
+		ValidatorReplaced { era: EraIndex, replaced: T::AccountId, substitute: T::AccountId },
+		/// The offence penalty tiers have been set.
+		OffencePenaltyTiersSet {
+			tiers: BoundedVec<(Perbill, RewardPoint), T::MaxOffencePenaltyTiers>,
+		},
+		/// The validator lost `points` era points in `era`, the era of its offence.
+		OffencePenalized { validator: T::AccountId, era: EraIndex, points: RewardPoint },
+		/// The payout of `validator_stash` for `era` in a batch of payouts failed as the era is
+		/// too old, `oldest_claimable` being the oldest era that can still be claimed.
+		PayoutEraTooOld { validator_stash: T::AccountId, era: EraIndex, oldest_claimable: EraIndex },
+		/// The next session change could not be estimated, and the current session was assumed
+		/// to end `fallback` blocks later in predicting the next election.
+		NextSessionUnknown { fallback: BlockNumberFor<T> },
 	}
 
 	#[pallet::error]
@@ -731,6 +1502,9 @@ pub mod pallet {
 		/// Attempting to target a stash that still has funds.
 		FundedTarget,
 		/// Invalid era to reward.
+		///
+		/// No longer returned, see [`Error::EraInFuture`], [`Error::EraTooOld`] and
+		/// [`Error::RewardsNotRecordedYet`].
 		InvalidEraToReward,
 		/// Invalid number of nominations.
 		InvalidNumberOfNominations,
@@ -758,15 +1532,92 @@ pub mod pallet {
 		TooManyValidators,
 		/// Commission is too low. Must be at least `MinCommission`.
 		CommissionTooLow,
+		/// The commission is raised by more than the validator committed to within an era.
+		CommissionIncreaseTooHigh,
+		/// The commission or its commitments are changed too soon after the last change.
+		CommissionChangeTooSoon,
 		/// Some bound is not met.
 		BoundNotMet,
+		/// The era to reward is older than `HistoryDepth` eras, and its rewards can no longer be
+		/// claimed. The oldest era that can still be claimed is `oldest_claimable`.
+		EraTooOld { oldest_claimable: EraIndex },
+		/// The rewards of the era to reward have not been recorded yet, as the era has not ended.
+		RewardsNotRecordedYet,
+		/// The slash defer duration is not less than the bonding duration.
+		InvalidSlashDeferDuration,
+		/// The slash defer duration cannot be shortened while slashes are still deferred.
+		SlashesPending,
+		/// The commission is not within any of the `CommissionTiers`.
+		CommissionNotInTier,
+		/// The commission tiers are too many, not sorted, or overlapping.
+		InvalidCommissionTiers,
+		/// The reward destination is an empty split, or its shares add up to more than 100%, or
+		/// it is a split whose accounts are not set.
+		InvalidPayeeSplit,
+		/// The target is not nominated by the stash.
+		NotNominated,
+		/// The validator has no such page of exposure in this era.
+		InvalidPage,
+		/// The stash is not exposed to the validator in this era.
+		NotExposed,
+		/// Exposures are computed out of an election, and can't be submitted.
+		ExternalExposuresDisabled,
+		/// The submitted exposures are not for the next era, or do not match the stakes.
+		InvalidExposures,
+		/// The validator is not disabled.
+		NotDisabled,
+		/// The stash is not a validator of the active era, or has already been replaced.
+		NotActiveValidator,
+		/// No runner-up of the active era is left to substitute the validator.
+		NoRunnerUp,
+		/// The next era has already been planned.
+		NextEraPlanned,
+		/// The offence penalty tiers are too many, or not sorted by slash fraction.
+		InvalidPenaltyTiers,
+		/// The stash has a deferred slash that is being applied, and cannot withdraw until then.
+		SlashBeingApplied,
+		/// The new stash of a validator has no session keys.
+		NoSessionKeys,
+		/// The stash was migrated to recently, and cannot be migrated again until the eras it was
+		/// migrated in are out of the history.
+		StashMigratedRecently,
+		/// The slash fraction is not lower than the one of the slash to reduce.
+		InvalidSlashFraction,
+		/// The era to reward has not been planned yet.
+		EraInFuture,
+	}
+
+	/// A reason for the staking pallet placing a hold on funds.
+	#[pallet::composite_enum]
+	pub enum HoldReason {
+		/// The bond of a stash is being moved to its new stash by [`Pallet::migrate_stash`].
+		#[codec(index = 0)]
+		StashMigration,
 	}
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
-		fn on_initialize(_now: BlockNumberFor<T>) -> Weight {
-			// just return the weight of the on_finalize.
-			T::DbWeight::get().reads(1)
+		fn on_initialize(now: BlockNumberFor<T>) -> Weight {
+			// the weight of the on_finalize, and of reading the cursor and the deferred election.
+			let weight =
+				T::DbWeight::get().reads(3).saturating_add(Self::retry_deferred_election(now));
+			match UnappliedSlashesCursor::<T>::get() {
+				Some(era) => weight.saturating_add(Self::apply_unapplied_slashes(
+					era,
+					T::MaxSlashesPerBlock::get(),
+				)),
+				None => weight,
+			}
+		}
+
+		fn on_idle(_now: BlockNumberFor<T>, remaining_weight: Weight) -> Weight {
+			let mut consumed = Self::do_migrate_nominations(remaining_weight);
+			consumed.saturating_accrue(match T::MaxAutoPayoutsPerBlock::get() {
+				0 => Weight::zero(),
+				budget => Self::do_auto_payouts(remaining_weight.saturating_sub(consumed), budget),
+			});
+			consumed
+				.saturating_add(Self::do_prune_loyalty(remaining_weight.saturating_sub(consumed)))
 		}
 
 		fn on_finalize(_n: BlockNumberFor<T>) {
@@ -783,32 +1634,233 @@ pub mod pallet {
 			// `on_finalize` weight is tracked in `on_initialize`
 		}
 
-		fn integrity_test() {
-			// ensure that we funnel the correct value to the `DataProvider::MaxVotesPerVoter`;
-			assert_eq!(
-				MaxNominationsOf::<T>::get(),
-				<Self as ElectionDataProvider>::MaxVotesPerVoter::get()
-			);
-			// and that MaxNominations is always greater than 1, since we count on this.
-			assert!(!MaxNominationsOf::<T>::get().is_zero());
+		fn integrity_test() {
+			// ensure that we funnel the correct value to the `DataProvider::MaxVotesPerVoter`;
+			assert_eq!(
+				MaxNominationsOf::<T>::get(),
+				<Self as ElectionDataProvider>::MaxVotesPerVoter::get()
+			);
+			// and that MaxNominations is always greater than 1, since we count on this.
+			assert!(!MaxNominationsOf::<T>::get().is_zero());
+
+			// ensure election results are always bounded with the same value
+			assert!(
+				<T::ElectionProvider as ElectionProviderBase>::MaxWinners::get() ==
+					<T::GenesisElectionProvider as ElectionProviderBase>::MaxWinners::get()
+			);
+
+			assert!(
+				T::SlashDeferDuration::get() < T::BondingDuration::get() || T::BondingDuration::get() == 0,
+				"As per documentation, slash defer duration ({}) should be less than bonding duration ({}).",
+				T::SlashDeferDuration::get(),
+				T::BondingDuration::get(),
+			);
+
+			if let Some(min_unbonding_duration) = T::MinUnbondingDuration::get() {
+				assert!(
+					T::SlashDeferDuration::get() < min_unbonding_duration,
+					"As per documentation, slash defer duration ({}) should be less than min unbonding duration ({}).",
+					T::SlashDeferDuration::get(),
+					min_unbonding_duration,
+				)
+			}
+		}
+
+		#[cfg(feature = "try-runtime")]
+		fn try_state(n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
+			Self::do_try_state(n)
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Get the ideal number of active validators.
+		pub fn validator_count() -> u32 {
+			ValidatorCount::<T>::get()
+		}
+
+		/// Get the minimum number of staking participants before emergency conditions are imposed.
+		pub fn minimum_validator_count() -> u32 {
+			MinimumValidatorCount::<T>::get()
+		}
+
+		/// Get the validators that may never be slashed or forcibly kicked out.
+		pub fn invulnerables() -> Vec<T::AccountId> {
+			Invulnerables::<T>::get()
+		}
+
+		/// Get the controller of a stash account.
+		pub fn bonded<EncodeLikeAccountId>(account_id: EncodeLikeAccountId) -> Option<T::AccountId>
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			Bonded::<T>::get(account_id)
+		}
+
+		/// Get the staking ledger of a controller account.
+		pub fn ledger<EncodeLikeAccountId>(
+			controller: EncodeLikeAccountId,
+		) -> Option<StakingLedger<T>>
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			Ledger::<T>::get(controller)
+		}
+
+		/// Get the staking ledger of a controller account, or [`Error::NotController`] if it is
+		/// not one.
+		pub fn try_ledger(controller: &T::AccountId) -> Result<StakingLedger<T>, Error<T>> {
+			Ledger::<T>::get(controller).ok_or(Error::<T>::NotController)
+		}
+
+		/// Get the controller of a stash account, or [`Error::NotStash`] if it is not one.
+		pub fn try_bonded(stash: &T::AccountId) -> Result<T::AccountId, Error<T>> {
+			Bonded::<T>::get(stash).ok_or(Error::<T>::NotStash)
+		}
+
+		/// Get the `(active, total)` balance of the ledger of a stash account.
+		pub fn stake_of<EncodeLikeAccountId>(
+			account_id: EncodeLikeAccountId,
+		) -> Option<(BalanceOf<T>, BalanceOf<T>)>
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			StakeOf::<T>::get(account_id)
+		}
+
+		/// Get the reward destination of a stash account.
+		pub fn payee<EncodeLikeAccountId>(
+			account_id: EncodeLikeAccountId,
+		) -> RewardDestination<T::AccountId>
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			Payee::<T>::get(account_id)
+		}
+
+		/// Get the preferences of a validator stash account.
+		pub fn validators<EncodeLikeAccountId>(account_id: EncodeLikeAccountId) -> ValidatorPrefs
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			Validators::<T>::get(account_id)
+		}
+
+		/// Get the nominations of a nominator stash account.
+		pub fn nominators<EncodeLikeAccountId>(
+			account_id: EncodeLikeAccountId,
+		) -> Option<Nominations<T>>
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			Nominators::<T>::get(account_id)
+		}
+
+		/// Get the current, i.e. latest planned, era index.
+		pub fn current_era() -> Option<EraIndex> {
+			CurrentEra::<T>::get()
+		}
+
+		/// Get the active era information.
+		pub fn active_era() -> Option<ActiveEraInfo> {
+			ActiveEra::<T>::get()
+		}
+
+		/// Get the session index at which an era started.
+		pub fn eras_start_session_index<EncodeLikeEraIndex>(
+			era_index: EncodeLikeEraIndex,
+		) -> Option<SessionIndex>
+		where
+			EncodeLikeEraIndex: codec::EncodeLike<EraIndex>,
+		{
+			ErasStartSessionIndex::<T>::get(era_index)
+		}
+
+		/// Get the preferences of a validator at an era.
+		pub fn eras_validator_prefs<EncodeLikeEraIndex, EncodeLikeAccountId>(
+			era_index: EncodeLikeEraIndex,
+			account_id: EncodeLikeAccountId,
+		) -> ValidatorPrefs
+		where
+			EncodeLikeEraIndex: codec::EncodeLike<EraIndex>,
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			ErasValidatorPrefs::<T>::get(era_index, account_id)
+		}
+
+		/// Get the loyalty streak of a nominator towards a validator.
+		pub fn nominator_loyalty<EncodeLikeNominator, EncodeLikeValidator>(
+			nominator: EncodeLikeNominator,
+			validator: EncodeLikeValidator,
+		) -> Option<LoyaltyStreak>
+		where
+			EncodeLikeNominator: codec::EncodeLike<T::AccountId>,
+			EncodeLikeValidator: codec::EncodeLike<T::AccountId>,
+		{
+			NominatorLoyalty::<T>::get(nominator, validator)
+		}
+
+		/// Get the total validator payout of an era.
+		pub fn eras_validator_reward<EncodeLikeEraIndex>(
+			era_index: EncodeLikeEraIndex,
+		) -> Option<BalanceOf<T>>
+		where
+			EncodeLikeEraIndex: codec::EncodeLike<EraIndex>,
+		{
+			ErasValidatorReward::<T>::get(era_index)
+		}
+
+		/// Get the reward points of an era.
+		pub fn eras_reward_points<EncodeLikeEraIndex>(
+			era_index: EncodeLikeEraIndex,
+		) -> EraRewardPoints<T::AccountId>
+		where
+			EncodeLikeEraIndex: codec::EncodeLike<EraIndex>,
+		{
+			ErasRewardPoints::<T>::get(era_index)
+		}
 
-			// ensure election results are always bounded with the same value
-			assert!(
-				<T::ElectionProvider as ElectionProviderBase>::MaxWinners::get() ==
-					<T::GenesisElectionProvider as ElectionProviderBase>::MaxWinners::get()
-			);
+		/// Get the total amount staked at an era.
+		pub fn eras_total_stake<EncodeLikeEraIndex>(era_index: EncodeLikeEraIndex) -> BalanceOf<T>
+		where
+			EncodeLikeEraIndex: codec::EncodeLike<EraIndex>,
+		{
+			ErasTotalStake::<T>::get(era_index)
+		}
 
-			assert!(
-				T::SlashDeferDuration::get() < T::BondingDuration::get() || T::BondingDuration::get() == 0,
-				"As per documentation, slash defer duration ({}) should be less than bonding duration ({}).",
-				T::SlashDeferDuration::get(),
-				T::BondingDuration::get(),
-			)
+		/// Get the mode of era forcing.
+		pub fn force_era() -> Forcing {
+			ForceEra::<T>::get()
 		}
 
-		#[cfg(feature = "try-runtime")]
-		fn try_state(n: BlockNumberFor<T>) -> Result<(), sp_runtime::TryRuntimeError> {
-			Self::do_try_state(n)
+		/// Get the percentage of the slash that is distributed to reporters.
+		pub fn slash_reward_fraction() -> Perbill {
+			SlashRewardFraction::<T>::get()
+		}
+
+		/// Get the amount given to the reporters of the slashes that were cancelled.
+		pub fn canceled_payout() -> BalanceOf<T> {
+			CanceledSlashPayout::<T>::get()
+		}
+
+		/// Get the slashing spans of a stash account.
+		pub fn slashing_spans<EncodeLikeAccountId>(
+			account_id: EncodeLikeAccountId,
+		) -> Option<slashing::SlashingSpans>
+		where
+			EncodeLikeAccountId: codec::EncodeLike<T::AccountId>,
+		{
+			SlashingSpans::<T>::get(account_id)
+		}
+
+		/// Get the last planned session.
+		pub fn current_planned_session() -> SessionIndex {
+			CurrentPlannedSession::<T>::get()
+		}
+
+		/// Get the indices of the validators that offended in the active era, and whether they are
+		/// disabled.
+		pub fn offending_validators() -> Vec<(u32, bool)> {
+			OffendingValidators::<T>::get()
 		}
 	}
 
@@ -839,7 +1891,7 @@ pub mod pallet {
 			let stash = ensure_signed(origin)?;
 			let controller_to_be_deprecated = stash.clone();
 
-			if <Bonded<T>>::contains_key(&stash) {
+			if <Bonded<T>>::contains_key(&stash) || MigratedStashes::<T>::contains_key(&stash) {
 				return Err(Error::<T>::AlreadyBonded.into())
 			}
 
@@ -847,6 +1899,9 @@ pub mod pallet {
 				return Err(Error::<T>::AlreadyPaired.into())
 			}
 
+			// the accounts to split across are only set once bonded.
+			ensure!(payee != RewardDestination::Split, Error::<T>::InvalidPayeeSplit);
+
 			// Reject a bond which is considered to be _dust_.
 			if value < T::Currency::minimum_balance() {
 				return Err(Error::<T>::InsufficientBond.into())
@@ -857,12 +1912,9 @@ pub mod pallet {
 			// You're auto-bonded forever, here. We might improve this by only bonding when
 			// you actually validate/nominate and remove once you unbond __everything__.
 			<Bonded<T>>::insert(&stash, &stash);
+			Status::<T>::insert(&stash, StakerRole::Idle);
 			<Payee<T>>::insert(&stash, payee);
 
-			let current_era = CurrentEra::<T>::get().unwrap_or(0);
-			let history_depth = T::HistoryDepth::get();
-			let last_reward_era = current_era.saturating_sub(history_depth);
-
 			let stash_balance = T::Currency::free_balance(&stash);
 			let value = value.min(stash_balance);
 			Self::deposit_event(Event::<T>::Bonded { stash: stash.clone(), amount: value });
@@ -871,12 +1923,7 @@ pub mod pallet {
 				total: value,
 				active: value,
 				unlocking: Default::default(),
-				claimed_rewards: (last_reward_era..current_era)
-					.try_collect()
-					// Since last_reward_era is calculated as `current_era -
-					// HistoryDepth`, following bound is always expected to be
-					// satisfied.
-					.defensive_map_err(|_| Error::<T>::BoundNotMet)?,
+				legacy_claimed_rewards: Default::default(),
 			};
 			Self::update_ledger(&controller_to_be_deprecated, &item);
 			Ok(())
@@ -904,8 +1951,8 @@ pub mod pallet {
 		) -> DispatchResult {
 			let stash = ensure_signed(origin)?;
 
-			let controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
-			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let controller = Self::try_bonded(&stash)?;
+			let mut ledger = Self::try_ledger(&controller)?;
 
 			let stash_balance = T::Currency::free_balance(&stash);
 			if let Some(extra) = stash_balance.checked_sub(&ledger.total) {
@@ -920,11 +1967,14 @@ pub mod pallet {
 
 				// NOTE: ledger must be updated prior to calling `Self::weight_of`.
 				Self::update_ledger(&controller, &ledger);
-				// update this staker in the sorted list, if they exist in it.
+				// update this staker in the sorted lists, if they exist in them.
 				if T::VoterList::contains(&stash) {
 					let _ =
 						T::VoterList::on_update(&stash, Self::weight_of(&ledger.stash)).defensive();
 				}
+				if T::TargetList::contains(&stash) {
+					let _ = T::TargetList::on_update(&stash, ledger.active).defensive();
+				}
 
 				Self::deposit_event(Event::<T>::Bonded { stash, amount: extra });
 			}
@@ -952,7 +2002,9 @@ pub mod pallet {
 		/// See also [`Call::withdraw_unbonded`].
 		#[pallet::call_index(2)]
 		#[pallet::weight(
-            T::WeightInfo::withdraw_unbonded_kill(SPECULATIVE_NUM_SPANS).saturating_add(T::WeightInfo::unbond()))
+            T::WeightInfo::withdraw_unbonded_kill(SPECULATIVE_NUM_SPANS)
+				.saturating_add(T::WeightInfo::unbond())
+				.saturating_add(Pallet::<T>::unbonding_queue_weight()))
         ]
 		pub fn unbond(
 			origin: OriginFor<T>,
@@ -977,7 +2029,7 @@ pub mod pallet {
 
 			// we need to fetch the ledger again because it may have been mutated in the call
 			// to `Self::do_withdraw_unbonded` above.
-			let mut ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let mut ledger = Self::try_ledger(&controller)?;
 			let mut value = value.min(ledger.active);
 
 			ensure!(
@@ -1006,35 +2058,40 @@ pub mod pallet {
 				// If a user runs into this error, they should chill first.
 				ensure!(ledger.active >= min_active_bond, Error::<T>::InsufficientBond);
 
-				// Note: in case there is no current era it is fine to bond one era more.
-				let era = Self::current_era().unwrap_or(0) + T::BondingDuration::get();
-				if let Some(chunk) = ledger.unlocking.last_mut().filter(|chunk| chunk.era == era) {
-					// To keep the chunk count down, we only keep one chunk per era. Since
-					// `unlocking` is a FiFo queue, if a chunk exists for `era` we know that it will
-					// be the last one.
-					chunk.value = chunk.value.defensive_saturating_add(value)
-				} else {
-					ledger
+				let era = Self::unbond_until(value);
+				// To keep the chunk count down, we only keep one chunk per era. `unlocking` is
+				// kept sorted by era, which varies with the unbonding queue.
+				match ledger.unlocking.binary_search_by_key(&era, |chunk| chunk.era) {
+					Ok(index) => {
+						let chunk = &mut ledger.unlocking[index];
+						chunk.value = chunk.value.defensive_saturating_add(value)
+					},
+					Err(index) => ledger
 						.unlocking
-						.try_push(UnlockChunk { value, era })
-						.map_err(|_| Error::<T>::NoMoreChunks)?;
+						.try_insert(index, UnlockChunk { value, era })
+						.map_err(|_| Error::<T>::NoMoreChunks)?,
 				};
 				// NOTE: ledger must be updated prior to calling `Self::weight_of`.
 				Self::update_ledger(&controller, &ledger);
 
-				// update this staker in the sorted list, if they exist in it.
+				// update this staker in the sorted lists, if they exist in them.
 				if T::VoterList::contains(&ledger.stash) {
 					let _ = T::VoterList::on_update(&ledger.stash, Self::weight_of(&ledger.stash))
 						.defensive();
 				}
+				if T::TargetList::contains(&ledger.stash) {
+					let _ = T::TargetList::on_update(&ledger.stash, ledger.active).defensive();
+				}
 
 				Self::deposit_event(Event::<T>::Unbonded { stash: ledger.stash, amount: value });
 			}
 
+			let unbond_weight =
+				T::WeightInfo::unbond().saturating_add(Self::unbonding_queue_weight());
 			let actual_weight = if let Some(withdraw_weight) = maybe_withdraw_weight {
-				Some(T::WeightInfo::unbond().saturating_add(withdraw_weight))
+				Some(unbond_weight.saturating_add(withdraw_weight))
 			} else {
-				Some(T::WeightInfo::unbond())
+				Some(unbond_weight)
 			};
 
 			Ok(actual_weight.into())
@@ -1081,20 +2138,31 @@ pub mod pallet {
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
 		#[pallet::call_index(4)]
-		#[pallet::weight(T::WeightInfo::validate())]
+		#[pallet::weight(
+			// `CommissionChanges` is read and written on top of the benchmarked weight.
+			T::WeightInfo::validate().saturating_add(T::DbWeight::get().reads_writes(1, 1))
+		)]
 		pub fn validate(origin: OriginFor<T>, prefs: ValidatorPrefs) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::try_ledger(&controller)?;
 
 			ensure!(ledger.active >= MinValidatorBond::<T>::get(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
 
 			// ensure their commission is correct.
 			ensure!(prefs.commission >= MinCommission::<T>::get(), Error::<T>::CommissionTooLow);
+			let tiers = CommissionTiers::<T>::get();
+			ensure!(
+				tiers.is_empty() ||
+					tiers.iter().any(|(min, max)| (*min..=*max).contains(&prefs.commission)),
+				Error::<T>::CommissionNotInTier
+			);
+			Self::note_commission_change(stash, &prefs)?;
 
 			// Only check limits if they are not already a validator.
-			if !Validators::<T>::contains_key(stash) {
+			let is_new = !Validators::<T>::contains_key(stash);
+			if is_new {
 				// If this error is reached, we need to adjust the `MinValidatorBond` and start
 				// calling `chill_other`. Until then, we explicitly block new validators to protect
 				// the runtime.
@@ -1108,6 +2176,11 @@ pub mod pallet {
 
 			Self::do_remove_nominator(stash);
 			Self::do_add_validator(stash, prefs.clone());
+			// validating again cancels a pending exit.
+			Self::dequeue_exit(stash);
+			if is_new {
+				Self::waitlist_validator(stash);
+			}
 			Self::deposit_event(Event::<T>::ValidatorPrefsSet { stash: ledger.stash, prefs });
 
 			Ok(())
@@ -1124,14 +2197,20 @@ pub mod pallet {
 		/// which is capped at CompactAssignments::LIMIT (T::MaxNominations).
 		/// - Both the reads and writes follow a similar pattern.
 		#[pallet::call_index(5)]
-		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32))]
+		#[pallet::weight(T::WeightInfo::nominate(targets.len() as u32).saturating_add(
+			if T::EvictLowestNominators::get() {
+				T::WeightInfo::evict_lowest_nominator(T::MaxEvictionScan::get())
+			} else {
+				Weight::zero()
+			}
+		))]
 		pub fn nominate(
 			origin: OriginFor<T>,
 			targets: Vec<AccountIdLookupOf<T>>,
 		) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
 
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::try_ledger(&controller)?;
 			ensure!(ledger.active >= MinNominatorBond::<T>::get(), Error::<T>::InsufficientBond);
 			let stash = &ledger.stash;
 
@@ -1139,12 +2218,12 @@ pub mod pallet {
 			if !Nominators::<T>::contains_key(stash) {
 				// If this error is reached, we need to adjust the `MinNominatorBond` and start
 				// calling `chill_other`. Until then, we explicitly block new nominators to protect
-				// the runtime.
+				// the runtime, unless they can take the place of the lowest-staked one.
 				if let Some(max_nominators) = MaxNominatorsCount::<T>::get() {
-					ensure!(
-						Nominators::<T>::count() < max_nominators,
-						Error::<T>::TooManyNominators
-					);
+					if Nominators::<T>::count() >= max_nominators {
+						ensure!(T::EvictLowestNominators::get(), Error::<T>::TooManyNominators);
+						Self::evict_lowest_nominator(stash, ledger.active)?;
+					}
 				}
 			}
 
@@ -1172,6 +2251,9 @@ pub mod pallet {
 				.try_into()
 				.map_err(|_| Error::<T>::TooManyNominators)?;
 
+			// nominating a target again exposes the nominator to all of its slashes.
+			targets.iter().for_each(|target| NominationExits::<T>::remove(stash, target));
+
 			let nominations = Nominations {
 				targets,
 				// Initial nominations are considered submitted at era 0. See `Nominations` doc.
@@ -1186,7 +2268,8 @@ pub mod pallet {
 
 		/// Declare no desire to either validate or nominate.
 		///
-		/// Effects will be felt at the beginning of the next era.
+		/// Effects will be felt at the beginning of the next era. If [`Config::MaxExitsPerEra`] is
+		/// set, a validator is instead put in [`ExitQueue`], and chilled once its turn comes.
 		///
 		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
 		///
@@ -1195,11 +2278,19 @@ pub mod pallet {
 		/// - Contains one read.
 		/// - Writes are limited to the `origin` account key.
 		#[pallet::call_index(6)]
-		#[pallet::weight(T::WeightInfo::chill())]
+		#[pallet::weight(
+			// queueing or dequeuing the exit reads the turn of the validator and the range of
+			// the queue, and writes them along with the queued turn.
+			T::WeightInfo::chill().saturating_add(T::DbWeight::get().reads_writes(2, 3))
+		)]
 		pub fn chill(origin: OriginFor<T>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
-			Self::chill_stash(&ledger.stash);
+			let ledger = Self::try_ledger(&controller)?;
+			if T::MaxExitsPerEra::get().is_some() && Validators::<T>::contains_key(&ledger.stash) {
+				Self::queue_exit(&ledger.stash);
+			} else {
+				Self::chill_stash(&ledger.stash);
+			}
 			Ok(())
 		}
 
@@ -1222,8 +2313,13 @@ pub mod pallet {
 			payee: RewardDestination<T::AccountId>,
 		) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::try_ledger(&controller)?;
 			let stash = &ledger.stash;
+			if payee == RewardDestination::Split {
+				ensure!(PayeeSplits::<T>::contains_key(stash), Error::<T>::InvalidPayeeSplit);
+			} else {
+				PayeeSplits::<T>::remove(stash);
+			}
 			<Payee<T>>::insert(stash, payee);
 			Ok(())
 		}
@@ -1246,7 +2342,7 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::set_controller())]
 		pub fn set_controller(origin: OriginFor<T>) -> DispatchResult {
 			let stash = ensure_signed(origin)?;
-			let old_controller = Self::bonded(&stash).ok_or(Error::<T>::NotStash)?;
+			let old_controller = Self::try_bonded(&stash)?;
 
 			if <Ledger<T>>::contains_key(&stash) {
 				return Err(Error::<T>::AlreadyPaired.into())
@@ -1434,7 +2530,10 @@ pub mod pallet {
 		///
 		/// Can be called by the `T::AdminOrigin`.
 		///
-		/// Parameters: era and indices of the slashes for that era to kill.
+		/// Parameters: era and ids of the slashes for that era to kill, as announced by
+		/// `SlashDeferred`.
+		///
+		/// Emits `SlashCancelled` for each of them.
 		#[pallet::call_index(17)]
 		#[pallet::weight(T::WeightInfo::cancel_deferred_slash(slash_indices.len() as u32))]
 		pub fn cancel_deferred_slash(
@@ -1447,34 +2546,41 @@ pub mod pallet {
 			ensure!(!slash_indices.is_empty(), Error::<T>::EmptyTargets);
 			ensure!(is_sorted_and_unique(&slash_indices), Error::<T>::NotSortedAndUnique);
 
-			let mut unapplied = UnappliedSlashes::<T>::get(&era);
-			let last_item = slash_indices[slash_indices.len() - 1];
-			ensure!((last_item as usize) < unapplied.len(), Error::<T>::InvalidSlashIndex);
-
-			for (removed, index) in slash_indices.into_iter().enumerate() {
-				let index = (index as usize) - removed;
-				unapplied.remove(index);
+			let slashes = slash_indices
+				.iter()
+				.map(|id| UnappliedSlashes::<T>::get(&era, id))
+				.collect::<Option<Vec<_>>>()
+				.ok_or(Error::<T>::InvalidSlashIndex)?;
+
+			for (id, slash) in slash_indices.into_iter().zip(slashes) {
+				UnappliedSlashes::<T>::remove(&era, id);
+				Self::note_pending_slash(era, &slash, false);
+				Self::deposit_event(Event::<T>::SlashCancelled {
+					validator: slash.validator,
+					era,
+					id,
+				});
 			}
-
-			UnappliedSlashes::<T>::insert(&era, &unapplied);
 			Ok(())
 		}
 
 		/// Pay out all the stakers behind a single validator for a single era.
 		///
 		/// - `validator_stash` is the stash account of the validator. Their nominators, up to
-		///   `T::MaxNominatorRewardedPerValidator`, will also receive their rewards.
+		///   `T::MaxNominatorRewardedPerValidator`, will also receive their rewards. The others can
+		///   be paid out with [`Call::payout_stakers_by_page`].
 		/// - `era` may be any era between `[current_era - history_depth; current_era]`.
 		///
+		/// Fails with `InvalidPage` if `validator_stash` was not elected in `era`, as it has no
+		/// exposure to pay out. This used to succeed without paying anything.
+		///
 		/// The origin of this call must be _Signed_. Any account can call this function, even if
 		/// it is not one of the stakers.
 		///
 		/// ## Complexity
 		/// - At most O(MaxNominatorRewardedPerValidator).
 		#[pallet::call_index(18)]
-		#[pallet::weight(T::WeightInfo::payout_stakers_alive_staked(
-			T::MaxNominatorRewardedPerValidator::get()
-		))]
+		#[pallet::weight(Pallet::<T>::max_payout_page_weight())]
 		pub fn payout_stakers(
 			origin: OriginFor<T>,
 			validator_stash: T::AccountId,
@@ -1498,11 +2604,13 @@ pub mod pallet {
 			#[pallet::compact] value: BalanceOf<T>,
 		) -> DispatchResultWithPostInfo {
 			let controller = ensure_signed(origin)?;
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::try_ledger(&controller)?;
 			ensure!(!ledger.unlocking.is_empty(), Error::<T>::NoUnlockChunk);
 
 			let initial_unlocking = ledger.unlocking.len() as u32;
+			let unlocking = ledger.unlocking.clone();
 			let (ledger, rebonded_value) = ledger.rebond(value);
+			Self::note_unlocking_taken(&unlocking, &ledger.unlocking);
 			// Last check: the new active amount of ledger must be more than ED.
 			ensure!(ledger.active >= T::Currency::minimum_balance(), Error::<T>::InsufficientBond);
 
@@ -1517,6 +2625,9 @@ pub mod pallet {
 				let _ = T::VoterList::on_update(&ledger.stash, Self::weight_of(&ledger.stash))
 					.defensive();
 			}
+			if T::TargetList::contains(&ledger.stash) {
+				let _ = T::TargetList::on_update(&ledger.stash, ledger.active).defensive();
+			}
 
 			let removed_chunks = 1u32 // for the case where the last iterated chunk is not removed
 				.saturating_add(initial_unlocking)
@@ -1552,9 +2663,7 @@ pub mod pallet {
 
 			let ed = T::Currency::minimum_balance();
 			let reapable = T::Currency::total_balance(&stash) < ed ||
-				Self::ledger(Self::bonded(stash.clone()).ok_or(Error::<T>::NotStash)?)
-					.map(|l| l.total)
-					.unwrap_or_default() < ed;
+				Self::ledger(Self::try_bonded(&stash)?).map(|l| l.total).unwrap_or_default() < ed;
 			ensure!(reapable, Error::<T>::FundedTarget);
 
 			Self::kill_stash(&stash, num_slashing_spans)?;
@@ -1578,7 +2687,7 @@ pub mod pallet {
 		#[pallet::weight(T::WeightInfo::kick(who.len() as u32))]
 		pub fn kick(origin: OriginFor<T>, who: Vec<AccountIdLookupOf<T>>) -> DispatchResult {
 			let controller = ensure_signed(origin)?;
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::try_ledger(&controller)?;
 			let stash = &ledger.stash;
 
 			for nom_stash in who
@@ -1587,17 +2696,7 @@ pub mod pallet {
 				.collect::<Result<Vec<T::AccountId>, _>>()?
 				.into_iter()
 			{
-				Nominators::<T>::mutate(&nom_stash, |maybe_nom| {
-					if let Some(ref mut nom) = maybe_nom {
-						if let Some(pos) = nom.targets.iter().position(|v| v == stash) {
-							nom.targets.swap_remove(pos);
-							Self::deposit_event(Event::<T>::Kicked {
-								nominator: nom_stash.clone(),
-								stash: stash.clone(),
-							});
-						}
-					}
-				});
+				Self::kick_nominator(stash, &nom_stash);
 			}
 
 			Ok(())
@@ -1687,7 +2786,7 @@ pub mod pallet {
 		pub fn chill_other(origin: OriginFor<T>, controller: T::AccountId) -> DispatchResult {
 			// Anyone can call this function.
 			let caller = ensure_signed(origin)?;
-			let ledger = Self::ledger(&controller).ok_or(Error::<T>::NotController)?;
+			let ledger = Self::try_ledger(&controller)?;
 			let stash = ledger.stash;
 
 			// In order for one user to chill another user, the following conditions must be met:
@@ -1776,6 +2875,479 @@ pub mod pallet {
 			MinCommission::<T>::put(new);
 			Ok(())
 		}
+
+		/// Pay out the stakers behind each `(validator_stash, era)` pair of `payouts`, as
+		/// `payout_stakers` would.
+		///
+		/// Unlike a batch of `payout_stakers` calls, a failing item does not revert the others. It
+		/// is skipped and reported with a [`Event::PayoutFailed`].
+		///
+		/// The origin of this call must be _Signed_. Any account can call this function, even if
+		/// it is not one of the stakers.
+		///
+		/// ## Complexity
+		/// - At most O(MaxNominatorRewardedPerValidator) per item of `payouts`, for up to
+		///   `MaxPayoutsPerBatch` items.
+		#[pallet::call_index(26)]
+		#[pallet::weight(
+			Pallet::<T>::max_payout_page_weight().saturating_mul(payouts.len() as u64)
+		)]
+		pub fn payout_stakers_batch(
+			origin: OriginFor<T>,
+			payouts: BoundedVec<(T::AccountId, EraIndex), T::MaxPayoutsPerBatch>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Ok(Some(Self::do_payout_stakers_batch(payouts.into_inner())).into())
+		}
+
+		/// Update the slashing parameters, without a runtime upgrade.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		///
+		/// * `defer_duration`: The number of eras that slashes are deferred by. Must be less than
+		///   the bonding duration. Removing it falls back to [`Config::SlashDeferDuration`].
+		///   Slashes that are already deferred are rescheduled according to the new duration, which
+		///   cannot be shorter than the current one while there are any.
+		/// * `reward_fraction`: The fraction of a slash that is paid out to its reporters.
+		#[pallet::call_index(27)]
+		#[pallet::weight(
+			T::WeightInfo::set_staking_configs_all_set().saturating_add(
+				T::DbWeight::get().reads_writes(
+					T::BondingDuration::get() as u64 + 1,
+					2 * T::BondingDuration::get() as u64 + 2,
+				)
+			)
+		)]
+		pub fn set_slashing_params(
+			origin: OriginFor<T>,
+			defer_duration: ConfigOp<EraIndex>,
+			reward_fraction: ConfigOp<Perbill>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			match defer_duration {
+				ConfigOp::Noop => (),
+				ConfigOp::Set(duration) => Self::set_slash_defer_duration(Some(duration))?,
+				ConfigOp::Remove => Self::set_slash_defer_duration(None)?,
+			}
+			match reward_fraction {
+				ConfigOp::Noop => (),
+				ConfigOp::Set(fraction) => SlashRewardFraction::<T>::put(fraction),
+				ConfigOp::Remove => SlashRewardFraction::<T>::kill(),
+			}
+
+			Self::deposit_event(Event::<T>::SlashingParamsSet {
+				defer_duration: Self::slash_defer_duration(),
+				reward_fraction: Self::slash_reward_fraction(),
+			});
+			Ok(())
+		}
+
+		/// Set the inclusive ranges of commission that validators can choose from. An empty list
+		/// lifts the restriction.
+		///
+		/// The tiers must be sorted and must not overlap, although a tier can start where the
+		/// previous one ends. They are only enforced when validators set their preferences.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		#[pallet::call_index(30)]
+		#[pallet::weight(T::WeightInfo::set_commission_tiers(tiers.len() as u32))]
+		pub fn set_commission_tiers(
+			origin: OriginFor<T>,
+			tiers: BoundedVec<(Perbill, Perbill), T::MaxCommissionTiers>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(
+				tiers.iter().all(|(min, max)| min <= max) &&
+					tiers.windows(2).all(|w| w[0].1 <= w[1].0),
+				Error::<T>::InvalidCommissionTiers
+			);
+
+			CommissionTiers::<T>::put(&tiers);
+			Self::deposit_event(Event::<T>::CommissionTiersSet { tiers });
+			Ok(())
+		}
+
+		/// Drop `target` from the nominations of the stash of the origin controller, and opt out
+		/// of the slashes of `target` for offences committed after the active era.
+		///
+		/// Unlike nominating again without `target`, which only takes effect at the next
+		/// election, this also covers an era that has already been planned with the stash exposed
+		/// to `target`, in which the stash forfeits its reward from `target` in exchange. The
+		/// stash is chilled if `target` was its only nomination.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		///
+		/// Emits `NominationDropped`.
+		#[pallet::call_index(31)]
+		#[pallet::weight(T::WeightInfo::drop_nomination())]
+		pub fn drop_nomination(
+			origin: OriginFor<T>,
+			target: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::try_ledger(&controller)?;
+			let stash = ledger.stash;
+			let target = T::Lookup::lookup(target)?;
+
+			let mut nominations = Nominators::<T>::get(&stash).ok_or(Error::<T>::NotNominated)?;
+			let position = nominations
+				.targets
+				.iter()
+				.position(|nominated| nominated == &target)
+				.ok_or(Error::<T>::NotNominated)?;
+			nominations.targets.remove(position);
+			if nominations.targets.is_empty() {
+				Self::chill_stash(&stash);
+			} else {
+				Self::do_add_nominator(&stash, nominations);
+			}
+
+			let era = Self::active_era().map(|e| e.index).unwrap_or(0);
+			let planned_era = Self::current_era().unwrap_or(era);
+			NominationExits::<T>::insert(&stash, &target, era);
+			ErasNominationExits::<T>::insert(planned_era, (&stash, &target), era);
+			Self::deposit_event(Event::<T>::NominationDropped { stash, target, era });
+			Ok(())
+		}
+
+		/// Same as [`Self::kick`], for the nominators of the calling validator that match
+		/// `filter`, instead of a list of them.
+		///
+		/// At most `limit` of the nominators of the validator are visited, starting after the
+		/// last one visited by the previous call with the same `filter`, if any. Call again until
+		/// [`KickCursor`] is cleared to go through all of them.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		#[pallet::call_index(32)]
+		#[pallet::weight(
+			T::WeightInfo::kick(*limit)
+				.saturating_add(T::DbWeight::get().reads(2 * (*limit as u64) + 2))
+				.saturating_add(T::DbWeight::get().writes(1))
+		)]
+		pub fn kick_by(
+			origin: OriginFor<T>,
+			filter: KickFilter<BalanceOf<T>>,
+			limit: u32,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::try_ledger(&controller)?;
+			let stash = &ledger.stash;
+
+			let nominators = match KickCursor::<T>::get(stash, filter) {
+				Some(last) => NominatorsOf::<T>::iter_key_prefix_from(
+					stash,
+					NominatorsOf::<T>::hashed_key_for(stash, last),
+				),
+				None => NominatorsOf::<T>::iter_key_prefix(stash),
+			};
+			// one more than needed, to know if we are done.
+			let nominators = nominators.take(limit as usize + 1).collect::<Vec<_>>();
+			let done = nominators.len() <= limit as usize;
+
+			let mut last = None;
+			for nominator in nominators.into_iter().take(limit as usize) {
+				let matches = Nominators::<T>::get(&nominator).map_or(false, |nominations| {
+					filter
+						.matches(&Self::slashable_balance_of(&nominator), nominations.submitted_in)
+				});
+				if matches {
+					Self::kick_nominator(stash, &nominator);
+				}
+				last = Some(nominator);
+			}
+
+			match last {
+				Some(last) if !done => KickCursor::<T>::insert(stash, filter, last),
+				_ => KickCursor::<T>::remove(stash, filter),
+			}
+			Ok(())
+		}
+
+		/// Pay out a single page of the stakers behind a validator for a single era.
+		///
+		/// Page 0 is the same as [`Call::payout_stakers`], and pays the validator along with its
+		/// top `T::MaxNominatorRewardedPerValidator` nominators. Each further page pays the next
+		/// `T::MaxNominatorRewardedPerValidator` nominators, by stake.
+		///
+		/// The origin of this call must be _Signed_. Any account can call this function, even if
+		/// it is not one of the stakers.
+		///
+		/// ## Complexity
+		/// - At most O(MaxNominatorRewardedPerValidator).
+		#[pallet::call_index(33)]
+		#[pallet::weight(Pallet::<T>::max_payout_page_weight())]
+		pub fn payout_stakers_by_page(
+			origin: OriginFor<T>,
+			validator_stash: T::AccountId,
+			era: EraIndex,
+			page: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			Self::do_payout_stakers_by_page(validator_stash, era, page)
+		}
+
+		/// Pay out the stakers behind a validator for up to `max_eras` of the eras in which they
+		/// have not been paid out yet, oldest first, as `payout_stakers` would.
+		///
+		/// Like `payout_stakers_batch`, a failing era does not revert the others. It is skipped
+		/// and reported with a [`Event::PayoutFailed`].
+		///
+		/// The origin of this call must be _Signed_. Any account can call this function, even if
+		/// it is not one of the stakers.
+		///
+		/// ## Complexity
+		/// - At most O(MaxNominatorRewardedPerValidator) per era, for up to `HistoryDepth` eras.
+		#[pallet::call_index(34)]
+		#[pallet::weight(Pallet::<T>::max_payout_page_weight()
+		.saturating_mul((*max_eras).min(T::HistoryDepth::get().saturating_add(1)) as u64)
+		.saturating_add(T::DbWeight::get().reads(
+			3 * (T::HistoryDepth::get() as u64 + 1)
+		)))]
+		pub fn payout_stakers_all(
+			origin: OriginFor<T>,
+			validator_stash: T::AccountId,
+			max_eras: u32,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			// finding the eras reads up to 3 items for each era of the history.
+			let scan_weight = T::DbWeight::get().reads(3 * (T::HistoryDepth::get() as u64 + 1));
+			let payouts = Self::unclaimed_eras(&validator_stash)
+				.into_iter()
+				.take(max_eras as usize)
+				.map(|era| (validator_stash.clone(), era))
+				.collect();
+			Ok(Some(Self::do_payout_stakers_batch(payouts).saturating_add(scan_weight)).into())
+		}
+
+		/// Pay out the reward of the caller alone, as a nominator of `validator_stash` in `era`,
+		/// exposed in `page` of its exposure.
+		///
+		/// The caller is skipped once the rest of its page of the exposure is paid out. This lets
+		/// the nominators of a validator that does not pay out its stakers recover their own
+		/// rewards, without paying for a whole page.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the stash of the nominator.
+		///
+		/// ## Complexity
+		/// - O(MaxNominatorRewardedPerValidator), as the whole page is needed to compute the reward
+		///   of the caller.
+		#[pallet::call_index(35)]
+		#[pallet::weight(Pallet::<T>::max_payout_page_weight())]
+		pub fn claim_my_reward(
+			origin: OriginFor<T>,
+			validator_stash: T::AccountId,
+			era: EraIndex,
+			page: u32,
+		) -> DispatchResultWithPostInfo {
+			let nominator = ensure_signed(origin)?;
+			Self::do_claim_nominator_reward(nominator, validator_stash, era, page)
+		}
+
+		/// Submit the exposures of the next era, in place of an election.
+		///
+		/// Each exposure must add up to its total, of registered validators, and be backed by
+		/// distinct nominators of its validator. No staker may back the validators with more than
+		/// its active stake in total. A new submission replaces the previous one.
+		///
+		/// The dispatch origin must be [`Config::ExposureOracle`], and
+		/// [`Config::ExternalExposures`] must be set.
+		///
+		/// ## Complexity
+		/// - O(S), where S is the number of stakers in `exposures`.
+		#[pallet::call_index(36)]
+		#[pallet::weight({
+			let stakers = exposures
+				.iter()
+				.map(|(_, exposure)| exposure.others.len() as u64 + 1)
+				.sum::<u64>();
+			// replacing the previous submission, of at most `MaxWinners` exposures.
+			let max_winners = <T::ElectionProvider as ElectionProviderBase>::MaxWinners::get();
+			T::DbWeight::get().reads_writes(
+				2 + 3 * stakers,
+				1 + max_winners as u64 + exposures.len() as u64,
+			)
+		})]
+		pub fn submit_exposures(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			exposures: Vec<(T::AccountId, Exposure<T::AccountId, BalanceOf<T>>)>,
+		) -> DispatchResult {
+			T::ExposureOracle::ensure_origin(origin)?;
+			Self::do_submit_exposures(era, exposures)
+		}
+
+		/// Migrate the bond, the funds and the role of `old_stash` to `new_stash`, without going
+		/// through the unbonding period.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller of `old_stash`.
+		/// `new_stash` must not be bonded, and its controller is itself. If `old_stash` is a
+		/// validator, `new_stash` must have set its session keys already, which the keys of
+		/// `old_stash` are not moved to. `old_stash` must keep the existential deposit on top of
+		/// its bond, which is moved on hold.
+		///
+		/// A validator keeps validating under `new_stash` from the next election, and its
+		/// nominators are pointed to `new_stash` in `on_idle`. The rewards and the slashes of
+		/// the eras in which `old_stash` was exposed are applied to the bond of `new_stash`. A
+		/// stash that was migrated to cannot be migrated again until these eras are pruned.
+		///
+		/// Emits `StashMigrated`.
+		#[pallet::call_index(37)]
+		#[pallet::weight(
+			T::WeightInfo::bond()
+				.saturating_add(
+					T::WeightInfo::validate()
+						.max(T::WeightInfo::nominate(MaxNominationsOf::<T>::get())),
+				)
+				.saturating_add(T::WeightInfo::chill())
+				.saturating_add(T::DbWeight::get().reads_writes(7, 13))
+		)]
+		pub fn migrate_stash(
+			origin: OriginFor<T>,
+			old_stash: AccountIdLookupOf<T>,
+			new_stash: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let old_stash = T::Lookup::lookup(old_stash)?;
+			let new_stash = T::Lookup::lookup(new_stash)?;
+			Self::do_migrate_stash(&controller, &old_stash, &new_stash)
+		}
+
+		/// Reduce a deferred slash to a slash of `new_fraction`, rather than cancelling it.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		///
+		/// Parameters: era and id of the slash for that era to reduce, as announced by
+		/// `SlashDeferred`, and the slash fraction to apply instead of the reported one, which it
+		/// must be lower than. The slashes of the validator and its nominators are recomputed
+		/// from their exposure, and the payout of the reporters shrinks alike.
+		///
+		/// Emits `SlashReduced`.
+		#[pallet::call_index(38)]
+		#[pallet::weight(T::WeightInfo::reduce_deferred_slash(
+			<T::ElectionProvider as ElectionProviderBase>::MaxBackersPerWinner::get()
+		))]
+		pub fn reduce_deferred_slash(
+			origin: OriginFor<T>,
+			era: EraIndex,
+			slash_id: u32,
+			new_fraction: Perbill,
+		) -> DispatchResultWithPostInfo {
+			T::AdminOrigin::ensure_origin(origin)?;
+
+			let slash_era = era.saturating_sub(Self::slash_defer_duration()).saturating_sub(1);
+			let nominators = UnappliedSlashes::<T>::try_mutate(&era, slash_id, |maybe_slash| {
+				let slash = maybe_slash.as_mut().ok_or(Error::<T>::InvalidSlashIndex)?;
+				Self::note_pending_slash(era, slash, false);
+				slashing::reduce_slash::<T>(slash, slash_era, new_fraction)?;
+				Self::note_pending_slash(era, slash, true);
+				Self::deposit_event(Event::<T>::SlashReduced {
+					validator: slash.validator.clone(),
+					era,
+					id: slash_id,
+					fraction: new_fraction,
+				});
+				Ok::<_, DispatchError>(slash.others.len() as u32)
+			})?;
+
+			Ok(Some(T::WeightInfo::reduce_deferred_slash(nominators)).into())
+		}
+
+		/// Set the share of the slashed funds that is burned, see [`SlashDestination`].
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		#[pallet::call_index(39)]
+		#[pallet::weight(T::WeightInfo::set_slash_destination())]
+		pub fn set_slash_destination(origin: OriginFor<T>, burned: Perbill) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			SlashDestination::<T>::put(burned);
+			Self::deposit_event(Event::<T>::SlashDestinationSet { burned });
+			Ok(())
+		}
+
+		/// Re-enable the disabled validator at `index` of the session for the rest of the era.
+		///
+		/// It is still counted as offending.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		#[pallet::call_index(40)]
+		#[pallet::weight(T::WeightInfo::re_enable_validator())]
+		pub fn re_enable_validator(origin: OriginFor<T>, index: u32) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			OffendingValidators::<T>::try_mutate(|offending| {
+				let position = offending
+					.binary_search_by_key(&index, |(index, _)| *index)
+					.ok()
+					.filter(|position| offending[*position].1)
+					.ok_or(Error::<T>::NotDisabled)?;
+				offending[position].1 = false;
+				T::SessionInterface::enable_validator(index);
+				Self::deposit_event(Event::<T>::ValidatorReEnabled { index });
+				Ok(())
+			})
+		}
+
+		/// Replace the validator `stash` for the rest of the active era by its best runner-up
+		/// that is still a validator, see [`ReplacedValidators`].
+		///
+		/// The substitute is backed by the exposure it had in the election, without the
+		/// nominators beyond `MaxNominatorRewardedPerValidator`.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		#[pallet::call_index(41)]
+		#[pallet::weight(T::WeightInfo::replace_validator(T::MaxRunnersUp::get()))]
+		pub fn replace_validator(
+			origin: OriginFor<T>,
+			stash: AccountIdLookupOf<T>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			let stash = T::Lookup::lookup(stash)?;
+			Self::do_replace_validator(&stash).map(|_| ())
+		}
+
+		/// Set the era points that the offenders lose for their offences, by severity, see
+		/// [`OffencePenaltyTiers`]. An empty list lifts the penalties.
+		///
+		/// The tiers must be sorted by slash fraction, without duplicates.
+		///
+		/// Can be called by the `T::AdminOrigin`.
+		#[pallet::call_index(42)]
+		#[pallet::weight(T::WeightInfo::set_offence_penalty_tiers(tiers.len() as u32))]
+		pub fn set_offence_penalty_tiers(
+			origin: OriginFor<T>,
+			tiers: BoundedVec<(Perbill, RewardPoint), T::MaxOffencePenaltyTiers>,
+		) -> DispatchResult {
+			T::AdminOrigin::ensure_origin(origin)?;
+			ensure!(tiers.windows(2).all(|w| w[0].0 < w[1].0), Error::<T>::InvalidPenaltyTiers);
+
+			OffencePenaltyTiers::<T>::put(&tiers);
+			Self::deposit_event(Event::<T>::OffencePenaltyTiersSet { tiers });
+			Ok(())
+		}
+
+		/// Split the rewards of the stash across `splits`, each account receiving its share of
+		/// them, and set its reward destination to [`RewardDestination::Split`].
+		///
+		/// The first account receives whatever is left once the others are paid. The splits must
+		/// not be empty, and their shares must not add up to more than 100%.
+		///
+		/// The dispatch origin for this call must be _Signed_ by the controller, not the stash.
+		#[pallet::call_index(43)]
+		#[pallet::weight(T::WeightInfo::set_payee_splits())]
+		pub fn set_payee_splits(origin: OriginFor<T>, splits: PayeeSplitsOf<T>) -> DispatchResult {
+			let controller = ensure_signed(origin)?;
+			let ledger = Self::try_ledger(&controller)?;
+			ensure!(
+				!splits.is_empty() &&
+					splits.iter().map(|(_, share)| share.deconstruct() as u64).sum::<u64>() <=
+						Perbill::one().deconstruct() as u64,
+				Error::<T>::InvalidPayeeSplit
+			);
+
+			PayeeSplits::<T>::insert(&ledger.stash, splits);
+			<Payee<T>>::insert(&ledger.stash, RewardDestination::Split);
+			Ok(())
+		}
 	}
 }
 
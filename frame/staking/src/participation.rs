@@ -0,0 +1,51 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ## Era points for session participation.
+//!
+//! Validators earn era points by authoring blocks, so a validator that was online for a whole
+//! session without authoring any block earns nothing for it. [`ParticipationPoints`] grants each
+//! validator reported as online a small, runtime-configured amount of era points instead.
+//!
+//! It is meant to be plugged in wherever session participation is reported through
+//! [`OnSessionParticipation`], e.g. as `pallet_im_online::Config::OnSessionParticipation`:
+//!
+//! ```ignore
+//! type OnSessionParticipation =
+//! 	pallet_staking::participation::ParticipationPoints<Runtime, ConstU32<1>>;
+//! ```
+
+use crate::{Config, Pallet};
+use frame_support::traits::Get;
+use sp_staking::{OnSessionParticipation, SessionIndex};
+use sp_std::marker::PhantomData;
+
+/// Grants `Points` era points in the active era to each validator that was online in a session.
+pub struct ParticipationPoints<T, Points>(PhantomData<(T, Points)>);
+
+impl<T: Config, Points: Get<u32>> OnSessionParticipation<T::AccountId>
+	for ParticipationPoints<T, Points>
+{
+	fn on_session_participation(_session: SessionIndex, online: &[T::AccountId]) {
+		let points = Points::get();
+		if points == 0 {
+			return
+		}
+
+		Pallet::<T>::reward_by_ids(online.iter().map(|validator| (validator.clone(), points)));
+	}
+}
@@ -50,14 +50,15 @@
 //! Based on research at <https://research.web3.foundation/en/latest/polkadot/slashing/npos.html>
 
 use crate::{
-	BalanceOf, Config, Error, Exposure, NegativeImbalanceOf, NominatorSlashInEra,
-	OffendingValidators, Pallet, Perbill, SessionInterface, SpanSlash, UnappliedSlash,
-	ValidatorSlashInEra,
+	AccountActivity, BalanceOf, Config, DisablingStrategy, Error, Exposure, NegativeImbalanceOf,
+	NominatorSlashInEra, OffendingValidators, Pallet, Perbill, ReplacedValidators,
+	SessionInterface, SlashDestination, SpanSlash, UnappliedSlash, ValidatorSlashInEra,
 };
 use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::{
 	ensure,
 	traits::{Currency, Defensive, Get, Imbalance, OnUnbalanced},
+	weights::Weight,
 };
 use scale_info::TypeInfo;
 use sp_runtime::{
@@ -65,7 +66,7 @@ use sp_runtime::{
 	DispatchResult, RuntimeDebug,
 };
 use sp_staking::{offence::DisableStrategy, EraIndex};
-use sp_std::vec::Vec;
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
 
 /// The proportion of the slashing reward to be paid out on the first slashing detection.
 /// This is f_1 in the paper.
@@ -225,19 +226,24 @@ pub(crate) struct SlashParams<'a, T: 'a + Config> {
 ///
 /// The pending slash record returned does not have initialized reporters. Those have
 /// to be set at a higher level, if any.
+///
+/// Also returns the weight of replacing the validator, if it was disabled and replaced.
 pub(crate) fn compute_slash<T: Config>(
 	params: SlashParams<T>,
-) -> Option<UnappliedSlash<T::AccountId, BalanceOf<T>>> {
+) -> (Option<UnappliedSlash<T::AccountId, BalanceOf<T>>>, Weight) {
 	let mut reward_payout = Zero::zero();
 	let mut val_slashed = Zero::zero();
 
+	// the totals are read on their own, such that the nominators of the exposure are only
+	// looked at once the slash turns out to be the maximum for the era.
+	let (own, total, _) = <Pallet<T>>::eras_stakers_totals(params.slash_era, params.stash);
+
 	// is the slash amount here a maximum for the era?
-	let own_slash = params.slash * params.exposure.own;
-	if params.slash * params.exposure.total == Zero::zero() {
+	let own_slash = params.slash * own;
+	if params.slash * total == Zero::zero() {
 		// kick out the validator even if they won't be slashed,
 		// as long as the misbehavior is from their most recent slashing span.
-		kick_out_if_recent::<T>(params);
-		return None
+		return (None, kick_out_if_recent::<T>(params))
 	}
 
 	let prior_slash_p = ValidatorSlashInEra::<T>::get(&params.slash_era, params.stash)
@@ -259,7 +265,7 @@ pub(crate) fn compute_slash<T: Config>(
 		// pays out some reward even if the latest report is not max-in-era.
 		// we opt to avoid the nominator lookups and edits and leave more rewards
 		// for more drastic misbehavior.
-		return None
+		return (None, Weight::zero())
 	}
 
 	// apply slash to validator.
@@ -286,23 +292,25 @@ pub(crate) fn compute_slash<T: Config>(
 	}
 
 	let disable_when_slashed = params.disable_strategy != DisableStrategy::Never;
-	add_offending_validator::<T>(params.stash, disable_when_slashed);
+	let replacement_weight =
+		add_offending_validator::<T>(params.stash, params.slash, disable_when_slashed);
 
 	let mut nominators_slashed = Vec::new();
 	reward_payout += slash_nominators::<T>(params.clone(), prior_slash_p, &mut nominators_slashed);
 
-	Some(UnappliedSlash {
+	let unapplied = UnappliedSlash {
 		validator: params.stash.clone(),
 		own: val_slashed,
 		others: nominators_slashed,
 		reporters: Vec::new(),
 		payout: reward_payout,
-	})
+	};
+	(Some(unapplied), replacement_weight)
 }
 
 // doesn't apply any slash, but kicks out the validator if the misbehavior is from
 // the most recent slashing span.
-fn kick_out_if_recent<T: Config>(params: SlashParams<T>) {
+fn kick_out_if_recent<T: Config>(params: SlashParams<T>) -> Weight {
 	// these are not updated by era-span or end-span.
 	let mut reward_payout = Zero::zero();
 	let mut val_slashed = Zero::zero();
@@ -320,21 +328,36 @@ fn kick_out_if_recent<T: Config>(params: SlashParams<T>) {
 	}
 
 	let disable_without_slash = params.disable_strategy == DisableStrategy::Always;
-	add_offending_validator::<T>(params.stash, disable_without_slash);
+	add_offending_validator::<T>(params.stash, params.slash, disable_without_slash)
 }
 
-/// Add the given validator to the offenders list and optionally disable it.
-/// If after adding the validator `OffendingValidatorsThreshold` is reached
+/// Add the given validator to the offenders list and optionally disable it, as decided by
+/// `DisablingStrategy`. If after adding the validator `OffendingValidatorsThreshold` is reached
 /// a new era will be forced.
-fn add_offending_validator<T: Config>(stash: &T::AccountId, disable: bool) {
-	OffendingValidators::<T>::mutate(|offending| {
+///
+/// A validator that gets disabled is replaced by a runner-up if `ReplaceDisabledValidators`, in
+/// which case the weight of the replacement is returned.
+fn add_offending_validator<T: Config>(
+	stash: &T::AccountId,
+	slash: Perbill,
+	disable: bool,
+) -> Weight {
+	// a replaced validator does not hold its seat anymore.
+	if ReplacedValidators::<T>::get().iter().any(|(who, _)| who == stash) {
+		return Weight::zero()
+	}
+
+	let disabled_now = OffendingValidators::<T>::mutate(|offending| {
 		let validators = T::SessionInterface::validators();
 		let validator_index = match validators.iter().position(|i| i == stash) {
 			Some(index) => index,
-			None => return,
+			None => return false,
 		};
 
 		let validator_index_u32 = validator_index as u32;
+		let disabled = offending.iter().filter(|(_, disabled)| *disabled).count() as u32;
+		let disable =
+			T::DisablingStrategy::decision(slash, disable, disabled, validators.len() as u32);
 
 		match offending.binary_search_by_key(&validator_index_u32, |(index, _)| *index) {
 			// this is a new offending validator
@@ -352,6 +375,7 @@ fn add_offending_validator<T: Config>(stash: &T::AccountId, disable: bool) {
 				if disable {
 					T::SessionInterface::disable_validator(validator_index_u32);
 				}
+				disable
 			},
 			Ok(index) => {
 				if disable && !offending[index].1 {
@@ -359,10 +383,19 @@ fn add_offending_validator<T: Config>(stash: &T::AccountId, disable: bool) {
 					// let's make sure we disable it now
 					offending[index].1 = true;
 					T::SessionInterface::disable_validator(validator_index_u32);
+					return true
 				}
+				false
 			},
 		}
 	});
+
+	if disabled_now && T::ReplaceDisabledValidators::get() {
+		// without a runner-up left, the validator just stays disabled.
+		<Pallet<T>>::do_replace_validator(stash).unwrap_or_default()
+	} else {
+		Weight::zero()
+	}
 }
 
 /// Slash nominators. Accepts general parameters and the prior slash percentage of the validator.
@@ -380,6 +413,16 @@ fn slash_nominators<T: Config>(
 		let stash = &nominator.who;
 		let mut nom_slashed = Zero::zero();
 
+		// the nominator dropped the validator before the era of the offence.
+		if Pallet::<T>::dropped_before(stash, params.stash, params.slash_era) {
+			continue
+		}
+
+		// the nominator had too little at stake to be worth slashing.
+		if params.slash * nominator.value < T::MinNominatorSlash::get() {
+			continue
+		}
+
 		// the era slash of a nominator always grows, if the validator
 		// had a new max slash for the era.
 		let era_slash = {
@@ -597,6 +640,8 @@ pub fn do_slash<T: Config>(
 	slashed_imbalance: &mut NegativeImbalanceOf<T>,
 	slash_era: EraIndex,
 ) {
+	// a stash exposed in `slash_era` may have since been migrated.
+	let stash = &<Pallet<T>>::migrated_stash(stash);
 	let controller = match <Pallet<T>>::bonded(stash).defensive() {
 		None => return,
 		Some(c) => c,
@@ -607,7 +652,9 @@ pub fn do_slash<T: Config>(
 		None => return, // nothing to do.
 	};
 
+	let unlocking = ledger.unlocking.clone();
 	let value = ledger.slash(value, T::Currency::minimum_balance(), slash_era);
+	<Pallet<T>>::note_unlocking_taken(&unlocking, &ledger.unlocking);
 
 	if !value.is_zero() {
 		let (imbalance, missing) = T::Currency::slash(stash, value);
@@ -621,6 +668,7 @@ pub fn do_slash<T: Config>(
 		<Pallet<T>>::update_ledger(&controller, &ledger);
 
 		// trigger the event
+		<Pallet<T>>::note_active_activity(stash, AccountActivity::SLASHED);
 		<Pallet<T>>::deposit_event(super::Event::<T>::Slashed {
 			staker: stash.clone(),
 			amount: value,
@@ -654,9 +702,94 @@ pub(crate) fn apply_slash<T: Config>(
 		);
 	}
 
+	// burn the configured share of the slash, the rest goes to the reporters and `T::Slash`.
+	let burned = SlashDestination::<T>::get() * slashed_imbalance.peek();
+	let (burned, slashed_imbalance) = slashed_imbalance.split(burned);
+	drop(burned);
+
 	pay_reporters::<T>(reward_payout, slashed_imbalance, &unapplied_slash.reporters);
 }
 
+/// Reduce a previously-unapplied slash for an offence in `slash_era`, as if the offence had been
+/// reported with the lower `fraction` in the first place.
+///
+/// The slashes of the validator and of its nominators are recomputed from their exposure in
+/// `slash_era`, and their era and span slashes are lowered alike, such that later offences are
+/// compared against the reduced slash. The payout of the reporters shrinks with the total slash.
+///
+/// Fails if `fraction` is not lower than the slash of the validator in `slash_era`.
+pub(crate) fn reduce_slash<T: Config>(
+	unapplied_slash: &mut UnappliedSlash<T::AccountId, BalanceOf<T>>,
+	slash_era: EraIndex,
+	fraction: Perbill,
+) -> DispatchResult {
+	let validator = unapplied_slash.validator.clone();
+	let prior_slash_p = ValidatorSlashInEra::<T>::get(&slash_era, &validator)
+		.map_or(Zero::zero(), |(prior_slash_proportion, _)| prior_slash_proportion);
+	ensure!(fraction < prior_slash_p, Error::<T>::InvalidSlashFraction);
+
+	let total_of = |slash: &UnappliedSlash<T::AccountId, BalanceOf<T>>| {
+		slash
+			.others
+			.iter()
+			.fold(slash.own, |acc, (_, value)| acc.saturating_add(*value))
+	};
+	let prior_total = total_of(unapplied_slash);
+
+	let exposure = <Pallet<T>>::eras_stakers(slash_era, &validator);
+	let own_slash = fraction * exposure.own;
+	ValidatorSlashInEra::<T>::insert(&slash_era, &validator, &(fraction, own_slash));
+	unapplied_slash.own = reduce_span_slash::<T>(
+		&validator,
+		slash_era,
+		unapplied_slash.own,
+		(prior_slash_p * exposure.own).saturating_sub(own_slash),
+	);
+
+	let exposed: BTreeMap<_, _> = exposure
+		.others
+		.into_iter()
+		.map(|individual| (individual.who, individual.value))
+		.collect();
+	for (nominator, nominator_slash) in unapplied_slash.others.iter_mut() {
+		let value = exposed.get(nominator).copied().unwrap_or_else(Zero::zero);
+		let reduction = (prior_slash_p * value).saturating_sub(fraction * value);
+		NominatorSlashInEra::<T>::mutate(&slash_era, &*nominator, |era_slash| {
+			if let Some(era_slash) = era_slash {
+				*era_slash = era_slash.saturating_sub(reduction);
+			}
+		});
+		*nominator_slash =
+			reduce_span_slash::<T>(nominator, slash_era, *nominator_slash, reduction);
+	}
+
+	if !prior_total.is_zero() {
+		let total = total_of(unapplied_slash);
+		unapplied_slash.payout =
+			Perbill::from_rational(total, prior_total) * unapplied_slash.payout;
+	}
+	Ok(())
+}
+
+// lowers the span slash of a stash in `slash_era` by `reduction`, of which no more than the
+// `unapplied` part can be taken back. returns what is left to apply.
+fn reduce_span_slash<T: Config>(
+	stash: &T::AccountId,
+	slash_era: EraIndex,
+	unapplied: BalanceOf<T>,
+	reduction: BalanceOf<T>,
+) -> BalanceOf<T> {
+	let reduction = reduction.min(unapplied);
+	let target_span = crate::SlashingSpans::<T>::get(stash)
+		.and_then(|spans| spans.iter().find(|span| span.contains_era(slash_era)));
+	if let Some(span) = target_span {
+		SpanSlash::<T>::mutate(&(stash.clone(), span.index), |span_record| {
+			span_record.slashed = span_record.slashed.saturating_sub(reduction);
+		});
+	}
+	unapplied - reduction
+}
+
 /// Apply a reward payout to some reporters, paying the rewards out of the slashed imbalance.
 fn pay_reporters<T: Config>(
 	reward_payout: BalanceOf<T>,
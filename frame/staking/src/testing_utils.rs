@@ -42,6 +42,8 @@ pub fn clear_validators_and_nominators<T: Config>() {
 	// whenever we touch nominators counter we should update `T::VoterList` as well.
 	#[allow(deprecated)]
 	Nominators::<T>::remove_all();
+	#[allow(deprecated)]
+	NominatorsOf::<T>::remove_all(None);
 
 	// NOTE: safe to call outside block production
 	T::VoterList::unsafe_clear();
@@ -0,0 +1,143 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to move a mock runtime with staking forward in time, by sessions or by eras.
+//!
+//! Blocks are produced one at a time, running the hooks of `pallet_session` and this pallet, and
+//! advancing `pallet_timestamp` by twice its minimum period per block. The hooks of any other
+//! pallet, such as the election provider, are not run.
+//!
+//! Runtimes whose other pallets must see every block as well can use
+//! [`advance_sessions_with`] instead, which produces full blocks.
+//!
+//! All functions leave the runtime in the middle of the last block: it has been initialized but
+//! not yet finalized, like in a regular test.
+
+use crate::{Config, Pallet as Staking};
+use frame_support::traits::{Get, Hooks, OnFinalize, OnInitialize};
+use frame_system::pallet_prelude::BlockNumberFor;
+use sp_runtime::traits::{Header, One, Saturating};
+use sp_staking::{EraIndex, SessionIndex};
+
+/// Move to the next block: finalize the current one, then initialize the next one.
+fn next_block<T>()
+where
+	T: Config + pallet_session::Config + pallet_timestamp::Config,
+{
+	let now = frame_system::Pallet::<T>::block_number();
+	Staking::<T>::on_finalize(now);
+
+	let next = now.saturating_add(One::one());
+	frame_system::Pallet::<T>::set_block_number(next);
+	pallet_session::Pallet::<T>::on_initialize(next);
+	Staking::<T>::on_initialize(next);
+
+	let block_time = T::MinimumPeriod::get().saturating_mul(2u32.into());
+	pallet_timestamp::Pallet::<T>::set_timestamp(
+		pallet_timestamp::Pallet::<T>::get().saturating_add(block_time),
+	);
+}
+
+/// Progress to block `n`, triggering session and era changes as we progress.
+pub fn run_to_block<T>(n: BlockNumberFor<T>)
+where
+	T: Config + pallet_session::Config + pallet_timestamp::Config,
+{
+	while frame_system::Pallet::<T>::block_number() < n {
+		next_block::<T>();
+	}
+}
+
+/// Progress by `n` sessions, to the first block of the last one.
+pub fn advance_sessions<T>(n: SessionIndex)
+where
+	T: Config + pallet_session::Config + pallet_timestamp::Config,
+{
+	let target = pallet_session::Pallet::<T>::current_index().saturating_add(n);
+	while pallet_session::Pallet::<T>::current_index() < target {
+		next_block::<T>();
+	}
+}
+
+/// Progress by `n` sessions, to the first block of the last one, producing full blocks.
+///
+/// Unlike [`advance_sessions`], `frame_system` initializes and finalizes each block, and the hooks
+/// of `Pallets`, e.g. a tuple of the pallets of the runtime that includes `pallet_session` and this
+/// pallet, are run in place of those of `pallet_session` and this pallet alone.
+pub fn advance_sessions_with<T, Pallets>(n: SessionIndex)
+where
+	T: Config + pallet_session::Config + pallet_timestamp::Config,
+	Pallets: OnInitialize<BlockNumberFor<T>> + OnFinalize<BlockNumberFor<T>>,
+{
+	let target = pallet_session::Pallet::<T>::current_index().saturating_add(n);
+	while pallet_session::Pallet::<T>::current_index() < target {
+		let now = frame_system::Pallet::<T>::block_number();
+		Pallets::on_finalize(now);
+
+		let parent_hash = if now > One::one() {
+			frame_system::Pallet::<T>::finalize().hash()
+		} else {
+			frame_system::Pallet::<T>::parent_hash()
+		};
+
+		let next = now.saturating_add(One::one());
+		frame_system::Pallet::<T>::reset_events();
+		frame_system::Pallet::<T>::initialize(&next, &parent_hash, &Default::default());
+
+		let block_time = T::MinimumPeriod::get().saturating_mul(2u32.into());
+		pallet_timestamp::Pallet::<T>::set_timestamp(
+			pallet_timestamp::Pallet::<T>::get().saturating_add(block_time),
+		);
+
+		Pallets::on_initialize(next);
+	}
+}
+
+/// Progress to the first block of the session in which `era` becomes active.
+///
+/// # Panics
+///
+/// If `era` is not in the future, or if no era becomes active in its place, for example because
+/// the election failed.
+pub fn run_to_era<T>(era: EraIndex)
+where
+	T: Config + pallet_session::Config + pallet_timestamp::Config,
+{
+	let active_era = || Staking::<T>::active_era().map_or(0, |info| info.index);
+	assert!(active_era() < era, "era {} is not in the future", era);
+
+	while active_era() < era {
+		advance_sessions::<T>(1);
+	}
+	assert_eq!(active_era(), era, "era {} was skipped", era);
+	// one way or another, the current era must have changed before the active era, so they
+	// must match at this point.
+	assert_eq!(Staking::<T>::current_era(), Some(era));
+}
+
+/// Progress by `n` eras, to the first block of the session in which the last one becomes active.
+///
+/// # Panics
+///
+/// Under the same conditions as [`run_to_era`], including if `n` is zero.
+pub fn advance_eras<T>(n: EraIndex)
+where
+	T: Config + pallet_session::Config + pallet_timestamp::Config,
+{
+	let active_era = Staking::<T>::active_era().map_or(0, |info| info.index);
+	run_to_era::<T>(active_era.saturating_add(n));
+}
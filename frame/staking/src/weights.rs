@@ -82,6 +82,15 @@ pub trait WeightInfo {
 	fn chill_other() -> Weight;
 	fn force_apply_min_commission() -> Weight;
 	fn set_min_commission() -> Weight;
+	fn drop_nomination() -> Weight;
+	fn reduce_deferred_slash(n: u32, ) -> Weight;
+	fn evict_lowest_nominator(s: u32, ) -> Weight;
+	fn set_commission_tiers(t: u32, ) -> Weight;
+	fn set_slash_destination() -> Weight;
+	fn re_enable_validator() -> Weight;
+	fn replace_validator(r: u32, ) -> Weight;
+	fn set_offence_penalty_tiers(t: u32, ) -> Weight;
+	fn set_payee_splits() -> Weight;
 }
 
 /// Weights for pallet_staking using the Substrate node and recommended hardware.
@@ -253,6 +262,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
 	/// Storage: Staking Nominators (r:128 w:128)
 	/// Proof: Staking Nominators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorsOf (r:0 w:128)
+	/// Proof: Staking NominatorsOf (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
 	/// The range of component `k` is `[1, 128]`.
 	fn kick(k: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -264,7 +275,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(Weight::from_parts(8_796_167, 0).saturating_mul(k.into()))
 			.saturating_add(T::DbWeight::get().reads(1_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(k.into())))
-			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(k.into())))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(k.into())))
 			.saturating_add(Weight::from_parts(0, 3033).saturating_mul(k.into()))
 	}
 	/// Storage: Staking Ledger (r:1 w:0)
@@ -289,6 +300,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Staking CounterForNominators (r:1 w:1)
 	/// Proof: Staking CounterForNominators (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorsOf (r:0 w:16)
+	/// Proof: Staking NominatorsOf (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
 	/// The range of component `n` is `[1, 16]`.
 	fn nominate(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -301,6 +314,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(12_u64))
 			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(n.into())))
 			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2520).saturating_mul(n.into()))
 	}
 	/// Storage: Staking Ledger (r:1 w:0)
@@ -317,6 +331,8 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
 	/// Storage: VoterList CounterForListNodes (r:1 w:1)
 	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorsOf (r:0 w:16)
+	/// Proof: Staking NominatorsOf (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
 	fn chill() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1748`
@@ -324,7 +340,7 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		// Minimum execution time: 60_430_000 picoseconds.
 		Weight::from_parts(62_702_000, 6248)
 			.saturating_add(T::DbWeight::get().reads(8_u64))
-			.saturating_add(T::DbWeight::get().writes(6_u64))
+			.saturating_add(T::DbWeight::get().writes(22_u64))
 	}
 	/// Storage: Staking Ledger (r:1 w:0)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
@@ -793,6 +809,201 @@ impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
 		Weight::from_parts(3_679_000, 0)
 			.saturating_add(T::DbWeight::get().writes(1_u64))
 	}
+	/// Storage: Staking Ledger (r:1 w:0)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking Nominators (r:1 w:1)
+	/// Proof: Staking Nominators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+	/// Storage: Staking Validators (r:1 w:0)
+	/// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+	/// Storage: Staking CounterForNominators (r:1 w:1)
+	/// Proof: Staking CounterForNominators (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: VoterList ListNodes (r:2 w:2)
+	/// Proof: VoterList ListNodes (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// Storage: VoterList ListBags (r:1 w:1)
+	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: VoterList CounterForListNodes (r:1 w:1)
+	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking ActiveEra (r:1 w:0)
+	/// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+	/// Storage: Staking CurrentEra (r:1 w:0)
+	/// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking NominationExits (r:0 w:1)
+	/// Proof: Staking NominationExits (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Staking ErasNominationExits (r:0 w:1)
+	/// Proof: Staking ErasNominationExits (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn drop_nomination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1748`
+		//  Estimated: `6248`
+		// Minimum execution time: 64_910_000 picoseconds.
+		Weight::from_parts(66_918_000, 6248)
+			.saturating_add(T::DbWeight::get().reads(10_u64))
+			.saturating_add(T::DbWeight::get().writes(8_u64))
+	}
+	/// Storage: Staking UnappliedSlashes (r:1 w:1)
+	/// Proof Skipped: Staking UnappliedSlashes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ValidatorSlashInEra (r:1 w:1)
+	/// Proof: Staking ValidatorSlashInEra (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersTotals (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersTotals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking SlashingSpans (r:65 w:0)
+	/// Proof Skipped: Staking SlashingSpans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking SpanSlash (r:65 w:65)
+	/// Proof: Staking SpanSlash (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorSlashInEra (r:64 w:64)
+	/// Proof: Staking NominatorSlashInEra (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 64]`.
+	fn reduce_deferred_slash(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6214`
+		//  Estimated: `9214`
+		// Minimum execution time: 59_637_000 picoseconds.
+		Weight::from_parts(61_482_337, 9214)
+			// Standard Error: 745_185
+			.saturating_add(Weight::from_parts(14_903_718, 0).saturating_mul(n.into()))
+			.saturating_add(T::DbWeight::get().reads(6_u64))
+			.saturating_add(T::DbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(T::DbWeight::get().writes(3_u64))
+			.saturating_add(T::DbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: VoterList ListNodes (r:17 w:2)
+	/// Proof: VoterList ListNodes (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// Storage: Staking Nominators (r:16 w:1)
+	/// Proof: Staking Nominators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+	/// Storage: Staking Bonded (r:1 w:0)
+	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Staking Ledger (r:1 w:0)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking Validators (r:1 w:0)
+	/// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+	/// Storage: Staking CounterForNominators (r:1 w:1)
+	/// Proof: Staking CounterForNominators (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: VoterList ListBags (r:1 w:1)
+	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: VoterList CounterForListNodes (r:1 w:1)
+	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking AccountEraActivity (r:1 w:1)
+	/// Proof Skipped: Staking AccountEraActivity (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `s` is `[1, 16]`.
+	fn evict_lowest_nominator(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3248`
+		//  Estimated: `6248`
+		// Minimum execution time: 56_370_000 picoseconds.
+		Weight::from_parts(58_114_290, 6248)
+			// Standard Error: 466_905
+			.saturating_add(Weight::from_parts(9_338_102, 0).saturating_mul(s.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(T::DbWeight::get().writes(7_u64))
+	}
+	/// Storage: Staking CommissionTiers (r:0 w:1)
+	/// Proof: Staking CommissionTiers (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// The range of component `t` is `[0, 16]`.
+	fn set_commission_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_794_000 picoseconds.
+		Weight::from_parts(3_912_000, 0)
+			// Standard Error: 743
+			.saturating_add(Weight::from_parts(14_870, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Staking SlashDestination (r:0 w:1)
+	/// Proof: Staking SlashDestination (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_slash_destination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_534_000 picoseconds.
+		Weight::from_parts(3_644_000, 0)
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Staking OffendingValidators (r:1 w:1)
+	/// Proof Skipped: Staking OffendingValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session DisabledValidators (r:1 w:1)
+	/// Proof Skipped: Session DisabledValidators (max_values: Some(1), max_size: None, mode: Measured)
+	fn re_enable_validator() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1650`
+		// Minimum execution time: 10_947_000 picoseconds.
+		Weight::from_parts(11_286_000, 1650)
+			.saturating_add(T::DbWeight::get().reads(2_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
+	/// Storage: Staking ActiveEra (r:1 w:0)
+	/// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+	/// Storage: Staking CurrentEra (r:1 w:0)
+	/// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Session Validators (r:1 w:0)
+	/// Proof Skipped: Session Validators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Staking ReplacedValidators (r:1 w:1)
+	/// Proof Skipped: Staking ReplacedValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Staking ErasRunnersUp (r:1 w:1)
+	/// Proof Skipped: Staking ErasRunnersUp (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking Validators (r:16 w:0)
+	/// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+	/// Storage: Staking AccountEraActivity (r:1 w:1)
+	/// Proof Skipped: Staking AccountEraActivity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasValidatorPrefs (r:0 w:1)
+	/// Proof: Staking ErasValidatorPrefs (max_values: None, max_size: Some(57), added: 2532, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersTotals (r:0 w:1)
+	/// Proof Skipped: Staking ErasStakersTotals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasStakersPaged (r:0 w:1)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasTotalStake (r:1 w:1)
+	/// Proof: Staking ErasTotalStake (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking ReplacementsQueued (r:0 w:1)
+	/// Proof: Staking ReplacementsQueued (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: Staking OffendingValidators (r:1 w:1)
+	/// Proof Skipped: Staking OffendingValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session DisabledValidators (r:1 w:1)
+	/// Proof Skipped: Session DisabledValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `r` is `[1, 16]`.
+	fn replace_validator(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6210`
+		//  Estimated: `9210`
+		// Minimum execution time: 95_459_000 picoseconds.
+		Weight::from_parts(98_412_000, 9210)
+			// Standard Error: 213_400
+			.saturating_add(Weight::from_parts(4_268_000, 0).saturating_mul(r.into()))
+			.saturating_add(T::DbWeight::get().reads(8_u64))
+			.saturating_add(T::DbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(T::DbWeight::get().writes(10_u64))
+	}
+	/// Storage: Staking OffencePenaltyTiers (r:0 w:1)
+	/// Proof: Staking OffencePenaltyTiers (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// The range of component `t` is `[0, 16]`.
+	fn set_offence_penalty_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_759_000 picoseconds.
+		Weight::from_parts(3_876_000, 0)
+			// Standard Error: 697
+			.saturating_add(Weight::from_parts(13_940, 0).saturating_mul(t.into()))
+			.saturating_add(T::DbWeight::get().writes(1_u64))
+	}
+	/// Storage: Staking Ledger (r:1 w:0)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking PayeeSplits (r:0 w:1)
+	/// Proof: Staking PayeeSplits (max_values: None, max_size: Some(329), added: 2804, mode: MaxEncodedLen)
+	/// Storage: Staking Payee (r:0 w:1)
+	/// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn set_payee_splits() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `808`
+		//  Estimated: `4556`
+		// Minimum execution time: 16_336_000 picoseconds.
+		Weight::from_parts(16_842_000, 4556)
+			.saturating_add(T::DbWeight::get().reads(1_u64))
+			.saturating_add(T::DbWeight::get().writes(2_u64))
+	}
 }
 
 // For backwards compatibility and tests
@@ -963,6 +1174,8 @@ impl WeightInfo for () {
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
 	/// Storage: Staking Nominators (r:128 w:128)
 	/// Proof: Staking Nominators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorsOf (r:0 w:128)
+	/// Proof: Staking NominatorsOf (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
 	/// The range of component `k` is `[1, 128]`.
 	fn kick(k: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -974,7 +1187,7 @@ impl WeightInfo for () {
 			.saturating_add(Weight::from_parts(8_796_167, 0).saturating_mul(k.into()))
 			.saturating_add(RocksDbWeight::get().reads(1_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(k.into())))
-			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(k.into())))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(k.into())))
 			.saturating_add(Weight::from_parts(0, 3033).saturating_mul(k.into()))
 	}
 	/// Storage: Staking Ledger (r:1 w:0)
@@ -999,6 +1212,8 @@ impl WeightInfo for () {
 	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
 	/// Storage: Staking CounterForNominators (r:1 w:1)
 	/// Proof: Staking CounterForNominators (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorsOf (r:0 w:16)
+	/// Proof: Staking NominatorsOf (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
 	/// The range of component `n` is `[1, 16]`.
 	fn nominate(n: u32, ) -> Weight {
 		// Proof Size summary in bytes:
@@ -1011,6 +1226,7 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(12_u64))
 			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(n.into())))
 			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes((1_u64).saturating_mul(n.into())))
 			.saturating_add(Weight::from_parts(0, 2520).saturating_mul(n.into()))
 	}
 	/// Storage: Staking Ledger (r:1 w:0)
@@ -1027,6 +1243,8 @@ impl WeightInfo for () {
 	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
 	/// Storage: VoterList CounterForListNodes (r:1 w:1)
 	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorsOf (r:0 w:16)
+	/// Proof: Staking NominatorsOf (max_values: None, max_size: Some(96), added: 2571, mode: MaxEncodedLen)
 	fn chill() -> Weight {
 		// Proof Size summary in bytes:
 		//  Measured:  `1748`
@@ -1034,7 +1252,7 @@ impl WeightInfo for () {
 		// Minimum execution time: 60_430_000 picoseconds.
 		Weight::from_parts(62_702_000, 6248)
 			.saturating_add(RocksDbWeight::get().reads(8_u64))
-			.saturating_add(RocksDbWeight::get().writes(6_u64))
+			.saturating_add(RocksDbWeight::get().writes(22_u64))
 	}
 	/// Storage: Staking Ledger (r:1 w:0)
 	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
@@ -1503,4 +1721,199 @@ impl WeightInfo for () {
 		Weight::from_parts(3_679_000, 0)
 			.saturating_add(RocksDbWeight::get().writes(1_u64))
 	}
+	/// Storage: Staking Ledger (r:1 w:0)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking Nominators (r:1 w:1)
+	/// Proof: Staking Nominators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+	/// Storage: Staking Validators (r:1 w:0)
+	/// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+	/// Storage: Staking CounterForNominators (r:1 w:1)
+	/// Proof: Staking CounterForNominators (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: VoterList ListNodes (r:2 w:2)
+	/// Proof: VoterList ListNodes (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// Storage: VoterList ListBags (r:1 w:1)
+	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: VoterList CounterForListNodes (r:1 w:1)
+	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking ActiveEra (r:1 w:0)
+	/// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+	/// Storage: Staking CurrentEra (r:1 w:0)
+	/// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking NominationExits (r:0 w:1)
+	/// Proof: Staking NominationExits (max_values: None, max_size: Some(84), added: 2559, mode: MaxEncodedLen)
+	/// Storage: Staking ErasNominationExits (r:0 w:1)
+	/// Proof: Staking ErasNominationExits (max_values: None, max_size: Some(88), added: 2563, mode: MaxEncodedLen)
+	fn drop_nomination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `1748`
+		//  Estimated: `6248`
+		// Minimum execution time: 64_910_000 picoseconds.
+		Weight::from_parts(66_918_000, 6248)
+			.saturating_add(RocksDbWeight::get().reads(10_u64))
+			.saturating_add(RocksDbWeight::get().writes(8_u64))
+	}
+	/// Storage: Staking UnappliedSlashes (r:1 w:1)
+	/// Proof Skipped: Staking UnappliedSlashes (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ValidatorSlashInEra (r:1 w:1)
+	/// Proof: Staking ValidatorSlashInEra (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersTotals (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersTotals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasStakersPaged (r:1 w:0)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking SlashingSpans (r:65 w:0)
+	/// Proof Skipped: Staking SlashingSpans (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking SpanSlash (r:65 w:65)
+	/// Proof: Staking SpanSlash (max_values: None, max_size: Some(76), added: 2551, mode: MaxEncodedLen)
+	/// Storage: Staking NominatorSlashInEra (r:64 w:64)
+	/// Proof: Staking NominatorSlashInEra (max_values: None, max_size: Some(68), added: 2543, mode: MaxEncodedLen)
+	/// The range of component `n` is `[0, 64]`.
+	fn reduce_deferred_slash(n: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6214`
+		//  Estimated: `9214`
+		// Minimum execution time: 59_637_000 picoseconds.
+		Weight::from_parts(61_482_337, 9214)
+			// Standard Error: 745_185
+			.saturating_add(Weight::from_parts(14_903_718, 0).saturating_mul(n.into()))
+			.saturating_add(RocksDbWeight::get().reads(6_u64))
+			.saturating_add(RocksDbWeight::get().reads((3_u64).saturating_mul(n.into())))
+			.saturating_add(RocksDbWeight::get().writes(3_u64))
+			.saturating_add(RocksDbWeight::get().writes((2_u64).saturating_mul(n.into())))
+	}
+	/// Storage: VoterList ListNodes (r:17 w:2)
+	/// Proof: VoterList ListNodes (max_values: None, max_size: Some(154), added: 2629, mode: MaxEncodedLen)
+	/// Storage: Staking Nominators (r:16 w:1)
+	/// Proof: Staking Nominators (max_values: None, max_size: Some(558), added: 3033, mode: MaxEncodedLen)
+	/// Storage: Staking Bonded (r:1 w:0)
+	/// Proof: Staking Bonded (max_values: None, max_size: Some(72), added: 2547, mode: MaxEncodedLen)
+	/// Storage: Staking Ledger (r:1 w:0)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking Validators (r:1 w:0)
+	/// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+	/// Storage: Staking CounterForNominators (r:1 w:1)
+	/// Proof: Staking CounterForNominators (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: VoterList ListBags (r:1 w:1)
+	/// Proof: VoterList ListBags (max_values: None, max_size: Some(82), added: 2557, mode: MaxEncodedLen)
+	/// Storage: VoterList CounterForListNodes (r:1 w:1)
+	/// Proof: VoterList CounterForListNodes (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Staking AccountEraActivity (r:1 w:1)
+	/// Proof Skipped: Staking AccountEraActivity (max_values: None, max_size: None, mode: Measured)
+	/// The range of component `s` is `[1, 16]`.
+	fn evict_lowest_nominator(s: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `3248`
+		//  Estimated: `6248`
+		// Minimum execution time: 56_370_000 picoseconds.
+		Weight::from_parts(58_114_290, 6248)
+			// Standard Error: 466_905
+			.saturating_add(Weight::from_parts(9_338_102, 0).saturating_mul(s.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((2_u64).saturating_mul(s.into())))
+			.saturating_add(RocksDbWeight::get().writes(7_u64))
+	}
+	/// Storage: Staking CommissionTiers (r:0 w:1)
+	/// Proof: Staking CommissionTiers (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// The range of component `t` is `[0, 16]`.
+	fn set_commission_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_794_000 picoseconds.
+		Weight::from_parts(3_912_000, 0)
+			// Standard Error: 743
+			.saturating_add(Weight::from_parts(14_870, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Staking SlashDestination (r:0 w:1)
+	/// Proof: Staking SlashDestination (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	fn set_slash_destination() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_534_000 picoseconds.
+		Weight::from_parts(3_644_000, 0)
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Staking OffendingValidators (r:1 w:1)
+	/// Proof Skipped: Staking OffendingValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session DisabledValidators (r:1 w:1)
+	/// Proof Skipped: Session DisabledValidators (max_values: Some(1), max_size: None, mode: Measured)
+	fn re_enable_validator() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `1650`
+		// Minimum execution time: 10_947_000 picoseconds.
+		Weight::from_parts(11_286_000, 1650)
+			.saturating_add(RocksDbWeight::get().reads(2_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
+	/// Storage: Staking ActiveEra (r:1 w:0)
+	/// Proof: Staking ActiveEra (max_values: Some(1), max_size: Some(13), added: 508, mode: MaxEncodedLen)
+	/// Storage: Staking CurrentEra (r:1 w:0)
+	/// Proof: Staking CurrentEra (max_values: Some(1), max_size: Some(4), added: 499, mode: MaxEncodedLen)
+	/// Storage: Session Validators (r:1 w:0)
+	/// Proof Skipped: Session Validators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Staking ReplacedValidators (r:1 w:1)
+	/// Proof Skipped: Staking ReplacedValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Staking ErasRunnersUp (r:1 w:1)
+	/// Proof Skipped: Staking ErasRunnersUp (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking Validators (r:16 w:0)
+	/// Proof: Staking Validators (max_values: None, max_size: Some(45), added: 2520, mode: MaxEncodedLen)
+	/// Storage: Staking AccountEraActivity (r:1 w:1)
+	/// Proof Skipped: Staking AccountEraActivity (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasValidatorPrefs (r:0 w:1)
+	/// Proof: Staking ErasValidatorPrefs (max_values: None, max_size: Some(57), added: 2532, mode: MaxEncodedLen)
+	/// Storage: Staking ErasStakersTotals (r:0 w:1)
+	/// Proof Skipped: Staking ErasStakersTotals (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasStakersPaged (r:0 w:1)
+	/// Proof Skipped: Staking ErasStakersPaged (max_values: None, max_size: None, mode: Measured)
+	/// Storage: Staking ErasTotalStake (r:1 w:1)
+	/// Proof: Staking ErasTotalStake (max_values: None, max_size: Some(28), added: 2503, mode: MaxEncodedLen)
+	/// Storage: Staking ReplacementsQueued (r:0 w:1)
+	/// Proof: Staking ReplacementsQueued (max_values: Some(1), max_size: Some(1), added: 496, mode: MaxEncodedLen)
+	/// Storage: Staking OffendingValidators (r:1 w:1)
+	/// Proof Skipped: Staking OffendingValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// Storage: Session DisabledValidators (r:1 w:1)
+	/// Proof Skipped: Session DisabledValidators (max_values: Some(1), max_size: None, mode: Measured)
+	/// The range of component `r` is `[1, 16]`.
+	fn replace_validator(r: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `6210`
+		//  Estimated: `9210`
+		// Minimum execution time: 95_459_000 picoseconds.
+		Weight::from_parts(98_412_000, 9210)
+			// Standard Error: 213_400
+			.saturating_add(Weight::from_parts(4_268_000, 0).saturating_mul(r.into()))
+			.saturating_add(RocksDbWeight::get().reads(8_u64))
+			.saturating_add(RocksDbWeight::get().reads((1_u64).saturating_mul(r.into())))
+			.saturating_add(RocksDbWeight::get().writes(10_u64))
+	}
+	/// Storage: Staking OffencePenaltyTiers (r:0 w:1)
+	/// Proof: Staking OffencePenaltyTiers (max_values: Some(1), max_size: Some(129), added: 624, mode: MaxEncodedLen)
+	/// The range of component `t` is `[0, 16]`.
+	fn set_offence_penalty_tiers(t: u32, ) -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `0`
+		//  Estimated: `0`
+		// Minimum execution time: 3_759_000 picoseconds.
+		Weight::from_parts(3_876_000, 0)
+			// Standard Error: 697
+			.saturating_add(Weight::from_parts(13_940, 0).saturating_mul(t.into()))
+			.saturating_add(RocksDbWeight::get().writes(1_u64))
+	}
+	/// Storage: Staking Ledger (r:1 w:0)
+	/// Proof: Staking Ledger (max_values: None, max_size: Some(1091), added: 3566, mode: MaxEncodedLen)
+	/// Storage: Staking PayeeSplits (r:0 w:1)
+	/// Proof: Staking PayeeSplits (max_values: None, max_size: Some(329), added: 2804, mode: MaxEncodedLen)
+	/// Storage: Staking Payee (r:0 w:1)
+	/// Proof: Staking Payee (max_values: None, max_size: Some(73), added: 2548, mode: MaxEncodedLen)
+	fn set_payee_splits() -> Weight {
+		// Proof Size summary in bytes:
+		//  Measured:  `808`
+		//  Estimated: `4556`
+		// Minimum execution time: 16_336_000 picoseconds.
+		Weight::from_parts(16_842_000, 4556)
+			.saturating_add(RocksDbWeight::get().reads(1_u64))
+			.saturating_add(RocksDbWeight::get().writes(2_u64))
+	}
 }
@@ -0,0 +1,83 @@
+// This file is part of Substrate.
+
+// Copyright (C) Parity Technologies (UK) Ltd.
+// SPDX-License-Identifier: Apache-2.0
+
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+// 	http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of a simple approval voting method.
+//!
+//! The candidates with the most approval stake win, and each voter splits its stake evenly among
+//! the winners it approves of. This is far cheaper than the other methods of this crate, at the
+//! expense of the quality of the solution, which makes it suitable as a fallback or a genesis
+//! election.
+
+use crate::{setup_inputs, ElectionResult, ExtendedBalance, IdentifierT, PerThing128, VoteWeight};
+use sp_arithmetic::traits::Zero;
+use sp_std::{cmp::Reverse, prelude::*};
+
+/// Execute the approval voting method.
+///
+/// This can be used interchangeably with `seq-phragmen` and offers a similar API, namely:
+///
+/// - The resulting edge weight distribution is normalized (thus, safe to use for submission).
+/// - The accuracy can be configured via the generic type `P`.
+/// - The algorithm is a _best-effort_ to elect `to_elect`. If less candidates are provided, less
+///   winners are returned, without an error.
+///
+/// Ties in approval stake are broken in favour of the candidate that comes first in `candidates`.
+///
+/// This can only fail if the normalization fails.
+pub fn approval_voting<AccountId: IdentifierT, P: PerThing128>(
+	to_elect: usize,
+	candidates: Vec<AccountId>,
+	voters: Vec<(AccountId, VoteWeight, impl IntoIterator<Item = AccountId>)>,
+) -> Result<ElectionResult<AccountId, P>, crate::Error> {
+	let (mut candidates, mut voters) = setup_inputs(candidates, voters);
+
+	// stable, hence the tie breaking.
+	candidates.sort_by_key(|c_ptr| Reverse(c_ptr.borrow().approval_stake));
+	candidates.truncate(to_elect);
+	candidates.iter().for_each(|c_ptr| c_ptr.borrow_mut().elected = true);
+
+	// edges start with zero weight, so only those of the winners need to be set.
+	for voter in &mut voters {
+		let elected = voter.edges.iter().filter(|e| e.candidate.borrow().elected).count();
+		if elected.is_zero() {
+			continue
+		}
+
+		let share = voter.budget / elected as ExtendedBalance;
+		// the indivisible rest goes to the first winners, one unit each.
+		let mut rest = voter.budget % elected as ExtendedBalance;
+		for edge in voter.edges.iter_mut().filter(|e| e.candidate.borrow().elected) {
+			let extra = rest.min(1);
+			rest -= extra;
+			edge.weight = share + extra;
+			let mut candidate = edge.candidate.borrow_mut();
+			candidate.backed_stake = candidate.backed_stake.saturating_add(edge.weight);
+		}
+	}
+
+	let mut assignments =
+		voters.into_iter().filter_map(|v| v.into_assignment()).collect::<Vec<_>>();
+	let _ = assignments
+		.iter_mut()
+		.try_for_each(|a| a.try_normalize().map_err(crate::Error::ArithmeticError))?;
+	let winners = candidates
+		.into_iter()
+		.map(|w_ptr| (w_ptr.borrow().who.clone(), w_ptr.borrow().backed_stake))
+		.collect();
+
+	Ok(ElectionResult { winners, assignments })
+}
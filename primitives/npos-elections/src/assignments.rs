@@ -29,7 +29,10 @@ use sp_std::vec::Vec;
 
 /// A voter's stake assignment among a set of targets, represented as ratios.
 #[derive(RuntimeDebug, Clone, Default)]
-#[cfg_attr(feature = "serde", derive(PartialEq, Eq, Encode, Decode))]
+#[cfg_attr(
+	feature = "serde",
+	derive(PartialEq, Eq, Encode, Decode, serde::Serialize, serde::Deserialize)
+)]
 pub struct Assignment<AccountId, P: PerThing> {
 	/// Voter's identifier.
 	pub who: AccountId,
@@ -80,7 +80,7 @@ use scale_info::TypeInfo;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 use sp_arithmetic::{traits::Zero, Normalizable, PerThing, Rational128, ThresholdOrd};
-use sp_core::{bounded::BoundedVec, RuntimeDebug};
+use sp_core::{bounded::BoundedVec, Get, RuntimeDebug};
 use sp_std::{
 	cell::RefCell, cmp::Ordering, collections::btree_map::BTreeMap, prelude::*, rc::Rc, vec,
 };
@@ -90,6 +90,7 @@ mod mock;
 #[cfg(test)]
 mod tests;
 
+pub mod approval_voting;
 mod assignments;
 pub mod balancing;
 pub mod helpers;
@@ -100,6 +101,7 @@ pub mod pjr;
 pub mod reduce;
 pub mod traits;
 
+pub use approval_voting::*;
 pub use assignments::{Assignment, StakedAssignment};
 pub use balancing::*;
 pub use helpers::*;
@@ -413,6 +415,7 @@ impl<AccountId: IdentifierT> Voter<AccountId> {
 
 /// Final result of the election.
 #[derive(RuntimeDebug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct ElectionResult<AccountId, P: PerThing> {
 	/// Just winners zipped with their approval stake. Note that the approval stake is merely the
 	/// sub of their received stake and could be used for very basic sorting and approval voting.
@@ -451,10 +454,114 @@ impl<AccountId> Default for Support<AccountId> {
 /// The main advantage of this is that it is encodable.
 pub type Supports<A> = Vec<(A, Support<A>)>;
 
-/// Same as `Supports` but bounded by `B`.
+/// Same as [`Support`], but with the list of backers bounded by `MaxBackers`.
 ///
-/// To note, the inner `Support` is still unbounded.
-pub type BoundedSupports<A, B> = BoundedVec<(A, Support<A>), B>;
+/// The encoding is the same as that of [`Support`].
+#[derive(Encode, Decode, TypeInfo)]
+#[scale_info(skip_type_params(MaxBackers))]
+pub struct BoundedSupport<AccountId, MaxBackers: Get<u32>> {
+	/// Total support.
+	pub total: ExtendedBalance,
+	/// Support from voters.
+	pub voters: BoundedVec<(AccountId, ExtendedBalance), MaxBackers>,
+}
+
+impl<AccountId, MaxBackers: Get<u32>> BoundedSupport<AccountId, MaxBackers> {
+	/// Bound `support`, keeping only the `MaxBackers` backers with the highest stake.
+	///
+	/// The `total` of the returned value only accounts for the backers that are kept. The second
+	/// element of the returned tuple is the number of backers that were removed.
+	pub fn sorted_truncate_from(mut support: Support<AccountId>) -> (Self, u32) {
+		let bound = MaxBackers::get() as usize;
+		if support.voters.len() <= bound {
+			let voters = BoundedVec::truncate_from(support.voters);
+			return (Self { total: support.total, voters }, 0)
+		}
+
+		support.voters.sort_by(|(_, a), (_, b)| b.cmp(a));
+		let removed = support.voters.len().saturating_sub(bound) as u32;
+		let voters = BoundedVec::truncate_from(support.voters);
+		let total = voters
+			.iter()
+			.fold(Zero::zero(), |acc: ExtendedBalance, (_, stake)| acc.saturating_add(*stake));
+		(Self { total, voters }, removed)
+	}
+}
+
+impl<AccountId, MaxBackers: Get<u32>> TryFrom<Support<AccountId>>
+	for BoundedSupport<AccountId, MaxBackers>
+{
+	type Error = ();
+
+	fn try_from(support: Support<AccountId>) -> Result<Self, Self::Error> {
+		let Support { total, voters } = support;
+		let voters = voters.try_into().map_err(|_| ())?;
+		Ok(Self { total, voters })
+	}
+}
+
+impl<AccountId, MaxBackers: Get<u32>> From<BoundedSupport<AccountId, MaxBackers>>
+	for Support<AccountId>
+{
+	fn from(support: BoundedSupport<AccountId, MaxBackers>) -> Self {
+		Self { total: support.total, voters: support.voters.into_inner() }
+	}
+}
+
+impl<AccountId, MaxBackers: Get<u32>> Default for BoundedSupport<AccountId, MaxBackers> {
+	fn default() -> Self {
+		Self { total: Default::default(), voters: Default::default() }
+	}
+}
+
+impl<AccountId: Clone, MaxBackers: Get<u32>> Clone for BoundedSupport<AccountId, MaxBackers> {
+	fn clone(&self) -> Self {
+		Self { total: self.total, voters: self.voters.clone() }
+	}
+}
+
+impl<AccountId: PartialEq, MaxBackers: Get<u32>> PartialEq
+	for BoundedSupport<AccountId, MaxBackers>
+{
+	fn eq(&self, other: &Self) -> bool {
+		self.total == other.total && self.voters == other.voters
+	}
+}
+
+impl<AccountId: Eq, MaxBackers: Get<u32>> Eq for BoundedSupport<AccountId, MaxBackers> {}
+
+impl<AccountId: sp_std::fmt::Debug, MaxBackers: Get<u32>> sp_std::fmt::Debug
+	for BoundedSupport<AccountId, MaxBackers>
+{
+	fn fmt(&self, f: &mut sp_std::fmt::Formatter<'_>) -> sp_std::fmt::Result {
+		f.debug_struct("BoundedSupport")
+			.field("total", &self.total)
+			.field("voters", &self.voters)
+			.finish()
+	}
+}
+
+/// Same as `Supports` but bounded by `MaxWinners`, with the backers of each winner bounded by
+/// `MaxBackersPerWinner`.
+pub type BoundedSupports<A, MaxWinners, MaxBackersPerWinner> =
+	BoundedVec<(A, BoundedSupport<A, MaxBackersPerWinner>), MaxWinners>;
+
+/// Try and convert some [`Supports`] into [`BoundedSupports`].
+///
+/// Fails if either there are more than `MaxWinners` winners, or any of them has more than
+/// `MaxBackersPerWinner` backers.
+pub fn try_bound_supports<A, MaxWinners: Get<u32>, MaxBackersPerWinner: Get<u32>>(
+	supports: Supports<A>,
+) -> Result<BoundedSupports<A, MaxWinners, MaxBackersPerWinner>, ()> {
+	if supports.len() > MaxWinners::get() as usize {
+		return Err(())
+	}
+	supports
+		.into_iter()
+		.map(|(who, support)| support.try_into().map(|s| (who, s)))
+		.collect::<Result<Vec<_>, _>>()
+		.and_then(|inner| inner.try_into().map_err(|_| ()))
+}
 
 /// Linkage from a winner to their [`Support`].
 ///
@@ -499,25 +606,44 @@ pub trait EvaluateSupport {
 
 impl<AccountId: IdentifierT> EvaluateSupport for Supports<AccountId> {
 	fn evaluate(&self) -> ElectionScore {
-		let mut minimal_stake = ExtendedBalance::max_value();
-		let mut sum_stake: ExtendedBalance = Zero::zero();
-		// NOTE: The third element might saturate but fine for now since this will run on-chain and
-		// need to be fast.
-		let mut sum_stake_squared: ExtendedBalance = Zero::zero();
-
-		for (_, support) in self {
-			sum_stake = sum_stake.saturating_add(support.total);
-			let squared = support.total.saturating_mul(support.total);
-			sum_stake_squared = sum_stake_squared.saturating_add(squared);
-			if support.total < minimal_stake {
-				minimal_stake = support.total;
-			}
-		}
+		self.as_slice().evaluate()
+	}
+}
 
-		ElectionScore { minimal_stake, sum_stake, sum_stake_squared }
+impl<AccountId: IdentifierT> EvaluateSupport for [(AccountId, Support<AccountId>)] {
+	fn evaluate(&self) -> ElectionScore {
+		evaluate_totals(self.iter().map(|(_, support)| support.total))
 	}
 }
 
+impl<AccountId: IdentifierT, MaxBackers: Get<u32>> EvaluateSupport
+	for [(AccountId, BoundedSupport<AccountId, MaxBackers>)]
+{
+	fn evaluate(&self) -> ElectionScore {
+		evaluate_totals(self.iter().map(|(_, support)| support.total))
+	}
+}
+
+/// Compute the [`ElectionScore`] of a set of winners, given the total support of each.
+fn evaluate_totals(totals: impl Iterator<Item = ExtendedBalance>) -> ElectionScore {
+	let mut minimal_stake = ExtendedBalance::max_value();
+	let mut sum_stake: ExtendedBalance = Zero::zero();
+	// NOTE: The third element might saturate but fine for now since this will run on-chain and
+	// need to be fast.
+	let mut sum_stake_squared: ExtendedBalance = Zero::zero();
+
+	for total in totals {
+		sum_stake = sum_stake.saturating_add(total);
+		let squared = total.saturating_mul(total);
+		sum_stake_squared = sum_stake_squared.saturating_add(squared);
+		if total < minimal_stake {
+			minimal_stake = total;
+		}
+	}
+
+	ElectionScore { minimal_stake, sum_stake, sum_stake_squared }
+}
+
 /// Converts raw inputs to types used in this crate.
 ///
 /// This will perform some cleanup that are most often important:
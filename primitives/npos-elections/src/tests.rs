@@ -18,10 +18,13 @@
 //! Tests for npos-elections.
 
 use crate::{
-	balancing, helpers::*, mock::*, seq_phragmen, seq_phragmen_core, setup_inputs, to_support_map,
-	Assignment, BalancingConfig, ElectionResult, ExtendedBalance, StakedAssignment, Support, Voter,
+	approval_voting, balancing, helpers::*, mock::*, seq_phragmen, seq_phragmen_core, setup_inputs,
+	to_support_map, try_bound_supports, Assignment, BalancingConfig, BoundedSupport,
+	ElectionResult, ExtendedBalance, StakedAssignment, Support, Voter,
 };
+use codec::{Decode, Encode};
 use sp_arithmetic::{PerU16, Perbill, Percent, Permill};
+use sp_core::ConstU32;
 use substrate_test_utils::assert_eq_uvec;
 
 #[test]
@@ -277,6 +280,45 @@ fn phragmen_poc_works() {
 	);
 }
 
+#[test]
+fn approval_voting_works() {
+	let candidates = vec![1, 2, 3, 4];
+	let voters = vec![(10, vec![1, 2]), (20, vec![1, 3]), (30, vec![2, 3, 4])];
+
+	let stake_of = create_stake_of(&[(10, 10), (20, 20), (30, 30)]);
+	let ElectionResult::<_, Perbill> { winners, assignments } = approval_voting(
+		2,
+		candidates,
+		voters
+			.iter()
+			.map(|(ref v, ref vs)| (*v, stake_of(v), vs.clone()))
+			.collect::<Vec<_>>(),
+	)
+	.unwrap();
+
+	// the most approved win, in order of approval, and are backed by an even split of the stake.
+	assert_eq!(winners, vec![(3, 35), (2, 25)]);
+	assert_eq_uvec!(
+		assignments,
+		vec![
+			Assignment { who: 10u64, distribution: vec![(2, Perbill::from_percent(100))] },
+			Assignment { who: 20, distribution: vec![(3, Perbill::from_percent(100))] },
+			Assignment {
+				who: 30,
+				distribution: vec![
+					(2, Perbill::from_percent(100 / 2)),
+					(3, Perbill::from_percent(100 / 2)),
+				],
+			},
+		]
+	);
+
+	// fewer candidates than seats: all of them win.
+	let ElectionResult::<_, Perbill> { winners, .. } =
+		approval_voting(5, vec![1u64, 2], vec![(10, 10, vec![1]), (20, 20, vec![1, 2])]).unwrap();
+	assert_eq!(winners, vec![(1, 20), (2, 10)]);
+}
+
 #[test]
 fn phragmen_poc_works_with_balancing() {
 	let candidates = vec![1, 2, 3];
@@ -694,6 +736,39 @@ fn duplicate_target_is_ignored_when_winner() {
 	);
 }
 
+#[test]
+fn bounded_support_truncates_lowest_backers() {
+	let support = Support { total: 60u128, voters: vec![(1u64, 10u128), (2, 30), (3, 20)] };
+
+	// within bounds, nothing changes.
+	let (bounded, removed) =
+		BoundedSupport::<u64, ConstU32<3>>::sorted_truncate_from(support.clone());
+	assert_eq!(removed, 0);
+	assert_eq!(Support::from(bounded), support);
+
+	// the lowest backer is removed, and the total is updated.
+	let (bounded, removed) =
+		BoundedSupport::<u64, ConstU32<2>>::sorted_truncate_from(support.clone());
+	assert_eq!(removed, 1);
+	assert_eq!(bounded.total, 50);
+	assert_eq!(bounded.voters.into_inner(), vec![(2, 30), (3, 20)]);
+
+	// the encoding is the same as that of an unbounded support.
+	let bounded = BoundedSupport::<u64, ConstU32<3>>::try_from(support.clone()).unwrap();
+	assert_eq!(bounded.encode(), support.encode());
+	assert_eq!(
+		BoundedSupport::<u64, ConstU32<3>>::decode(&mut &support.encode()[..]).unwrap(),
+		bounded
+	);
+	assert!(BoundedSupport::<u64, ConstU32<2>>::try_from(support.clone()).is_err());
+
+	// both the number of winners and backers are checked.
+	let supports = vec![(10u64, support.clone()), (20, support)];
+	assert!(try_bound_supports::<_, ConstU32<2>, ConstU32<3>>(supports.clone()).is_ok());
+	assert!(try_bound_supports::<_, ConstU32<1>, ConstU32<3>>(supports.clone()).is_err());
+	assert!(try_bound_supports::<_, ConstU32<2>, ConstU32<2>>(supports).is_err());
+}
+
 mod assignment_convert_normalize {
 	use super::*;
 	#[test]
@@ -21,7 +21,7 @@
 //! approaches in general. Definitions related to sessions, slashing, etc go here.
 
 use crate::currency_to_vote::CurrencyToVote;
-use codec::{FullCodec, MaxEncodedLen};
+use codec::{Decode, Encode, FullCodec, MaxEncodedLen};
 use scale_info::TypeInfo;
 use sp_core::RuntimeDebug;
 use sp_runtime::{DispatchError, DispatchResult, Saturating};
@@ -49,6 +49,67 @@ pub enum StakerStatus<AccountId> {
 	Nominator(Vec<AccountId>),
 }
 
+/// The role a bonded stash currently plays in the staking system.
+///
+/// Unlike [`StakerStatus`], this does not carry the nominations, and is meant to be stored and
+/// queried as-is.
+#[derive(Encode, Decode, MaxEncodedLen, Clone, Copy, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub enum StakerRole {
+	/// Bonded, but neither validating nor nominating.
+	Idle,
+	/// Nominating some validators.
+	Nominator,
+	/// Declared as a validator candidate.
+	Validator,
+}
+
+/// How much of its bounds an election snapshot used.
+#[derive(
+	Encode, Decode, MaxEncodedLen, Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, TypeInfo,
+)]
+pub struct SnapshotUtilization {
+	/// The number of items in the snapshot.
+	pub size: u32,
+	/// The maximum number of items that the snapshot could hold, if its count is bounded.
+	pub bound: Option<u32>,
+	/// The number of items of the sorted list that did not make it into the snapshot.
+	pub excluded: u32,
+}
+
+/// The inflation of an era, as computed when it ended.
+#[derive(
+	Encode, Decode, MaxEncodedLen, Clone, Copy, Default, Eq, PartialEq, RuntimeDebug, TypeInfo,
+)]
+pub struct EraInflation<Balance> {
+	/// The total stake backing the validators of the era.
+	pub staked: Balance,
+	/// The total issuance at the end of the era.
+	pub issuance: Balance,
+	/// The payout of the validators and their nominators, including what was carried over from
+	/// previous eras.
+	pub validator_payout: Balance,
+	/// The remainder of the era payout, not paid to the stakers.
+	pub remainder: Balance,
+}
+
+/// A summary of the health of the staking elections, meant to be monitored.
+#[derive(Encode, Decode, Clone, Eq, PartialEq, RuntimeDebug, TypeInfo)]
+pub struct ElectionHealthReport<BlockNumber> {
+	/// The utilization of the last voter snapshot. Its `excluded` voters are mostly the
+	/// nominators with the least stake.
+	pub voters: SnapshotUtilization,
+	/// The utilization of the last target snapshot.
+	pub targets: SnapshotUtilization,
+	/// The number of elections that failed in a row since the last successful one.
+	pub consecutive_failures: u32,
+	/// The block of the last successful election, if any.
+	pub last_success: Option<BlockNumber>,
+	/// The number of blocks since the last successful election, if any.
+	pub blocks_since_last_success: Option<BlockNumber>,
+	/// Whether the voter and target lists are in sync with the validators and nominators.
+	pub lists_consistent: bool,
+}
+
 /// A struct that reflects stake that an account has in the staking system. Provides a set of
 /// methods to operate on it's properties. Aimed at making `StakingInterface` more concise.
 #[derive(RuntimeDebug, Clone, Copy, Eq, PartialEq, Default)]
@@ -129,6 +190,16 @@ pub trait OnStakingUpdate<AccountId, Balance> {
 	}
 }
 
+/// Something that is notified of the validators that participated in a session.
+///
+/// This allows rewarding validators for being online, independently of how many blocks they
+/// authored.
+#[impl_trait_for_tuples::impl_for_tuples(10)]
+pub trait OnSessionParticipation<ValidatorId> {
+	/// Fired before `session` ends, with the validators that were `online` during it.
+	fn on_session_participation(session: SessionIndex, online: &[ValidatorId]);
+}
+
 /// A generic representation of a staking implementation.
 ///
 /// This interface uses the terminology of NPoS, but it is aims to be generic enough to cover other
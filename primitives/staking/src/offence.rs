@@ -187,6 +187,23 @@ pub trait OnOffenceHandler<Reporter, Offender, Res> {
 		session: SessionIndex,
 		disable_strategy: DisableStrategy,
 	) -> Res;
+
+	/// Same as [`Self::on_offence`], for an offence of `kind` in the encoded `time_slot`, which
+	/// identify it along with its offenders.
+	///
+	/// This lets the receiver recognise an offence that is reported again. Defaults to
+	/// [`Self::on_offence`].
+	fn on_offence_of_kind(
+		kind: &Kind,
+		time_slot: &[u8],
+		offenders: &[OffenceDetails<Reporter, Offender>],
+		slash_fraction: &[Perbill],
+		session: SessionIndex,
+		disable_strategy: DisableStrategy,
+	) -> Res {
+		let _ = (kind, time_slot);
+		Self::on_offence(offenders, slash_fraction, session, disable_strategy)
+	}
 }
 
 impl<Reporter, Offender, Res: Default> OnOffenceHandler<Reporter, Offender, Res> for () {